@@ -0,0 +1,63 @@
+/* rustboy の C API
+ * cargo rustc --release -p rustboy-core --lib --features ffi --crate-type cdylib で
+ * target/release/librustboy.so (.dylib / .dll) を作ってリンクする
+ */
+#ifndef RUSTBOY_H
+#define RUSTBOY_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* rustboy_set_buttons に渡すボタンのビット */
+#define RUSTBOY_A (1 << 0)
+#define RUSTBOY_B (1 << 1)
+#define RUSTBOY_SELECT (1 << 2)
+#define RUSTBOY_START (1 << 3)
+#define RUSTBOY_RIGHT (1 << 4)
+#define RUSTBOY_LEFT (1 << 5)
+#define RUSTBOY_UP (1 << 6)
+#define RUSTBOY_DOWN (1 << 7)
+
+/* 画面の大きさ。rustboy_framebuffer は RGBA で WIDTH * HEIGHT * 4 バイト */
+#define RUSTBOY_WIDTH 160
+#define RUSTBOY_HEIGHT 144
+
+typedef struct Emulator Emulator;
+
+/* ROM イメージをコピーしてエミュレータを作る。ROM が壊れていれば NULL */
+Emulator *rustboy_create(const uint8_t *rom, size_t len);
+
+/* 次の VBlank まで実行する。成功すれば 0、CPU が停止すれば -1 */
+int rustboy_step_frame(Emulator *emulator);
+
+/* 最後に実行したフレームの画面。次に rustboy_step_frame か rustboy_destroy を呼ぶまで有効 */
+const uint8_t *rustboy_framebuffer(const Emulator *emulator);
+
+/* 押されているボタンを RUSTBOY_A などの論理和で指定する。次に指定するまで押したままになる */
+void rustboy_set_buttons(Emulator *emulator, uint8_t buttons);
+
+/* address のメモリを CPU と同じように読む。emulator が NULL なら 0xFF */
+uint8_t rustboy_read(const Emulator *emulator, uint16_t address);
+
+/* address のメモリに CPU と同じように書き込む */
+void rustboy_write(Emulator *emulator, uint16_t address, uint8_t data);
+
+/* address から len バイトを buffer に読み込む。IO レジスタは状態を変えずに読む
+ * 0xFFFF の次は 0x0000 に戻る。成功すれば 0、失敗すれば -1 で buffer は 0xFF で埋まる */
+int rustboy_read_range(const Emulator *emulator, uint16_t address, uint8_t *buffer, size_t len);
+
+/* data の len バイトを address から順に CPU と同じように書き込む。成功すれば 0、失敗すれば -1 */
+int rustboy_write_range(Emulator *emulator, uint16_t address, const uint8_t *data, size_t len);
+
+/* エミュレータを破棄する。NULL なら何もしない */
+void rustboy_destroy(Emulator *emulator);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
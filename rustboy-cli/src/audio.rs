@@ -0,0 +1,101 @@
+use std::fs::File;
+use std::io::BufWriter;
+
+use rustboy::{write_wav, MotherBoard, CPU};
+
+// 書き出す WAV のサンプリング周波数
+const SAMPLE_RATE: u32 = 44100;
+
+/// 鳴らした音をフレームごとに溜めて WAV に書き出す(--dump-audio)
+/// 他のエミュレータの出力と比べられるように、実時間ではなく実機での経過時間で区切る
+pub struct AudioDump {
+    path: String,
+    // 書き出すサンプル数。None なら終了するまで
+    limit: Option<u64>,
+    // 溜め始めたときの経過サイクル
+    origin: u64,
+    pcm: Vec<u8>,
+    written: bool,
+}
+
+impl AudioDump {
+    /// seconds は実機で何秒分を書き出すか
+    pub fn new(path: &str, seconds: Option<u32>, mb: &MotherBoard) -> Self {
+        Self {
+            path: path.to_string(),
+            limit: seconds.map(|s| s as u64 * SAMPLE_RATE as u64),
+            origin: mb.elapsed(),
+            pcm: Vec::new(),
+            written: false,
+        }
+    }
+
+    /// フレームを描画するごとに呼び、前回からの分を溜める。seconds に達したら書き出す
+    pub fn capture(&mut self, mb: &MotherBoard) {
+        if self.written {
+            return;
+        }
+        let (start, len) = self.next_range(mb.elapsed());
+        self.pcm
+            .extend(mb.sound().mixed_pcm(SAMPLE_RATE, start, len));
+        if self
+            .limit
+            .is_some_and(|limit| self.pcm.len() as u64 >= limit)
+        {
+            self.write();
+        }
+    }
+
+    // elapsed までに溜めるサンプルの、先頭からの位置と数
+    fn next_range(&self, elapsed: u64) -> (u64, usize) {
+        let start = self.pcm.len() as u64;
+        let mut end = elapsed.saturating_sub(self.origin) * SAMPLE_RATE as u64 / CPU::CLOCK as u64;
+        if let Some(limit) = self.limit {
+            end = end.min(limit);
+        }
+        (start, end.saturating_sub(start) as usize)
+    }
+
+    fn write(&mut self) {
+        self.written = true;
+        let result = File::create(&self.path)
+            .and_then(|file| write_wav(BufWriter::new(file), SAMPLE_RATE, &self.pcm));
+        match result {
+            Ok(_) => eprintln!("Dump audio: {} ({} samples)", self.path, self.pcm.len()),
+            Err(e) => eprintln!("Failed to dump audio: {}", e),
+        }
+    }
+}
+
+// seconds に達する前に終了したら、それまでの分を書き出す
+impl Drop for AudioDump {
+    fn drop(&mut self) {
+        if !self.written {
+            self.write();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_range() {
+        let mut dump = AudioDump {
+            path: String::new(),
+            limit: Some(SAMPLE_RATE as u64),
+            origin: 100,
+            pcm: Vec::new(),
+            written: true,
+        };
+        // 1 秒経つと 44100 サンプル
+        assert_eq!(dump.next_range(100 + CPU::CLOCK as u64), (0, 44100));
+        assert_eq!(dump.next_range(50), (0, 0));
+        // 続きから溜め、seconds を超えた分は溜めない
+        dump.pcm = vec![0x80; 44000];
+        assert_eq!(dump.next_range(100 + 2 * CPU::CLOCK as u64), (44000, 100));
+        dump.limit = None;
+        assert_eq!(dump.next_range(100 + 2 * CPU::CLOCK as u64), (44000, 44200));
+    }
+}
@@ -0,0 +1,10 @@
+use std::env;
+use std::process;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if let Err(e) = rustboy_cli::framediff_command(&args) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
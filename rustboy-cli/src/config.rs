@@ -0,0 +1,892 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Read};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rustboy::{
+    lookup_compat, Accuracy, Colorization, CompatEntry, KeyBindings, LinkMode, MbcSelect,
+    PixelData, RamInit, DEFAULT_KEY_HOLD, DEFAULT_PALETTE,
+};
+
+use serde::Deserialize;
+
+use crate::input::{Hotkeys, InputMode};
+use crate::lcd::{AsciiStyle, BrailleStyle, Resize};
+use crate::logger;
+use crate::pacer::FrameSkip;
+use crate::postprocess::Filter;
+
+/// 描画先
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frontend {
+    // 8点点字で描画する
+    Braille,
+    // 1ピクセル1文字で描画する
+    Terminal,
+    // 明るさに応じた ASCII 文字で描画する
+    Ascii,
+}
+
+impl FromStr for Frontend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "braille" => Ok(Frontend::Braille),
+            "terminal" => Ok(Frontend::Terminal),
+            "ascii" => Ok(Frontend::Ascii),
+            _ => Err(format!("Unknown frontend: {}", s)),
+        }
+    }
+}
+
+/// 引数と設定ファイルから構築される設定値群
+/// 互換性データベース、設定ファイル、ゲームごとの設定、引数の順に上書きする
+pub struct Config {
+    pub rom_file: String,
+    // ROM ファイルの代わりに組み込みのデモ ROM を動かす
+    pub demo: bool,
+    // 読み込み時に当てる IPS / BPS のパッチ
+    pub patch: Option<String>,
+    // 1命令ごとにデバッガを呼び出す
+    pub debug: bool,
+    // 画面、レジスタ、逆アセンブル、メモリを並べたデバッガで動かす
+    #[cfg(feature = "tui")]
+    pub tui: bool,
+    // 起動時から録画する場合の出力先
+    #[cfg(feature = "recorder")]
+    pub record_file: Option<String>,
+    // カウンタを公開するポート
+    #[cfg(feature = "metrics")]
+    pub metrics_port: Option<u16>,
+    pub frontend: Frontend,
+    // 点字で描画するときの閾値、ディザリング、反転
+    pub braille: BrailleStyle,
+    // ASCII で描画するときの文字と縦横比の補正
+    pub ascii: AsciiStyle,
+    // 端末に収まるように切り出して縮小する
+    pub resize: Resize,
+    // 切り出しと縮小の後に、書いた順に掛ける後処理
+    pub postprocess: Vec<Filter>,
+    // 色番号 0 - 3 に対応する表示色
+    pub palette: [PixelData; 4],
+    // 背景とスプライトで別の色を付ける。あれば palette より優先する
+    pub colorization: Option<Colorization>,
+    pub keys: KeyBindings,
+    // 一時停止、コマ送り、早送り、スロー再生
+    pub hotkeys: Hotkeys,
+    // 標準入力を1行ずつ読むか1文字ずつ読むか
+    pub input: InputMode,
+    // キーを押してから自動で離すまでのミリ秒
+    pub key_hold: u64,
+    // セーブデータの保存先。None なら ROM と同じディレクトリ
+    pub save_dir: Option<String>,
+    // 1.0 で実機と同じ速さ。0 なら制限しない
+    pub speed: f64,
+    // 描画を間引く。エミュレーションは全てのフレームで行う
+    pub frameskip: FrameSkip,
+    // 通信ケーブルで繋ぐ相手
+    pub link: Option<LinkMode>,
+    // FPS や速度などの状態行を表示する
+    pub show_fps: bool,
+    // フレーム数、経過時間、入力を画面の下に表示する
+    pub show_overlay: bool,
+    // 前のフレームを混ぜる割合(0 - 100%)。液晶の残像を真似る
+    pub ghosting: u8,
+    // 部品ごとのログの詳しさ。"mbc=debug,cpu=trace" のように書く
+    pub log: String,
+    // 実機の不具合を再現するか
+    pub accuracy: Accuracy,
+    // STOP を NOP として扱う。STOP で止まったまま動かなくなる ROM 用
+    pub stop_as_nop: bool,
+    // 電源投入時の RAM の中身
+    pub ram_init: RamInit,
+    // ヘッダと ROM が食い違うときの MBC の選び方
+    pub mbc: MbcSelect,
+    // このフレーム数を描画したら終了する
+    pub exit_after_frames: Option<u64>,
+    // シリアル通信でこの文字列を送信したら終了する
+    pub exit_on_serial: Option<String>,
+    // PC がこのサイクル数だけ狭い範囲に留まったら止まったとみなす
+    pub watchdog: Option<u64>,
+    // VBlank ごとに書き込み直すアドレスと値(チート)
+    pub freezes: Vec<(u16, u8)>,
+    // 鳴らした音を書き出す WAV ファイルと、実機で何秒分を書き出すか(None なら終了するまで)
+    pub dump_audio: Option<String>,
+    pub dump_audio_seconds: Option<u32>,
+}
+
+impl Config {
+    pub fn new(args: &[String]) -> Result<Config, String> {
+        if args.len() < 2 {
+            return Err("Several arguments are missing.".to_string());
+        }
+        let mut config = Config::with_rom(&args[1]);
+        let header = read_header(&config.rom_file);
+        if let Some(entry) = header
+            .as_ref()
+            .and_then(|(title, checksum)| lookup_compat(title, *checksum))
+        {
+            config.apply_compat(entry);
+        }
+
+        // 引数で上書きするので設定ファイルを先に読み込む
+        let config_file = args[2..]
+            .windows(2)
+            .find(|w| w[0] == "--config")
+            .map(|w| PathBuf::from(&w[1]));
+        if let Some(path) = config_file.clone().or_else(default_config_file) {
+            match fs::read_to_string(&path) {
+                Ok(text) => {
+                    let ids = game_ids(&header);
+                    config
+                        .load(&text, &ids)
+                        .map_err(|e| format!("{}: {}", path.display(), e))?;
+                }
+                // 既定の設定ファイルは無くても良い
+                Err(e) if config_file.is_none() && e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("{}: {}", path.display(), e)),
+            }
+        }
+
+        let mut options = args[2..].iter();
+        while let Some(option) = options.next() {
+            let mut value = || {
+                options
+                    .next()
+                    .ok_or_else(|| format!("{} requires a value.", option))
+            };
+            match option.as_str() {
+                "--debug" => config.debug = true,
+                #[cfg(feature = "tui")]
+                "--tui" => config.tui = true,
+                "--fps" => config.show_fps = true,
+                "--overlay" => config.show_overlay = true,
+                "--stop-as-nop" => config.stop_as_nop = true,
+                "--scale" => {
+                    let scale = value()?
+                        .parse()
+                        .map_err(|_| "--scale requires a number.".to_string())?;
+                    config.set_scale(scale)?
+                }
+                "--crop" => config.resize.crop = Some(value()?.parse()?),
+                "--colorize" => config.colorization = parse_colorization(value()?)?,
+                "--postprocess" => config.postprocess = Filter::parse_list(value()?)?,
+                "--dither" => config.braille.dither = true,
+                "--invert" => config.braille.invert = true,
+                "--threshold" => {
+                    config.braille.threshold = value()?
+                        .parse()
+                        .map_err(|_| "--threshold requires a number from 0 to 255.".to_string())?
+                }
+                "--ramp" => config.ascii.ramp = AsciiStyle::parse_ramp(value()?)?,
+                "--aspect" => config.ascii.aspect = true,
+                "--log" => config.set_log(value()?)?,
+                "--config" => {
+                    value()?;
+                }
+                "--frontend" => config.frontend = value()?.parse()?,
+                "--accuracy" => config.accuracy = value()?.parse()?,
+                "--ram-init" => config.ram_init = parse_ram_init(value()?)?,
+                "--mbc" => config.mbc = value()?.parse()?,
+                "--input" => config.input = value()?.parse()?,
+                "--key-hold" => {
+                    config.key_hold = value()?
+                        .parse()
+                        .map_err(|_| "--key-hold requires milliseconds.".to_string())?
+                }
+                "--ghosting" => {
+                    config.ghosting = value()?
+                        .parse()
+                        .ok()
+                        .filter(|w| *w <= 100)
+                        .ok_or_else(|| "--ghosting requires a percentage.".to_string())?
+                }
+                "--speed" => {
+                    config.speed = value()?
+                        .parse()
+                        .map_err(|_| "--speed requires a number.".to_string())?
+                }
+                "--frameskip" => config.frameskip = value()?.parse()?,
+                "--exit-after-frames" => {
+                    let frames = value()?
+                        .parse()
+                        .map_err(|_| "--exit-after-frames requires a number.".to_string())?;
+                    config.exit_after_frames = Some(frames);
+                }
+                "--exit-on-serial" => config.exit_on_serial = Some(value()?.clone()),
+                "--freeze" => config.freezes.push(parse_freeze(value()?)?),
+                "--dump-audio" => config.dump_audio = Some(value()?.clone()),
+                "--dump-audio-seconds" => {
+                    let seconds = value()?
+                        .parse()
+                        .map_err(|_| "--dump-audio-seconds requires a number.".to_string())?;
+                    config.dump_audio_seconds = Some(seconds);
+                }
+                "--watchdog" => {
+                    let millions: u64 = value()?
+                        .parse()
+                        .map_err(|_| "--watchdog requires millions of cycles.".to_string())?;
+                    config.watchdog = Some(millions * 1_000_000);
+                }
+                "--save-dir" => config.save_dir = Some(value()?.clone()),
+                "--patch" => config.patch = Some(value()?.clone()),
+                "--link" => config.link = Some(LinkMode::Connect(value()?.clone())),
+                "--link-listen" => {
+                    let port = value()?
+                        .parse()
+                        .map_err(|_| "--link-listen requires a port number.".to_string())?;
+                    config.link = Some(LinkMode::Listen(port));
+                }
+                #[cfg(feature = "recorder")]
+                "--record" => config.record_file = Some(value()?.clone()),
+                #[cfg(feature = "metrics")]
+                "--metrics" => {
+                    let port = value()?
+                        .parse()
+                        .map_err(|_| "--metrics requires a port number.".to_string())?;
+                    config.metrics_port = Some(port);
+                }
+                _ => return Err("Unknown option.".to_string()),
+            }
+        }
+        Ok(config)
+    }
+
+    fn with_rom(rom_file: &str) -> Self {
+        Config {
+            rom_file: rom_file.to_string(),
+            demo: rom_file == "--demo",
+            patch: Option::None,
+            debug: false,
+            #[cfg(feature = "tui")]
+            tui: false,
+            #[cfg(feature = "recorder")]
+            record_file: Option::None,
+            #[cfg(feature = "metrics")]
+            metrics_port: Option::None,
+            frontend: Frontend::Braille,
+            braille: BrailleStyle::default(),
+            ascii: AsciiStyle::default(),
+            resize: Resize::default(),
+            postprocess: vec![],
+            palette: DEFAULT_PALETTE,
+            colorization: Option::None,
+            keys: KeyBindings::default(),
+            hotkeys: Hotkeys::default(),
+            input: InputMode::Line,
+            key_hold: DEFAULT_KEY_HOLD.as_millis() as u64,
+            save_dir: Option::None,
+            speed: 1.0,
+            frameskip: FrameSkip::Fixed(1),
+            link: Option::None,
+            show_fps: false,
+            show_overlay: false,
+            ghosting: 0,
+            log: String::new(),
+            accuracy: Accuracy::Fast,
+            stop_as_nop: false,
+            ram_init: RamInit::Zero,
+            mbc: MbcSelect::Header,
+            exit_after_frames: Option::None,
+            exit_on_serial: Option::None,
+            watchdog: Option::None,
+            freezes: vec![],
+            dump_audio: Option::None,
+            dump_audio_seconds: Option::None,
+        }
+    }
+
+    // 互換性データベースで分かっている設定を既定値の代わりにする
+    fn apply_compat(&mut self, entry: &CompatEntry) {
+        if let Some(mbc) = entry.mbc {
+            self.mbc = mbc;
+        }
+        if let Some(accuracy) = entry.accuracy {
+            self.accuracy = accuracy;
+        }
+        if let Some(stop_as_nop) = entry.stop_as_nop {
+            self.stop_as_nop = stop_as_nop;
+        }
+        if let Some(colorization) = entry.colorization {
+            self.colorization = Some(colorization);
+        }
+        if let Some(ghosting) = entry.ghosting {
+            self.ghosting = ghosting;
+        }
+    }
+
+    // 設定ファイルを反映する。ids に一致する [game."..."] があればそれも反映する
+    fn load(&mut self, text: &str, ids: &[String]) -> Result<(), String> {
+        let file: Settings = toml::from_str(text).map_err(|e| e.to_string())?;
+        self.apply(&file)?;
+        if let Some(hotkeys) = &file.hotkeys {
+            self.apply_hotkeys(hotkeys);
+        }
+        let games = file.game.unwrap_or_default();
+        for (id, game) in &games {
+            if game.hotkeys.is_some() || game.game.is_some() {
+                return Err(format!("Unknown table in [game.\"{}\"]", id));
+            }
+        }
+        for id in ids {
+            if let Some(game) = games.get(id) {
+                self.apply(game)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, settings: &Settings) -> Result<(), String> {
+        if let Some(s) = &settings.frontend {
+            self.frontend = s.parse()?;
+        }
+        if let Some(s) = &settings.accuracy {
+            self.accuracy = s.parse()?;
+        }
+        if let Some(b) = settings.stop_as_nop {
+            self.stop_as_nop = b;
+        }
+        if let Some(s) = &settings.ram_init {
+            self.ram_init = parse_ram_init(s)?;
+        }
+        if let Some(s) = &settings.mbc {
+            self.mbc = s.parse()?;
+        }
+        if let Some(n) = settings.speed {
+            self.speed = n;
+        }
+        match &settings.frameskip {
+            Some(FrameSkipSetting::Fixed(n)) => self.frameskip = n.to_string().parse()?,
+            Some(FrameSkipSetting::Text(s)) => self.frameskip = s.parse()?,
+            None => {}
+        }
+        if let Some(s) = &settings.input {
+            self.input = s.parse()?;
+        }
+        if let Some(n) = settings.key_hold {
+            self.key_hold = n;
+        }
+        if let Some(b) = settings.show_fps {
+            self.show_fps = b;
+        }
+        if let Some(b) = settings.show_overlay {
+            self.show_overlay = b;
+        }
+        if let Some(n) = settings.ghosting {
+            if n > 100 {
+                return Err("ghosting requires a percentage.".to_string());
+            }
+            self.ghosting = n;
+        }
+        if let Some(n) = settings.scale {
+            self.set_scale(n)?;
+        }
+        if let Some(s) = &settings.crop {
+            self.resize.crop = Some(s.parse()?);
+        }
+        if let Some(s) = &settings.postprocess {
+            self.postprocess = Filter::parse_list(s)?;
+        }
+        if let Some(b) = settings.dither {
+            self.braille.dither = b;
+        }
+        if let Some(b) = settings.invert {
+            self.braille.invert = b;
+        }
+        if let Some(n) = settings.threshold {
+            self.braille.threshold = n;
+        }
+        if let Some(s) = &settings.ramp {
+            self.ascii.ramp = AsciiStyle::parse_ramp(s)?;
+        }
+        if let Some(b) = settings.aspect {
+            self.ascii.aspect = b;
+        }
+        if let Some(s) = &settings.log {
+            self.set_log(s)?;
+        }
+        if let Some(s) = &settings.save_dir {
+            self.save_dir = Some(expand_home(s));
+        }
+        if let Some(s) = &settings.patch {
+            self.patch = Some(expand_home(s));
+        }
+        // palette を指定したら色付けをやめる。同じ表の colorize の方が優先される
+        if let Some(colors) = &settings.palette {
+            self.palette = parse_palette(colors)?;
+            self.colorization = None;
+        }
+        if let Some(s) = &settings.colorize {
+            self.colorization = parse_colorization(s)?;
+        }
+        if let Some(values) = &settings.freeze {
+            self.freezes = values
+                .iter()
+                .map(|s| parse_freeze(s))
+                .collect::<Result<_, _>>()?;
+        }
+        if let Some(keys) = &settings.keys {
+            self.apply_keys(keys);
+        }
+        Ok(())
+    }
+
+    // 拡大は画素の大きさが揃うように整数倍だけにする
+    fn set_scale(&mut self, scale: f64) -> Result<(), String> {
+        if !(scale > 0.0 && (scale <= 1.0 || scale.fract() == 0.0 && scale <= 8.0)) {
+            return Err("scale requires a number from 0 to 1, or an integer up to 8.".to_string());
+        }
+        self.resize.scale = scale;
+        Ok(())
+    }
+
+    // 設定ファイルと引数の両方に書かれていれば、後から指定した部品のレベルが優先される
+    fn set_log(&mut self, spec: &str) -> Result<(), String> {
+        logger::parse(spec)?;
+        if !self.log.is_empty() {
+            self.log.push(',');
+        }
+        self.log.push_str(spec);
+        Ok(())
+    }
+
+    fn apply_keys(&mut self, keys: &KeyTable) {
+        let bindings = [
+            (keys.a, &mut self.keys.a),
+            (keys.b, &mut self.keys.b),
+            (keys.start, &mut self.keys.start),
+            (keys.select, &mut self.keys.select),
+            (keys.up, &mut self.keys.up),
+            (keys.down, &mut self.keys.down),
+            (keys.left, &mut self.keys.left),
+            (keys.right, &mut self.keys.right),
+        ];
+        for (key, binding) in bindings {
+            if let Some(c) = key {
+                *binding = c;
+            }
+        }
+    }
+
+    fn apply_hotkeys(&mut self, hotkeys: &HotkeyTable) {
+        let bindings = [
+            (hotkeys.pause, &mut self.hotkeys.pause),
+            (hotkeys.frame_advance, &mut self.hotkeys.frame_advance),
+            (hotkeys.fast_forward, &mut self.hotkeys.fast_forward),
+            (hotkeys.slow_motion, &mut self.hotkeys.slow_motion),
+        ];
+        for (key, binding) in bindings {
+            if let Some(c) = key {
+                *binding = c;
+            }
+        }
+    }
+}
+
+/// 設定ファイルに書ける値。トップレベルと [game."..."] で同じものを使う
+// 書かれていない値は None で、既定値や前の設定をそのまま残す
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Settings {
+    frontend: Option<String>,
+    accuracy: Option<String>,
+    stop_as_nop: Option<bool>,
+    ram_init: Option<String>,
+    mbc: Option<String>,
+    speed: Option<f64>,
+    frameskip: Option<FrameSkipSetting>,
+    input: Option<String>,
+    key_hold: Option<u64>,
+    show_fps: Option<bool>,
+    show_overlay: Option<bool>,
+    ghosting: Option<u8>,
+    scale: Option<f64>,
+    crop: Option<String>,
+    postprocess: Option<String>,
+    dither: Option<bool>,
+    invert: Option<bool>,
+    threshold: Option<u8>,
+    ramp: Option<String>,
+    aspect: Option<bool>,
+    log: Option<String>,
+    save_dir: Option<String>,
+    patch: Option<String>,
+    palette: Option<Vec<String>>,
+    colorize: Option<String>,
+    freeze: Option<Vec<String>>,
+    keys: Option<KeyTable>,
+    // [hotkeys] と [game."..."] はトップレベルにだけ書ける
+    hotkeys: Option<HotkeyTable>,
+    game: Option<BTreeMap<String, Settings>>,
+}
+
+// frameskip = 2 か frameskip = "auto"
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FrameSkipSetting {
+    Fixed(u32),
+    Text(String),
+}
+
+// キーの割り当ては1文字
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeyTable {
+    a: Option<char>,
+    b: Option<char>,
+    start: Option<char>,
+    select: Option<char>,
+    up: Option<char>,
+    down: Option<char>,
+    left: Option<char>,
+    right: Option<char>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HotkeyTable {
+    pause: Option<char>,
+    frame_advance: Option<char>,
+    fast_forward: Option<char>,
+    slow_motion: Option<char>,
+}
+
+// $XDG_CONFIG_HOME/rustboy/config.toml か ~/.config/rustboy/config.toml
+fn default_config_file() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("rustboy").join("config.toml"))
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home, rest),
+        _ => path.to_string(),
+    }
+}
+
+// ヘッダのタイトルとグローバルチェックサム。読めなければ None
+fn read_header(rom_file: &str) -> Option<(String, u16)> {
+    let mut header = Vec::new();
+    let read = File::open(rom_file).and_then(|f| f.take(0x150).read_to_end(&mut header));
+    if read.is_err() || header.len() < 0x150 {
+        return None;
+    }
+    let title: Vec<u8> = header[0x134..0x144]
+        .iter()
+        .take_while(|b| **b != 0)
+        .copied()
+        .collect();
+    let title = String::from_utf8_lossy(&title).trim().to_string();
+    let checksum = u16::from_be_bytes([header[0x14E], header[0x14F]]);
+    Some((title, checksum))
+}
+
+// ゲームごとの設定を探すためのタイトルとグローバルチェックサム(0xXXXX)
+fn game_ids(header: &Option<(String, u16)>) -> Vec<String> {
+    match header {
+        Some((title, checksum)) => vec![title.clone(), format!("0x{:04X}", checksum)],
+        None => vec![],
+    }
+}
+
+// "C0A0=63" のように16進数で書いたアドレスと値
+fn parse_freeze(s: &str) -> Result<(u16, u8), String> {
+    let error = || format!("Invalid freeze: {}", s);
+    let (address, value) = s.split_once('=').ok_or_else(error)?;
+    let hex = |v: &str| v.trim().trim_start_matches("0x").to_string();
+    let address = u16::from_str_radix(&hex(address), 16).map_err(|_| error())?;
+    let value = u8::from_str_radix(&hex(value), 16).map_err(|_| error())?;
+    Ok((address, value))
+}
+
+// プリセットの名前。"none" なら色を付けない
+fn parse_colorization(s: &str) -> Result<Option<Colorization>, String> {
+    match s {
+        "none" => Ok(None),
+        _ => s.parse().map(Some),
+    }
+}
+
+// "#RRGGBB" か "RRGGBB" の4色
+fn parse_palette(colors: &[String]) -> Result<[PixelData; 4], String> {
+    let mut palette = DEFAULT_PALETTE;
+    if colors.len() != palette.len() {
+        return Err("palette requires 4 colors.".to_string());
+    }
+    for (color, value) in palette.iter_mut().zip(colors) {
+        let rgb = value.trim_start_matches('#');
+        match u32::from_str_radix(rgb, 16) {
+            Ok(v) if rgb.len() == 6 => {
+                *color = PixelData((v >> 16) as u8, (v >> 8) as u8, v as u8, 0);
+            }
+            _ => return Err(format!("Invalid color: {}", rgb)),
+        }
+    }
+    Ok(palette)
+}
+
+// seed を省略した "random" は起動ごとに変える。使った seed はリセット時に cpu=info でログに出る
+fn parse_ram_init(s: &str) -> Result<RamInit, String> {
+    if s != "random" {
+        return s.parse();
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?;
+    Ok(RamInit::Random(now.as_nanos() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = r##"
+# コメント
+frontend = "terminal"  # 行末のコメント
+speed = 2
+palette = ["#E0F8D0", '88C070', "346856", "081820",]
+
+[keys]
+start = "\n"
+"select" = 's'
+
+[game."POKEMON RED".keys]
+a = "z"
+"##;
+        let file: Settings = toml::from_str(text).unwrap();
+        assert_eq!(file.frontend.as_deref(), Some("terminal"));
+        assert_eq!(file.speed, Some(2.0));
+        assert_eq!(
+            file.palette.unwrap(),
+            ["#E0F8D0", "88C070", "346856", "081820"]
+        );
+        let keys = file.keys.unwrap();
+        assert_eq!(keys.start, Some('\n'));
+        assert_eq!(keys.select, Some('s'));
+        let game = &file.game.unwrap()["POKEMON RED"];
+        assert_eq!(game.keys.as_ref().unwrap().a, Some('z'));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut config = Config::with_rom("rom.gb");
+        for text in [
+            "speed = fast",
+            "\n[keys",
+            "a = \"b",
+            "speed = 1\nspeed = 2",
+            "freeze = [\"C0A0=63\" \"C000=FF\"]",
+            "[keys]\na = \"ab\"",
+            "[hotkeys]\npause = 1",
+            "[game.\"TETRIS\".hotkeys]",
+        ] {
+            assert!(config.load(text, &[]).is_err(), "{}", text);
+        }
+        let error = config.load("\n[keys", &[]).unwrap_err();
+        assert!(error.contains("line 2"), "{}", error);
+    }
+
+    #[test]
+    fn test_load() {
+        let text = r#"
+speed = 2.0
+save_dir = "/tmp/saves"
+show_fps = true
+show_overlay = true
+ghosting = 40
+colorize = "pastel"
+log = "warn,mbc=debug"
+dither = true
+threshold = 160
+scale = 0.5
+crop = "0, 8, 160, 128"
+postprocess = "saturation=0.8,upscale=2"
+ramp = " -=@"
+aspect = true
+input = "raw"
+key_hold = 200
+ram_init = "pattern(0x55)"
+frameskip = 2
+
+[keys]
+a = "x"
+
+[hotkeys]
+pause = "q"
+
+[game."TETRIS"]
+speed = 0
+patch = "~/patches/tetris.ips"
+accuracy = "accurate"
+stop_as_nop = true
+freeze = ["C0A0=63", "0xA000 = 0x01"]
+palette = ["FFFFFF", "C0C0C0", "606060", "000000"]
+
+[game."0x1234".keys]
+b = "y"
+"#;
+        let mut config = Config::with_rom("rom.gb");
+        config.load(text, &[]).unwrap();
+        assert_eq!(config.speed, 2.0);
+        assert_eq!(config.save_dir, Some("/tmp/saves".to_string()));
+        assert!(config.show_fps);
+        assert!(config.show_overlay);
+        assert_eq!(config.ghosting, 40);
+        assert_eq!(config.colorization, Some(Colorization::PASTEL));
+        assert_eq!(config.log, "warn,mbc=debug");
+        assert!(config.braille.dither);
+        assert_eq!(config.braille.threshold, 160);
+        assert_eq!(config.resize.scale, 0.5);
+        assert_eq!(config.resize.crop.unwrap().y, 8);
+        assert_eq!(
+            config.postprocess,
+            [Filter::Saturation(0.8), Filter::Upscale(2)]
+        );
+        assert_eq!(config.ascii.ramp, vec![' ', '-', '=', '@']);
+        assert!(config.ascii.aspect);
+        assert_eq!(config.input, InputMode::Raw);
+        assert_eq!(config.key_hold, 200);
+        assert_eq!(config.ram_init, RamInit::Pattern(0x55));
+        assert_eq!(config.frameskip, FrameSkip::Fixed(2));
+        assert_eq!(config.keys.a, 'x');
+        assert_eq!(config.keys.b, 'b');
+        assert_eq!(config.hotkeys.pause, 'q');
+        assert_eq!(config.hotkeys.fast_forward, 'f');
+        assert!(config.palette == DEFAULT_PALETTE);
+
+        let mut config = Config::with_rom("rom.gb");
+        let ids = vec!["TETRIS".to_string(), "0x1234".to_string()];
+        config.load(text, &ids).unwrap();
+        assert_eq!(config.speed, 0.0);
+        assert_eq!(config.accuracy, Accuracy::Accurate);
+        assert!(config.stop_as_nop);
+        assert_eq!(config.freezes, [(0xC0A0, 0x63), (0xA000, 0x01)]);
+        assert!(config.patch.unwrap().ends_with("/patches/tetris.ips"));
+        assert_eq!(config.keys.a, 'x');
+        assert_eq!(config.keys.b, 'y');
+        assert!(config.palette[1] == PixelData(0xC0, 0xC0, 0xC0, 0));
+        // ゲームごとの palette で色付けをやめる
+        assert_eq!(config.colorization, None);
+
+        let mut config = Config::with_rom("rom.gb");
+        assert!(config.load("speed = \"fast\"", &[]).is_err());
+        assert!(config.load("[keys]\nturbo = \"t\"", &[]).is_err());
+        assert!(config.load("[sound]", &[]).is_err());
+        assert!(config.load("log = \"gpu=debug\"", &[]).is_err());
+        assert!(config.load("threshold = 256", &[]).is_err());
+        assert!(config.load("input = \"mouse\"", &[]).is_err());
+        assert!(config.load("ram_init = \"ones\"", &[]).is_err());
+        config.load("ram_init = \"random\"", &[]).unwrap();
+        assert!(matches!(config.ram_init, RamInit::Random(_)));
+        config.load("frameskip = \"auto\"", &[]).unwrap();
+        assert_eq!(config.frameskip, FrameSkip::Auto);
+        assert!(config.load("frameskip = 0", &[]).is_err());
+        assert!(config.load("scale = 0", &[]).is_err());
+        assert!(config.load("scale = 1.5", &[]).is_err());
+        config.load("scale = 3", &[]).unwrap();
+        assert_eq!(config.resize.scale, 3.0);
+        assert!(config.load("crop = \"0,0,160\"", &[]).is_err());
+        assert!(config.load("ramp = \"#\"", &[]).is_err());
+        assert!(config.load("colorize = \"sepia\"", &[]).is_err());
+        assert!(config.load("freeze = [\"C0A0\"]", &[]).is_err());
+        assert!(config.load("freeze = [\"C0A0=100\"]", &[]).is_err());
+    }
+
+    #[test]
+    fn test_args_override_config_file() {
+        let path = env::temp_dir().join(format!("rustboy-config-{}.toml", std::process::id()));
+        fs::write(&path, "frontend = \"terminal\"\nspeed = 3\n").unwrap();
+        let args: Vec<String> = [
+            "rustboy",
+            "rom.gb",
+            "--config",
+            path.to_str().unwrap(),
+            "--speed",
+            "0.5",
+            "--link-listen",
+            "5000",
+            "--mbc",
+            "auto",
+            "--exit-after-frames",
+            "3600",
+            "--exit-on-serial",
+            "Passed",
+            "--watchdog",
+            "50",
+            "--ghosting",
+            "30",
+            "--stop-as-nop",
+            "--colorize",
+            "dark-green",
+            "--freeze",
+            "FF90=01",
+            "--freeze",
+            "C000=FF",
+            "--dump-audio",
+            "out.wav",
+            "--dump-audio-seconds",
+            "10",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let config = Config::new(&args).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(config.frontend, Frontend::Terminal);
+        assert_eq!(config.speed, 0.5);
+        assert_eq!(config.link, Some(LinkMode::Listen(5000)));
+        assert_eq!(config.mbc, MbcSelect::Auto);
+        assert_eq!(config.exit_after_frames, Some(3600));
+        assert_eq!(config.exit_on_serial, Some("Passed".to_string()));
+        assert_eq!(config.watchdog, Some(50_000_000));
+        assert_eq!(config.ghosting, 30);
+        assert!(config.stop_as_nop);
+        assert_eq!(config.colorization, Some(Colorization::DARK_GREEN));
+        assert_eq!(config.freezes, [(0xFF90, 0x01), (0xC000, 0xFF)]);
+        assert_eq!(config.dump_audio, Some("out.wav".to_string()));
+        assert_eq!(config.dump_audio_seconds, Some(10));
+
+        let args: Vec<String> = ["rustboy", "rom.gb", "--config", "/nonexistent/config.toml"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert!(Config::new(&args).is_err());
+    }
+
+    #[test]
+    fn test_compat() {
+        let dir = env::temp_dir().join(format!("rustboy-compat-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let rom = dir.join("oam_bug.gb");
+        let mut header = vec![0; 0x150];
+        header[0x134..0x13B].copy_from_slice(b"OAM_BUG");
+        fs::write(&rom, header).unwrap();
+        let config_file = dir.join("config.toml");
+        fs::write(&config_file, "[game.\"OAM_BUG\"]\nghosting = 20\n").unwrap();
+        let args = |extra: &[&str]| -> Vec<String> {
+            let rom = rom.to_str().unwrap();
+            let config_file = config_file.to_str().unwrap();
+            ["rustboy", rom, "--config", config_file]
+                .iter()
+                .chain(extra)
+                .map(|s| s.to_string())
+                .collect()
+        };
+        // データベースの設定を既定値にして、設定ファイルと引数で上書きする
+        let config = Config::new(&args(&[])).unwrap();
+        assert_eq!(config.accuracy, Accuracy::Accurate);
+        assert_eq!(config.ghosting, 20);
+        let config = Config::new(&args(&["--accuracy", "fast"])).unwrap();
+        assert_eq!(config.accuracy, Accuracy::Fast);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
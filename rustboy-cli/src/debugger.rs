@@ -0,0 +1,482 @@
+use std::fs;
+use std::io::{self, stdin, stdout, Write};
+use std::path::PathBuf;
+use std::process::exit;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use rustboy::{io_register_name, Bus, CpuError, MotherBoard, CPU};
+
+use crate::input;
+use crate::logger;
+use crate::session::{self, Session};
+
+// 1行読む。SIGINT などで終了を求められたら入力を待たずに None を返す
+fn prompt(message: &str) -> Option<String> {
+    if input::quit_requested() {
+        return None;
+    }
+    print!("{}", message);
+    stdout().flush().ok();
+    // 読み込みは止められないので別のスレッドで待つ
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut input = String::new();
+        stdin().read_line(&mut input).ok();
+        tx.send(input).ok();
+    });
+    loop {
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(input) => return Some(input.trim().to_string()),
+            Err(RecvTimeoutError::Timeout) if !input::quit_requested() => {}
+            Err(_) => return None,
+        }
+    }
+}
+
+fn parse_address(arg: &str) -> Option<u16> {
+    u16::from_str_radix(arg.trim_start_matches("0x"), 16).ok()
+}
+
+// `log <subsystem> on|off|<level>` を --log と同じ書式にする。on は debug、all は全部品
+fn log_spec(subsystem: &str, level: &str) -> String {
+    let level = if level == "on" { "debug" } else { level };
+    match subsystem {
+        "all" => level.to_string(),
+        _ => format!("{}={}", subsystem, level),
+    }
+}
+
+// 16バイトずつ表示する。IO レジスタを読んでも状態が変わらないように peek を使う
+fn print_memory(mb: &MotherBoard, address: u16, len: usize) {
+    for line in memory_lines(address, &mb.read_range(address, len)) {
+        println!("{}", line);
+    }
+}
+
+// IO レジスタを含む行には「名前=値」を添える
+fn memory_lines(address: u16, data: &[u8]) -> Vec<String> {
+    data.chunks(16)
+        .enumerate()
+        .map(|(i, line)| {
+            let start = address.wrapping_add(i as u16 * 16);
+            let bytes: Vec<String> = line.iter().map(|b| format!("{:02X}", b)).collect();
+            let registers: Vec<String> = line
+                .iter()
+                .enumerate()
+                .filter_map(|(j, b)| {
+                    io_register_name(start.wrapping_add(j as u16))
+                        .map(|name| format!("{}={:02X}", name, b))
+                })
+                .collect();
+            if registers.is_empty() {
+                format!("0x{:04X}: {}", start, bytes.join(" "))
+            } else {
+                format!(
+                    "0x{:04X}: {}  ; {}",
+                    start,
+                    bytes.join(" "),
+                    registers.join(" ")
+                )
+            }
+        })
+        .collect()
+}
+
+// 64KB のアドレス空間をそのまま書き出す。BGB のメモリダンプと同じ形式で、バイナリエディタでも開ける
+// 形式を崩さないように、ROM のタイトルとバンクなどは <file>.txt に書く
+fn dump_memory(cpu: &CPU, mb: &MotherBoard, file: &str) -> io::Result<String> {
+    let memory = mb.read_range(0x0000, 0x10000);
+    fs::write(file, &memory)?;
+    let path = format!("{}.txt", file);
+    let mbc = mb.cartridge().mbc_state().to_string();
+    fs::write(&path, dump_header(&memory, &mbc, &cpu.dump_registers()))?;
+    Ok(path)
+}
+
+// タイトルは現在のバンク 0 のヘッダ(0x0134 - 0x0143)から読む
+fn dump_header(memory: &[u8], mbc: &str, registers: &str) -> String {
+    let title: String = memory[0x0134..0x0144]
+        .iter()
+        .take_while(|b| **b != 0)
+        .map(|b| {
+            if b.is_ascii_graphic() || *b == b' ' {
+                *b as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    format!(
+        "title: {}\n{}\n{}\nmemory: 0x0000 - 0xFFFF ({} bytes)\n",
+        title.trim(),
+        mbc,
+        registers,
+        memory.len()
+    )
+}
+
+pub struct BreakPoint {
+    // ブレークポイントとコマンド履歴
+    session: Session,
+    // session save / load で読み書きするファイル
+    session_path: Option<PathBuf>,
+    should_stop: bool,
+    counter: u64,
+    // プロンプトを抜けた後にリセットする。デバッガは CPU を借用しているので、ここではリセットできない
+    reset: bool,
+}
+
+impl BreakPoint {
+    pub fn new(points: &[u16]) -> Self {
+        Self {
+            session: Session {
+                breakpoints: points.to_vec(),
+                ..Session::default()
+            },
+            session_path: None,
+            should_stop: false,
+            counter: 0,
+            reset: false,
+        }
+    }
+
+    /// path のセッションがあれば読み込む。session save / load もこのファイルを使う
+    pub fn open_session(&mut self, path: PathBuf) {
+        match session::load(&path) {
+            Ok(Some(session)) => {
+                println!("Load session: {}", path.display());
+                self.session = session;
+            }
+            Ok(None) => {}
+            Err(e) => println!("Failed to load session: {}", e),
+        }
+        self.session_path = Some(path);
+    }
+
+    /// reset コマンドが入力されていれば true を返して取り消す
+    pub fn take_reset(&mut self) -> bool {
+        std::mem::take(&mut self.reset)
+    }
+
+    pub fn breakpoint(&mut self, opcode: u16, cpu: &CPU, mb: &MotherBoard) {
+        let int = mb.interruption();
+        let timer = mb.timer();
+        println!("COUNTS: {:}", self.counter);
+        println!("CYCLES: {:}", mb.cycles());
+        println!("OPCODE: 0x{:04X?}", opcode);
+        println!("{}", cpu.dump_registers());
+        println!("{}", int.dump_interrupt_flags());
+        println!("{}", int.dump_interrupt_enables());
+        println!("{}", timer.dump_timer());
+        self.counter += 1;
+        if !self.should_stop
+            & !self.session.breakpoints.contains(&opcode)
+            & !self.session.counts.contains(&self.counter)
+        {
+            return;
+        }
+        self.should_stop = false;
+        self.interact(cpu, mb);
+    }
+
+    /// 実行できない命令に遭遇したときに呼ばれる
+    pub fn trap(&mut self, error: &CpuError, cpu: &CPU, mb: &MotherBoard) {
+        self.stop(&error.to_string(), cpu, mb);
+    }
+
+    /// 命令の外から止めるときに呼ばれる。message を出力してプロンプトに入る
+    pub fn stop(&mut self, message: &str, cpu: &CPU, mb: &MotherBoard) {
+        println!("{}", message);
+        println!("{}", cpu.dump_registers());
+        self.interact(cpu, mb);
+    }
+
+    fn interact(&mut self, cpu: &CPU, mb: &MotherBoard) {
+        let int = mb.interruption();
+        let sound = mb.sound();
+        loop {
+            // 終了はメインループに任せて、セーブデータを書き出させる
+            let Some(input) = prompt("Breakpoint >>> ") else {
+                println!("Quit");
+                break;
+            };
+            let commands: Vec<&str> = input.split(" ").collect();
+            if !input.is_empty() {
+                self.session.push_history(&input);
+            }
+            match commands[0] {
+                "continue" | "c" => {
+                    println!("Continue");
+                    break;
+                }
+                "next" | "n" => {
+                    println!("Next");
+                    self.should_stop = true;
+                    break;
+                }
+                "break" | "b" => {
+                    if let Some(arg) = commands.get(1) {
+                        let without_prefix = arg.trim_start_matches("0x");
+                        let point = u16::from_str_radix(without_prefix, 16).unwrap();
+                        self.session.breakpoints.push(point);
+                        println!("Add breakpoint: {:04X?}", point);
+                    }
+                }
+                "remove" | "r" => {
+                    if let Some(arg) = commands.get(1) {
+                        let without_prefix = arg.trim_start_matches("0x");
+                        let point = u16::from_str_radix(without_prefix, 16).unwrap();
+                        let breakpoints = &mut self.session.breakpoints;
+                        if let Some(i) = breakpoints.iter().position(|p| *p == point) {
+                            breakpoints.remove(i);
+                        }
+                        println!("Remove breakpoint: {:04X?}", point);
+                    }
+                }
+                "count" | "bc" => {
+                    if let Some(arg) = commands.get(1) {
+                        let without_prefix = arg.trim_start_matches("0x");
+                        let point = u64::from_str_radix(without_prefix, 10).unwrap();
+                        self.session.counts.push(point);
+                        println!("Add breakpoint: {:}", point);
+                    }
+                }
+                "vram" => match (commands.get(1), commands.get(2)) {
+                    // vram tiles [n]
+                    (Some(&"tiles"), Some(n)) => match n.parse() {
+                        Ok(n) => match mb.ppu().dump_tile(n) {
+                            Ok(tile) => println!("{}", tile),
+                            Err(e) => println!("{}", e),
+                        },
+                        Err(_) => println!("usage: vram tiles [n]"),
+                    },
+                    (Some(&"tiles"), None) => println!("{}", mb.ppu().dump_tiles()),
+                    // vram map [9800|9C00]
+                    (Some(&"map"), base) => {
+                        let base = base.map(|b| b.trim_start_matches("0x"));
+                        match base {
+                            None | Some("9800") => println!("{}", mb.ppu().dump_tile_map(0x9800)),
+                            Some("9C00") | Some("9c00") => {
+                                println!("{}", mb.ppu().dump_tile_map(0x9C00))
+                            }
+                            _ => println!("usage: vram map [9800|9C00]"),
+                        }
+                    }
+                    _ => println!("usage: vram tiles [n] | vram map [9800|9C00]"),
+                },
+                "oam" => println!("{}", mb.ppu().dump_oam()),
+                "bank" => {
+                    // bank | bank rom <n> | bank ram <n>
+                    let n = commands.get(2).and_then(|v| v.parse().ok());
+                    let result = match (commands.get(1), n) {
+                        (None, _) => Ok(()),
+                        (Some(&"rom"), Some(n)) => mb.cartridge().force_rom_bank(n),
+                        (Some(&"ram"), Some(n)) => mb.cartridge().force_ram_bank(n),
+                        _ => Err("usage: bank | bank rom <n> | bank ram <n>".to_string()),
+                    };
+                    match result {
+                        Ok(_) => println!("{}", mb.cartridge().mbc_state()),
+                        Err(e) => println!("{}", e),
+                    }
+                }
+                "x" => {
+                    // x <address> [len]
+                    let address = commands.get(1).and_then(|v| parse_address(v));
+                    let len = commands.get(2).and_then(|v| v.parse().ok()).unwrap_or(16);
+                    match address {
+                        Some(address) => print_memory(mb, address, len),
+                        None => println!("usage: x <address> [len]"),
+                    }
+                }
+                "set" => {
+                    // set <address> <value>
+                    let address = commands.get(1).and_then(|v| parse_address(v));
+                    let value = commands
+                        .get(2)
+                        .and_then(|v| u8::from_str_radix(v.trim_start_matches("0x"), 16).ok());
+                    match (address, value) {
+                        (Some(address), Some(value)) => {
+                            mb.poke(address, value);
+                            print_memory(mb, address, 1);
+                        }
+                        _ => println!("usage: set <address> <value>"),
+                    }
+                }
+                "freeze" => {
+                    // freeze | freeze <address> <value>
+                    let address = commands.get(1).and_then(|v| parse_address(v));
+                    let value = commands
+                        .get(2)
+                        .and_then(|v| u8::from_str_radix(v.trim_start_matches("0x"), 16).ok());
+                    match (commands.get(1), address, value) {
+                        (None, _, _) => {
+                            for (address, value) in mb.freezes() {
+                                println!("{:04X} = {:02X}", address, value);
+                            }
+                        }
+                        (_, Some(address), Some(value)) => match mb.freeze(address, value) {
+                            Ok(_) => println!("Freeze: {:04X} = {:02X}", address, value),
+                            Err(e) => println!("{}", e),
+                        },
+                        _ => println!("usage: freeze | freeze <address> <value>"),
+                    }
+                }
+                "unfreeze" => match commands.get(1).and_then(|v| parse_address(v)) {
+                    // unfreeze <address>
+                    Some(address) if mb.unfreeze(address) => println!("Unfreeze: {:04X}", address),
+                    Some(address) => println!("Not frozen: {:04X}", address),
+                    None => println!("usage: unfreeze <address>"),
+                },
+                "log" => match (commands.get(1), commands.get(2)) {
+                    // log | log <subsystem> on|off|<level>
+                    (None, _) => {
+                        for (target, level) in logger::levels() {
+                            println!("{} = {}", target, level.as_str().to_lowercase());
+                        }
+                    }
+                    (Some(subsystem), Some(level)) => {
+                        let spec = log_spec(subsystem, level);
+                        match logger::configure(&spec) {
+                            Ok(_) => println!("Log: {}", spec),
+                            Err(e) => println!("{}", e),
+                        }
+                    }
+                    _ => println!("usage: log | log <subsystem|all> on|off|<level>"),
+                },
+                "session" => match (commands.get(1), &self.session_path) {
+                    // session | session save | session load
+                    (None, _) => print!("{}", self.session.to_text()),
+                    (Some(&"save"), Some(path)) => match session::store(&self.session, path) {
+                        Ok(_) => println!("Save session: {}", path.display()),
+                        Err(e) => println!("Failed to save session: {}", e),
+                    },
+                    (Some(&"load"), Some(path)) => match session::load(path) {
+                        Ok(Some(session)) => {
+                            println!("Load session: {}", path.display());
+                            self.session = session;
+                        }
+                        Ok(None) => println!("Session not found: {}", path.display()),
+                        Err(e) => println!("Failed to load session: {}", e),
+                    },
+                    (Some(&"save" | &"load"), None) => println!("No session file"),
+                    _ => println!("usage: session | session save | session load"),
+                },
+                "dump" => match commands.get(1) {
+                    // dump <file>
+                    Some(file) => match dump_memory(cpu, mb, file) {
+                        Ok(header) => println!("Dump memory: {} ({})", file, header),
+                        Err(e) => println!("Failed to dump memory: {}", e),
+                    },
+                    None => println!("usage: dump <file>"),
+                },
+                "history" | "h" => {
+                    // history [n]
+                    let n = commands.get(1).and_then(|v| v.parse().ok()).unwrap_or(16);
+                    println!("{}", cpu.history(n));
+                }
+                "print" | "p" => match commands.get(1) {
+                    Some(&"reg") => println!("{}", cpu.dump_registers()),
+                    Some(&"ifg") => println!("{}", int.dump_interrupt_flags()),
+                    Some(&"ie") => println!("{}", int.dump_interrupt_enables()),
+                    Some(&"vram") => println!("{}", mb.ppu().dump_vram()),
+                    Some(&"stack") => println!("{:?}", mb.stack()),
+                    Some(&"count") => println!("{:?}", self.counter),
+                    Some(&"cycles") => println!("{:?}", mb.cycles()),
+                    Some(&"apu") => println!("{}", sound.dump_apu()),
+                    Some(&"timer") => println!("{}", mb.timer().dump_timer()),
+                    Some(&"serial") => println!("{:?}", mb.serial()),
+                    Some(&"sgb") => println!("{:?}", mb.ppu().sgb()),
+                    #[cfg(feature = "recorder")]
+                    Some(&"record") => println!("{}", mb.ppu().dump_recorder()),
+                    _ => println!(
+                        "available: reg, stack, vram, count, cycles, apu, timer, serial, sgb"
+                    ),
+                },
+                #[cfg(feature = "recorder")]
+                "record" | "rec" => match commands.get(1) {
+                    // record stop
+                    Some(&"stop") => match mb.ppu().stop_recording() {
+                        Ok(_) => println!("Stop recording"),
+                        Err(e) => println!("Failed to stop recording: {}", e),
+                    },
+                    // record <file> [scale] [skip]
+                    Some(file) => {
+                        let scale = commands.get(2).and_then(|v| v.parse().ok()).unwrap_or(1);
+                        let skip = commands.get(3).and_then(|v| v.parse().ok()).unwrap_or(1);
+                        match mb.ppu().start_recording(file, scale, skip) {
+                            Ok(_) => println!("Start recording: {}", file),
+                            Err(e) => println!("Failed to start recording: {}", e),
+                        }
+                    }
+                    None => {
+                        println!("usage: record <file.gif|file.png> [scale] [skip] | record stop")
+                    }
+                },
+                "wave" | "w" => match commands.get(1) {
+                    // wave <file> [seconds]
+                    Some(file) => {
+                        let seconds = commands.get(2).and_then(|v| v.parse().ok()).unwrap_or(1);
+                        match sound.dump_wave(file, seconds) {
+                            Ok(_) => println!("Dump wave channel: {}", file),
+                            Err(e) => println!("Failed to dump wave channel: {}", e),
+                        }
+                    }
+                    None => println!("usage: wave <file.wav> [seconds]"),
+                },
+                "reset" => {
+                    println!("Reset");
+                    self.reset = true;
+                    break;
+                }
+                "quit" | "q" => {
+                    #[cfg(feature = "recorder")]
+                    mb.ppu().stop_recording().ok();
+                    println!("Bye");
+                    exit(0);
+                }
+                "" => {}
+                _ => {
+                    println!("Command not found");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_header() {
+        let mut memory = vec![0; 0x10000];
+        memory[0x0134..0x013B].copy_from_slice(b"TETRIS\x01");
+        let header = dump_header(&memory, "ROM bank: 1/2", "PC: 0150");
+        assert_eq!(
+            header,
+            "title: TETRIS.\nROM bank: 1/2\nPC: 0150\nmemory: 0x0000 - 0xFFFF (65536 bytes)\n"
+        );
+    }
+
+    #[test]
+    fn test_memory_lines() {
+        assert_eq!(
+            memory_lines(0xC000, &[0x01, 0x02]),
+            ["0xC000: 01 02".to_string()]
+        );
+        assert_eq!(
+            memory_lines(0xFF44, &[0x90, 0x00, 0xC1]),
+            ["0xFF44: 90 00 C1  ; LY=90 LYC=00 DMA=C1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_log_spec() {
+        assert_eq!(log_spec("mbc", "on"), "mbc=debug");
+        assert_eq!(log_spec("ppu", "trace"), "ppu=trace");
+        assert_eq!(log_spec("all", "off"), "off");
+        assert!(logger::parse(&log_spec("gpu", "on")).is_err());
+    }
+}
@@ -0,0 +1,307 @@
+use std::fs;
+use std::ops::Range;
+
+use rustboy::{cb_mnemonic, io_register_name, mnemonic, Rom, BANK_SIZE_ROM};
+
+// ヘッダの領域。命令ではなくデータとして出力する
+const HEADER: Range<usize> = 0x0104..0x0150;
+
+// ヘッダの項目 (開始位置, バイト数, 名前)
+// https://gbdev.io/pandocs/The_Cartridge_Header.html
+const HEADER_FIELDS: [(usize, usize, &str); 13] = [
+    (0x0104, 0x30, "Nintendo logo"),
+    (0x0134, 0x0F, "Title"),
+    (0x0143, 0x01, "CGB flag"),
+    (0x0144, 0x02, "New licensee code"),
+    (0x0146, 0x01, "SGB flag"),
+    (0x0147, 0x01, "Cartridge type"),
+    (0x0148, 0x01, "ROM size"),
+    (0x0149, 0x01, "RAM size"),
+    (0x014A, 0x01, "Destination code"),
+    (0x014B, 0x01, "Old licensee code"),
+    (0x014C, 0x01, "Mask ROM version"),
+    (0x014D, 0x01, "Header checksum"),
+    (0x014E, 0x02, "Global checksum"),
+];
+
+// RST と割り込みの飛び先、電源投入後の開始位置
+const VECTORS: [(usize, &str); 14] = [
+    (0x0000, "RST 00H"),
+    (0x0008, "RST 08H"),
+    (0x0010, "RST 10H"),
+    (0x0018, "RST 18H"),
+    (0x0020, "RST 20H"),
+    (0x0028, "RST 28H"),
+    (0x0030, "RST 30H"),
+    (0x0038, "RST 38H"),
+    (0x0040, "VBlank interrupt"),
+    (0x0048, "LCD STAT interrupt"),
+    (0x0050, "Timer interrupt"),
+    (0x0058, "Serial interrupt"),
+    (0x0060, "Joypad interrupt"),
+    (0x0100, "Entry point"),
+];
+
+// データとして出力するときの1行のバイト数
+const DATA_WIDTH: usize = 8;
+
+/// ROM の start バイト目から len バイトを逆アセンブルする
+/// 1行に1命令を「バンク:アドレス バイト列 ニーモニック」の形で出力する
+/// ヘッダは項目ごとのデータとして出力し、既知の飛び先には名前を付ける
+pub fn disassemble(rom: &[u8], start: usize, len: usize) -> String {
+    let end = start.saturating_add(len).min(rom.len());
+    let mut lines = Vec::new();
+    let mut offset = start;
+    while offset < end {
+        if let Some((_, name)) = VECTORS.iter().find(|(o, _)| *o == offset) {
+            lines.push(format!("; {}", name));
+        }
+        if HEADER.contains(&offset) {
+            let (field, size, name) = HEADER_FIELDS
+                .iter()
+                .find(|(o, size, _)| (*o..o + size).contains(&offset))
+                .unwrap();
+            if *field == offset {
+                lines.push(format!("; {}", name));
+            }
+            let size = (field + size).min(end).min(offset + DATA_WIDTH) - offset;
+            lines.push(data(offset, &rom[offset..offset + size]));
+            offset += size;
+            continue;
+        }
+        let size = match rom[offset] {
+            0xCB => 2,
+            opcode => mnemonic(opcode).map_or(1, |(_, size)| size as usize),
+        };
+        // 範囲の終わりやヘッダにかかる命令は読めないのでデータとして出力する
+        let limit = if offset < HEADER.start {
+            end.min(HEADER.start)
+        } else {
+            end
+        };
+        let bytes = &rom[offset..offset + size.min(limit - offset)];
+        if bytes.len() < size || (size == 1 && mnemonic(bytes[0]).is_none()) {
+            lines.push(data(offset, bytes));
+        } else {
+            lines.push(format!(
+                "{}  {:<9} {}",
+                address(offset),
+                hex(bytes),
+                instruction(address_in_bank(offset), bytes)
+            ));
+        }
+        offset += bytes.len();
+    }
+    lines.join("\n") + "\n"
+}
+
+/// pc から始まる bytes を1命令として読み、ニーモニックとバイト数を返す
+/// 未定義の命令や途中で切れた命令は 1 バイトのデータとする。デバッガが実行中のメモリを読むのに使う
+pub fn decode(pc: u16, bytes: &[u8]) -> (String, usize) {
+    let size = match bytes.first() {
+        Some(0xCB) => 2,
+        Some(opcode) => mnemonic(*opcode).map_or(1, |(_, size)| size as usize),
+        None => return (String::new(), 0),
+    };
+    if bytes.len() < size || (size == 1 && mnemonic(bytes[0]).is_none()) {
+        return (format!("db 0x{:02X}", bytes[0]), 1);
+    }
+    (instruction(pc, &bytes[..size]), size)
+}
+
+// バンク番号と CPU から見えるアドレス。バンク1以降は 0x4000 - 0x7FFF に見える
+fn address(offset: usize) -> String {
+    format!(
+        "{:02X}:{:04X}",
+        offset / BANK_SIZE_ROM,
+        address_in_bank(offset)
+    )
+}
+
+fn address_in_bank(offset: usize) -> u16 {
+    if offset < BANK_SIZE_ROM {
+        offset as u16
+    } else {
+        (BANK_SIZE_ROM + offset % BANK_SIZE_ROM) as u16
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn data(offset: usize, bytes: &[u8]) -> String {
+    let values = bytes
+        .iter()
+        .map(|b| format!("0x{:02X}", b))
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!("{}  db {}", address(offset), values)
+}
+
+// オペランドを値に置き換えたニーモニック
+fn instruction(pc: u16, bytes: &[u8]) -> String {
+    let (template, _) = match bytes {
+        [0xCB, opcode] => return cb_mnemonic(*opcode),
+        [opcode, ..] => mnemonic(*opcode).unwrap(),
+        [] => unreachable!(),
+    };
+    match bytes[1..] {
+        [n] => {
+            let offset = n as i8;
+            let signed = if offset < 0 {
+                format!("-0x{:02X}", offset.unsigned_abs())
+            } else {
+                format!("+0x{:02X}", offset)
+            };
+            // JR の飛び先は次の命令からの相対位置
+            let r8 = if template.starts_with("JR") {
+                let target = pc.wrapping_add(2).wrapping_add(offset as u16);
+                format!("0x{:04X}", target)
+            } else {
+                signed.clone()
+            };
+            let text = template
+                .replace("d8", &format!("0x{:02X}", n))
+                .replace("a8", &format!("0xFF{:02X}", n))
+                .replace("+r8", &signed)
+                .replace("r8", &r8);
+            // LDH は IO レジスタを読み書きすることが多いので名前を添える
+            match io_register_name(0xFF00 | n as u16) {
+                Some(name) if template.contains("a8") => format!("{}  ; {}", text, name),
+                _ => text,
+            }
+        }
+        [lo, hi] => {
+            let nn = format!("0x{:04X}", u16::from_le_bytes([lo, hi]));
+            template.replace("d16", &nn).replace("a16", &nn)
+        }
+        _ => template.to_string(),
+    }
+}
+
+// 0x から始まれば16進数、それ以外は10進数
+fn parse_number(s: &str) -> Option<usize> {
+    match s.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+/// `rustboy disasm <rom> [--start 0x150] [--len N] [--output <file>]`
+/// ROM を逆アセンブルして標準出力かファイルに書き出す。既定では ROM 全体
+pub fn command(args: &[String]) -> Result<(), String> {
+    let usage = || {
+        "usage: rustboy disasm <rom> [--start <offset>] [--len <bytes>] [--output <file>]"
+            .to_string()
+    };
+    let rom = match args {
+        [_, _, rom, ..] => rom,
+        _ => return Err(usage()),
+    };
+    let mut start = 0;
+    let mut len = usize::MAX;
+    let mut output = None;
+    let mut options = args[3..].iter();
+    while let Some(option) = options.next() {
+        let value = options.next().ok_or_else(usage)?;
+        match option.as_str() {
+            "--start" => start = parse_number(value).ok_or_else(usage)?,
+            "--len" => len = parse_number(value).ok_or_else(usage)?,
+            "--output" => output = Some(value),
+            _ => return Err(usage()),
+        }
+    }
+    let data = Rom::open(rom)?;
+    let data = data.bytes();
+    if start >= data.len() {
+        return Err(format!(
+            "start 0x{:X} is out of the ROM ({} bytes)",
+            start,
+            data.len()
+        ));
+    }
+    let listing = disassemble(data, start, len);
+    match output {
+        Some(file) => {
+            fs::write(file, listing).map_err(|e| format!("{}: {}", file, e))?;
+            println!("Disassembled {} to {}", rom, file);
+        }
+        None => print!("{}", listing),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instructions() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x0150..0x015E].copy_from_slice(&[
+            0x3E, 0x05, // LD A, 0x05
+            0xE0, 0xFF, // LDH (0xFFFF), A ; IE
+            0xC3, 0x50, 0x01, // JP 0x0150
+            0x18, 0xF5, // JR 0x014E
+            0xCB, 0x7C, // BIT 7, H
+            0xF8, 0xFE, // LD HL, SP-0x02
+            0xD3, // 未定義
+        ]);
+        assert_eq!(
+            disassemble(&rom, 0x0150, 14),
+            "00:0150  3E 05     LD A, 0x05\n\
+             00:0152  E0 FF     LDH (0xFFFF), A  ; IE\n\
+             00:0154  C3 50 01  JP 0x0150\n\
+             00:0157  18 F5     JR 0x014E\n\
+             00:0159  CB 7C     BIT 7, H\n\
+             00:015B  F8 FE     LD HL, SP-0x02\n\
+             00:015D  db 0xD3\n"
+        );
+        // 範囲の終わりにかかる命令はデータになる
+        assert_eq!(disassemble(&rom, 0x0154, 2), "00:0154  db 0xC3, 0x50\n");
+        // バンク1以降は 0x4000 - 0x7FFF のアドレスで表示する
+        rom[0x4000] = 0x01;
+        assert_eq!(
+            disassemble(&rom, 0x4000, 3),
+            "01:4000  01 00 00  LD BC, 0x0000\n"
+        );
+        // メモリ上の1命令
+        assert_eq!(decode(0x0157, &rom[0x0157..0x015A]), ("JR 0x014E".to_string(), 2));
+        assert_eq!(decode(0x0154, &rom[0x0154..0x0156]), ("db 0xC3".to_string(), 1));
+        assert_eq!(decode(0, &[0xF0, 0x44]).0, "LDH A, (0xFF44)  ; LY");
+        // 名前の無いアドレスには付けない
+        assert_eq!(decode(0, &[0xF0, 0x80]).0, "LDH A, (0xFF80)");
+    }
+
+    #[test]
+    fn test_header() {
+        let mut rom = vec![0; 0x8000];
+        rom[0x0101..0x0104].copy_from_slice(&[0xC3, 0x50, 0x01]);
+        rom[0x0134..0x0138].copy_from_slice(b"TEST");
+        let listing = disassemble(&rom, 0x0100, 0x50);
+        let lines: Vec<&str> = listing.lines().collect();
+        assert_eq!(
+            lines[..5],
+            [
+                "; Entry point",
+                "00:0100  00        NOP",
+                "00:0101  C3 50 01  JP 0x0150",
+                "; Nintendo logo",
+                "00:0104  db 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00",
+            ]
+        );
+        assert!(listing
+            .contains("; Title\n00:0134  db 0x54, 0x45, 0x53, 0x54, 0x00, 0x00, 0x00, 0x00\n"));
+        assert!(listing.ends_with("; Global checksum\n00:014E  db 0x00, 0x00\n"));
+        // ヘッダにかかる命令はデータになる
+        rom[0x0103] = 0x01;
+        assert!(disassemble(&rom, 0x0103, 2).starts_with("00:0103  db 0x01\n; Nintendo logo\n"));
+        // 割り込みの飛び先
+        assert!(disassemble(&rom, 0x0040, 1).starts_with("; VBlank interrupt\n"));
+    }
+}
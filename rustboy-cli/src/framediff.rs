@@ -0,0 +1,240 @@
+use std::fs;
+
+use rustboy::{FrameBuffer, PixelData, PixelSource};
+
+use crate::golden;
+
+mod png;
+
+// 何も指定しなければ最初の20ピクセルだけ表示する
+const DEFAULT_COUNT: usize = 20;
+
+/// 比べ方
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Compare {
+    // 色番号(0 - 3)を比べる。パレットの違うエミュレータのスクリーンショットとも比べられる
+    Shade,
+    // RGB が完全に一致するか比べる
+    Exact,
+}
+
+/// 2つの画面で異なるピクセル
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelDiff {
+    pub x: usize,
+    pub y: usize,
+    pub expected: PixelData,
+    pub actual: PixelData,
+}
+
+/// 画面ごとに明るい順に色番号(0 - 3)を振る
+/// 4色使っていれば明るさの順位で、それ以外は明るさを4段階に分けて決める
+pub fn shades(frame_buffer: &FrameBuffer) -> [[u8; 160]; 144] {
+    let mut colors: Vec<(u8, u8, u8)> = Vec::new();
+    for pixel in frame_buffer.iter().flatten() {
+        let rgb = (pixel.0, pixel.1, pixel.2);
+        if !colors.contains(&rgb) {
+            colors.push(rgb);
+        }
+    }
+    let luminance = |(r, g, b): (u8, u8, u8)| PixelData(r, g, b, 0).luminance();
+    colors.sort_by_key(|&rgb| std::cmp::Reverse(luminance(rgb)));
+    let mut shades = [[0; 160]; 144];
+    for (row, line) in shades.iter_mut().zip(frame_buffer.iter()) {
+        for (shade, pixel) in row.iter_mut().zip(line.iter()) {
+            let rgb = (pixel.0, pixel.1, pixel.2);
+            *shade = if colors.len() == 4 {
+                colors.iter().position(|&c| c == rgb).unwrap() as u8
+            } else {
+                3 - luminance(rgb) / 64
+            };
+        }
+    }
+    shades
+}
+
+/// expected と actual で異なるピクセルを左上から順に返す
+pub fn diff(expected: &FrameBuffer, actual: &FrameBuffer, compare: Compare) -> Vec<PixelDiff> {
+    let (expected_shades, actual_shades) = (shades(expected), shades(actual));
+    let mut diffs = Vec::new();
+    for y in 0..144 {
+        for x in 0..160 {
+            let (e, a) = (expected[y][x], actual[y][x]);
+            let differ = match compare {
+                Compare::Shade => expected_shades[y][x] != actual_shades[y][x],
+                Compare::Exact => (e.0, e.1, e.2) != (a.0, a.1, a.2),
+            };
+            if differ {
+                diffs.push(PixelDiff {
+                    x,
+                    y,
+                    expected: e,
+                    actual: a,
+                });
+            }
+        }
+    }
+    diffs
+}
+
+/// 160x144 の PNG を読み込む
+pub fn load_png(path: &str) -> Result<Box<FrameBuffer>, String> {
+    let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    let image = png::decode(&bytes).map_err(|e| format!("{}: {}", path, e))?;
+    if (image.width, image.height) != (160, 144) {
+        return Err(format!(
+            "{}: Expected 160x144, got {}x{}",
+            path, image.width, image.height
+        ));
+    }
+    let mut frame_buffer = Box::new([[PixelData(0, 0, 0, 0); 160]; 144]);
+    for (pixel, data) in frame_buffer.iter_mut().flatten().zip(image.pixels) {
+        *pixel = data;
+    }
+    Ok(frame_buffer)
+}
+
+fn hex(pixel: &PixelData) -> String {
+    format!("#{:02X}{:02X}{:02X}", pixel.0, pixel.1, pixel.2)
+}
+
+fn describe(source: &PixelSource) -> String {
+    let sprite = match source.sprite {
+        Some(index) => format!(", sprite {}", index),
+        None => String::new(),
+    };
+    format!(
+        "map 0x{:04X} -> tile 0x{:02X} @ 0x{:04X}{}",
+        source.map_address, source.tile_number, source.tile_address, sprite
+    )
+}
+
+/// `framediff <expected.png> <actual.png> [-n <count>] [--exact] [--rom <rom> <frames>]`
+/// 異なるピクセルを count 個まで表示する。ROM を渡すと frames フレーム実行した後の VRAM から、
+/// そのピクセルを描いた背景マップのエントリ、タイル、スプライトを求める
+pub fn command(args: &[String]) -> Result<(), String> {
+    let usage = || {
+        "usage: framediff <expected.png> <actual.png> [-n <count>] [--exact] [--rom <rom> <frames>]"
+            .to_string()
+    };
+    let (expected, actual) = match args {
+        [_, expected, actual, ..] => (expected, actual),
+        _ => return Err(usage()),
+    };
+    let mut count = DEFAULT_COUNT;
+    let mut compare = Compare::Shade;
+    let mut rom = Option::None;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-n" => count = rest.next().and_then(|n| n.parse().ok()).ok_or_else(usage)?,
+            "--exact" => compare = Compare::Exact,
+            "--rom" => {
+                let name = rest.next().ok_or_else(usage)?;
+                let frames: u64 = rest.next().and_then(|n| n.parse().ok()).ok_or_else(usage)?;
+                rom = Some((name, frames));
+            }
+            _ => return Err(usage()),
+        }
+    }
+
+    let diffs = diff(&*load_png(expected)?, &*load_png(actual)?, compare);
+    let mb = match rom {
+        Some((name, frames)) => Some(golden::boot(golden::load_rom(name)?, frames)?),
+        None => None,
+    };
+    for d in diffs.iter().take(count) {
+        let source = match &mb {
+            Some(mb) => format!(
+                "  {}",
+                describe(&mb.borrow().ppu().pixel_source(d.x as u16, d.y as u16))
+            ),
+            None => String::new(),
+        };
+        println!(
+            "({:3}, {:3}): expected {}, actual {}{}",
+            d.x,
+            d.y,
+            hex(&d.expected),
+            hex(&d.actual),
+            source
+        );
+    }
+    if diffs.is_empty() {
+        println!("Frames match");
+        return Ok(());
+    }
+    Err(format!("{} of {} pixels differ", diffs.len(), 160 * 144))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustboy::demo_rom;
+
+    #[test]
+    fn test_diff() {
+        let gray = [
+            PixelData(255, 255, 255, 0),
+            PixelData(170, 170, 170, 0),
+            PixelData(85, 85, 85, 0),
+            PixelData(0, 0, 0, 0),
+        ];
+        // 別のパレットで描いた同じ画面
+        let green = [
+            PixelData(155, 188, 15, 0xFF),
+            PixelData(139, 172, 15, 0xFF),
+            PixelData(48, 98, 48, 0xFF),
+            PixelData(15, 56, 15, 0xFF),
+        ];
+        let mut expected = Box::new([[gray[0]; 160]; 144]);
+        let mut actual = Box::new([[green[0]; 160]; 144]);
+        expected[0][..4].copy_from_slice(&gray);
+        actual[0][..4].copy_from_slice(&green);
+        assert_eq!(diff(&expected, &actual, Compare::Shade), vec![]);
+        assert_eq!(diff(&expected, &actual, Compare::Exact).len(), 160 * 144);
+
+        actual[10][20] = green[3];
+        assert_eq!(
+            diff(&expected, &actual, Compare::Shade),
+            vec![PixelDiff {
+                x: 20,
+                y: 10,
+                expected: gray[0],
+                actual: green[3],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_shades() {
+        // 4色使っていれば明るさの順位
+        let palette = [
+            PixelData(224, 248, 208, 0),
+            PixelData(136, 192, 112, 0),
+            PixelData(52, 104, 86, 0),
+            PixelData(8, 24, 32, 0),
+        ];
+        let mut frame = Box::new([[palette[0]; 160]; 144]);
+        for (i, pixel) in palette.iter().enumerate() {
+            frame[0][i] = *pixel;
+        }
+        assert_eq!(shades(&frame)[0][..5], [0, 1, 2, 3, 0]);
+        // それ以外は明るさを4段階に分ける
+        frame[0][1] = palette[0];
+        assert_eq!(shades(&frame)[0][..5], [0, 0, 2, 3, 0]);
+    }
+
+    #[test]
+    fn test_preview() {
+        // rustboy::preview の PNG を読み込むと、同じフレーム数だけ動かした画面と一致する
+        let image = png::decode(&rustboy::preview(demo_rom(), 30).unwrap()).unwrap();
+        assert_eq!((image.width, image.height), (160, 144));
+        let mut decoded = Box::new([[PixelData(0, 0, 0, 0); 160]; 144]);
+        for (pixel, data) in decoded.iter_mut().flatten().zip(image.pixels) {
+            *pixel = data;
+        }
+        let expected = golden::run(demo_rom(), 30).unwrap();
+        assert_eq!(diff(&expected, &decoded, Compare::Exact), vec![]);
+    }
+}
@@ -0,0 +1,450 @@
+// PNG の読み込み。他のエミュレータのスクリーンショットと比べられるように、
+// インターレース以外の全ての色の種類とビット深度を読める
+use rustboy::PixelData;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// 読み込んだ画像。pixels は左上から行ごとに並ぶ
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<PixelData>,
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Image, String> {
+    if bytes.len() < 8 || bytes[..8] != SIGNATURE {
+        return Err("Not a PNG file".to_string());
+    }
+    let mut header = Option::None;
+    let mut palette = Vec::new();
+    let mut data = Vec::new();
+    let mut rest = &bytes[8..];
+    while rest.len() >= 12 {
+        let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        let kind = &rest[4..8];
+        let body = rest.get(8..8 + len).ok_or("Truncated chunk")?;
+        match kind {
+            b"IHDR" => header = Some(Header::parse(body)?),
+            b"PLTE" => {
+                palette = body
+                    .chunks(3)
+                    .map(|c| PixelData(c[0], c[1], c[2], 0xFF))
+                    .collect()
+            }
+            b"IDAT" => data.extend_from_slice(body),
+            b"IEND" => break,
+            _ => (),
+        }
+        // CRC は確認しない
+        rest = rest.get(12 + len..).unwrap_or(&[]);
+    }
+    let header = header.ok_or("Missing IHDR")?;
+    // zlib のヘッダ2バイトを飛ばす。Adler-32 は確認しない
+    let raw = inflate(data.get(2..).ok_or("Missing IDAT")?)?;
+    let rows = unfilter(&raw, &header)?;
+    let pixels = rows
+        .iter()
+        .flat_map(|row| header.pixels(row, &palette))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Image {
+        width: header.width,
+        height: header.height,
+        pixels,
+    })
+}
+
+struct Header {
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    color_type: u8,
+}
+
+impl Header {
+    fn parse(body: &[u8]) -> Result<Self, String> {
+        if body.len() != 13 {
+            return Err("Invalid IHDR".to_string());
+        }
+        let header = Self {
+            width: u32::from_be_bytes(body[0..4].try_into().unwrap()) as usize,
+            height: u32::from_be_bytes(body[4..8].try_into().unwrap()) as usize,
+            bit_depth: body[8],
+            color_type: body[9],
+        };
+        if body[12] != 0 {
+            return Err("Interlaced PNG is not supported".to_string());
+        }
+        if header.channels() == 0 || ![1, 2, 4, 8, 16].contains(&header.bit_depth) {
+            return Err(format!(
+                "Unsupported color type {} with bit depth {}",
+                header.color_type, header.bit_depth
+            ));
+        }
+        Ok(header)
+    }
+
+    // 1ピクセルのサンプル数
+    fn channels(&self) -> usize {
+        match self.color_type {
+            // グレースケール、インデックスカラー
+            0 | 3 => 1,
+            // グレースケール + アルファ
+            4 => 2,
+            2 => 3,
+            6 => 4,
+            _ => 0,
+        }
+    }
+
+    // フィルタの単位になる1ピクセルのバイト数(1バイト未満なら1)
+    fn bytes_per_pixel(&self) -> usize {
+        (self.channels() * self.bit_depth as usize).div_ceil(8)
+    }
+
+    fn stride(&self) -> usize {
+        (self.width * self.channels() * self.bit_depth as usize).div_ceil(8)
+    }
+
+    // フィルタを戻した1行をピクセルに変換する
+    fn pixels<'a>(
+        &'a self,
+        row: &'a [u8],
+        palette: &'a [PixelData],
+    ) -> impl Iterator<Item = Result<PixelData, String>> + 'a {
+        let depth = self.bit_depth as usize;
+        // 16bit のサンプルは上位バイトだけを使う。8bit 未満は 0 - 255 に広げる
+        let sample = move |i: usize| -> u8 {
+            match depth {
+                16 => row[i * 2],
+                8 => row[i],
+                // 1バイトに複数のサンプルが上位ビットから詰まっている
+                _ => (row[i * depth / 8] >> (8 - depth - i * depth % 8)) & ((1 << depth) - 1),
+            }
+        };
+        let scale = move |v: u8| -> u8 {
+            match depth {
+                1 => v * 0xFF,
+                2 => v * 0x55,
+                4 => v * 0x11,
+                _ => v,
+            }
+        };
+        let channels = self.channels();
+        let color_type = self.color_type;
+        (0..self.width).map(move |x| {
+            let i = x * channels;
+            match color_type {
+                0 => {
+                    let v = scale(sample(i));
+                    Ok(PixelData(v, v, v, 0xFF))
+                }
+                2 => Ok(PixelData(sample(i), sample(i + 1), sample(i + 2), 0xFF)),
+                3 => palette
+                    .get(sample(i) as usize)
+                    .copied()
+                    .ok_or_else(|| "Palette index out of range".to_string()),
+                4 => Ok(PixelData(sample(i), sample(i), sample(i), sample(i + 1))),
+                _ => Ok(PixelData(
+                    sample(i),
+                    sample(i + 1),
+                    sample(i + 2),
+                    sample(i + 3),
+                )),
+            }
+        })
+    }
+}
+
+// 行ごとのフィルタを戻す
+fn unfilter(raw: &[u8], header: &Header) -> Result<Vec<Vec<u8>>, String> {
+    let stride = header.stride();
+    let bpp = header.bytes_per_pixel();
+    if raw.len() < (stride + 1) * header.height {
+        return Err("Image data is too short".to_string());
+    }
+    let mut rows: Vec<Vec<u8>> = Vec::with_capacity(header.height);
+    let empty = vec![0; stride];
+    for line in raw.chunks(stride + 1).take(header.height) {
+        let (filter, data) = (line[0], &line[1..]);
+        let prev = rows.last().unwrap_or(&empty);
+        let mut row = data.to_vec();
+        for i in 0..stride {
+            let a = if i >= bpp { row[i - bpp] } else { 0 };
+            let b = prev[i];
+            let c = if i >= bpp { prev[i - bpp] } else { 0 };
+            row[i] = row[i].wrapping_add(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(format!("Unknown filter type {}", filter)),
+            });
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+// DEFLATE の下位ビットから読む
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit: u8,
+}
+
+impl BitReader<'_> {
+    fn bits(&mut self, n: u8) -> Result<u16, String> {
+        let mut v = 0u16;
+        for i in 0..n {
+            let byte = self.data.get(self.pos).ok_or("Unexpected end of data")?;
+            v |= (((byte >> self.bit) & 1) as u16) << i;
+            self.bit += 1;
+            if self.bit == 8 {
+                self.bit = 0;
+                self.pos += 1;
+            }
+        }
+        Ok(v)
+    }
+
+    // 無圧縮ブロックはバイト境界から始まる
+    fn align(&mut self) {
+        if self.bit > 0 {
+            self.bit = 0;
+            self.pos += 1;
+        }
+    }
+}
+
+// 符号長から作る正規ハフマン符号
+struct Huffman {
+    // 符号長ごとの符号の数
+    counts: [u16; 16],
+    // 符号の小さい順に並べた記号
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols: Vec<u16> = (0..lengths.len() as u16)
+            .filter(|&s| lengths[s as usize] != 0)
+            .collect();
+        symbols.sort_by_key(|&s| lengths[s as usize]);
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String> {
+        // 符号長 len の最初の符号 first から count 個が連続して割り当てられている
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err("Invalid Huffman code".to_string())
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// 符号長の符号長を並べる順序
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// DEFLATE を展開する
+/// https://www.rfc-editor.org/rfc/rfc1951
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = BitReader {
+        data,
+        pos: 0,
+        bit: 0,
+    };
+    let mut out = Vec::new();
+    loop {
+        let is_final = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align();
+                let header = data
+                    .get(reader.pos..reader.pos + 4)
+                    .ok_or("Unexpected end of data")?;
+                let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+                let start = reader.pos + 4;
+                out.extend_from_slice(
+                    data.get(start..start + len)
+                        .ok_or("Unexpected end of data")?,
+                );
+                reader.pos = start + len;
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                let literal = Huffman::new(&lengths);
+                let distance = Huffman::new(&[5; 30]);
+                inflate_block(&mut reader, &mut out, &literal, &distance)?;
+            }
+            2 => {
+                let (literal, distance) = dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &mut out, &literal, &distance)?;
+            }
+            _ => return Err("Invalid block type".to_string()),
+        }
+        if is_final {
+            return Ok(out);
+        }
+    }
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let literals = reader.bits(5)? as usize + 257;
+    let distances = reader.bits(5)? as usize + 1;
+    let code_lengths = reader.bits(4)? as usize + 4;
+    let mut lengths = [0u8; 19];
+    for &i in &CODE_LENGTH_ORDER[..code_lengths] {
+        lengths[i] = reader.bits(3)? as u8;
+    }
+    let code = Huffman::new(&lengths);
+    let mut lengths = Vec::with_capacity(literals + distances);
+    while lengths.len() < literals + distances {
+        let (len, repeat) = match code.decode(reader)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (
+                *lengths.last().ok_or("No previous code length")?,
+                3 + reader.bits(2)?,
+            ),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+        lengths.extend((0..repeat).map(|_| len));
+    }
+    if lengths.len() > literals + distances {
+        return Err("Too many code lengths".to_string());
+    }
+    Ok((
+        Huffman::new(&lengths[..literals]),
+        Huffman::new(&lengths[literals..]),
+    ))
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    out: &mut Vec<u8>,
+    literal: &Huffman,
+    distance: &Huffman,
+) -> Result<(), String> {
+    loop {
+        let symbol = literal.decode(reader)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let i = symbol - 257;
+                if i >= LENGTH_BASE.len() {
+                    return Err("Invalid length code".to_string());
+                }
+                let len = (LENGTH_BASE[i] + reader.bits(LENGTH_EXTRA[i])?) as usize;
+                let d = distance.decode(reader)? as usize;
+                if d >= DISTANCE_BASE.len() {
+                    return Err("Invalid distance code".to_string());
+                }
+                let dist = (DISTANCE_BASE[d] + reader.bits(DISTANCE_EXTRA[d])?) as usize;
+                if dist > out.len() {
+                    return Err("Distance too far back".to_string());
+                }
+                // 重なっている場合があるので1バイトずつ写す
+                let start = out.len() - dist;
+                for k in 0..len {
+                    out.push(out[start + k]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inflate() {
+        // 無圧縮ブロック
+        let stored = [0x01, 0x03, 0x00, 0xFC, 0xFF, 1, 2, 3];
+        assert_eq!(inflate(&stored).unwrap(), [1, 2, 3]);
+        // 固定ハフマン符号(Python の zlib で圧縮したもの)
+        let fixed = [0x4B, 0x4C, 0x4A, 0x4E, 0x84, 0x21, 0x00];
+        assert_eq!(inflate(&fixed).unwrap(), b"abcabcabcabc");
+        // 動的ハフマン符号
+        let dynamic = [
+            0x05, 0xC1, 0x01, 0x01, 0x00, 0x30, 0x0C, 0xC2, 0x30, 0xAD, 0x65, 0x85, 0xFB, 0x77,
+            0xF0, 0x84, 0x54, 0xE7, 0xC9, 0x96, 0xF5, 0x1D, 0x11, 0xD2, 0x0F,
+        ];
+        assert_eq!(inflate(&dynamic).unwrap(), b"abeddfdcdaffbfegcabdaabe");
+    }
+
+    #[test]
+    fn test_decode() {
+        // 2x2 の RGB 画像。1行目は Sub、2行目は Up フィルタ
+        let png = [
+            0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48,
+            0x44, 0x52, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x08, 0x02, 0x00, 0x00,
+            0x00, 0xFD, 0xD4, 0x9A, 0x73, 0x00, 0x00, 0x00, 0x15, 0x49, 0x44, 0x41, 0x54, 0x78,
+            0x9C, 0x63, 0xFC, 0xCF, 0xC0, 0xC0, 0xF8, 0x9F, 0x81, 0x89, 0x81, 0x01, 0xC8, 0x60,
+            0x00, 0x00, 0x1A, 0x1A, 0x03, 0x02, 0x4D, 0x59, 0xBD, 0x14, 0x00, 0x00, 0x00, 0x00,
+            0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+        ];
+        let image = decode(&png).unwrap();
+        assert_eq!((image.width, image.height), (2, 2));
+        assert!(
+            image.pixels
+                == [
+                    PixelData(255, 0, 0, 0xFF),
+                    PixelData(0, 255, 0, 0xFF),
+                    PixelData(255, 0, 255, 0xFF),
+                    PixelData(0, 255, 0, 0xFF),
+                ]
+        );
+        assert!(decode(b"GIF89a").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[cfg(feature = "recorder")]
+use rustboy::Recorder;
+use rustboy::{demo_rom, FrameBuffer, MotherBoard};
+
+// 組み込みのデモ ROM を表す名前
+const DEMO: &str = "--demo";
+
+/// goldens ファイルの1行。`<rom> <frames> <hash>` の形式で書く
+#[derive(Debug, Clone, PartialEq)]
+pub struct Golden {
+    // ROM ファイルのパス。--demo なら組み込みのデモ ROM
+    pub rom: String,
+    pub frames: u64,
+    pub hash: u64,
+}
+
+/// 画面の FNV-1a ハッシュ。Rust のバージョンが変わっても同じ値になる
+pub fn frame_hash(frame_buffer: &FrameBuffer) -> u64 {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for pixel in frame_buffer.iter().flatten() {
+        for b in [pixel.0, pixel.1, pixel.2, pixel.3] {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01B3);
+        }
+    }
+    hash
+}
+
+/// rom をヘッドレスで frames フレーム実行した画面
+pub fn run(rom: Vec<u8>, frames: u64) -> Result<Arc<FrameBuffer>, String> {
+    let frame = boot(rom, frames)?.borrow().ppu().front_buffer();
+    Ok(frame)
+}
+
+/// rom をヘッドレスで frames フレーム実行したマザーボード
+pub fn boot(rom: Vec<u8>, frames: u64) -> Result<Rc<RefCell<MotherBoard>>, String> {
+    let mb = MotherBoard::headless(rom);
+    mb.borrow().reset();
+    for _ in 0..frames {
+        mb.borrow().run_until_vblank().map_err(|e| e.to_string())?;
+    }
+    Ok(mb)
+}
+
+/// ROM ファイルを読み込む。--demo なら組み込みのデモ ROM
+pub fn load_rom(name: &str) -> Result<Vec<u8>, String> {
+    if name == DEMO {
+        return Ok(demo_rom());
+    }
+    fs::read(name).map_err(|e| format!("{}: {}", name, e))
+}
+
+/// 空行と # から始まる行は無視する
+pub fn parse(text: &str) -> Result<Vec<Golden>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(
+            |(i, line)| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [rom, frames, hash] => Ok(Golden {
+                    rom: rom.to_string(),
+                    frames: frames
+                        .parse()
+                        .map_err(|_| format!("line {}: Invalid frames", i + 1))?,
+                    hash: u64::from_str_radix(hash, 16)
+                        .map_err(|_| format!("line {}: Invalid hash", i + 1))?,
+                }),
+                _ => Err(format!("line {}: Expected '<rom> <frames> <hash>'", i + 1)),
+            },
+        )
+        .collect()
+}
+
+fn format(goldens: &[Golden]) -> String {
+    let mut text =
+        String::from("# <rom> <frames> <hash>\n# rustboy golden update <file> で更新する\n");
+    for g in goldens {
+        text += &format!("{} {} {:016x}\n", g.rom, g.frames, g.hash);
+    }
+    text
+}
+
+/// 全ての ROM を実行して、ハッシュが一致しなかったものを実際のハッシュと共に返す
+pub fn check(goldens: &[Golden]) -> Result<Vec<(Golden, u64)>, String> {
+    let mut mismatches = Vec::new();
+    for golden in goldens {
+        let frame = run(load_rom(&golden.rom)?, golden.frames)?;
+        let hash = frame_hash(&frame);
+        if hash != golden.hash {
+            #[cfg(feature = "recorder")]
+            save_screenshot(golden, &frame)?;
+            mismatches.push((golden.clone(), hash));
+        }
+    }
+    Ok(mismatches)
+}
+
+// 差分を目で確認できるように、実際の画面を <rom>-<frames>.actual.png に書き出す
+#[cfg(feature = "recorder")]
+fn save_screenshot(golden: &Golden, frame: &FrameBuffer) -> Result<(), String> {
+    let stem = Path::new(golden.rom.trim_start_matches('-'))
+        .file_stem()
+        .map_or("rom".into(), |s| s.to_string_lossy());
+    let path = format!("{}-{}.actual.png", stem, golden.frames);
+    let error = |e: std::io::Error| format!("{}: {}", path, e);
+    let mut recorder = Recorder::new(&path, 1, 1).map_err(error)?;
+    recorder.capture(frame).map_err(error)?;
+    recorder.finish().map_err(error)
+}
+
+/// `rustboy golden check <goldens.txt>`
+/// `rustboy golden update <goldens.txt> [<rom> <frames>]`
+/// update は全てのハッシュを現在の描画結果で書き直す。ROM を指定すると追加する
+pub fn command(args: &[String]) -> Result<(), String> {
+    let usage = || "usage: rustboy golden check|update <goldens.txt> [<rom> <frames>]".to_string();
+    let (action, file) = match args {
+        [_, _, action, file, ..] => (action, file),
+        _ => return Err(usage()),
+    };
+    let text = match fs::read_to_string(file) {
+        Ok(text) => text,
+        // update なら新しく作る
+        Err(_) if action == "update" && !Path::new(file).exists() => String::new(),
+        Err(e) => return Err(format!("{}: {}", file, e)),
+    };
+    let mut goldens = parse(&text).map_err(|e| format!("{}: {}", file, e))?;
+
+    match (action.as_str(), &args[4..]) {
+        ("check", []) => {
+            let mismatches = check(&goldens)?;
+            for (golden, hash) in &mismatches {
+                println!(
+                    "{} ({} frames): expected {:016x}, got {:016x}",
+                    golden.rom, golden.frames, golden.hash, hash
+                );
+            }
+            if !mismatches.is_empty() {
+                return Err(format!(
+                    "{} of {} goldens differ",
+                    mismatches.len(),
+                    goldens.len()
+                ));
+            }
+            println!("{} goldens match", goldens.len());
+        }
+        ("update", rest) => {
+            if let [rom, frames] = rest {
+                let frames = frames.parse().map_err(|_| usage())?;
+                goldens.retain(|g| !(g.rom == *rom && g.frames == frames));
+                goldens.push(Golden {
+                    rom: rom.clone(),
+                    frames,
+                    hash: 0,
+                });
+            } else if !rest.is_empty() {
+                return Err(usage());
+            }
+            for golden in &mut goldens {
+                golden.hash = frame_hash(&*run(load_rom(&golden.rom)?, golden.frames)?);
+            }
+            fs::write(file, format(&goldens)).map_err(|e| format!("{}: {}", file, e))?;
+            println!("Updated {} goldens in {}", goldens.len(), file);
+        }
+        _ => return Err(usage()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let goldens = parse("# comment\n\n--demo 10 00000000000000ff\n").unwrap();
+        assert_eq!(
+            goldens,
+            vec![Golden {
+                rom: DEMO.to_string(),
+                frames: 10,
+                hash: 0xFF,
+            }]
+        );
+        assert_eq!(parse(&format(&goldens)).unwrap(), goldens);
+        assert!(parse("--demo 10").is_err());
+        assert!(parse("--demo ten 00ff").is_err());
+    }
+
+    #[test]
+    fn test_goldens() {
+        // リポジトリの goldens.txt のうち、ROM ファイルが無くても動かせるデモ ROM だけ確認する
+        let goldens: Vec<Golden> = parse(include_str!("../../goldens.txt"))
+            .unwrap()
+            .into_iter()
+            .filter(|g| g.rom == DEMO)
+            .collect();
+        assert!(!goldens.is_empty());
+        assert_eq!(check(&goldens).unwrap(), vec![]);
+    }
+}
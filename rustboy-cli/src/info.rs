@@ -0,0 +1,66 @@
+use rustboy::{Cartridge, MbcSelect};
+
+/// `rustboy info <rom>`
+/// ヘッダのタイトル、発売元、カートリッジの種類などを表示する
+pub fn command(args: &[String]) -> Result<(), String> {
+    let rom = match args {
+        [_, _, rom] => rom,
+        _ => return Err("usage: rustboy info <rom>".to_string()),
+    };
+    let cartridge = Cartridge::open(rom, MbcSelect::Header)?;
+    println!("{}", describe(&cartridge));
+    Ok(())
+}
+
+// 1行に1項目。ヘッダに無い項目は省く
+fn describe(cartridge: &Cartridge) -> String {
+    let header = cartridge.header();
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let mut lines = vec![format!("title: {}", header.title_str())];
+    if let Some(code) = header.manufacturer_code() {
+        lines.push(format!("manufacturer code: {}", code));
+    }
+    lines.push(format!(
+        "licensee: {}",
+        header.licensee().unwrap_or("unknown")
+    ));
+    lines.push(format!("type: {:?}", header.cartridge_type));
+    lines.push(format!(
+        "ROM: {:?}, RAM: {:?}",
+        header.rom_size, header.ram_size
+    ));
+    lines.push(format!(
+        "CGB: {}, SGB: {}, battery: {}",
+        yes_no(header.supports_cgb()),
+        yes_no(header.supports_sgb()),
+        yes_no(header.has_battery())
+    ));
+    lines.push(format!(
+        "global checksum: 0x{:04X}",
+        cartridge.global_checksum()
+    ));
+    if let Some(entry) = cartridge.compat() {
+        lines.push(format!("compat: {:?}", entry));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustboy::demo_rom;
+
+    #[test]
+    fn test_describe() {
+        let cartridge = Cartridge::from_bytes(demo_rom());
+        assert_eq!(
+            describe(&cartridge),
+            "title: RUSTBOY DEMO\n\
+             licensee: unknown\n\
+             type: RomOnly\n\
+             ROM: KBytes32, RAM: NoRam\n\
+             CGB: no, SGB: no, battery: no\n\
+             global checksum: 0x0000"
+        );
+    }
+}
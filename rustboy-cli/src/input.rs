@@ -0,0 +1,184 @@
+use std::io::{self, BufReader, Read};
+use std::process::{self, Command};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use signal_hook::consts::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+use crate::pacer::Control;
+
+/// 実行速度の操作に対応させるキー
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hotkeys {
+    pub pause: char,
+    pub frame_advance: char,
+    pub fast_forward: char,
+    pub slow_motion: char,
+}
+
+impl Default for Hotkeys {
+    fn default() -> Self {
+        Self {
+            pause: 'p',
+            frame_advance: '.',
+            fast_forward: 'f',
+            slow_motion: 's',
+        }
+    }
+}
+
+impl Hotkeys {
+    fn control(&self, c: char) -> Option<Control> {
+        match c {
+            c if c == self.pause => Some(Control::Pause),
+            c if c == self.frame_advance => Some(Control::FrameAdvance),
+            c if c == self.fast_forward => Some(Control::FastForward),
+            c if c == self.slow_motion => Some(Control::SlowMotion),
+            _ => None,
+        }
+    }
+}
+
+/// 標準入力の読み方
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputMode {
+    // 1行ずつ読む。Enter を押すまで届かないが、1行に書いたキーは同時に押される
+    Line,
+    // 端末を非カノニカルモードにして1文字ずつ読む
+    Raw,
+}
+
+impl FromStr for InputMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "line" => Ok(InputMode::Line),
+            "raw" => Ok(InputMode::Raw),
+            _ => Err(format!("Unknown input mode: {}", s)),
+        }
+    }
+}
+
+/// 非カノニカルモードにした端末。drop で元に戻す
+pub struct RawTerminal;
+
+// RawTerminal で1文字ずつ読むモードにしているか。シグナルですぐに終了するときに戻す
+static RAW: AtomicBool = AtomicBool::new(false);
+// SIGINT か SIGTERM を受け取ったか。デバッガのプロンプトで待っている間も確かめる
+static QUIT: AtomicBool = AtomicBool::new(false);
+
+impl RawTerminal {
+    pub fn enable() -> io::Result<Self> {
+        stty(&["-icanon", "-echo", "min", "1"])?;
+        RAW.store(true, Ordering::SeqCst);
+        Ok(RawTerminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        RAW.store(false, Ordering::SeqCst);
+        let _ = stty(&["icanon", "echo"]);
+    }
+}
+
+/// watch_signals で終了を求められたか
+pub fn quit_requested() -> bool {
+    QUIT.load(Ordering::SeqCst)
+}
+
+fn stty(args: &[&str]) -> io::Result<()> {
+    let status = Command::new("stty").args(args).status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("stty failed"))
+    }
+}
+
+// 1行に書かれたキーを1文字ずつに分ける。空行は Enter を1回押したものとする
+fn line_keys(line: &str) -> Vec<String> {
+    let keys = line.trim_end_matches(&['\r', '\n'][..]);
+    if keys.is_empty() {
+        return vec!["\n".to_string()];
+    }
+    keys.chars().map(String::from).collect()
+}
+
+/// 標準入力を読むスレッドを起動する
+/// ホットキーは速度の操作として control_tx に、それ以外はキー入力として振り分ける
+pub fn spawn_stdin_reader(
+    hotkeys: Hotkeys,
+    mode: InputMode,
+    control_tx: Sender<Control>,
+) -> Receiver<String> {
+    let (key_tx, key_rx) = mpsc::channel::<String>();
+    match mode {
+        InputMode::Line => thread::spawn(move || read_lines(hotkeys, key_tx, control_tx)),
+        InputMode::Raw => thread::spawn(move || read_chars(hotkeys, key_tx, control_tx)),
+    };
+    key_rx
+}
+
+/// SIGINT と SIGTERM を Control::Quit として送る。セーブデータを書き出してから終了させる
+/// 終了を待たずにもう一度送られたら、端末だけ戻してすぐに終了する
+pub fn watch_signals(control_tx: Sender<Control>) -> io::Result<()> {
+    let mut signals = Signals::new([SIGINT, SIGTERM])?;
+    thread::spawn(move || {
+        for (i, _) in signals.forever().enumerate() {
+            QUIT.store(true, Ordering::SeqCst);
+            if i > 0 || control_tx.send(Control::Quit).is_err() {
+                if RAW.load(Ordering::SeqCst) {
+                    let _ = stty(&["icanon", "echo"]);
+                }
+                process::exit(130);
+            }
+        }
+    });
+    Ok(())
+}
+
+// 行頭がホットキーなら行全体を速度の操作とする
+fn read_lines(hotkeys: Hotkeys, key_tx: Sender<String>, control_tx: Sender<Control>) {
+    loop {
+        let mut buffer = String::new();
+        io::stdin().read_line(&mut buffer).unwrap();
+        match buffer.chars().next().and_then(|c| hotkeys.control(c)) {
+            Some(control) => control_tx.send(control).unwrap(),
+            None => {
+                for key in line_keys(&buffer) {
+                    key_tx.send(key).unwrap();
+                }
+            }
+        }
+    }
+}
+
+fn read_chars(hotkeys: Hotkeys, key_tx: Sender<String>, control_tx: Sender<Control>) {
+    for byte in BufReader::new(io::stdin()).bytes() {
+        let c = byte.unwrap() as char;
+        match hotkeys.control(c) {
+            Some(control) => control_tx.send(control).unwrap(),
+            None => key_tx.send(c.to_string()).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_keys() {
+        assert_eq!(line_keys("a\n"), vec!["a"]);
+        // 右を押しながら A
+        assert_eq!(line_keys("la\r\n"), vec!["l", "a"]);
+        assert_eq!(line_keys("\n"), vec!["\n"]);
+        assert_eq!("raw".parse(), Ok(InputMode::Raw));
+        assert!("mouse".parse::<InputMode>().is_err());
+    }
+}
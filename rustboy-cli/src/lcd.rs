@@ -0,0 +1,468 @@
+use std::str::FromStr;
+
+use rustboy::{braille_lines, FrameBuffer, FrameInfo, PixelData, Screen, Viewport, LCD};
+
+use crate::postprocess::Pipeline;
+
+// 4x4 のベイヤー行列。0 - 15 の閾値が偏りなく散らばる
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// 点字の点を打つ画素の選び方
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BrailleStyle {
+    // 明るさがこれ未満の画素に点を打つ
+    pub threshold: u8,
+    // ベイヤー行列で閾値をずらして、中間の明るさを点の密度で表す
+    pub dither: bool,
+    // 明るい画素に点を打つ。背景が暗い端末用
+    pub invert: bool,
+}
+
+impl Default for BrailleStyle {
+    fn default() -> Self {
+        Self {
+            threshold: 128,
+            dither: false,
+            invert: false,
+        }
+    }
+}
+
+impl BrailleStyle {
+    /// (x, y) にある pixel に点を打つか
+    pub fn dot(&self, pixel: &PixelData, x: usize, y: usize) -> bool {
+        let luminance = pixel.luminance() as i16;
+        let threshold = if self.dither {
+            // 閾値 128 のとき 8 - 248 に散らばる
+            (BAYER[y % 4][x % 4] as i16 * 2 + 1) * 8 + self.threshold as i16 - 128
+        } else {
+            self.threshold as i16
+        };
+        (luminance < threshold) != self.invert
+    }
+}
+
+/// ASCII で描画するときの文字の選び方
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsciiStyle {
+    // 明るい順に並べた文字。4文字なら色番号 0 - 3 がそれぞれの文字になる
+    pub ramp: Vec<char>,
+    // 文字は縦長なので、縦2ピクセルを1文字にまとめて縦横比を合わせる
+    pub aspect: bool,
+}
+
+impl Default for AsciiStyle {
+    fn default() -> Self {
+        Self {
+            ramp: vec![' ', '.', ':', '#'],
+            aspect: false,
+        }
+    }
+}
+
+impl AsciiStyle {
+    /// 明るい順に2文字以上並べた文字列を読む
+    pub fn parse_ramp(s: &str) -> Result<Vec<char>, String> {
+        let ramp: Vec<char> = s.chars().collect();
+        if ramp.len() < 2 {
+            return Err(format!(
+                "ASCII ramp requires at least 2 characters: {:?}",
+                s
+            ));
+        }
+        Ok(ramp)
+    }
+
+    /// 明るさを文字に変換する
+    pub fn glyph(&self, luminance: u8) -> char {
+        let n = self.ramp.len();
+        self.ramp[(255 - luminance) as usize * n / 256]
+    }
+
+    /// 画素を1行ずつ文字列にする。aspect なら縦2ピクセルの明るさの平均を使う
+    pub fn lines(&self, pixels: &[Vec<PixelData>]) -> Vec<String> {
+        let rows = if self.aspect { 2 } else { 1 };
+        pixels
+            .chunks(rows)
+            .map(|pair| {
+                (0..pair[0].len())
+                    .map(|x| {
+                        let sum: usize = pair.iter().map(|row| row[x].luminance() as usize).sum();
+                        self.glyph((sum / pair.len()) as u8)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// 描画する範囲
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Crop {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl FromStr for Crop {
+    type Err = String;
+
+    // "x,y,w,h"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values: Vec<usize> = s
+            .split(',')
+            .map(|v| v.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Invalid crop: {}", s))?;
+        match values[..] {
+            [x, y, width, height] if width > 0 && height > 0 => Ok(Crop {
+                x,
+                y,
+                width,
+                height,
+            }),
+            _ => Err(format!("Invalid crop: {}", s)),
+        }
+    }
+}
+
+/// 文字に変換する前に切り出して縮小する。小さな端末で行が折り返さないようにする
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resize {
+    // None なら全体
+    pub crop: Option<Crop>,
+    // 1.0 で等倍
+    pub scale: f64,
+}
+
+impl Default for Resize {
+    fn default() -> Self {
+        Self {
+            crop: None,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Resize {
+    /// 切り出してから最近傍法で拡大縮小する
+    pub fn apply<R: AsRef<[PixelData]>>(&self, rows: &[R]) -> Vec<Vec<PixelData>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.as_ref().len());
+        // 画面からはみ出す部分は切り捨てる
+        let crop = self.crop.unwrap_or(Crop {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        });
+        let (left, top) = (crop.x.min(width), crop.y.min(height));
+        let (width, height) = (crop.width.min(width - left), crop.height.min(height - top));
+        if width == 0 || height == 0 {
+            return vec![];
+        }
+        let scaled = |n: usize| ((n as f64 * self.scale).round() as usize).max(1);
+        (0..scaled(height))
+            .map(|y| {
+                let row = rows[top + ((y as f64 / self.scale) as usize).min(height - 1)].as_ref();
+                (0..scaled(width))
+                    .map(|x| row[left + ((x as f64 / self.scale) as usize).min(width - 1)])
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// 状態行。実行速度が集計されていなければ表示しない
+fn status_line(info: &FrameInfo) -> Option<String> {
+    let stats = info.stats?;
+    Some(format!(
+        "FPS: {:.1} (host {:.1})  speed: {:.0}%  bank: {}",
+        stats.emulated_fps,
+        stats.host_fps,
+        stats.speed * 100.0,
+        stats.rom_bank
+    ))
+}
+
+// フレーム数、実機での経過時間、入力の行。オーバーレイを表示しない場合は None
+// 入力は上下左右、Select、Start、B、A の順で、押されていないボタンは '.'
+fn overlay_line(info: &FrameInfo) -> Option<String> {
+    if !info.overlay {
+        return None;
+    }
+    let time = info.emulated_time();
+    let input = &info.input;
+    let buttons: String = [
+        (input.up, 'U'),
+        (input.down, 'D'),
+        (input.left, 'L'),
+        (input.right, 'R'),
+        (input.select, 's'),
+        (input.start, 'S'),
+        (input.b, 'B'),
+        (input.a, 'A'),
+    ]
+    .iter()
+    .map(|&(pressed, c)| if pressed { c } else { '.' })
+    .collect();
+    Some(format!(
+        "frame: {}  time: {}:{:02}.{:03}  input: {}",
+        info.frame,
+        time.as_secs() / 60,
+        time.as_secs() % 60,
+        time.subsec_millis(),
+        buttons
+    ))
+}
+
+pub struct Terminal {
+    pipeline: Pipeline,
+}
+
+impl Terminal {
+    pub fn new(pipeline: Pipeline) -> Self {
+        Terminal { pipeline }
+    }
+
+    // 1ピクセル1文字で描画する
+    fn draw_pixels<R: AsRef<[PixelData]>>(&self, rows: &[R], info: &FrameInfo) {
+        let mut buf = String::new();
+
+        // clear
+        buf += "\x1b[2J";
+        for (i, line) in self.pipeline.apply(rows).iter().enumerate() {
+            buf += &format!("{:03?}", i);
+            for pixel in line {
+                buf += &format!("{:?}", pixel);
+            }
+            buf += "\n";
+        }
+        if let Some(overlay) = overlay_line(info) {
+            buf += &overlay;
+            buf += "\n";
+        }
+        if let Some(status) = status_line(info) {
+            buf += &status;
+        }
+        eprintln!("{}", buf);
+    }
+}
+
+impl LCD for Terminal {
+    fn draw(&self, frame_buffer: &FrameBuffer, info: &FrameInfo) {
+        self.draw_pixels(frame_buffer, info);
+    }
+
+    fn draw_with_border(&self, _frame_buffer: &FrameBuffer, screen: &Screen, info: &FrameInfo) {
+        self.draw_pixels(screen, info);
+    }
+
+    fn viewport(&self) -> Viewport {
+        Viewport::SgbBorder
+    }
+}
+
+/// 8点点字で標準出力に描画する
+pub struct BrailleTerminal {
+    style: BrailleStyle,
+    pipeline: Pipeline,
+}
+
+impl BrailleTerminal {
+    pub fn new(style: BrailleStyle, pipeline: Pipeline) -> Self {
+        BrailleTerminal { style, pipeline }
+    }
+}
+
+impl LCD for BrailleTerminal {
+    fn draw(&self, frame_buffer: &FrameBuffer, info: &FrameInfo) {
+        // TODO: capacityの指定
+        let mut buf = String::new();
+        // clear
+        buf += "\x1b[2J";
+        let pixels = self.pipeline.apply(frame_buffer);
+        let width = pixels.first().map_or(0, Vec::len);
+        let lines = braille_lines(width, pixels.len(), |x, y| {
+            self.style.dot(&pixels[y][x], x, y)
+        });
+        for (i, line) in lines.iter().enumerate() {
+            buf += &format!("{:03?}{}\n", i * 4, line);
+        }
+        if let Some(overlay) = overlay_line(info) {
+            buf += &overlay;
+            buf += "\n";
+        }
+        if let Some(status) = status_line(info) {
+            buf += &status;
+        }
+        eprintln!("{}", buf);
+    }
+}
+
+/// Unicode の点字を表示できない端末向けに、1ピクセルを1文字の ASCII で描画する
+pub struct AsciiTerminal {
+    style: AsciiStyle,
+    pipeline: Pipeline,
+}
+
+impl AsciiTerminal {
+    pub fn new(style: AsciiStyle, pipeline: Pipeline) -> Self {
+        AsciiTerminal { style, pipeline }
+    }
+}
+
+impl LCD for AsciiTerminal {
+    fn draw(&self, frame_buffer: &FrameBuffer, info: &FrameInfo) {
+        let mut buf = String::new();
+        // clear
+        buf += "\x1b[2J";
+        let pixels = self.pipeline.apply(frame_buffer);
+        let rows = if self.style.aspect { 2 } else { 1 };
+        for (i, line) in self.style.lines(&pixels).iter().enumerate() {
+            buf += &format!("{:03?}{}\n", i * rows, line);
+        }
+        if let Some(overlay) = overlay_line(info) {
+            buf += &overlay;
+            buf += "\n";
+        }
+        if let Some(status) = status_line(info) {
+            buf += &status;
+        }
+        eprintln!("{}", buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustboy::{ButtonState, DEFAULT_PALETTE};
+
+    // 4x4 の範囲で点を打つ数
+    fn dots(style: &BrailleStyle, pixel: &PixelData) -> usize {
+        (0..16).filter(|i| style.dot(pixel, i % 4, i / 4)).count()
+    }
+
+    #[test]
+    fn test_resize() {
+        let [white, _, _, black] = DEFAULT_PALETTE;
+        // 左上 2x2 だけ黒い 4x4
+        let mut rows = [[white; 4]; 4];
+        rows[0][..2].copy_from_slice(&[black; 2]);
+        rows[1][..2].copy_from_slice(&[black; 2]);
+        let half = Resize {
+            scale: 0.5,
+            ..Resize::default()
+        };
+        let pixels = half.apply(&rows);
+        assert_eq!(pixels.len(), 2);
+        assert!(pixels[0] == vec![black, white]);
+        assert!(pixels[1] == vec![white, white]);
+
+        // 範囲を切り出してから縮小する。はみ出す部分は切り捨てる
+        let crop = Resize {
+            crop: Some("1,1,8,8".parse().unwrap()),
+            scale: 1.0,
+        };
+        let pixels = crop.apply(&rows);
+        assert_eq!((pixels.len(), pixels[0].len()), (3, 3));
+        assert!(pixels[0][0] == black && pixels[0][1] == white);
+        assert!(Resize {
+            crop: Some("4,0,1,1".parse().unwrap()),
+            scale: 1.0,
+        }
+        .apply(&rows)
+        .is_empty());
+        assert!("1,2,3".parse::<Crop>().is_err());
+        assert!("0,0,0,1".parse::<Crop>().is_err());
+    }
+
+    #[test]
+    fn test_overlay_line() {
+        let mut info = FrameInfo {
+            frame: 3600,
+            cycles: 4194304 * 61 + 4194304 / 2,
+            speed: 1.0,
+            stats: None,
+            input: ButtonState {
+                up: true,
+                start: true,
+                a: true,
+                ..ButtonState::default()
+            },
+            overlay: false,
+        };
+        assert_eq!(overlay_line(&info), None);
+        info.overlay = true;
+        assert_eq!(
+            overlay_line(&info).unwrap(),
+            "frame: 3600  time: 1:01.500  input: U....S.A"
+        );
+    }
+
+    #[test]
+    fn test_braille_style() {
+        let [white, light, dark, black] = DEFAULT_PALETTE;
+        // 閾値だけなら暗い2色が黒になる
+        let style = BrailleStyle::default();
+        let counts: Vec<usize> = [white, light, dark, black]
+            .iter()
+            .map(|p| dots(&style, p))
+            .collect();
+        assert_eq!(counts, vec![0, 0, 16, 16]);
+
+        // ディザリングすると中間の色も点の密度で区別できる
+        let style = BrailleStyle {
+            dither: true,
+            ..BrailleStyle::default()
+        };
+        let counts: Vec<usize> = [white, light, dark, black]
+            .iter()
+            .map(|p| dots(&style, p))
+            .collect();
+        assert_eq!(counts, vec![0, 5, 11, 16]);
+
+        // 閾値を上げると全体が暗くなる
+        let style = BrailleStyle {
+            threshold: 200,
+            ..BrailleStyle::default()
+        };
+        assert_eq!(dots(&style, &light), 16);
+
+        // 反転すると明るい画素に点を打つ
+        let style = BrailleStyle {
+            invert: true,
+            ..BrailleStyle::default()
+        };
+        assert_eq!(dots(&style, &white), 16);
+        assert_eq!(dots(&style, &black), 0);
+    }
+
+    #[test]
+    fn test_ascii_style() {
+        let [white, light, dark, black] = DEFAULT_PALETTE;
+        // 4色がそれぞれ既定の4文字になる
+        let style = AsciiStyle::default();
+        let pixels = vec![vec![white, light, dark, black], vec![black; 4]];
+        assert_eq!(style.lines(&pixels), vec![" .:#", "####"]);
+
+        // 縦2ピクセルの平均を1文字にする。行数が奇数なら最後の行はそのまま
+        let style = AsciiStyle {
+            aspect: true,
+            ..AsciiStyle::default()
+        };
+        assert_eq!(style.lines(&pixels), vec!["::##"]);
+        let mut odd = pixels.clone();
+        odd.push(vec![white; 4]);
+        assert_eq!(style.lines(&odd), vec!["::##", "    "]);
+
+        // 2文字なら明るさの半分で分ける
+        let style = AsciiStyle {
+            ramp: AsciiStyle::parse_ramp("@ ").unwrap(),
+            aspect: false,
+        };
+        assert_eq!(style.lines(&pixels[..1]), vec!["@@  "]);
+        assert!(AsciiStyle::parse_ramp("#").is_err());
+    }
+}
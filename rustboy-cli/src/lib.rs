@@ -0,0 +1,29 @@
+// 端末で動かすためのフロントエンド。エミュレーションは rustboy-core が受け持つ
+
+pub use config::Config;
+pub use disasm::command as disasm_command;
+pub use framediff::{command as framediff_command, diff as frame_diff, Compare, PixelDiff};
+pub use golden::command as golden_command;
+pub use info::command as info_command;
+pub use logger::init as init_logger;
+pub use run::{run, Exit};
+pub use save::command as save_command;
+
+mod audio;
+mod config;
+mod debugger;
+mod disasm;
+mod framediff;
+mod golden;
+mod info;
+mod input;
+mod lcd;
+mod logger;
+mod pacer;
+mod postprocess;
+mod run;
+mod save;
+mod session;
+#[cfg(feature = "tui")]
+mod tui;
+mod watchdog;
@@ -0,0 +1,148 @@
+// rustboy-core が log クレートで出すログを、部品ごとのレベルで絞って標準エラー出力に書く
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use log::{LevelFilter, Log, Metadata, Record};
+use rustboy::LOG_TARGETS;
+
+// 部品ごとのレベル。通信ケーブルの受信スレッドからも参照する
+// 既定では警告まで出力する
+#[allow(clippy::declare_interior_mutable_const)]
+const DEFAULT_LEVEL: AtomicUsize = AtomicUsize::new(LevelFilter::Warn as usize);
+static LEVELS: [AtomicUsize; LOG_TARGETS.len()] = [DEFAULT_LEVEL; LOG_TARGETS.len()];
+
+// 数値の小さい順
+const LEVEL_FILTERS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+struct Logger;
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= level(metadata.target())
+    }
+
+    // 標準出力は画面の描画に使う
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Logger = Logger;
+
+/// ログの出力先を標準エラー出力にする。呼ぶまではログを捨てる
+pub fn init() {
+    // 2回目以降は既に設定されているので何もしない
+    let _ = log::set_logger(&LOGGER);
+    update_max_level();
+}
+
+fn position(target: &str) -> Option<usize> {
+    LOG_TARGETS.iter().position(|t| *t == target)
+}
+
+/// 部品の今のレベル。rustboy-core 以外の target は既定のレベルにする
+pub fn level(target: &str) -> LevelFilter {
+    match position(target) {
+        Some(i) => LEVEL_FILTERS[LEVELS[i].load(Ordering::Relaxed)],
+        None => LevelFilter::Warn,
+    }
+}
+
+fn set_level(target: &str, level: LevelFilter) {
+    if let Some(i) = position(target) {
+        LEVELS[i].store(level as usize, Ordering::Relaxed);
+    }
+}
+
+// 一番詳しい部品に合わせる。これより詳しいログはマクロの時点で捨てられる
+fn update_max_level() {
+    let max = LOG_TARGETS.iter().map(|t| level(t)).max();
+    log::set_max_level(max.unwrap_or(LevelFilter::Off));
+}
+
+/// 全部品の今のレベル。デバッガで実行中に切り替えるときに表示する
+pub fn levels() -> Vec<(&'static str, LevelFilter)> {
+    LOG_TARGETS.iter().map(|t| (*t, level(t))).collect()
+}
+
+/// "debug" なら全部品、"mbc=debug,cpu=trace" なら部品ごとのレベル
+pub fn parse(spec: &str) -> Result<Vec<(Option<&'static str>, LevelFilter)>, String> {
+    let parse_level = |s: &str| {
+        s.parse::<LevelFilter>()
+            .map_err(|_| format!("Unknown log level: {}", s))
+    };
+    let parse_target = |s: &str| {
+        LOG_TARGETS
+            .into_iter()
+            .find(|t| *t == s)
+            .ok_or_else(|| format!("Unknown log target: {}", s))
+    };
+    spec.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.split_once('=') {
+            Some((target, level)) => Ok((
+                Some(parse_target(target.trim())?),
+                parse_level(level.trim())?,
+            )),
+            None => Ok((None, parse_level(s)?)),
+        })
+        .collect()
+}
+
+/// parse と同じ書式でレベルを設定する。後に書いた方が優先される
+pub fn configure(spec: &str) -> Result<(), String> {
+    for (target, level) in parse(spec)? {
+        match target {
+            Some(target) => set_level(target, level),
+            None => LOG_TARGETS.iter().for_each(|t| set_level(t, level)),
+        }
+    }
+    update_max_level();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("debug").unwrap(), vec![(None, LevelFilter::Debug)]);
+        assert_eq!(
+            parse("warn, mbc=debug,cpu=trace").unwrap(),
+            vec![
+                (None, LevelFilter::Warn),
+                (Some("mbc"), LevelFilter::Debug),
+                (Some("cpu"), LevelFilter::Trace),
+            ]
+        );
+        assert_eq!(parse("").unwrap(), vec![]);
+        assert!(parse("gpu=debug").is_err());
+        assert!(parse("mbc=verbose").is_err());
+    }
+
+    #[test]
+    fn test_configure() {
+        // 他のテストと共有するので sgb だけ変える
+        configure("sgb=trace").unwrap();
+        assert_eq!(level("sgb"), LevelFilter::Trace);
+        assert_eq!(log::max_level(), LevelFilter::Trace);
+        configure("sgb=warn").unwrap();
+        assert!(levels().contains(&("sgb", LevelFilter::Warn)));
+        let metadata = |level| Metadata::builder().target("sgb").level(level).build();
+        assert!(LOGGER.enabled(&metadata(log::Level::Warn)));
+        assert!(!LOGGER.enabled(&metadata(log::Level::Info)));
+        assert!(configure("sgb=loud").is_err());
+    }
+}
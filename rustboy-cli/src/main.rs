@@ -0,0 +1,38 @@
+use std::env;
+use std::process;
+
+use rustboy_cli::Config;
+
+// サブコマンド
+type Command = fn(&[String]) -> Result<(), String>;
+
+fn main() {
+    rustboy_cli::init_logger();
+    let args: Vec<String> = env::args().collect();
+    let command: Option<Command> = match args.get(1).map(String::as_str) {
+        Some("save") => Some(rustboy_cli::save_command),
+        Some("golden") => Some(rustboy_cli::golden_command),
+        Some("disasm") => Some(rustboy_cli::disasm_command),
+        Some("info" | "--info") => Some(rustboy_cli::info_command),
+        _ => None,
+    };
+    if let Some(command) = command {
+        if let Err(e) = command(&args) {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    let config = Config::new(&args).unwrap_or_else(|e| {
+        eprintln!("Failed to parse args: {}", e);
+        process::exit(1);
+    });
+
+    match rustboy_cli::run(config) {
+        Ok(exit) => process::exit(exit.code()),
+        Err(e) => {
+            eprintln!("Some error: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::mpsc::Receiver;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use rustboy::{FrameBuffer, FrameInfo, Screen, Viewport, CPU, LCD};
+
+// 1フレームの T-Cycle 数。フレームごとに待機する
+const FRAME_CYCLES: u64 = 70224;
+// これ以上遅れたら追いつこうとせずに基準を取り直す
+const MAX_LAG: Duration = Duration::from_millis(100);
+// 自動で間引くときに、1回の描画にまとめる最大のフレーム数
+const MAX_AUTO_FRAMESKIP: u32 = 8;
+// 自動で間引くときに、これだけ続けて間に合えば間引くフレームを減らす
+const AUTO_RECOVER_FRAMES: u32 = 60;
+
+/// 実行中の速度の操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Control {
+    // 一時停止と再開
+    Pause,
+    // 一時停止中に1フレームだけ進める
+    FrameAdvance,
+    // 2倍速、4倍速、等速の順に切り替える
+    FastForward,
+    // 0.5倍速と等速を切り替える
+    SlowMotion,
+    // SIGINT などで終了を求められた。一時停止中でも再開して終了させる
+    Quit,
+}
+
+/// 描画の間引き方。エミュレーションは全てのフレームで行う
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameSkip {
+    // N フレームに1回だけ描画する。1 なら全て描画する
+    Fixed(u32),
+    // 描画が間に合わなければ間引くフレームを増やし、しばらく間に合えば減らす
+    Auto,
+}
+
+// "auto" か 1 以上の数
+impl FromStr for FrameSkip {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(FrameSkip::Auto),
+            n => match n.parse() {
+                Ok(n) if n > 0 => Ok(FrameSkip::Fixed(n)),
+                _ => Err(format!(
+                    "Invalid frameskip: {} (expected N >= 1 or auto)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+/// Pacer が決めた間隔で描画する LCD。間引いたフレームは包んだ LCD に渡さない
+pub struct SkipFrames {
+    lcd: Box<dyn LCD>,
+    // 何フレームに1回描画するか。Pacer と共有する
+    interval: Rc<Cell<u32>>,
+}
+
+impl SkipFrames {
+    pub fn new(lcd: Box<dyn LCD>, interval: Rc<Cell<u32>>) -> Self {
+        Self { lcd, interval }
+    }
+
+    fn should_draw(&self, info: &FrameInfo) -> bool {
+        info.frame.is_multiple_of(self.interval.get().max(1) as u64)
+    }
+}
+
+impl LCD for SkipFrames {
+    fn draw(&self, frame_buffer: &FrameBuffer, info: &FrameInfo) {
+        if self.should_draw(info) {
+            self.lcd.draw(frame_buffer, info);
+        }
+    }
+    fn draw_with_border(&self, frame_buffer: &FrameBuffer, screen: &Screen, info: &FrameInfo) {
+        if self.should_draw(info) {
+            self.lcd.draw_with_border(frame_buffer, screen, info);
+        }
+    }
+    fn viewport(&self) -> Viewport {
+        self.lcd.viewport()
+    }
+}
+
+/// 実機と同じ速さで動くように待機する
+pub struct Pacer {
+    // 1.0 で実機と同じ速さ。0 以下なら待機しない
+    speed: f64,
+    // 早送り、スロー再生の倍率
+    multiplier: f64,
+    paused: bool,
+    quit: bool,
+    // コマ送り中は、このフレーム数まで描画したら一時停止する
+    advance_until: Option<u64>,
+    controls: Receiver<Control>,
+    // 基準とする時刻と、その時点の経過 T-Cycle 数
+    origin: Instant,
+    origin_cycles: u64,
+    next_sync: u64,
+    frameskip: FrameSkip,
+    // 今の描画の間隔と、SkipFrames と共有する値。コマ送り中は全て描画する
+    skip: u32,
+    interval: Rc<Cell<u32>>,
+    // 自動で間引くときに、続けて間に合ったフレーム数
+    on_time: u32,
+}
+
+impl Pacer {
+    pub fn new(speed: f64, cycles: u64, controls: Receiver<Control>) -> Self {
+        Self {
+            speed,
+            multiplier: 1.0,
+            paused: false,
+            quit: false,
+            advance_until: Option::None,
+            controls,
+            origin: Instant::now(),
+            origin_cycles: cycles,
+            next_sync: cycles + FRAME_CYCLES,
+            frameskip: FrameSkip::Fixed(1),
+            skip: 1,
+            interval: Rc::new(Cell::new(1)),
+            on_time: 0,
+        }
+    }
+
+    /// 描画を間引く。interval は SkipFrames に渡したもの
+    pub fn with_frameskip(mut self, frameskip: FrameSkip, interval: Rc<Cell<u32>>) -> Self {
+        self.frameskip = frameskip;
+        self.skip = match frameskip {
+            FrameSkip::Fixed(n) => n,
+            FrameSkip::Auto => 1,
+        };
+        self.interval = interval;
+        self.publish();
+        self
+    }
+
+    /// 命令を実行するたびに経過時間(等速の T-Cycle 数)と描画したフレーム数を渡す
+    /// CGB の倍速モードでも CPU の T-Cycle 数ではなく等速に換算した値を渡す
+    /// 一時停止中は再開されるまで戻らない
+    pub fn tick(&mut self, cycles: u64, frames: u64) {
+        while let Ok(control) = self.controls.try_recv() {
+            self.handle(control, cycles, frames);
+        }
+        if self.advance_until.is_some_and(|until| frames >= until) {
+            self.advance_until = Option::None;
+            self.paused = true;
+            self.publish();
+        }
+        while self.paused {
+            match self.controls.recv() {
+                Ok(control) => self.handle(control, cycles, frames),
+                // 操作する手段が無いので再開する
+                Err(_) => self.paused = false,
+            }
+        }
+
+        // リセットされた
+        if cycles < self.origin_cycles {
+            self.rebase(cycles);
+        }
+        if self.speed <= 0.0 || cycles < self.next_sync {
+            return;
+        }
+        self.next_sync = cycles + FRAME_CYCLES;
+        let target = Duration::from_secs_f64(
+            (cycles - self.origin_cycles) as f64
+                / (CPU::CLOCK as f64 * self.speed * self.multiplier),
+        );
+        let frame = Duration::from_secs_f64(
+            FRAME_CYCLES as f64 / (CPU::CLOCK as f64 * self.speed * self.multiplier),
+        );
+        let elapsed = self.origin.elapsed();
+        self.adjust_frameskip(elapsed > target + frame);
+        if target > elapsed {
+            sleep(target - elapsed);
+        } else if elapsed - target > MAX_LAG {
+            // デバッガで止めていた場合など
+            self.rebase(cycles);
+        }
+    }
+
+    /// Control::Quit を受け取ったか
+    pub fn quit_requested(&self) -> bool {
+        self.quit
+    }
+
+    fn handle(&mut self, control: Control, cycles: u64, frames: u64) {
+        match control {
+            Control::Pause => {
+                self.paused = !self.paused;
+                self.advance_until = Option::None;
+            }
+            Control::FrameAdvance => {
+                if self.paused {
+                    self.paused = false;
+                    self.advance_until = Some(frames + 1);
+                }
+            }
+            Control::FastForward => {
+                self.multiplier = if self.multiplier == 2.0 {
+                    4.0
+                } else if self.multiplier == 4.0 {
+                    1.0
+                } else {
+                    2.0
+                };
+            }
+            Control::SlowMotion => {
+                self.multiplier = if self.multiplier == 0.5 { 1.0 } else { 0.5 };
+            }
+            Control::Quit => {
+                self.quit = true;
+                self.paused = false;
+                self.advance_until = Option::None;
+            }
+        }
+        // 速度が変わったり停止していた分を待たないように基準を取り直す
+        self.rebase(cycles);
+        self.publish();
+    }
+
+    // 1フレーム以上遅れていれば描画を間引き、しばらく間に合っていれば戻す
+    fn adjust_frameskip(&mut self, behind: bool) {
+        if self.frameskip != FrameSkip::Auto {
+            return;
+        }
+        if behind {
+            self.skip = (self.skip + 1).min(MAX_AUTO_FRAMESKIP);
+            self.on_time = 0;
+        } else {
+            self.on_time += 1;
+            if self.on_time >= AUTO_RECOVER_FRAMES {
+                self.skip = (self.skip - 1).max(1);
+                self.on_time = 0;
+            }
+        }
+        self.publish();
+    }
+
+    // コマ送りでは進めたフレームを必ず表示する
+    fn publish(&self) {
+        let interval = if self.advance_until.is_some() {
+            1
+        } else {
+            self.skip
+        };
+        self.interval.set(interval);
+    }
+
+    fn rebase(&mut self, cycles: u64) {
+        self.origin = Instant::now();
+        self.origin_cycles = cycles;
+        self.next_sync = cycles + FRAME_CYCLES;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustboy::DEFAULT_PALETTE;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_speed_controls() {
+        let (_tx, rx) = mpsc::channel();
+        let mut pacer = Pacer::new(1.0, 0, rx);
+        pacer.handle(Control::FastForward, 0, 0);
+        assert_eq!(pacer.multiplier, 2.0);
+        pacer.handle(Control::FastForward, 0, 0);
+        assert_eq!(pacer.multiplier, 4.0);
+        pacer.handle(Control::FastForward, 0, 0);
+        assert_eq!(pacer.multiplier, 1.0);
+        pacer.handle(Control::SlowMotion, 0, 0);
+        assert_eq!(pacer.multiplier, 0.5);
+        pacer.handle(Control::FastForward, 0, 0);
+        assert_eq!(pacer.multiplier, 2.0);
+        pacer.handle(Control::SlowMotion, 0, 0);
+        assert_eq!(pacer.multiplier, 0.5);
+        pacer.handle(Control::SlowMotion, 0, 0);
+        assert_eq!(pacer.multiplier, 1.0);
+    }
+
+    #[test]
+    fn test_frame_advance() {
+        let (tx, rx) = mpsc::channel();
+        let mut pacer = Pacer::new(0.0, 0, rx);
+        // 一時停止中でなければ何もしない
+        pacer.handle(Control::FrameAdvance, 0, 10);
+        assert_eq!(pacer.advance_until, None);
+
+        // 一時停止してからコマ送りすると、次のフレームまで進んで止まる
+        tx.send(Control::Pause).unwrap();
+        tx.send(Control::FrameAdvance).unwrap();
+        pacer.tick(100, 10);
+        assert!(!pacer.paused);
+        assert_eq!(pacer.advance_until, Some(11));
+        pacer.tick(200, 10);
+        assert!(!pacer.paused);
+
+        // 送信側が無ければ止まったままにはならない
+        drop(tx);
+        pacer.tick(300, 11);
+        assert!(!pacer.paused);
+        assert_eq!(pacer.advance_until, None);
+    }
+
+    #[test]
+    fn test_quit() {
+        let (tx, rx) = mpsc::channel();
+        let mut pacer = Pacer::new(0.0, 0, rx);
+        assert!(!pacer.quit_requested());
+        // 一時停止中でも戻る
+        tx.send(Control::Pause).unwrap();
+        tx.send(Control::Quit).unwrap();
+        pacer.tick(100, 10);
+        assert!(!pacer.paused);
+        assert!(pacer.quit_requested());
+    }
+
+    struct Count(Rc<Cell<u64>>);
+
+    impl LCD for Count {
+        fn draw(&self, _frame_buffer: &FrameBuffer, _info: &FrameInfo) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_frameskip() {
+        assert_eq!("3".parse(), Ok(FrameSkip::Fixed(3)));
+        assert_eq!("auto".parse(), Ok(FrameSkip::Auto));
+        assert!("0".parse::<FrameSkip>().is_err());
+
+        // 3 フレームに 1 回描画する
+        let (tx, rx) = mpsc::channel();
+        let interval = Rc::new(Cell::new(1));
+        let mut pacer =
+            Pacer::new(0.0, 0, rx).with_frameskip(FrameSkip::Fixed(3), Rc::clone(&interval));
+        let count = Rc::new(Cell::new(0));
+        let lcd = SkipFrames::new(Box::new(Count(Rc::clone(&count))), Rc::clone(&interval));
+        let draw = |frame| {
+            let info = FrameInfo {
+                frame,
+                cycles: 0,
+                speed: 1.0,
+                stats: None,
+                input: Default::default(),
+                overlay: false,
+            };
+            lcd.draw(&[[DEFAULT_PALETTE[0]; 160]; 144], &info);
+        };
+        (0..9).for_each(draw);
+        assert_eq!(count.get(), 3);
+        // コマ送りでは全て描画する
+        tx.send(Control::Pause).unwrap();
+        tx.send(Control::FrameAdvance).unwrap();
+        pacer.tick(0, 10);
+        draw(10);
+        assert_eq!(count.get(), 4);
+        drop(tx);
+        pacer.tick(0, 11);
+        assert_eq!(interval.get(), 3);
+
+        // 遅れると間引くフレームを増やし、しばらく間に合えば減らす
+        let (_tx, rx) = mpsc::channel();
+        let mut pacer =
+            Pacer::new(1.0, 0, rx).with_frameskip(FrameSkip::Auto, Rc::clone(&interval));
+        assert_eq!(interval.get(), 1);
+        pacer.adjust_frameskip(true);
+        pacer.adjust_frameskip(true);
+        assert_eq!(interval.get(), 3);
+        for _ in 0..AUTO_RECOVER_FRAMES {
+            pacer.adjust_frameskip(false);
+        }
+        assert_eq!(interval.get(), 2);
+        for _ in 0..MAX_AUTO_FRAMESKIP * 2 {
+            pacer.adjust_frameskip(true);
+        }
+        assert_eq!(interval.get(), MAX_AUTO_FRAMESKIP);
+    }
+}
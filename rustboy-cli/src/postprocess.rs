@@ -0,0 +1,292 @@
+use std::str::FromStr;
+
+use rustboy::PixelData;
+
+use crate::lcd::Resize;
+
+/// 後処理で扱う画面。拡大すると大きさが変わるので行のリストで持つ
+pub type Image = Vec<Vec<PixelData>>;
+
+// fit の余白。どの描画方法でも空白になる
+const MARGIN: PixelData = PixelData(0xFF, 0xFF, 0xFF, 0);
+
+/// 表示する前に画面に掛ける処理。どのフロントエンドでも同じ順に掛けて同じ見た目にする
+pub trait PostProcess {
+    fn process(&self, image: Image) -> Image;
+}
+
+impl PostProcess for Resize {
+    fn process(&self, image: Image) -> Image {
+        self.apply(&image)
+    }
+}
+
+/// 設定で選べる後処理。"gamma=2.2" のように書く
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    // 1.0 でそのまま。大きいほど中間色が明るくなる
+    Gamma(f64),
+    // 1.0 でそのまま。0 で白黒になる。CGB の鮮やかな色を抑えるのに使う
+    Saturation(f64),
+    // 奇数行をこの割合(0 - 100%)だけ暗くする
+    Scanlines(u8),
+    // 最近傍法で整数倍に拡大する
+    Upscale(usize),
+    // 幅 x 高さに収まる最大の整数倍(収まらなければ整数分の1)にして、余白を付けて中央に置く
+    Fit(usize, usize),
+    // 縦横比を無視して幅 x 高さいっぱいに引き伸ばす
+    Stretch(usize, usize),
+}
+
+// "320x288"
+fn parse_size(s: &str) -> Option<(usize, usize)> {
+    let (width, height) = s.split_once('x')?;
+    match (width.trim().parse(), height.trim().parse()) {
+        (Ok(width), Ok(height)) if width > 0 && height > 0 => Some((width, height)),
+        _ => None,
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("Invalid postprocess: {}", s);
+        let (name, value) = s.trim().split_once('=').ok_or_else(error)?;
+        let value = value.trim();
+        match name.trim() {
+            "gamma" => match value.parse() {
+                Ok(g) if g > 0.0 => Ok(Filter::Gamma(g)),
+                _ => Err(error()),
+            },
+            "saturation" => match value.parse() {
+                Ok(s) if s >= 0.0 => Ok(Filter::Saturation(s)),
+                _ => Err(error()),
+            },
+            "scanlines" => match value.parse() {
+                Ok(p) if p <= 100 => Ok(Filter::Scanlines(p)),
+                _ => Err(error()),
+            },
+            "upscale" => match value.parse() {
+                Ok(n) if (1..=8).contains(&n) => Ok(Filter::Upscale(n)),
+                _ => Err(error()),
+            },
+            "fit" => parse_size(value)
+                .map(|(w, h)| Filter::Fit(w, h))
+                .ok_or_else(error),
+            "stretch" => parse_size(value)
+                .map(|(w, h)| Filter::Stretch(w, h))
+                .ok_or_else(error),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl Filter {
+    /// "gamma=2.2,upscale=2" のようにカンマで区切った後処理を、書いた順に並べる
+    pub fn parse_list(s: &str) -> Result<Vec<Filter>, String> {
+        s.split(',')
+            .filter(|f| !f.trim().is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    fn map_pixels(image: Image, f: impl Fn(PixelData) -> PixelData) -> Image {
+        image
+            .into_iter()
+            .map(|line| line.into_iter().map(&f).collect())
+            .collect()
+    }
+
+    // 幅 x 高さの画像を作る。(x, y) が元の画像のどこに当たるかを source で決め、None なら余白
+    fn sample(
+        image: &Image,
+        (width, height): (usize, usize),
+        source: impl Fn(usize, usize) -> Option<(usize, usize)>,
+    ) -> Image {
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| source(x, y).map_or(MARGIN, |(x, y)| image[y][x]))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+impl PostProcess for Filter {
+    fn process(&self, image: Image) -> Image {
+        match *self {
+            Filter::Gamma(gamma) => {
+                let table: Vec<u8> = (0..=255)
+                    .map(|c| (255.0 * (c as f64 / 255.0).powf(1.0 / gamma)).round() as u8)
+                    .collect();
+                let c = |v: u8| table[v as usize];
+                Filter::map_pixels(image, |p| PixelData(c(p.0), c(p.1), c(p.2), p.3))
+            }
+            Filter::Saturation(saturation) => Filter::map_pixels(image, |p| {
+                let l = p.luminance() as f64;
+                let c = |v: u8| (l + (v as f64 - l) * saturation).round().clamp(0.0, 255.0) as u8;
+                PixelData(c(p.0), c(p.1), c(p.2), p.3)
+            }),
+            Filter::Scanlines(percent) => {
+                let c = |v: u8| (v as u16 * (100 - percent as u16) / 100) as u8;
+                image
+                    .into_iter()
+                    .enumerate()
+                    .map(|(y, line)| match y % 2 {
+                        0 => line,
+                        _ => line
+                            .into_iter()
+                            .map(|p| PixelData(c(p.0), c(p.1), c(p.2), p.3))
+                            .collect(),
+                    })
+                    .collect()
+            }
+            Filter::Upscale(n) => image
+                .into_iter()
+                .flat_map(|line| {
+                    let line: Vec<PixelData> =
+                        line.into_iter().flat_map(|p| [p].repeat(n)).collect();
+                    vec![line; n]
+                })
+                .collect(),
+            Filter::Fit(width, height) => {
+                let (w, h) = (image.first().map_or(0, Vec::len), image.len());
+                if w == 0 {
+                    return image;
+                }
+                // 拡大率は num / den。どちらかは 1 なので画素の大きさが揃う
+                let (num, den) = match (width / w).min(height / h) {
+                    0 => (1, w.div_ceil(width).max(h.div_ceil(height))),
+                    n => (n, 1),
+                };
+                let left = (width - w * num / den) / 2;
+                let top = (height - h * num / den) / 2;
+                Filter::sample(&image, (width, height), |x, y| {
+                    let (x, y) = (
+                        x.checked_sub(left)? * den / num,
+                        y.checked_sub(top)? * den / num,
+                    );
+                    (x < w && y < h).then_some((x, y))
+                })
+            }
+            Filter::Stretch(width, height) => {
+                let (w, h) = (image.first().map_or(0, Vec::len), image.len());
+                if w == 0 {
+                    return image;
+                }
+                Filter::sample(&image, (width, height), |x, y| {
+                    Some((x * w / width, y * h / height))
+                })
+            }
+        }
+    }
+}
+
+/// フロントエンドが描画する前に掛ける後処理の並び
+/// 先に切り出しと縮小(Resize)を掛けるので、crop はゲームボーイの画面の座標のまま指定できる
+pub struct Pipeline {
+    stages: Vec<Box<dyn PostProcess>>,
+}
+
+impl Pipeline {
+    pub fn new(resize: Resize, filters: &[Filter]) -> Self {
+        let mut stages: Vec<Box<dyn PostProcess>> = vec![Box::new(resize)];
+        for filter in filters {
+            stages.push(Box::new(*filter));
+        }
+        Self { stages }
+    }
+
+    pub fn apply<R: AsRef<[PixelData]>>(&self, rows: &[R]) -> Image {
+        let image = rows.iter().map(|row| row.as_ref().to_vec()).collect();
+        self.stages
+            .iter()
+            .fold(image, |image, stage| stage.process(image))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRAY: PixelData = PixelData(0x80, 0x80, 0x80, 0);
+    const RED: PixelData = PixelData(0xC0, 0x40, 0x40, 0);
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            Filter::parse_list("gamma=2.2, saturation=0.5,scanlines=30,upscale=2"),
+            Ok(vec![
+                Filter::Gamma(2.2),
+                Filter::Saturation(0.5),
+                Filter::Scanlines(30),
+                Filter::Upscale(2),
+            ])
+        );
+        assert_eq!(
+            Filter::parse_list("fit=320x288,stretch=80 x 24"),
+            Ok(vec![Filter::Fit(320, 288), Filter::Stretch(80, 24)])
+        );
+        assert!(Filter::parse_list("fit=320").is_err());
+        assert!(Filter::parse_list("stretch=0x24").is_err());
+        assert_eq!(Filter::parse_list(""), Ok(vec![]));
+        assert!(Filter::parse_list("upscale=0").is_err());
+        assert!(Filter::parse_list("scanlines=101").is_err());
+        assert!(Filter::parse_list("blur=1").is_err());
+        assert!(Filter::parse_list("gamma").is_err());
+    }
+
+    #[test]
+    fn test_filters() {
+        let image = vec![vec![GRAY, RED], vec![GRAY, RED]];
+        let gamma = Filter::Gamma(2.0).process(image.clone());
+        assert!(gamma[0][0] == PixelData(0xB5, 0xB5, 0xB5, 0));
+        assert!(Filter::Gamma(1.0).process(image.clone()) == image);
+        // 明るさを保ったまま色を薄くする
+        let gray = Filter::Saturation(0.0).process(image.clone());
+        assert!(gray[0][1] == PixelData(0x6A, 0x6A, 0x6A, 0));
+        let scanlines = Filter::Scanlines(50).process(image.clone());
+        assert!(scanlines[0][0] == GRAY);
+        assert!(scanlines[1][0] == PixelData(0x40, 0x40, 0x40, 0));
+        let upscaled = Filter::Upscale(3).process(image);
+        assert_eq!((upscaled[0].len(), upscaled.len()), (6, 6));
+        assert!(upscaled[5][2] == GRAY && upscaled[5][3] == RED);
+    }
+
+    #[test]
+    fn test_fit() {
+        let image = vec![vec![GRAY, RED], vec![GRAY, RED]];
+        // 7x5 には 2 倍まで収まり、左右に 1 列 + 2 列、上下に 0 行 + 1 行の余白が付く
+        let fitted = Filter::Fit(7, 5).process(image.clone());
+        assert_eq!((fitted[0].len(), fitted.len()), (7, 5));
+        assert!(fitted[0][0] == MARGIN && fitted[0][5] == MARGIN && fitted[4][1] == MARGIN);
+        assert!(fitted[0][1] == GRAY && fitted[0][2] == GRAY);
+        assert!(fitted[3][3] == RED && fitted[3][4] == RED);
+        // 収まらなければ整数分の1に縮める
+        let shrunk = Filter::Fit(1, 3).process(image.clone());
+        assert_eq!((shrunk[0].len(), shrunk.len()), (1, 3));
+        assert!(shrunk[0][0] == MARGIN && shrunk[1][0] == GRAY && shrunk[2][0] == MARGIN);
+
+        // 引き伸ばすと縦横比が変わる
+        let stretched = Filter::Stretch(6, 3).process(image);
+        assert_eq!((stretched[0].len(), stretched.len()), (6, 3));
+        assert!(stretched[2][2] == GRAY && stretched[2][3] == RED);
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let frame = [[GRAY; 4]; 4];
+        let resize = Resize {
+            crop: Some("0,0,2,2".parse().unwrap()),
+            scale: 1.0,
+        };
+        // 切り出してから拡大し、拡大した後の奇数行を暗くする
+        let pipeline = Pipeline::new(resize, &[Filter::Upscale(2), Filter::Scanlines(100)]);
+        let image = pipeline.apply(&frame);
+        assert_eq!((image[0].len(), image.len()), (4, 4));
+        assert!(image[0][0] == GRAY);
+        assert!(image[1][0] == PixelData(0, 0, 0, 0));
+    }
+}
@@ -0,0 +1,360 @@
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+
+use rustboy::{
+    demo_rom, Cartridge, CpuError, Event, HardwareQuirks, Link, LinkMode, MotherBoard,
+    StatsCollector, LCD,
+};
+
+use crate::audio::AudioDump;
+use crate::config::{Config, Frontend};
+use crate::debugger::BreakPoint;
+use crate::input::{self, InputMode, RawTerminal};
+use crate::lcd::{AsciiTerminal, BrailleTerminal, Terminal};
+use crate::logger;
+use crate::pacer::{Control, Pacer, SkipFrames};
+use crate::postprocess::Pipeline;
+use crate::save::{self, SaveWriter};
+use crate::session;
+use crate::watchdog::{self, Watchdog};
+#[cfg(feature = "tui")]
+use crate::tui::{Dashboard, NoDisplay};
+
+// クラッシュ時に出力する実行履歴の命令数
+const HISTORY_DUMP_SIZE: usize = 32;
+
+/// エミュレーションを終えた理由。CI やスクリプトは終了コードで区別する
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exit {
+    // 利用者が終了した
+    Quit,
+    // --exit-on-serial の文字列を送信した
+    Serial,
+    // --exit-after-frames のフレーム数を描画した
+    Frames,
+    // --watchdog で暴走を検出した
+    Stuck,
+    // SIGINT か SIGTERM を受け取った
+    Interrupted,
+}
+
+impl Exit {
+    /// 終了コード。1 はエラーで終了したときに使う
+    pub fn code(self) -> i32 {
+        match self {
+            Exit::Quit | Exit::Serial => 0,
+            Exit::Frames => 2,
+            Exit::Stuck => 3,
+            Exit::Interrupted => 130,
+        }
+    }
+}
+
+// シリアル通信で送信されたバイトを溜めて、文字列が現れたか調べる
+struct SerialWatch {
+    pattern: Vec<u8>,
+    sent: Rc<RefCell<Vec<u8>>>,
+}
+
+impl SerialWatch {
+    fn new(mb: &MotherBoard, pattern: &str) -> Self {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let s = Rc::clone(&sent);
+        mb.subscribe(move |event| {
+            if let Event::SerialSent(data) = event {
+                s.borrow_mut().push(*data);
+            }
+        });
+        Self {
+            pattern: pattern.as_bytes().to_vec(),
+            sent,
+        }
+    }
+
+    fn matched(&self) -> bool {
+        self.pattern.is_empty()
+            || self
+                .sent
+                .borrow()
+                .windows(self.pattern.len())
+                .any(|w| w == self.pattern)
+    }
+
+    // 送信された文字列。テスト ROM の結果をログに残す
+    fn output(&self) -> String {
+        String::from_utf8_lossy(&self.sent.borrow()).into_owned()
+    }
+}
+
+/// エントリポイント
+pub fn run(config: Config) -> Result<Exit, Box<dyn Error>> {
+    logger::configure(&config.log)?;
+    #[cfg(feature = "tui")]
+    if config.tui {
+        return run_dashboard(config);
+    }
+    // 終了するまで端末を非カノニカルモードのままにする
+    let _raw = match config.input {
+        InputMode::Raw => Some(RawTerminal::enable().map_err(|e| format!("input: {}", e))?),
+        InputMode::Line => None,
+    };
+    let (control_tx, controls) = mpsc::channel();
+    input::watch_signals(control_tx.clone()).map_err(|e| format!("signal: {}", e))?;
+    let keys = input::spawn_stdin_reader(config.hotkeys, config.input, control_tx);
+    // 描画の間隔は Pacer が決める
+    let interval = Rc::new(Cell::new(1));
+    let lcd = Box::new(SkipFrames::new(frontend(&config), Rc::clone(&interval)));
+    let mb = build(&config, lcd, keys)?;
+    mb.borrow()
+        .set_key_hold(Duration::from_millis(config.key_hold));
+    if let Some(mode) = &config.link {
+        if let LinkMode::Listen(port) = mode {
+            eprintln!("Waiting for link on port {}", port);
+        }
+        let link = Link::open(mode).map_err(|e| format!("link: {}", e))?;
+        mb.borrow().set_link(Box::new(link));
+    }
+    let exit = main_loop(
+        &mb.borrow(),
+        &config,
+        controls,
+        interval,
+        #[cfg(feature = "tui")]
+        None,
+    )?;
+    Ok(exit)
+}
+
+/// 端末全体をデバッガにして動かす。キー入力も Dashboard が受け取る
+#[cfg(feature = "tui")]
+fn run_dashboard(config: Config) -> Result<Exit, Box<dyn Error>> {
+    let (key_tx, keys) = mpsc::channel();
+    let (control_tx, controls) = mpsc::channel();
+    input::watch_signals(control_tx.clone()).map_err(|e| format!("signal: {}", e))?;
+    let mb = build(&config, Box::new(NoDisplay), keys)?;
+    mb.borrow()
+        .set_key_hold(Duration::from_millis(config.key_hold));
+    let dashboard = Dashboard::new(key_tx, control_tx, config.hotkeys, config.braille);
+    // 画面は Dashboard が描くので間引かない
+    let interval = Rc::new(Cell::new(1));
+    let exit = main_loop(&mb.borrow(), &config, controls, interval, Some(dashboard))?;
+    Ok(exit)
+}
+
+fn frontend(config: &Config) -> Box<dyn LCD> {
+    let pipeline = Pipeline::new(config.resize, &config.postprocess);
+    match config.frontend {
+        Frontend::Braille => Box::new(BrailleTerminal::new(config.braille, pipeline)),
+        Frontend::Terminal => Box::new(Terminal::new(pipeline)),
+        Frontend::Ascii => Box::new(AsciiTerminal::new(config.ascii.clone(), pipeline)),
+    }
+}
+
+/// 設定に従って lcd に描画するマザーボードを組み立てる
+/// keys はキー入力の送信元
+fn build(
+    config: &Config,
+    lcd: Box<dyn LCD>,
+    keys: Receiver<String>,
+) -> Result<Rc<RefCell<MotherBoard>>, String> {
+    let cartridge = match (&config.patch, config.demo) {
+        (_, true) => Cartridge::from_bytes(demo_rom()),
+        (Some(patch), false) => Cartridge::with_patch(&config.rom_file, patch, config.mbc)?,
+        (None, false) => Cartridge::open(&config.rom_file, config.mbc)?,
+    };
+    let mb = MotherBoard::with_cartridge(cartridge, lcd, Box::new(keys), config.keys);
+    mb.borrow().ppu().set_palette(config.palette);
+    if let Some(colorization) = config.colorization {
+        mb.borrow().ppu().set_colorization(colorization);
+    }
+    mb.borrow().ppu().set_overlay(config.show_overlay);
+    mb.borrow().ppu().set_ghosting(config.ghosting);
+    mb.borrow()
+        .set_quirks(HardwareQuirks::from(config.accuracy));
+    mb.borrow().set_ram_init(config.ram_init);
+    mb.borrow().set_stop_as_nop(config.stop_as_nop);
+    for &(address, value) in &config.freezes {
+        mb.borrow().freeze(address, value)?;
+    }
+    Ok(mb)
+}
+
+// クラッシュ時の調査用に直前の命令を出力する
+fn dump_history(mb: &MotherBoard) {
+    eprintln!("history:\n{}", mb.cpu().history(HISTORY_DUMP_SIZE));
+}
+
+// 終了する前に、書き出していない RAM の変更をセーブデータに書き出す
+fn flush_save(mb: &MotherBoard, writer: &mut Option<SaveWriter>) {
+    if let Some(writer) = writer {
+        if let Err(e) = writer.flush(&mut mb.cartridge()) {
+            eprintln!("Failed to save: {}", e);
+        }
+    }
+}
+
+fn main_loop(
+    mb: &MotherBoard,
+    config: &Config,
+    controls: Receiver<Control>,
+    interval: Rc<Cell<u32>>,
+    #[cfg(feature = "tui")] mut dashboard: Option<Dashboard>,
+) -> Result<Exit, CpuError> {
+    let mut bp = BreakPoint::new(&[]);
+    // Dashboard を使うときはプロンプトのデバッガは使わない
+    #[cfg(feature = "tui")]
+    let debug = config.debug && dashboard.is_none();
+    #[cfg(not(feature = "tui"))]
+    let debug = config.debug;
+    mb.reset();
+    // 前回のブレークポイントを引き継ぐ
+    if debug {
+        if let Some(path) = session::session_path(mb.cartridge().global_checksum()) {
+            bp.open_session(path);
+        }
+    }
+    // 電池付きのカートリッジはセーブデータを読み書きする
+    let has_battery = mb.cartridge().has_battery();
+    let mut save_file = has_battery.then(|| {
+        let path = save::save_path(config);
+        if let Err(e) = save::load(&mut mb.cartridge(), &path) {
+            eprintln!("Failed to load save data: {}", e);
+        }
+        SaveWriter::new(path)
+    });
+    let serial = config
+        .exit_on_serial
+        .as_ref()
+        .map(|pattern| SerialWatch::new(mb, pattern));
+    let mut watchdog = config.watchdog.map(Watchdog::new);
+    // 終了するときに drop で書き出す
+    let mut audio = config
+        .dump_audio
+        .as_ref()
+        .map(|path| AudioDump::new(path, config.dump_audio_seconds, mb));
+    let mut frames = mb.frames();
+    let mut pacer = Pacer::new(config.speed, mb.elapsed(), controls)
+        .with_frameskip(config.frameskip, interval);
+    let mut stats = config
+        .show_fps
+        .then(|| StatsCollector::new(mb.elapsed(), frames));
+    #[cfg(feature = "metrics")]
+    let exporter = config
+        .metrics_port
+        .and_then(|port| match rustboy::serve_metrics(port) {
+            Ok(exporter) => Some(exporter),
+            Err(e) => {
+                eprintln!("Failed to serve metrics: {}", e);
+                None
+            }
+        });
+    #[cfg(feature = "recorder")]
+    if let Some(file) = &config.record_file {
+        if let Err(e) = mb.ppu().start_recording(file, 1, 1) {
+            eprintln!("Failed to start recording: {}", e);
+        }
+    }
+    loop {
+        let opcode = match panic::catch_unwind(AssertUnwindSafe(|| mb.step())) {
+            Ok(Ok((opcode, _))) => opcode,
+            Ok(Err(e)) => {
+                flush_save(mb, &mut save_file);
+                // 端末を元に戻してから履歴を出力する
+                #[cfg(feature = "tui")]
+                if let Some(mut dashboard) = dashboard.take() {
+                    dashboard.trap(&e, mb).ok();
+                }
+                dump_history(mb);
+                if debug {
+                    // 終了する前にデバッガで状態を確認できるようにする
+                    bp.trap(&e, &mb.cpu(), mb);
+                }
+                return Err(e);
+            }
+            Err(e) => {
+                flush_save(mb, &mut save_file);
+                #[cfg(feature = "tui")]
+                drop(dashboard.take());
+                dump_history(mb);
+                panic::resume_unwind(e);
+            }
+        };
+        if debug {
+            bp.breakpoint(opcode, &mb.cpu(), mb);
+            if bp.take_reset() {
+                mb.reset();
+            }
+        }
+        if let Some(watchdog) = &mut watchdog {
+            let interrupts = watchdog::total(&mb.cpu().interrupts());
+            if watchdog.check(mb.cpu_state().pc, interrupts, mb.cycles()) {
+                let report = watchdog.report(mb);
+                if debug {
+                    bp.stop(&report, &mb.cpu(), mb);
+                } else {
+                    flush_save(mb, &mut save_file);
+                    #[cfg(feature = "tui")]
+                    drop(dashboard.take());
+                    eprintln!("{}", report);
+                    dump_history(mb);
+                    return Ok(Exit::Stuck);
+                }
+            }
+        }
+        #[cfg(feature = "tui")]
+        if let Some(dashboard) = &mut dashboard {
+            if !dashboard.step(mb).expect("Failed to draw dashboard") {
+                flush_save(mb, &mut save_file);
+                return Ok(Exit::Quit);
+            }
+        }
+        if mb.frames() != frames {
+            frames = mb.frames();
+            if let Some(writer) = &mut save_file {
+                if let Err(e) = writer.frame(&mut mb.cartridge()) {
+                    eprintln!("Failed to save: {}", e);
+                }
+            }
+            if let Some(audio) = &mut audio {
+                audio.capture(mb);
+            }
+            if let Some(collector) = &mut stats {
+                let bank = mb.cartridge().current_rom_bank();
+                if let Some(s) = collector.tick(mb.elapsed(), frames, bank) {
+                    mb.ppu().set_stats(s);
+                }
+            }
+            #[cfg(feature = "metrics")]
+            if let Some(exporter) = &exporter {
+                *exporter.lock().unwrap() = mb.metrics();
+            }
+            let exit = if serial.as_ref().is_some_and(SerialWatch::matched) {
+                Some(Exit::Serial)
+            } else if config.exit_after_frames.is_some_and(|n| frames >= n) {
+                Some(Exit::Frames)
+            } else {
+                None
+            };
+            if let Some(exit) = exit {
+                flush_save(mb, &mut save_file);
+                #[cfg(feature = "tui")]
+                drop(dashboard.take());
+                if let Some(watch) = &serial {
+                    println!("{}", watch.output());
+                }
+                return Ok(exit);
+            }
+        }
+        pacer.tick(mb.elapsed(), mb.frames());
+        if pacer.quit_requested() {
+            flush_save(mb, &mut save_file);
+            #[cfg(feature = "tui")]
+            drop(dashboard.take());
+            return Ok(Exit::Interrupted);
+        }
+    }
+}
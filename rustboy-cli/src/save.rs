@@ -0,0 +1,236 @@
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use rustboy::Cartridge;
+
+use crate::config::Config;
+
+// RAM への書き込みが止まってから書き出すまでのフレーム数(約1秒)
+const DEBOUNCE_FRAMES: u32 = 60;
+
+/// ROM に対応するセーブデータのパス
+/// save_dir が無ければ ROM と同じディレクトリに拡張子を .sav にして置く
+pub fn save_path(config: &Config) -> PathBuf {
+    let path = Path::new(&config.rom_file).with_extension("sav");
+    match &config.save_dir {
+        Some(dir) => Path::new(dir).join(path.file_name().unwrap()),
+        None => path,
+    }
+}
+
+/// セーブデータを読み込む。まだ無ければ何もしない
+pub fn load(cartridge: &mut Cartridge, path: &Path) -> Result<(), String> {
+    match fs::read(path) {
+        Ok(data) => cartridge
+            .import_ram(&data)
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+/// セーブデータを書き出す
+/// 一時ファイルに書いてから置き換えるので、途中で落ちても前のセーブデータが残る
+pub fn store(cartridge: &Cartridge, path: &Path) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    let tmp = path.with_file_name(name);
+    let result = File::create(&tmp)
+        .and_then(|mut file| {
+            file.write_all(&cartridge.export_ram())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// 電池付きのカートリッジの RAM をセーブデータに書き戻す
+/// 書き込むたびにファイルを書き換えないように、書き込みが止まってから書き出す
+pub struct SaveWriter {
+    path: PathBuf,
+    // 書き出していない変更があれば、最後に書き込まれてから経ったフレーム数
+    idle: Option<u32>,
+}
+
+impl SaveWriter {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, idle: None }
+    }
+
+    /// フレームを描画するごとに呼ぶ。DEBOUNCE_FRAMES の間書き込みが無ければ書き出す
+    pub fn frame(&mut self, cartridge: &mut Cartridge) -> Result<(), String> {
+        if cartridge.take_ram_dirty() {
+            self.idle = Some(0);
+            return Ok(());
+        }
+        match self.idle {
+            Some(n) if n + 1 >= DEBOUNCE_FRAMES => self.flush(cartridge),
+            Some(n) => {
+                self.idle = Some(n + 1);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// 書き出していない変更があればすぐに書き出す。終了するときに呼ぶ
+    /// 書き出せなかった変更は残し、DEBOUNCE_FRAMES 後か次の flush で書き直す
+    pub fn flush(&mut self, cartridge: &mut Cartridge) -> Result<(), String> {
+        if cartridge.take_ram_dirty() || self.idle.is_some() {
+            self.idle = Some(0);
+            store(cartridge, &self.path)?;
+            self.idle = None;
+        }
+        Ok(())
+    }
+}
+
+/// `rustboy save export <rom> <out.sav> [options]`
+/// `rustboy save import <rom> <in.sav> [options]`
+/// セーブデータを他のエミュレータと受け渡す
+pub fn command(args: &[String]) -> Result<(), String> {
+    let usage = || "usage: rustboy save export|import <rom> <file.sav> [options]".to_string();
+    let (action, rom, file) = match args {
+        [_, _, action, rom, file, ..] => (action, rom, file),
+        _ => return Err(usage()),
+    };
+    // 保存先を決めるために、残りの引数は通常の起動時と同じように解釈する
+    let mut config_args = vec![args[0].clone(), rom.clone()];
+    config_args.extend_from_slice(&args[5..]);
+    let config = Config::new(&config_args)?;
+    let path = save_path(&config);
+    let mut cartridge = Cartridge::open(rom, config.mbc)?;
+
+    match action.as_str() {
+        "export" => {
+            if !path.exists() {
+                return Err(format!("{}: save data not found", path.display()));
+            }
+            load(&mut cartridge, &path)?;
+            fs::write(file, cartridge.export_ram()).map_err(|e| format!("{}: {}", file, e))?;
+            println!("Exported {} to {}", path.display(), file);
+        }
+        "import" => {
+            let data = fs::read(file).map_err(|e| format!("{}: {}", file, e))?;
+            cartridge
+                .import_ram(&data)
+                .map_err(|e| format!("{}: {}", file, e))?;
+            store(&cartridge, &path)?;
+            println!("Imported {} to {}", file, path.display());
+        }
+        _ => return Err(usage()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    // RAM 8KB の MBC1 + バッテリー
+    fn build_rom() -> Vec<u8> {
+        let mut rom = rustboy::build_rom(0x8000, b"SAVETEST", &[]);
+        rom[0x147] = 0x03;
+        rom[0x149] = 0x02;
+        rustboy::set_header_checksum(&mut rom);
+        rom
+    }
+
+    #[test]
+    fn test_import_export() {
+        let mut cartridge = Cartridge::from_bytes(build_rom());
+        assert!(cartridge.has_battery());
+        // RAM を有効にして書き込む
+        cartridge.write(0x0000, 0x0A);
+        cartridge.write(0xA000, 0x12);
+        cartridge.write(0xBFFF, 0x34);
+        assert!(cartridge.take_ram_dirty());
+        assert!(!cartridge.take_ram_dirty());
+
+        let dir = env::temp_dir().join(format!("rustboy-save-{}", std::process::id()));
+        let path = dir.join("game.sav");
+        store(&cartridge, &path).unwrap();
+        let data = fs::read(&path).unwrap();
+        assert_eq!(data.len(), 8 * 1024);
+        assert_eq!((data[0], data[0x1FFF]), (0x12, 0x34));
+
+        // RTC 付きの .sav も読み込める
+        let mut imported = Cartridge::from_bytes(build_rom());
+        let mut with_rtc = data.clone();
+        with_rtc.extend([0; 48]);
+        fs::write(&path, &with_rtc).unwrap();
+        load(&mut imported, &path).unwrap();
+        assert_eq!(imported.export_ram(), data);
+
+        // サイズが合わなければエラー
+        fs::write(&path, [0; 100]).unwrap();
+        assert!(load(&mut imported, &path).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+
+        // 無ければ何もしない
+        assert!(load(&mut imported, &path).is_ok());
+    }
+
+    #[test]
+    fn test_save_writer() {
+        let mut cartridge = Cartridge::from_bytes(build_rom());
+        cartridge.write(0x0000, 0x0A);
+        let dir = env::temp_dir().join(format!("rustboy-writer-{}", std::process::id()));
+        let path = dir.join("game.sav");
+        let mut writer = SaveWriter::new(path.clone());
+
+        // 書き込みが続いている間は書き出さない
+        for i in 0..DEBOUNCE_FRAMES * 2 {
+            cartridge.write(0xA000, i as u8);
+            writer.frame(&mut cartridge).unwrap();
+        }
+        assert!(!path.exists());
+        for _ in 0..DEBOUNCE_FRAMES - 1 {
+            writer.frame(&mut cartridge).unwrap();
+        }
+        assert!(!path.exists());
+        writer.frame(&mut cartridge).unwrap();
+        assert_eq!(fs::read(&path).unwrap()[0], 119);
+        // 一時ファイルは残らない
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        // 終了するときは待たずに書き出す
+        cartridge.write(0xA000, 0x56);
+        writer.frame(&mut cartridge).unwrap();
+        writer.flush(&mut cartridge).unwrap();
+        assert_eq!(fs::read(&path).unwrap()[0], 0x56);
+        fs::remove_dir_all(&dir).unwrap();
+        // 変更が無ければ書き出さない
+        writer.flush(&mut cartridge).unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_save_writer_retry() {
+        let mut cartridge = Cartridge::from_bytes(build_rom());
+        cartridge.write(0x0000, 0x0A);
+        cartridge.write(0xA000, 0x78);
+        let dir = env::temp_dir().join(format!("rustboy-retry-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // ディレクトリを作れないので書き出せない
+        let blocker = dir.join("file");
+        fs::write(&blocker, []).unwrap();
+        let mut writer = SaveWriter::new(blocker.join("game.sav"));
+        assert!(writer.flush(&mut cartridge).is_err());
+
+        // 書き出せるようになれば、失敗した分を書き出す
+        let path = dir.join("game.sav");
+        writer.path = path.clone();
+        writer.flush(&mut cartridge).unwrap();
+        assert_eq!(fs::read(&path).unwrap()[0], 0x78);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
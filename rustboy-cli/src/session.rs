@@ -0,0 +1,127 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+// 保存するコマンド履歴の上限
+const HISTORY_SIZE: usize = 100;
+
+/// デバッガのブレークポイントとコマンド履歴。再起動しても同じ設定でデバッグを続けられるように保存する
+/// ファイルは1行に1項目で、"break 0150", "count 1000", "history x c000 16" のように書く
+#[derive(Debug, Default, PartialEq)]
+pub struct Session {
+    pub breakpoints: Vec<u16>,
+    pub counts: Vec<u64>,
+    pub history: Vec<String>,
+}
+
+impl Session {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut session = Session::default();
+        for (i, line) in text.lines().enumerate() {
+            let error = || format!("line {}: {}", i + 1, line);
+            match line.split_once(' ') {
+                Some(("break", v)) => session
+                    .breakpoints
+                    .push(u16::from_str_radix(v, 16).map_err(|_| error())?),
+                Some(("count", v)) => session.counts.push(v.parse().map_err(|_| error())?),
+                Some(("history", v)) => session.push_history(v),
+                _ if line.trim().is_empty() => {}
+                _ => return Err(error()),
+            }
+        }
+        Ok(session)
+    }
+
+    pub fn to_text(&self) -> String {
+        let breakpoints = self.breakpoints.iter().map(|p| format!("break {:04X}", p));
+        let counts = self.counts.iter().map(|c| format!("count {}", c));
+        let history = self.history.iter().map(|h| format!("history {}", h));
+        let lines: Vec<String> = breakpoints.chain(counts).chain(history).collect();
+        lines.join("\n") + "\n"
+    }
+
+    /// 入力したコマンドを履歴に加える。古いものから捨てる
+    pub fn push_history(&mut self, command: &str) {
+        self.history.push(command.to_string());
+        if self.history.len() > HISTORY_SIZE {
+            self.history.remove(0);
+        }
+    }
+}
+
+/// ROM ごとのセッションファイルのパス。グローバルチェックサムで区別する
+/// $XDG_DATA_HOME/rustboy/sessions か ~/.local/share/rustboy/sessions に置く
+pub fn session_path(checksum: u16) -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(
+        dir.join("rustboy")
+            .join("sessions")
+            .join(format!("{:04X}.session", checksum)),
+    )
+}
+
+/// セッションを読み込む。まだ無ければ None
+pub fn load(path: &Path) -> Result<Option<Session>, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Session::parse(&text)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+pub fn store(session: &Session, path: &Path) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    fs::write(path, session.to_text()).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "break 0150\nbreak FF80\ncount 1000\nhistory x c000 16\nhistory c\n";
+        let session = Session::parse(text).unwrap();
+        assert_eq!(session.breakpoints, [0x0150, 0xFF80]);
+        assert_eq!(session.counts, [1000]);
+        assert_eq!(session.history, ["x c000 16", "c"]);
+        assert_eq!(session.to_text(), text);
+        assert!(Session::parse("break zz").is_err());
+        assert!(Session::parse("watch c000").is_err());
+        assert_eq!(Session::parse("\n"), Ok(Session::default()));
+    }
+
+    #[test]
+    fn test_push_history() {
+        let mut session = Session::default();
+        for i in 0..=HISTORY_SIZE {
+            session.push_history(&i.to_string());
+        }
+        assert_eq!(session.history.len(), HISTORY_SIZE);
+        assert_eq!(session.history[0], "1");
+    }
+
+    #[test]
+    fn test_load_and_store() {
+        let path = env::temp_dir()
+            .join(format!("rustboy-session-{}", std::process::id()))
+            .join("0000.session");
+        assert_eq!(load(&path), Ok(None));
+        let session = Session {
+            breakpoints: vec![0x0100],
+            counts: vec![],
+            history: vec!["n".to_string()],
+        };
+        store(&session, &path).unwrap();
+        assert_eq!(load(&path), Ok(Some(session)));
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
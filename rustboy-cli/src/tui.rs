@@ -0,0 +1,313 @@
+// 画面、レジスタ、逆アセンブル、メモリを1つの端末に並べるデバッガ
+// プロンプトのデバッガと違い、実行を止めずに状態を眺めたり1命令ずつ進めたりできる
+use std::io;
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use rustboy::{braille_lines, CpuError, FrameBuffer, FrameInfo, MotherBoard, LCD};
+
+use crate::disasm;
+use crate::input::Hotkeys;
+use crate::lcd::BrailleStyle;
+use crate::pacer::Control;
+
+// 点字1文字が 2x4 ピクセルなので、160x144 は 80x36 文字になる
+const SCREEN_WIDTH: u16 = 80 + 2;
+const SCREEN_HEIGHT: u16 = 36 + 2;
+// 逆アセンブルに並べる実行済みの命令数
+const HISTORY_LINES: usize = 8;
+// 1命令は最大 3 バイトなので、これだけ読めば画面に収まる分を逆アセンブルできる
+const DISASM_BYTES: usize = 64;
+const MEMORY_ROW: u16 = 16;
+
+/// 画面は Dashboard が描くので、PPU からは何も描画しない
+pub struct NoDisplay;
+
+impl LCD for NoDisplay {
+    fn draw(&self, _frame_buffer: &FrameBuffer, _info: &FrameInfo) {}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Run,
+    Pause,
+    // 1命令実行したら止まる
+    Step,
+    // フレーム数がこの値から変わったら止まる
+    Frame(u64),
+}
+
+/// 端末全体を使うデバッガ。drop で端末を元に戻す
+pub struct Dashboard {
+    terminal: DefaultTerminal,
+    // ゲームへのキー入力
+    keys: Sender<String>,
+    // 早送りとスロー再生は Pacer に任せる
+    controls: Sender<Control>,
+    hotkeys: Hotkeys,
+    braille: BrailleStyle,
+    mode: Mode,
+    // 最後に描画したフレーム。実行中はフレームが変わったときだけ描画する
+    frames: u64,
+    // メモリ表示の先頭アドレス
+    memory: u16,
+    message: String,
+    // キー入力を処理した後にリセットする
+    reset: bool,
+}
+
+impl Dashboard {
+    pub fn new(
+        keys: Sender<String>,
+        controls: Sender<Control>,
+        hotkeys: Hotkeys,
+        braille: BrailleStyle,
+    ) -> Self {
+        Self {
+            terminal: ratatui::init(),
+            keys,
+            controls,
+            hotkeys,
+            braille,
+            // 実行する前に状態を確認できるように止めておく
+            mode: Mode::Pause,
+            frames: u64::MAX,
+            memory: 0xC000,
+            message: String::new(),
+            reset: false,
+        }
+    }
+
+    /// 1命令ごとに呼ぶ。止まっている間は再開するまで戻らない
+    /// 終了が指示されたら false を返す
+    pub fn step(&mut self, mb: &MotherBoard) -> io::Result<bool> {
+        let frames = mb.frames();
+        let stop = match self.mode {
+            Mode::Run => false,
+            Mode::Frame(start) => frames != start,
+            Mode::Pause | Mode::Step => true,
+        };
+        if !stop {
+            if frames != self.frames {
+                self.frames = frames;
+                self.draw(mb)?;
+                while event::poll(Duration::ZERO)? {
+                    if !self.handle(event::read()?, frames) {
+                        return Ok(false);
+                    }
+                    self.apply_reset(mb);
+                }
+            }
+            return Ok(true);
+        }
+        self.mode = Mode::Pause;
+        self.message = "Paused".to_string();
+        self.wait(mb)
+    }
+
+    /// 実行できない命令に遭遇したときに呼ばれる。終了が指示されるまで状態を表示する
+    pub fn trap(&mut self, error: &CpuError, mb: &MotherBoard) -> io::Result<()> {
+        self.mode = Mode::Pause;
+        self.message = error.to_string();
+        while self.wait(mb)? {
+            // これ以上は実行できないので止めたままにする
+            self.mode = Mode::Pause;
+        }
+        Ok(())
+    }
+
+    // 再開されるまでキー入力を待つ
+    fn wait(&mut self, mb: &MotherBoard) -> io::Result<bool> {
+        let frames = mb.frames();
+        while self.mode == Mode::Pause {
+            self.draw(mb)?;
+            if !self.handle(event::read()?, frames) {
+                return Ok(false);
+            }
+            self.apply_reset(mb);
+        }
+        self.message.clear();
+        Ok(true)
+    }
+
+    fn apply_reset(&mut self, mb: &MotherBoard) {
+        if std::mem::take(&mut self.reset) {
+            mb.reset();
+        }
+    }
+
+    // 終了するなら false を返す
+    fn handle(&mut self, event: Event, frames: u64) -> bool {
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        else {
+            return true;
+        };
+        match code {
+            KeyCode::F(12) => return false,
+            KeyCode::Char('q') if modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::F(2) => self.reset = true,
+            KeyCode::F(5) => self.mode = Mode::Run,
+            KeyCode::F(6) => self.mode = Mode::Frame(frames),
+            KeyCode::F(10) => self.mode = Mode::Step,
+            KeyCode::Esc => self.mode = Mode::Pause,
+            KeyCode::PageUp => self.memory = self.memory.wrapping_sub(MEMORY_ROW * 8),
+            KeyCode::PageDown => self.memory = self.memory.wrapping_add(MEMORY_ROW * 8),
+            KeyCode::Enter => self.press('\n'),
+            KeyCode::Char(c) if c == self.hotkeys.pause => {
+                self.mode = match self.mode {
+                    Mode::Pause => Mode::Run,
+                    _ => Mode::Pause,
+                }
+            }
+            KeyCode::Char(c) if c == self.hotkeys.frame_advance => {
+                self.mode = Mode::Frame(frames)
+            }
+            KeyCode::Char(c) if c == self.hotkeys.fast_forward => {
+                let _ = self.controls.send(Control::FastForward);
+            }
+            KeyCode::Char(c) if c == self.hotkeys.slow_motion => {
+                let _ = self.controls.send(Control::SlowMotion);
+            }
+            KeyCode::Char(c) => self.press(c),
+            _ => {}
+        }
+        true
+    }
+
+    fn press(&self, key: char) {
+        let _ = self.keys.send(key.to_string());
+    }
+
+    fn draw(&mut self, mb: &MotherBoard) -> io::Result<()> {
+        let screen = self.screen(mb);
+        let registers = registers(mb);
+        let disassembly = disassembly(mb);
+        let memory = memory(mb, self.memory);
+        let status = format!(
+            "{:?} {} | F2 reset  F5 run  F6 frame  F10 step  Esc pause  PgUp/PgDn memory  F12 quit",
+            self.mode, self.message
+        );
+        self.terminal
+            .draw(|frame: &mut Frame| {
+                let [main, bottom] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(1)])
+                        .areas(frame.area());
+                let [left, right] =
+                    Layout::horizontal([Constraint::Length(SCREEN_WIDTH), Constraint::Min(0)])
+                        .areas(main);
+                let [lcd, mem] =
+                    Layout::vertical([Constraint::Length(SCREEN_HEIGHT), Constraint::Min(0)])
+                        .areas(left);
+                let [reg, dis] = Layout::vertical([Constraint::Length(8), Constraint::Min(0)])
+                    .areas(right);
+                pane(frame, lcd, "Screen", screen);
+                pane(frame, reg, "Registers", registers);
+                pane(frame, dis, "Disassembly", disassembly);
+                pane(frame, mem, "Memory", memory);
+                frame.render_widget(Paragraph::new(status), bottom);
+            })
+            .map(|_| ())
+    }
+
+    fn screen(&self, mb: &MotherBoard) -> Vec<String> {
+        let buffer = mb.ppu().front_buffer();
+        braille_lines(buffer[0].len(), buffer.len(), |x, y| {
+            self.braille.dot(&buffer[y][x], x, y)
+        })
+    }
+}
+
+impl Drop for Dashboard {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+fn pane(frame: &mut Frame, area: Rect, title: &str, lines: Vec<String>) {
+    let lines: Vec<Line> = lines.into_iter().map(Line::from).collect();
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title)),
+        area,
+    );
+}
+
+// フラグは F の上位 4 ビット
+fn registers(mb: &MotherBoard) -> Vec<String> {
+    let s = mb.cpu_state();
+    let flag = |bit: u8, name: char| if s.f >> bit & 1 == 1 { name } else { '-' };
+    let int = mb.interruption();
+    vec![
+        format!(
+            "AF {:02X}{:02X}  BC {:02X}{:02X}  DE {:02X}{:02X}  HL {:02X}{:02X}",
+            s.a, s.f, s.b, s.c, s.d, s.e, s.h, s.l
+        ),
+        format!(
+            "SP {:04X}  PC {:04X}  flags {}{}{}{}  ime {}  halted {}  stopped {}",
+            s.sp,
+            s.pc,
+            flag(7, 'Z'),
+            flag(6, 'N'),
+            flag(5, 'H'),
+            flag(4, 'C'),
+            s.ime,
+            s.halted,
+            s.stopped
+        ),
+        int.dump_interrupt_flags(),
+        int.dump_interrupt_enables(),
+        mb.timer().dump_timer(),
+        format!("cycles {}  frames {}", mb.cycles(), mb.frames()),
+    ]
+}
+
+// 実行済みの命令に続けて、PC から先を読んだ通りに逆アセンブルする
+fn disassembly(mb: &MotherBoard) -> Vec<String> {
+    let mut lines: Vec<String> = mb
+        .cpu()
+        .history(HISTORY_LINES)
+        .lines()
+        .map(|line| format!("  {}", line))
+        .collect();
+    let pc = mb.cpu_state().pc;
+    let bytes = mb.read_range(pc, DISASM_BYTES);
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let (text, size) = disasm::decode(pc.wrapping_add(offset as u16), &bytes[offset..]);
+        let marker = if offset == 0 { '>' } else { ' ' };
+        lines.push(format!(
+            "{} {:04X}  {}",
+            marker,
+            pc.wrapping_add(offset as u16),
+            text
+        ));
+        offset += size;
+    }
+    lines
+}
+
+// IO レジスタを読んでも状態が変わらないように read_range を使う
+fn memory(mb: &MotherBoard, address: u16) -> Vec<String> {
+    mb.read_range(address, 0x200)
+        .chunks(MEMORY_ROW as usize)
+        .enumerate()
+        .map(|(i, line)| {
+            let bytes: Vec<String> = line.iter().map(|b| format!("{:02X}", b)).collect();
+            format!(
+                "{:04X}: {}",
+                address.wrapping_add(i as u16 * MEMORY_ROW),
+                bytes.join(" ")
+            )
+        })
+        .collect()
+}
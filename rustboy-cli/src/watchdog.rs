@@ -0,0 +1,113 @@
+use rustboy::{InterruptCounts, MotherBoard};
+
+use crate::disasm;
+
+// PC がこの幅(バイト)より広く動けばループを抜けたとみなす
+const WINDOW: u16 = 16;
+
+/// PC が狭い範囲を回り続け、割り込みも起きないまま一定サイクル経ったことを検出する
+/// 未実装の命令や割り込みを待ち続けている(暴走している)ときによく起きる
+pub struct Watchdog {
+    limit: u64,
+    low: u16,
+    high: u16,
+    since: u64,
+    interrupts: u64,
+}
+
+impl Watchdog {
+    /// limit は何サイクル留まったら止まったとみなすか
+    pub fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            low: 0,
+            high: 0,
+            since: 0,
+            interrupts: 0,
+        }
+    }
+
+    /// 命令を実行するごとに呼ぶ。止まっていれば true を返し、次の検出に備えて数え直す
+    pub fn check(&mut self, pc: u16, interrupts: u64, cycles: u64) -> bool {
+        let low = self.low.min(pc);
+        let high = self.high.max(pc);
+        if interrupts != self.interrupts || high - low >= WINDOW || cycles < self.since {
+            self.rearm(pc, interrupts, cycles);
+            return false;
+        }
+        self.low = low;
+        self.high = high;
+        if cycles - self.since < self.limit {
+            return false;
+        }
+        self.since = cycles;
+        true
+    }
+
+    fn rearm(&mut self, pc: u16, interrupts: u64, cycles: u64) {
+        self.low = pc;
+        self.high = pc;
+        self.since = cycles;
+        self.interrupts = interrupts;
+    }
+
+    /// 止まっていた範囲とその逆アセンブル
+    pub fn report(&self, mb: &MotherBoard) -> String {
+        let mut lines = vec![format!(
+            "PC stuck in 0x{:04X}-0x{:04X} for {} cycles without interrupts",
+            self.low, self.high, self.limit
+        )];
+        // 最後の命令の途中で切れないようにオペランドの分まで読む
+        let bytes = mb.read_range(self.low, (self.high - self.low) as usize + 3);
+        let mut offset = 0;
+        while offset <= (self.high - self.low) as usize {
+            let address = self.low.wrapping_add(offset as u16);
+            let (text, size) = disasm::decode(address, &bytes[offset..]);
+            lines.push(format!("  {:04X}  {}", address, text));
+            offset += size;
+        }
+        lines.join("\n")
+    }
+}
+
+/// 処理した割り込みの合計
+pub fn total(counts: &InterruptCounts) -> u64 {
+    counts.v_blank + counts.lcd_stat + counts.timer + counts.serial + counts.joypad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let mut watchdog = Watchdog::new(100);
+        assert!(!watchdog.check(0x0150, 0, 0));
+        assert!(!watchdog.check(0x0152, 0, 60));
+        assert!(watchdog.check(0x0150, 0, 120));
+        // 検出した後は数え直す
+        assert!(!watchdog.check(0x0152, 0, 180));
+        assert!(watchdog.check(0x0150, 0, 220));
+        // 割り込みが起きれば数え直す
+        assert!(!watchdog.check(0x0152, 1, 300));
+        assert!(!watchdog.check(0x0150, 1, 380));
+        // 範囲の外に出れば数え直す
+        assert!(!watchdog.check(0x0200, 1, 420));
+        assert!(!watchdog.check(0x0202, 1, 500));
+        assert!(watchdog.check(0x0200, 1, 520));
+    }
+
+    #[test]
+    fn test_report() {
+        let mb = MotherBoard::headless(rustboy::demo_rom());
+        let mb = mb.borrow();
+        // JR -2 で同じ命令を回り続ける
+        mb.write_range(0xC000, &[0x18, 0xFE]);
+        let mut watchdog = Watchdog::new(8);
+        assert!(!watchdog.check(0xC000, 0, 0));
+        assert!(watchdog.check(0xC000, 0, 12));
+        let report = watchdog.report(&mb);
+        assert!(report.starts_with("PC stuck in 0xC000-0xC000 for 8 cycles"));
+        assert!(report.contains("C000  JR 0xC000"));
+    }
+}
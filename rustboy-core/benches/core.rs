@@ -0,0 +1,90 @@
+// ヘッドレスで動かしたコアの速度を測る
+// cargo bench で実行する。結果は target/criterion に残り、前回との差も表示される
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rustboy::{build_rom, MotherBoard};
+
+// よく使う命令を混ぜたループ
+const DISPATCH: [u8; 15] = [
+    0x04, // INC B
+    0x80, // ADD A, B
+    0xCB, 0x37, // SWAP A
+    0x4F, // LD C, A
+    0xA9, // XOR C
+    0x21, 0x00, 0xC0, // LD HL, 0xC000
+    0x77, // LD (HL), A
+    0x7E, // LD A, (HL)
+    0xC5, // PUSH BC
+    0xC1, // POP BC
+    0x18, 0xF1, // JR loop
+];
+
+// VRAM と OAM を埋めてスプライトを表示してから止まる
+const FILL_VRAM: [u8; 26] = [
+    0x21, 0x00, 0x80, // LD HL, 0x8000
+    0x7D, // LD A, L
+    0x22, // LD (HL+), A
+    0x7C, // LD A, H
+    0xFE, 0xA0, // CP 0xA0
+    0x20, 0xF9, // JR NZ, -7
+    0x21, 0x00, 0xFE, // LD HL, 0xFE00
+    0x7D, // LD A, L
+    0x22, // LD (HL+), A
+    0x7D, // LD A, L
+    0xFE, 0xA0, // CP 0xA0
+    0x20, 0xF9, // JR NZ, -7
+    0x3E, 0x93, // LD A, 0x93
+    0xE0, 0x40, // LDH (0x40), A   スプライトを有効にする
+    0x18, 0xFE, // JR -2
+];
+
+// ヘッドレスで1フレーム描画する速さ
+fn bench_frames(c: &mut Criterion) {
+    let mb = MotherBoard::headless(build_rom(0x8000, b"BENCH", &DISPATCH));
+    let mb = mb.borrow();
+    mb.reset();
+    let mut group = c.benchmark_group("frames");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("frame", |b| {
+        b.iter(|| {
+            let until = mb.frames() + 1;
+            while mb.frames() < until {
+                mb.step().unwrap();
+            }
+        })
+    });
+    group.finish();
+}
+
+// 1行のスキャンラインの描画コスト
+fn bench_scan_line(c: &mut Criterion) {
+    let mb = MotherBoard::headless(build_rom(0x8000, b"BENCH", &FILL_VRAM));
+    let mb = mb.borrow();
+    mb.reset();
+    for _ in 0..60000 {
+        mb.step().unwrap();
+    }
+    let mut ppu = mb.ppu();
+    let mut group = c.benchmark_group("scan_line");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("line", |b| {
+        b.iter(|| {
+            ppu.tick(228);
+            ppu.tick(228);
+        })
+    });
+    group.finish();
+}
+
+// 命令のデコードと実行のオーバーヘッド
+fn bench_dispatch(c: &mut Criterion) {
+    let mb = MotherBoard::headless(build_rom(0x8000, b"BENCH", &DISPATCH));
+    let mb = mb.borrow();
+    mb.reset();
+    let mut group = c.benchmark_group("dispatch");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("op", |b| b.iter(|| mb.step().unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_frames, bench_scan_line, bench_dispatch);
+criterion_main!(benches);
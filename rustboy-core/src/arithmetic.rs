@@ -21,19 +21,23 @@ impl ArithmeticUtil<u8> for u8 {
     }
 }
 
-impl ArithmeticUtil<u16> for u16 {
-    fn calc_half_carry(&self, rhs: u16) -> bool {
-        ((self & 0x000F) + (rhs & 0x000F)) & 0x0010 == 0x0010
-    }
-    fn calc_carry(&self, rhs: u16) -> bool {
-        ((*self as u32 & 0x0000FFFF) + (rhs as u32 & 0x0000FFFF)) & 0x00010000 == 0x00010000
-    }
-    fn calc_half_borrow(&self, rhs: u16) -> bool {
-        (*self & 0x00FF) < (rhs & 0x00FF)
-    }
-    fn calc_borrow(&self, rhs: u16) -> bool {
-        *self < rhs
-    }
+/// ADD HL, rr の結果と H, C フラグ
+/// 16bit の加算では H は bit 11、C は bit 15 からの桁上がりになる
+pub fn add16_hl(hl: u16, rr: u16) -> (u16, bool, bool) {
+    let h = (hl & 0x0FFF) + (rr & 0x0FFF) > 0x0FFF;
+    let (result, c) = hl.overflowing_add(rr);
+    (result, h, c)
+}
+
+/// ADD SP, e8 と LD HL, SP+e8 の結果と H, C フラグ
+/// e8 は符号付きで足すが、フラグは下位バイト同士を符号なしで足したときの bit 3 と bit 7 からの桁上がりになる
+pub fn add_sp_e8(sp: u16, e8: u8) -> (u16, bool, bool) {
+    let low = sp as u8;
+    (
+        sp.add_signed_u8(e8),
+        low.calc_half_carry(e8),
+        low.calc_carry(e8),
+    )
 }
 
 pub trait ToSigned {
@@ -113,6 +117,63 @@ mod tests {
         assert_eq!(0b1111_1110u8.calc_borrow(0b1111_1111), true);
     }
 
+    // 参照実装。各ビットへの桁上がりは入力と結果の排他的論理和に現れる
+    fn carries(a: u32, b: u32) -> u32 {
+        a ^ b ^ (a + b)
+    }
+
+    // テスト用の疑似乱数(xorshift32)
+    fn xorshift(state: &mut u32) -> u32 {
+        *state ^= *state << 13;
+        *state ^= *state >> 17;
+        *state ^= *state << 5;
+        *state
+    }
+
+    #[test]
+    fn test_add16_hl() {
+        assert_eq!(add16_hl(0x0FFF, 0x0001), (0x1000, true, false));
+        assert_eq!(add16_hl(0x00FF, 0x0001), (0x0100, false, false));
+        assert_eq!(add16_hl(0x8000, 0x8000), (0x0000, false, true));
+        assert_eq!(add16_hl(0xFFFF, 0xFFFF), (0xFFFE, true, true));
+        let mut state = 0x1234_5678;
+        for _ in 0..1_000_000 {
+            let r = xorshift(&mut state);
+            let (hl, rr) = ((r >> 16) as u16, r as u16);
+            let c = carries(hl as u32, rr as u32);
+            assert_eq!(
+                add16_hl(hl, rr),
+                (hl.wrapping_add(rr), c & 0x1000 != 0, c & 0x10000 != 0),
+                "{:04X} + {:04X}",
+                hl,
+                rr
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_sp_e8() {
+        assert_eq!(add_sp_e8(0xFFF8, 0x08), (0x0000, true, true));
+        assert_eq!(add_sp_e8(0x0000, 0xFF), (0xFFFF, false, false));
+        assert_eq!(add_sp_e8(0x1000, 0x80), (0x0F80, false, false));
+        // フラグは下位バイトと e8 だけで決まるので、その組み合わせは全て試して上位バイトは乱数にする
+        let mut state = 0x9ABC_DEF0;
+        for low in 0..=0xFF {
+            for e8 in 0..=0xFF {
+                let sp = (xorshift(&mut state) as u16) & 0xFF00 | low;
+                let c = carries(low as u32, e8 as u32);
+                let result = (sp as i32 + e8 as u8 as i8 as i32) as u16;
+                assert_eq!(
+                    add_sp_e8(sp, e8 as u8),
+                    (result, c & 0x10 != 0, c & 0x100 != 0),
+                    "{:04X} + {:02X}",
+                    sp,
+                    e8
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_to_signed_u16() {
         assert_eq!((0 as u8).to_signed_u16(), 0);
@@ -0,0 +1,37 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Unicodeの8点点字の配列。配列の添字が点の位置に相当。
+// 下位8bitが点の位置を表し、論理和がとれる。
+// [0x2801, 0x2808],
+// [0x2802, 0x2810],
+// [0x2804, 0x2820],
+// [0x2840, 0x2880],
+const BRAILLES: [[u32; 2]; 4] = [
+    ['⠁' as u32, '⠈' as u32],
+    ['⠂' as u32, '⠐' as u32],
+    ['⠄' as u32, '⠠' as u32],
+    ['⡀' as u32, '⢀' as u32],
+];
+
+/// width x height の領域のうち dot(x, y) が true の点を点字で描き、4ピクセルごとの行を返す
+pub fn lines(width: usize, height: usize, dot: impl Fn(usize, usize) -> bool) -> Vec<String> {
+    let mut lines = Vec::with_capacity(height.div_ceil(4));
+    for top in (0..height).step_by(4) {
+        let mut line = String::with_capacity(width.div_ceil(2) * 3);
+        for left in (0..width).step_by(2) {
+            // 点のない点字
+            let mut c = 0x2800;
+            for y in top..(top + 4).min(height) {
+                for x in left..(left + 2).min(width) {
+                    if dot(x, y) {
+                        c |= BRAILLES[y % 4][x % 2];
+                    }
+                }
+            }
+            line.push(char::from_u32(c).unwrap());
+        }
+        lines.push(line);
+    }
+    lines
+}
@@ -0,0 +1,710 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+
+use log::warn;
+
+pub use header::CartridgeHeader;
+use header::{CartridgeType, RamSize};
+use mbc1::Mbc1;
+pub use patch::apply_patch;
+pub use rom::Rom;
+use rom_only::RomOnly;
+
+use crate::compat::{self, CompatEntry};
+use crate::Address;
+
+mod header;
+mod licensee;
+mod mbc1;
+mod patch;
+mod rom;
+mod rom_only;
+
+// ROMバンク1つのサイズは16KB
+pub const BANK_SIZE_ROM: usize = 16 * 1024;
+// RAMバンク1つのサイズは8KB
+pub const BANK_SIZE_RAM: usize = 8 * 1024;
+// .sav の末尾に付く RTC のデータ(MBC3のみ)。44 バイトの古い形式もある
+const RTC_SIZES: [usize; 2] = [48, 44];
+// create_mbc が対応しているカートリッジの種類(ROM のみと MBC1)
+const SUPPORTED_TYPES: [u8; 6] = [0x00, 0x01, 0x02, 0x03, 0x08, 0x09];
+
+pub type RamBank = [u8; BANK_SIZE_RAM];
+
+/// ヘッダと実際の ROM が食い違うときの MBC の選び方
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MbcSelect {
+    // ヘッダの通りに作る。範囲外へのアクセスは無視する
+    Header,
+    // 実際の ROM サイズを扱える MBC を選び、RAM が無くても 8KB 用意する
+    Auto,
+}
+
+impl FromStr for MbcSelect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "header" => Ok(MbcSelect::Header),
+            "auto" => Ok(MbcSelect::Auto),
+            _ => Err(format!("Unknown MBC selection: {}", s)),
+        }
+    }
+}
+
+pub struct Cartridge {
+    header: CartridgeHeader,
+
+    // Memory Bank Controller
+    mbc: Box<dyn Mbc>,
+    // セーブデータに書き出していない変更があるか
+    ram_dirty: bool,
+    // バンクを切り替えた回数
+    bank_switches: u64,
+}
+
+impl Debug for Cartridge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // rom_data は表示しない
+        write!(
+            f,
+            "{:?}, num_of_banks: {}, current_bank_num: {}",
+            self.header,
+            self.header.rom_size.num_of_banks(),
+            self.mbc.current_rom_bank()
+        )
+    }
+}
+
+impl Cartridge {
+    /// ファイルから読み込む。壊れた ROM や未対応のカートリッジでも panic せずにエラーを返す
+    #[cfg(feature = "std")]
+    pub fn open(filename: &str, select: MbcSelect) -> Result<Self, String> {
+        let rom = Rom::open(filename)?;
+        Self::check_rom(rom.bytes()).map_err(|e| format!("{}: {}", filename, e))?;
+        Ok(Self::from_rom(rom, select))
+    }
+
+    /// from_bytes で panic しない ROM か確かめる
+    pub fn check_rom(buf: &[u8]) -> Result<(), String> {
+        if buf.len() < 0x150 {
+            return Err("Too small for a ROM".to_string());
+        }
+        Self::validate_checksum(buf).map_err(|e| e.to_string())?;
+        CartridgeHeader::parse(&buf[0x100..0x150])?;
+        if !SUPPORTED_TYPES.contains(&buf[0x147]) {
+            return Err(format!("Unsupported cartridge type 0x{:02X}", buf[0x147]));
+        }
+        Ok(())
+    }
+
+    /// IPS / BPS のパッチを当ててから構築する。ヘッダの確認はパッチを当てた後に行う
+    #[cfg(feature = "std")]
+    pub fn with_patch(filename: &str, patch_file: &str, select: MbcSelect) -> Result<Self, String> {
+        let rom = Rom::open(filename)?;
+        let patch = std::fs::read(patch_file).map_err(|e| format!("{}: {}", patch_file, e))?;
+        let rom = apply_patch(rom.bytes(), &patch).map_err(|e| format!("{}: {}", patch_file, e))?;
+        Self::check_rom(&rom)
+            .map_err(|e| format!("{} patched with {}: {}", filename, patch_file, e))?;
+        Ok(Self::from_bytes_with(rom, select))
+    }
+
+    /// メモリ上のROMイメージから構築する
+    pub fn from_bytes(buf: Vec<u8>) -> Self {
+        Self::from_bytes_with(buf, MbcSelect::Header)
+    }
+
+    /// select で MBC の選び方を指定して構築する
+    pub fn from_bytes_with(buf: Vec<u8>, select: MbcSelect) -> Self {
+        Self::from_rom(Rom::new(buf), select)
+    }
+
+    // ROM はコピーせずにそのまま MBC に渡す
+    fn from_rom(mut rom: Rom, select: MbcSelect) -> Self {
+        let buf = rom.bytes();
+        // header checksum
+        Self::validate_checksum(buf).expect("Rom file checksum failed");
+
+        let header = CartridgeHeader::parse(&buf[0x100..]).expect("Unknown value in the header");
+        for problem in Self::check_header(&header, buf.len()) {
+            warn!(target: "mbc", "{}", problem);
+        }
+
+        let mut mbc_type = &header.cartridge_type;
+        let mut ram_size = &header.ram_size;
+        // 吸い出し方によってはヘッダのサイズと合わないので、ヘッダに合わせて切り詰めるか 0xFF で埋める
+        let mut banks = header.rom_size.num_of_banks();
+        if select == MbcSelect::Auto {
+            // ヘッダより大きい場合は切り詰めずに、扱える MBC を選ぶ
+            banks = banks.max(buf.len().div_ceil(BANK_SIZE_ROM).next_power_of_two());
+            if banks > 2 && header.is_rom_only() {
+                warn!(target: "mbc", "Using MBC1 to address {} ROM banks", banks);
+                mbc_type = &CartridgeType::Mbc1;
+            }
+            if ram_size.num_of_banks() == 0 {
+                ram_size = &RamSize::KB8;
+            }
+        }
+        rom.set_num_of_banks(banks);
+
+        let mbc = Self::create_mbc(mbc_type, ram_size, rom);
+        Self {
+            header,
+            mbc,
+            ram_dirty: false,
+            bank_switches: 0,
+        }
+    }
+
+    fn validate_checksum(buf: &[u8]) -> Result<i16, &'static str> {
+        // https://gbdev.io/pandocs/The_Cartridge_Header.html#014d---header-checksum
+        let mut x: i16 = 0;
+        for m in 0x134..=0x14C {
+            x = (x - buf[m] as i16 - 1) & 0x00FF;
+        }
+        if x == buf[0x14D] as i16 {
+            Ok(x)
+        } else {
+            Err("Broken Data")
+        }
+    }
+
+    // ヘッダの内容が実際の ROM と矛盾していないか確認する
+    fn check_header(header: &CartridgeHeader, len: usize) -> Vec<String> {
+        let mut problems = Vec::new();
+        let size = header.rom_size.num_of_banks() * BANK_SIZE_ROM;
+        if len != size {
+            problems.push(format!(
+                "ROM size ({} bytes) does not match the header ({} bytes)",
+                len, size
+            ));
+        }
+        if let Some(banks) = header.max_rom_banks() {
+            if len.max(size) > banks * BANK_SIZE_ROM {
+                problems.push(format!(
+                    "{:?} cannot address {} bytes of ROM",
+                    header.cartridge_type,
+                    len.max(size)
+                ));
+            }
+        }
+        let ram_banks = header.ram_size.num_of_banks();
+        if header.has_ram() && ram_banks == 0 {
+            problems.push(format!(
+                "{:?} has RAM but the header declares no RAM",
+                header.cartridge_type
+            ));
+        }
+        if !header.has_ram() && ram_banks > 0 {
+            problems.push(format!(
+                "{:?} has no RAM but the header declares {:?}",
+                header.cartridge_type, header.ram_size
+            ));
+        }
+        problems
+    }
+
+    fn create_mbc(mbc_type: &CartridgeType, ram_size: &RamSize, rom: Rom) -> Box<dyn Mbc> {
+        match mbc_type {
+            CartridgeType::RomOnly | CartridgeType::RomRam | CartridgeType::RomRamBattery => {
+                Box::new(RomOnly::new(rom, ram_size))
+            }
+            CartridgeType::Mbc1 | CartridgeType::Mbc1Ram | CartridgeType::Mbc1RamBattery => {
+                Box::new(Mbc1::new(rom, ram_size))
+            }
+            // check_rom を通していない ROM。読めるゲームもあるので MBC1 として扱う
+            _ => {
+                warn!(target: "mbc", "{:?} is not supported, using MBC1", mbc_type);
+                Box::new(Mbc1::new(rom, ram_size))
+            }
+        }
+    }
+
+    /// MBC のレジスタを電源投入時の状態に戻す。電池で保持される RAM は消さない
+    pub fn reset(&mut self) {
+        self.mbc.reset();
+    }
+
+    pub fn supports_sgb(&self) -> bool {
+        self.header.supports_sgb()
+    }
+
+    pub fn supports_cgb(&self) -> bool {
+        self.header.supports_cgb()
+    }
+
+    pub fn has_battery(&self) -> bool {
+        self.header.has_battery()
+    }
+
+    /// ヘッダのグローバルチェックサム(0x014E - 0x014F)。ROM ごとのファイルの名前に使う
+    pub fn global_checksum(&self) -> u16 {
+        u16::from_be_bytes(self.header.global_checksum)
+    }
+
+    /// ヘッダ。タイトルや発売元を表示するときに使う
+    pub fn header(&self) -> &CartridgeHeader {
+        &self.header
+    }
+
+    /// 互換性データベースに登録されている、このゲーム向けの設定
+    pub fn compat(&self) -> Option<&'static CompatEntry> {
+        compat::lookup(&self.header.title_str(), self.global_checksum())
+    }
+
+    /// 他のエミュレータと共通の .sav 形式で RAM の内容を書き出す
+    /// RAM をそのまま並べ、RTC がある場合は末尾に付ける
+    pub fn export_ram(&self) -> Vec<u8> {
+        let mut data = self.mbc.ram();
+        if let Some(rtc) = self.mbc.rtc() {
+            data.extend(rtc);
+        }
+        data
+    }
+
+    /// .sav 形式のデータを RAM に読み込む
+    pub fn import_ram(&mut self, data: &[u8]) -> Result<(), String> {
+        // MbcSelect::Auto ではヘッダと RAM のサイズが異なる
+        let size = self.mbc.ram().len();
+        let valid = match data.len().checked_sub(size) {
+            Some(0) => true,
+            Some(rtc) => RTC_SIZES.contains(&rtc),
+            None => false,
+        };
+        if !valid {
+            return Err(format!(
+                "save data size mismatch: expected {} bytes (+ RTC), got {}",
+                size,
+                data.len()
+            ));
+        }
+        let (ram, rtc) = data.split_at(size);
+        self.mbc.load_ram(ram);
+        if !rtc.is_empty() {
+            // RTC を持たない MBC では無視される
+            self.mbc.load_rtc(rtc);
+        }
+        self.ram_dirty = false;
+        Ok(())
+    }
+
+    /// 0x4000 - 0x7FFF に割り当てられている ROM バンク
+    pub fn current_rom_bank(&self) -> usize {
+        self.mbc.current_rom_bank()
+    }
+
+    /// ROM/RAM バンクを切り替えた回数
+    pub fn bank_switches(&self) -> u64 {
+        self.bank_switches
+    }
+
+    pub fn mbc_state(&self) -> MbcState {
+        self.mbc.state()
+    }
+
+    /// デバッガから ROM バンクを切り替える。バンク0を選べないといった制限は無い
+    pub fn force_rom_bank(&mut self, bank: usize) -> Result<(), String> {
+        let banks = self.mbc.state().rom_banks;
+        if bank >= banks {
+            return Err(format!(
+                "ROM bank {} is out of range (0-{})",
+                bank,
+                banks - 1
+            ));
+        }
+        self.mbc.force_rom_bank(bank);
+        Ok(())
+    }
+
+    pub fn force_ram_bank(&mut self, bank: usize) -> Result<(), String> {
+        let banks = self.mbc.state().ram_banks;
+        if bank >= banks {
+            return Err(format!(
+                "RAM bank {} is out of range ({} banks)",
+                bank, banks
+            ));
+        }
+        self.mbc.force_ram_bank(bank);
+        Ok(())
+    }
+
+    /// 前回の呼び出しから RAM に書き込まれたか
+    pub fn take_ram_dirty(&mut self) -> bool {
+        core::mem::take(&mut self.ram_dirty)
+    }
+
+    pub fn read(&self, address: Address) -> u8 {
+        self.mbc.read(address)
+    }
+
+    pub fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xA000..=0xBFFF => {
+                self.ram_dirty = true;
+                self.mbc.write(address, data);
+            }
+            _ => {
+                let banks = (self.mbc.current_rom_bank(), self.mbc.current_ram_bank());
+                self.mbc.write(address, data);
+                if banks != (self.mbc.current_rom_bank(), self.mbc.current_ram_bank()) {
+                    self.bank_switches += 1;
+                }
+            }
+        }
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        self.mbc.save_state(state)
+    }
+}
+
+/// デバッガで表示する MBC の状態
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MbcState {
+    pub rom_bank: usize,
+    pub ram_bank: usize,
+    pub rom_banks: usize,
+    pub ram_banks: usize,
+    pub ram_enabled: bool,
+    // MBC1 のバンクモード。切り替えの無い MBC は None
+    pub banking_mode: Option<&'static str>,
+}
+
+impl Display for MbcState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ROM bank: {}/{}, RAM bank: {}/{} ({}), mode: {}",
+            self.rom_bank,
+            self.rom_banks,
+            self.ram_bank,
+            self.ram_banks,
+            if self.ram_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            },
+            self.banking_mode.unwrap_or("-")
+        )
+    }
+}
+
+pub trait Mbc {
+    // デバッグ用
+    fn current_rom_bank(&self) -> usize;
+    fn current_ram_bank(&self) -> usize;
+    fn state(&self) -> MbcState;
+    // レジスタを経由せずにバンクを切り替える。範囲は Cartridge で確認する
+    fn force_rom_bank(&mut self, bank: usize);
+    fn force_ram_bank(&mut self, bank: usize);
+    // バンク番号などのレジスタを電源投入時に戻す。RAM はそのまま
+    fn reset(&mut self);
+    // ROM/RAMの読み込み
+    fn read(&self, address: Address) -> u8;
+    // ROM/RAMの書き込み（ROM内の一部がMBC制御レジスタへの書き込みにも利用される）
+    fn write(&mut self, address: Address, data: u8);
+    // ROM 以外の状態(バンク番号やRAM)を書き出す
+    fn save_state(&self, state: &mut Vec<u8>);
+    // 全バンクの RAM を並べたもの
+    fn ram(&self) -> Vec<u8>;
+    fn load_ram(&mut self, data: &[u8]);
+    // RTC を持つ MBC3 のみ .sav に付ける 48 バイトを返す
+    fn rtc(&self) -> Option<Vec<u8>> {
+        None
+    }
+    fn load_rtc(&mut self, _data: &[u8]) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo;
+    use proptest::prelude::*;
+
+    // ヘッダの ROM サイズが 64KB の MBC1
+    fn build_rom(len: usize) -> Vec<u8> {
+        build_rom_with(len, 0x01, 0x01, 0x00)
+    }
+
+    // カートリッジの種類、ROM サイズ、RAM サイズをヘッダに書き込む
+    fn build_rom_with(len: usize, cartridge_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+        let mut rom = demo::build_rom(len, b"", &[]);
+        rom[0x147] = cartridge_type;
+        rom[0x148] = rom_size;
+        rom[0x149] = ram_size;
+        demo::set_header_checksum(&mut rom);
+        rom
+    }
+
+    #[test]
+    fn test_rom_size_mismatch() {
+        // 吸い出しが途中で切れていても 0xFF で埋めて読める
+        let mut rom = build_rom(0x8000 + 100);
+        rom[0x8000] = 0x12;
+        let mut cartridge = Cartridge::from_bytes(rom);
+        cartridge.write(0x2000, 2);
+        assert_eq!(cartridge.read(0x4000), 0x12);
+        assert_eq!(cartridge.read(0x4064), 0xFF);
+        cartridge.write(0x2000, 3);
+        assert_eq!(cartridge.read(0x4000), 0xFF);
+        // 同じバンクを選択し直しても数えない
+        cartridge.write(0x2000, 3);
+        assert_eq!(cartridge.bank_switches(), 2);
+
+        // 余分なデータは切り捨てる
+        let mut rom = build_rom(0x20000);
+        rom[0xC000] = 0x34;
+        let mut cartridge = Cartridge::from_bytes(rom);
+        cartridge.write(0x2000, 3);
+        assert_eq!(cartridge.read(0x4000), 0x34);
+        assert!(format!("{:?}", cartridge).contains("num_of_banks: 4"));
+    }
+
+    #[test]
+    fn test_check_rom() {
+        assert!(Cartridge::check_rom(&build_rom(0x8000)).is_ok());
+        assert!(Cartridge::check_rom(&[0; 0x100]).is_err());
+        let mut rom = build_rom(0x8000);
+        rom[0x14D] ^= 0xFF;
+        assert!(Cartridge::check_rom(&rom).is_err());
+        // ヘッダに無い ROM サイズ
+        let rom = build_rom_with(0x8000, 0x01, 0x40, 0x00);
+        assert_eq!(
+            Cartridge::check_rom(&rom),
+            Err("Unknown ROM size 0x40".to_string())
+        );
+        // MBC3 は未対応なのでエラーにする
+        let rom = build_rom_with(0x8000, 0x13, 0x00, 0x02);
+        assert_eq!(
+            Cartridge::check_rom(&rom),
+            Err("Unsupported cartridge type 0x13".to_string())
+        );
+        // 確かめずに構築しても panic せず、MBC1 として読み書きできる
+        let mut cartridge = Cartridge::from_bytes_with(rom, MbcSelect::Auto);
+        cartridge.write(0x0000, 0x0A);
+        cartridge.write(0xA000, 0x56);
+        assert_eq!(cartridge.read(0xA000), 0x56);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_with_patch() {
+        let dir = std::env::temp_dir().join(format!("rustboy-patch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let rom_file = dir.join("game.gb");
+        let patch_file = dir.join("fix.ips");
+        let (rom_file, patch_file) = (rom_file.to_str().unwrap(), patch_file.to_str().unwrap());
+        let mut rom = build_rom(0x8000);
+        rom[0x150] = 0x12;
+        std::fs::write(rom_file, &rom).unwrap();
+
+        // 0x0150 に 0x34 を書き込む
+        std::fs::write(patch_file, b"PATCH\x00\x01\x50\x00\x01\x34EOF").unwrap();
+        let cartridge = Cartridge::with_patch(rom_file, patch_file, MbcSelect::Header).unwrap();
+        assert_eq!(cartridge.read(0x0150), 0x34);
+
+        // ヘッダのチェックサムを壊すパッチは panic せずにエラーにする
+        let checksum = rom[0x14D] ^ 0xFF;
+        let mut patch = b"PATCH\x00\x01\x4D\x00\x01".to_vec();
+        patch.push(checksum);
+        patch.extend(b"EOF");
+        std::fs::write(patch_file, patch).unwrap();
+        let result = Cartridge::with_patch(rom_file, patch_file, MbcSelect::Header);
+        assert!(result.unwrap_err().contains("Broken Data"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_header() {
+        let header = |rom: &[u8]| CartridgeHeader::parse(&rom[0x100..]).unwrap();
+        // ROM のみで 64KB
+        let rom = build_rom_with(0x10000, 0x00, 0x00, 0x00);
+        let problems = Cartridge::check_header(&header(&rom), rom.len());
+        assert_eq!(problems.len(), 2);
+        assert!(problems[1].contains("RomOnly cannot address 65536 bytes"));
+        // RAM 付きの MBC1 なのに RAM サイズが 0
+        let rom = build_rom_with(0x8000, 0x02, 0x00, 0x00);
+        let problems = Cartridge::check_header(&header(&rom), rom.len());
+        assert_eq!(
+            problems,
+            vec!["Mbc1Ram has RAM but the header declares no RAM"]
+        );
+        // 矛盾が無い
+        let rom = build_rom_with(0x8000, 0x03, 0x00, 0x02);
+        assert!(Cartridge::check_header(&header(&rom), rom.len()).is_empty());
+    }
+
+    #[test]
+    fn test_mbc_select() {
+        // ROM のみと書かれた 64KB の自作 ROM
+        let mut rom = build_rom_with(0x10000, 0x00, 0x00, 0x00);
+        rom[0xC000] = 0x34;
+        let mut cartridge = Cartridge::from_bytes(rom.clone());
+        // ヘッダの通りならバンクは切り替わらず、RAM への書き込みも無視される
+        cartridge.write(0x2000, 3);
+        assert_eq!(cartridge.read(0x4000), 0x00);
+        cartridge.write(0xA000, 0x56);
+        assert_eq!(cartridge.read(0xA000), 0xFF);
+
+        // MBC1 として扱い、RAM も用意する
+        let mut cartridge = Cartridge::from_bytes_with(rom, MbcSelect::Auto);
+        cartridge.write(0x2000, 3);
+        assert_eq!(cartridge.read(0x4000), 0x34);
+        cartridge.write(0x0000, 0x0A);
+        cartridge.write(0xA000, 0x56);
+        assert_eq!(cartridge.read(0xA000), 0x56);
+        assert_eq!(cartridge.export_ram().len(), BANK_SIZE_RAM);
+
+        // RAM の無い MBC1 でも書き込める
+        let mut cartridge =
+            Cartridge::from_bytes_with(build_rom_with(0x8000, 0x01, 0x00, 0x00), MbcSelect::Auto);
+        cartridge.write(0x0000, 0x0A);
+        cartridge.write(0xA000, 0x78);
+        assert_eq!(cartridge.read(0xA000), 0x78);
+        assert!("auto".parse::<MbcSelect>().is_ok());
+        assert!("mbc5".parse::<MbcSelect>().is_err());
+    }
+
+    #[test]
+    fn test_mbc_state() {
+        // 128KB ROM と 32KB RAM の MBC1
+        let mut rom = build_rom_with(0x20000, 0x03, 0x02, 0x03);
+        rom[0x4000 * 3] = 0x34;
+        let mut cartridge = Cartridge::from_bytes(rom);
+        cartridge.write(0x0000, 0x0A);
+        cartridge.write(0x6000, 0x01);
+        cartridge.write(0x4000, 0x02);
+        let state = cartridge.mbc_state();
+        assert_eq!(
+            state,
+            MbcState {
+                rom_bank: 1,
+                ram_bank: 2,
+                rom_banks: 8,
+                ram_banks: 4,
+                ram_enabled: true,
+                banking_mode: Some("ram"),
+            }
+        );
+        assert_eq!(
+            state.to_string(),
+            "ROM bank: 1/8, RAM bank: 2/4 (enabled), mode: ram"
+        );
+
+        // 強制的に切り替えたバンクから読める
+        cartridge.force_rom_bank(3).unwrap();
+        assert_eq!(cartridge.read(0x4000), 0x34);
+        assert!(cartridge.force_rom_bank(8).is_err());
+        assert!(cartridge.force_ram_bank(4).is_err());
+    }
+
+    // 各バンクの全バイトにバンク番号を書いた ROM。読めた値から選ばれているバンクが分かる
+    // バンク0はヘッダを除いて 0 になる
+    fn numbered_rom(cartridge_type: u8, rom_size: u8, ram_size: u8) -> Vec<u8> {
+        let len = BANK_SIZE_ROM << (rom_size + 1);
+        let mut rom = build_rom_with(len, cartridge_type, rom_size, ram_size);
+        for (i, bank) in rom.chunks_mut(BANK_SIZE_ROM).enumerate().skip(1) {
+            bank.fill(i as u8);
+        }
+        rom
+    }
+
+    // (カートリッジの種類, ROM サイズ, RAM サイズ) の組み合わせ
+    // ROM は 2MB まで、RAM は 32KB まで
+    fn cartridge_kinds() -> impl Strategy<Value = (u8, u8, u8)> {
+        prop_oneof![
+            Just((0x00, 0x00, 0x00)),
+            Just((0x08, 0x00, 0x02)),
+            (
+                0x01u8..=0x03,
+                0x00u8..=0x06,
+                prop_oneof![Just(0x00), Just(0x02), Just(0x03)]
+            ),
+        ]
+    }
+
+    // ROM と RAM の領域への書き込みの列
+    fn cartridge_writes() -> impl Strategy<Value = Vec<(Address, u8)>> {
+        let address = prop_oneof![0x0000u16..=0x7FFF, 0xA000u16..=0xBFFF];
+        prop::collection::vec((address, any::<u8>()), 0..64)
+    }
+
+    // バンクを切り替えるレジスタへの書き込みの列
+    fn register_writes() -> impl Strategy<Value = Vec<(Address, u8)>> {
+        prop::collection::vec((0x2000u16..=0x7FFF, any::<u8>()), 0..16)
+    }
+
+    proptest! {
+        // どんな書き込みをしてもパニックせず、ROM にあるバンクが選ばれる
+        #[test]
+        fn prop_bank_in_rom(kind in cartridge_kinds(), writes in cartridge_writes()) {
+            let (cartridge_type, rom_size, ram_size) = kind;
+            let mut cartridge = Cartridge::from_bytes(numbered_rom(cartridge_type, rom_size, ram_size));
+            let banks = cartridge.mbc_state().rom_banks;
+            for (address, data) in writes {
+                cartridge.write(address, data);
+                let state = cartridge.mbc_state();
+                prop_assert!(state.rom_bank < banks);
+                prop_assert_eq!(cartridge.read(0x4000) as usize, state.rom_bank);
+                prop_assert!((cartridge.read(0x0000) as usize) < banks);
+                prop_assert!(state.ram_banks == 0 || state.ram_bank < state.ram_banks);
+            }
+        }
+
+        // RAM を無効にすると、その後どのバンクを選んでも 0xFF が読める
+        #[test]
+        fn prop_disabled_ram(
+            rom_size in 0x00u8..=0x06,
+            writes in cartridge_writes(),
+            disable in any::<u8>().prop_filter("enables RAM", |d| d & 0x0F != 0x0A),
+            switches in register_writes(),
+            address in 0xA000u16..=0xBFFF,
+        ) {
+            let mut cartridge = Cartridge::from_bytes(numbered_rom(0x03, rom_size, 0x03));
+            cartridge.write(0x0000, 0x0A);
+            for (address, data) in writes {
+                cartridge.write(address, data);
+            }
+            cartridge.write(0x0000, disable);
+            for (address, data) in switches {
+                cartridge.write(address, data);
+            }
+            prop_assert!(!cartridge.mbc_state().ram_enabled);
+            prop_assert_eq!(cartridge.read(address), 0xFF);
+        }
+
+        // 別のバンクに切り替えてから戻すと、同じ ROM と RAM が見える
+        #[test]
+        fn prop_bank_round_trip(
+            rom_size in 0x00u8..=0x06,
+            bank in 0x00u8..=0x7F,
+            other in 0x00u8..=0x7F,
+            offset in 0x0000u16..0x2000,
+            data in any::<u8>(),
+        ) {
+            let mut cartridge = Cartridge::from_bytes(numbered_rom(0x03, rom_size, 0x03));
+            let banks = cartridge.mbc_state().rom_banks;
+            let select = |cartridge: &mut Cartridge, bank: u8| {
+                cartridge.write(0x2000, bank & 0x1F);
+                cartridge.write(0x4000, bank >> 5);
+            };
+            // RAM バンクも bank の上位2bitで選ぶ
+            cartridge.write(0x0000, 0x0A);
+            cartridge.write(0x6000, 0x01);
+            select(&mut cartridge, bank);
+            // 下位5bitが 0 なら +1 され、ROM に無いバンクは上位のビットが無視される
+            let expected = if bank & 0x1F == 0 { bank | 1 } else { bank } as usize % banks;
+            prop_assert_eq!(cartridge.read(0x4000) as usize, expected);
+            cartridge.write(0xA000 + offset, data);
+
+            select(&mut cartridge, other);
+            select(&mut cartridge, bank);
+            prop_assert_eq!(cartridge.read(0x4000) as usize, expected);
+            prop_assert_eq!(cartridge.read(0xA000 + offset), data);
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt::{Debug, Formatter};
+
+use super::licensee;
+
+/// 0x0104 - 0x0133 の任天堂のロゴ。起動時にブート ROM が確認する
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+pub struct CartridgeHeader {
+    // 0100-0103
+    pub entry_point: [u8; 4],
+    // 0104-0133
+    pub nintendo_logo: [u8; 48],
+    // 0134-0143
+    pub title: [u8; 16],
+
+    // title に含まれる
+    // // 013F-0142
+    // manufacturer_code: [u8; 4],
+    // // 0143
+    // cgb_flag: u8,
+
+    // 0144-0145
+    pub new_licensee_code: [u8; 2],
+    // 0146
+    pub sgb_flag: u8,
+    // 0147
+    pub cartridge_type: CartridgeType,
+    // 0148
+    pub rom_size: RomSize,
+    // 0149
+    pub ram_size: RamSize,
+    // 014A
+    pub destination_code: u8,
+    // 014B
+    pub old_licensee_code: u8,
+    // 014C
+    pub mask_rom_version_number: u8,
+    // 014D
+    pub header_checksum: u8,
+    // 014E-014F
+    pub global_checksum: [u8; 2],
+}
+
+impl CartridgeHeader {
+    /// 0x0100 - 0x014F を読む。カートリッジの種類、ROM と RAM のサイズが知らない値ならエラー
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let bytes: &[u8; 0x50] = bytes
+            .get(..0x50)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| "Too small for a ROM header".to_string())?;
+        Ok(Self {
+            entry_point: bytes[0x00..0x04].try_into().unwrap(),
+            nintendo_logo: bytes[0x04..0x34].try_into().unwrap(),
+            title: bytes[0x34..0x44].try_into().unwrap(),
+            new_licensee_code: [bytes[0x44], bytes[0x45]],
+            sgb_flag: bytes[0x46],
+            cartridge_type: CartridgeType::try_from(bytes[0x47])?,
+            rom_size: RomSize::try_from(bytes[0x48])?,
+            ram_size: RamSize::try_from(bytes[0x49])?,
+            destination_code: bytes[0x4A],
+            old_licensee_code: bytes[0x4B],
+            mask_rom_version_number: bytes[0x4C],
+            header_checksum: bytes[0x4D],
+            global_checksum: [bytes[0x4E], bytes[0x4F]],
+        })
+    }
+
+    /// 電池でRAMの内容を保持するか
+    pub fn has_battery(&self) -> bool {
+        matches!(
+            self.cartridge_type,
+            CartridgeType::Mbc1RamBattery
+                | CartridgeType::Mbc2Battery
+                | CartridgeType::RomRamBattery
+                | CartridgeType::Mmm01RamBattery
+                | CartridgeType::Mbc3TimerBattery
+                | CartridgeType::Mbc3TimerRamBatter
+                | CartridgeType::Mbc3RamBattery
+                | CartridgeType::Mbc5RamBattery
+                | CartridgeType::Mbc5RumbleRamBattery
+                | CartridgeType::Mbc7SensorRumbleRamZBattery
+                | CartridgeType::HuC1RamBattery
+        )
+    }
+
+    /// カートリッジに RAM が載っているか
+    pub fn has_ram(&self) -> bool {
+        matches!(
+            self.cartridge_type,
+            CartridgeType::Mbc1Ram
+                | CartridgeType::Mbc1RamBattery
+                | CartridgeType::Mbc2
+                | CartridgeType::Mbc2Battery
+                | CartridgeType::RomRam
+                | CartridgeType::RomRamBattery
+                | CartridgeType::Mmm01Ram
+                | CartridgeType::Mmm01RamBattery
+                | CartridgeType::Mbc3TimerRamBatter
+                | CartridgeType::Mbc3Ram
+                | CartridgeType::Mbc3RamBattery
+                | CartridgeType::Mbc5Ram
+                | CartridgeType::Mbc5RamBattery
+                | CartridgeType::Mbc5RumbleRam
+                | CartridgeType::Mbc5RumbleRamBattery
+                | CartridgeType::Mbc7SensorRumbleRamZBattery
+                | CartridgeType::HuC1RamBattery
+        )
+    }
+
+    /// バンク切り替えの無いカートリッジか
+    pub fn is_rom_only(&self) -> bool {
+        matches!(
+            self.cartridge_type,
+            CartridgeType::RomOnly | CartridgeType::RomRam | CartridgeType::RomRamBattery
+        )
+    }
+
+    /// MBC が扱える ROM バンク数。未対応の MBC は None
+    pub fn max_rom_banks(&self) -> Option<usize> {
+        match self.cartridge_type {
+            CartridgeType::RomOnly | CartridgeType::RomRam | CartridgeType::RomRamBattery => {
+                Some(2)
+            }
+            CartridgeType::Mbc1 | CartridgeType::Mbc1Ram | CartridgeType::Mbc1RamBattery => {
+                Some(128)
+            }
+            _ => None,
+        }
+    }
+
+    /// SGB の機能に対応しているか
+    /// 旧ライセンシーコードが 0x33 で、SGB フラグが 0x03 の場合のみ有効
+    pub fn supports_sgb(&self) -> bool {
+        self.sgb_flag == 0x03 && self.old_licensee_code == 0x33
+    }
+
+    /// CGB の機能に対応しているか
+    /// タイトルの最後の1バイトが CGB フラグ(0x80: 両対応、0xC0: CGB 専用)
+    pub fn supports_cgb(&self) -> bool {
+        self.title[15] & 0x80 == 0x80
+    }
+
+    /// 表示用のタイトル。0x00 以降と、表示できない文字は含めない
+    /// 古いカートリッジは 16 文字で、CGB フラグがあれば 15 文字、製造者コードもあれば 11 文字
+    pub fn title_str(&self) -> String {
+        let len = if self.manufacturer_code().is_some() {
+            11
+        } else if self.supports_cgb() {
+            15
+        } else {
+            16
+        };
+        let title: String = self.title[..len]
+            .iter()
+            .take_while(|&&b| b != 0)
+            .filter(|b| b.is_ascii_graphic() || **b == b' ')
+            .map(|&b| b as char)
+            .collect();
+        title.trim_end().to_string()
+    }
+
+    /// 0x013F - 0x0142 の製造者コード
+    /// タイトルと区別できないので、CGB 対応で新ライセンシーコードを使い、
+    /// 4 文字とも英大文字か数字の場合だけ製造者コードとみなす
+    pub fn manufacturer_code(&self) -> Option<String> {
+        let code = &self.title[11..15];
+        let valid = self.supports_cgb()
+            && self.old_licensee_code == 0x33
+            && code
+                .iter()
+                .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit());
+        valid.then(|| code.iter().map(|&b| b as char).collect())
+    }
+
+    /// 発売元の名前。旧ライセンシーコードが 0x33 なら新ライセンシーコードで探す
+    /// コードが無いか表に無ければ None
+    pub fn licensee(&self) -> Option<&'static str> {
+        match self.old_licensee_code {
+            0x33 => licensee::new(self.new_licensee_code),
+            code => licensee::old(code),
+        }
+    }
+}
+
+// ログに出すので、バイト列ではなく読める形で表示する。ロゴとエントリポイントは省く
+impl Debug for CartridgeHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CartridgeHeader")
+            .field("title", &self.title_str())
+            .field("manufacturer_code", &self.manufacturer_code())
+            .field("licensee", &self.licensee())
+            .field("cgb", &self.supports_cgb())
+            .field("sgb", &self.supports_sgb())
+            .field("cartridge_type", &self.cartridge_type)
+            .field("rom_size", &self.rom_size)
+            .field("ram_size", &self.ram_size)
+            .field("destination_code", &self.destination_code)
+            .field("mask_rom_version_number", &self.mask_rom_version_number)
+            .field(
+                "global_checksum",
+                &format_args!("0x{:04X}", u16::from_be_bytes(self.global_checksum)),
+            )
+            .finish()
+    }
+}
+
+#[derive(Debug, PartialEq, Hash)]
+pub enum CartridgeType {
+    RomOnly = 0x00,
+    Mbc1 = 0x01,
+    Mbc1Ram = 0x02,
+    Mbc1RamBattery = 0x03,
+    Mbc2 = 0x05,
+    Mbc2Battery = 0x06,
+    RomRam = 0x08,
+    RomRamBattery = 0x09,
+    Mmm01 = 0x0B,
+    Mmm01Ram = 0x0C,
+    Mmm01RamBattery = 0x0D,
+    Mbc3TimerBattery = 0x0F,
+    Mbc3TimerRamBatter = 0x10,
+    Mbc3 = 0x11,
+    Mbc3Ram = 0x12,
+    Mbc3RamBattery = 0x13,
+    Mbc5 = 0x19,
+    Mbc5Ram = 0x1A,
+    Mbc5RamBattery = 0x1B,
+    Mbc5Rumble = 0x1C,
+    Mbc5RumbleRam = 0x1D,
+    Mbc5RumbleRamBattery = 0x1E,
+    Mbc6 = 0x20,
+    Mbc7SensorRumbleRamZBattery = 0x22,
+    PocketCamera = 0xFC,
+    BandaiTama5 = 0xFD,
+    HuC3 = 0xFE,
+    HuC1RamBattery = 0xFF,
+}
+
+impl TryFrom<u8> for CartridgeType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x00 => CartridgeType::RomOnly,
+            0x01 => CartridgeType::Mbc1,
+            0x02 => CartridgeType::Mbc1Ram,
+            0x03 => CartridgeType::Mbc1RamBattery,
+            0x05 => CartridgeType::Mbc2,
+            0x06 => CartridgeType::Mbc2Battery,
+            0x08 => CartridgeType::RomRam,
+            0x09 => CartridgeType::RomRamBattery,
+            0x0B => CartridgeType::Mmm01,
+            0x0C => CartridgeType::Mmm01Ram,
+            0x0D => CartridgeType::Mmm01RamBattery,
+            0x0F => CartridgeType::Mbc3TimerBattery,
+            0x10 => CartridgeType::Mbc3TimerRamBatter,
+            0x11 => CartridgeType::Mbc3,
+            0x12 => CartridgeType::Mbc3Ram,
+            0x13 => CartridgeType::Mbc3RamBattery,
+            0x19 => CartridgeType::Mbc5,
+            0x1A => CartridgeType::Mbc5Ram,
+            0x1B => CartridgeType::Mbc5RamBattery,
+            0x1C => CartridgeType::Mbc5Rumble,
+            0x1D => CartridgeType::Mbc5RumbleRam,
+            0x1E => CartridgeType::Mbc5RumbleRamBattery,
+            0x20 => CartridgeType::Mbc6,
+            0x22 => CartridgeType::Mbc7SensorRumbleRamZBattery,
+            0xFC => CartridgeType::PocketCamera,
+            0xFD => CartridgeType::BandaiTama5,
+            0xFE => CartridgeType::HuC3,
+            0xFF => CartridgeType::HuC1RamBattery,
+            _ => return Err(format!("Unknown cartridge type 0x{:02X}", value)),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum RomSize {
+    // TODO: バンク数Nも enum から取得できるようにしたい
+    KBytes32 = 0x00,
+    KBytes64 = 0x01,
+    KBytes128 = 0x02,
+    KBytes256 = 0x03,
+    KBytes512 = 0x04,
+    MBytes1 = 0x05,
+    MBytes2 = 0x06,
+    MBytes4 = 0x07,
+    MBytes8 = 0x08,
+    MBytes1_1 = 0x52,
+    MBytes1_2 = 0x53,
+    MBytes1_5 = 0x54,
+}
+
+impl TryFrom<u8> for RomSize {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x00 => RomSize::KBytes32,
+            0x01 => RomSize::KBytes64,
+            0x02 => RomSize::KBytes128,
+            0x03 => RomSize::KBytes256,
+            0x04 => RomSize::KBytes512,
+            0x05 => RomSize::MBytes1,
+            0x06 => RomSize::MBytes2,
+            0x07 => RomSize::MBytes4,
+            0x08 => RomSize::MBytes8,
+            0x52 => RomSize::MBytes1_1,
+            0x53 => RomSize::MBytes1_2,
+            0x54 => RomSize::MBytes1_5,
+            _ => return Err(format!("Unknown ROM size 0x{:02X}", value)),
+        })
+    }
+}
+
+impl RomSize {
+    pub fn num_of_banks(&self) -> usize {
+        match self {
+            RomSize::KBytes32 => 2,
+            RomSize::KBytes64 => 4,
+            RomSize::KBytes128 => 8,
+            RomSize::KBytes256 => 16,
+            RomSize::KBytes512 => 32,
+            RomSize::MBytes1 => 64,
+            RomSize::MBytes2 => 128,
+            RomSize::MBytes4 => 256,
+            RomSize::MBytes8 => 512,
+            RomSize::MBytes1_1 => 72,
+            RomSize::MBytes1_2 => 80,
+            RomSize::MBytes1_5 => 96,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RamSize {
+    NoRam = 0x00,
+    UnUsed = 0x01,
+    KB8 = 0x02,
+    KB32 = 0x03,
+    KB128 = 0x04,
+    KB64 = 0x05,
+}
+
+impl TryFrom<u8> for RamSize {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0x00 => RamSize::NoRam,
+            0x01 => RamSize::UnUsed,
+            0x02 => RamSize::KB8,
+            0x03 => RamSize::KB32,
+            0x04 => RamSize::KB128,
+            0x05 => RamSize::KB64,
+            _ => return Err(format!("Unknown RAM size 0x{:02X}", value)),
+        })
+    }
+}
+
+impl RamSize {
+    pub fn num_of_banks(&self) -> usize {
+        match self {
+            RamSize::NoRam | RamSize::UnUsed => 0,
+            RamSize::KB8 => 1,
+            RamSize::KB32 => 4,
+            RamSize::KB64 => 8,
+            RamSize::KB128 => 16,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0x0100 - 0x014F のヘッダ。タイトルとライセンシーコードだけを書き込む
+    fn header(title: &[u8], old_licensee: u8, new_licensee: &[u8; 2]) -> CartridgeHeader {
+        let mut bytes = [0u8; 0x50];
+        bytes[0x34..0x34 + title.len()].copy_from_slice(title);
+        bytes[0x44..0x46].copy_from_slice(new_licensee);
+        bytes[0x4B] = old_licensee;
+        CartridgeHeader::parse(&bytes).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut bytes = [0u8; 0x50];
+        bytes[0x47] = 0x13;
+        bytes[0x48] = 0x05;
+        bytes[0x49] = 0x03;
+        let h = CartridgeHeader::parse(&bytes).unwrap();
+        assert_eq!(h.cartridge_type, CartridgeType::Mbc3RamBattery);
+        assert_eq!(h.rom_size.num_of_banks(), 64);
+        assert_eq!(h.ram_size.num_of_banks(), 4);
+        // 知らない値は enum にせずにエラーにする
+        bytes[0x48] = 0x40;
+        assert_eq!(
+            CartridgeHeader::parse(&bytes).err(),
+            Some("Unknown ROM size 0x40".to_string())
+        );
+        bytes[0x48] = 0x00;
+        bytes[0x49] = 0x06;
+        assert!(CartridgeHeader::parse(&bytes).is_err());
+        bytes[0x49] = 0x00;
+        bytes[0x47] = 0x04;
+        assert!(CartridgeHeader::parse(&bytes).is_err());
+        assert!(CartridgeHeader::parse(&bytes[..0x4F]).is_err());
+    }
+
+    #[test]
+    fn test_title_str() {
+        // 16 文字
+        let h = header(b"SUPER MARIOLAND2", 0x01, b"00");
+        assert_eq!(h.title_str(), "SUPER MARIOLAND2");
+        assert_eq!(h.licensee(), Some("Nintendo"));
+        assert_eq!(h.manufacturer_code(), None);
+        // 0x00 以降は含めない
+        assert_eq!(header(b"TETRIS\0\0X", 0x01, b"00").title_str(), "TETRIS");
+        // CGB フラグは含めない
+        let h = header(b"POKEMON YELLOW\0\x80", 0x33, b"01");
+        assert_eq!(h.title_str(), "POKEMON YELLOW");
+        assert_eq!(h.licensee(), Some("Nintendo R&D1"));
+        // 製造者コード
+        let h = header(b"POKEMON_SLVAAXE\xC0", 0x33, b"01");
+        assert_eq!(h.title_str(), "POKEMON_SLV");
+        assert_eq!(h.manufacturer_code(), Some("AAXE".to_string()));
+        // 旧ライセンシーコードなら製造者コードとみなさない
+        let h = header(b"ABCDEFGHIJKLMNO\x80", 0xA4, b"00");
+        assert_eq!(h.title_str(), "ABCDEFGHIJKLMNO");
+        assert_eq!(h.licensee(), Some("Konami"));
+        // 表示できない文字は含めない
+        assert_eq!(header(b"GAME\x7F\x01!", 0x00, b"00").title_str(), "GAME!");
+        assert_eq!(header(b"GAME", 0x33, b"ZZ").licensee(), None);
+        assert_eq!(header(b"GAME", 0x00, b"00").licensee(), None);
+    }
+}
@@ -0,0 +1,350 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use log::{debug, info};
+
+use super::header::NINTENDO_LOGO;
+use super::{Mbc, MbcState, RamBank, RamSize, Rom, BANK_SIZE_RAM};
+use crate::Address;
+
+pub struct Mbc1 {
+    rom: Rom,
+    ram_banks: Vec<RamBank>,
+    // 0x2000 - 0x3FFF: ROM バンクの下位5bit。0 は 1 として扱う
+    bank1: usize,
+    // 0x4000 - 0x5FFF: ROM バンクの上位2bit、または RAM バンク
+    bank2: usize,
+    bank_mode: BankMode,
+    ram_mode: RamMode,
+    // 複数のゲームを詰め合わせたカートリッジ(MBC1M)
+    // bank1 の最上位ビットが繋がっていないので、bank2 は4bit目からになる
+    multicart: bool,
+}
+
+enum BankMode {
+    // bank2 は 0x4000 - 0x7FFF の ROM バンクにだけ使う。RAM バンクは 0x00 のみ
+    Rom,
+    // bank2 を 0x0000 - 0x3FFF の ROM バンクと RAM バンクにも使う
+    // 1MB 以上の ROM では 0x0000 - 0x3FFF にバンク 0x20, 0x40, 0x60 が見える
+    Ram,
+}
+
+enum RamMode {
+    Disable,
+    Enable,
+}
+
+impl Mbc1 {
+    pub fn new(rom: Rom, ram_size: &RamSize) -> Self {
+        let multicart = Self::is_multicart(&rom);
+        if multicart {
+            info!(target: "mbc", "MBC1 multicart detected");
+        }
+        Self {
+            rom,
+            ram_banks: vec![[0; BANK_SIZE_RAM]; ram_size.num_of_banks()],
+            bank1: 1,
+            bank2: 0,
+            bank_mode: BankMode::Rom,
+            ram_mode: RamMode::Disable,
+            multicart,
+        }
+    }
+
+    // 詰め合わせの 1MB の ROM は、各ゲームの先頭(バンク 0x10, 0x20, 0x30)にもヘッダがある
+    // メニューの入ったバンク 0 の他に、2つ目のゲームのロゴがあれば MBC1M とみなす
+    fn is_multicart(rom: &Rom) -> bool {
+        rom.num_of_banks() == 64
+            && [0x00, 0x10]
+                .iter()
+                .all(|&bank| rom.bank(bank).get(0x0104..0x0134) == Some(&NINTENDO_LOGO[..]))
+    }
+
+    // bank2 をどのビットに繋ぐか
+    fn bank2_shift(&self) -> usize {
+        if self.multicart {
+            4
+        } else {
+            5
+        }
+    }
+
+    // 0x0000 - 0x3FFF のバンク
+    fn low_rom_bank(&self) -> usize {
+        match self.bank_mode {
+            BankMode::Rom => 0,
+            BankMode::Ram => (self.bank2 << self.bank2_shift()) % self.rom.num_of_banks(),
+        }
+    }
+
+    // 0x4000 - 0x7FFF のバンク。ROM に無いバンクは上位のビットが無視される
+    fn high_rom_bank(&self) -> usize {
+        self.selected_rom_bank() % self.rom.num_of_banks()
+    }
+
+    // レジスタで選ばれているバンク。ROM に無いバンクのこともある
+    fn selected_rom_bank(&self) -> usize {
+        let bank1 = if self.multicart {
+            self.bank1 & 0x0F
+        } else {
+            self.bank1
+        };
+        (self.bank2 << self.bank2_shift()) | bank1
+    }
+
+    // ROM や RAM に無いバンクを選んだときは、実機と同じく繋がっているビットだけで選ぶ
+    fn log_mirrored_banks(&self) {
+        let rom_bank = self.selected_rom_bank();
+        if rom_bank >= self.rom.num_of_banks() {
+            debug!(
+                target: "mbc",
+                "ROM bank 0x{:02X} is mirrored to 0x{:02X}",
+                rom_bank,
+                self.high_rom_bank()
+            );
+        }
+        if matches!(self.bank_mode, BankMode::Ram)
+            && self.ram_banks.len() > 1
+            && self.bank2 >= self.ram_banks.len()
+        {
+            debug!(
+                target: "mbc",
+                "RAM bank {} is mirrored to {}",
+                self.bank2,
+                self.current_ram_bank()
+            );
+        }
+    }
+}
+
+impl Mbc for Mbc1 {
+    fn current_rom_bank(&self) -> usize {
+        self.high_rom_bank()
+    }
+    fn current_ram_bank(&self) -> usize {
+        match self.bank_mode {
+            BankMode::Rom => 0,
+            // 8KB の RAM ではバンク番号が無視される
+            BankMode::Ram => self.bank2 % self.ram_banks.len().max(1),
+        }
+    }
+    fn state(&self) -> MbcState {
+        MbcState {
+            rom_bank: self.current_rom_bank(),
+            ram_bank: self.current_ram_bank(),
+            rom_banks: self.rom.num_of_banks(),
+            ram_banks: self.ram_banks.len(),
+            ram_enabled: matches!(self.ram_mode, RamMode::Enable),
+            banking_mode: Some(match self.bank_mode {
+                BankMode::Rom => "rom",
+                BankMode::Ram => "ram",
+            }),
+        }
+    }
+    fn force_rom_bank(&mut self, bank: usize) {
+        // bank1 の 0 を 1 にする変換を通さないので、バンク 0 も選べる
+        let shift = self.bank2_shift();
+        self.bank1 = bank & ((1 << shift) - 1);
+        self.bank2 = bank >> shift;
+    }
+    fn force_ram_bank(&mut self, bank: usize) {
+        self.bank_mode = BankMode::Ram;
+        self.bank2 = bank;
+    }
+    fn reset(&mut self) {
+        self.bank1 = 1;
+        self.bank2 = 0;
+        self.bank_mode = BankMode::Rom;
+        self.ram_mode = RamMode::Disable;
+    }
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0x0000..=0x3FFF => {
+                // バンク0から読み込み
+                self.rom.read(self.low_rom_bank(), address as usize)
+            }
+            0x4000..=0x7FFF => {
+                // バンク1-Nから読み込み
+                self.rom
+                    .read(self.high_rom_bank(), (address - 0x4000) as usize)
+            }
+            0xA000..=0xBFFF => {
+                // カートリッジ内のRAM。無効な場合や RAM が無い場合は 0xFF
+                match (&self.ram_mode, self.ram_banks.get(self.current_ram_bank())) {
+                    (RamMode::Enable, Some(bank)) => bank[(address - 0xA000) as usize],
+                    _ => 0xFF,
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn ram(&self) -> Vec<u8> {
+        self.ram_banks.concat()
+    }
+    fn load_ram(&mut self, data: &[u8]) {
+        for (bank, chunk) in self.ram_banks.iter_mut().zip(data.chunks(BANK_SIZE_RAM)) {
+            bank.copy_from_slice(chunk);
+        }
+    }
+    fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend(self.bank1.to_le_bytes());
+        state.extend(self.bank2.to_le_bytes());
+        state.push(match self.bank_mode {
+            BankMode::Rom => 0,
+            BankMode::Ram => 1,
+        });
+        state.push(match self.ram_mode {
+            RamMode::Disable => 0,
+            RamMode::Enable => 1,
+        });
+        for bank in &self.ram_banks {
+            state.extend(bank);
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0x0000..=0x1FFF => {
+                // 外部RAMの有効/無効切替
+                match data & 0x0F {
+                    0x0A => {
+                        self.ram_mode = RamMode::Enable;
+                    }
+                    _ => {
+                        self.ram_mode = RamMode::Disable;
+                    }
+                }
+            }
+            0x2000..=0x3FFF => {
+                // ROM バンク番号の下位5bit (書き込み専用)
+                // 0 を書き込むと 1 になる。MBC1M でも5bit全体で判定する
+                self.bank1 = match data & 0x1F {
+                    0 => 1,
+                    bank => bank as usize,
+                };
+                self.log_mirrored_banks();
+            }
+            0x4000..=0x5FFF => {
+                // RAM バンク番号または、 ROM バンク番号の上位2bit (書き込み専用)
+                self.bank2 = (data & 0x03) as usize;
+                self.log_mirrored_banks();
+            }
+            0x6000..=0x7FFF => {
+                match data & 0x1 {
+                    0 => {
+                        self.bank_mode = BankMode::Rom;
+                    }
+                    1 => {
+                        self.bank_mode = BankMode::Ram;
+                    }
+                    _ => unreachable!(),
+                }
+                self.log_mirrored_banks();
+            }
+            0xA000..=0xBFFF => {
+                let bank = self.current_ram_bank();
+                if let (RamMode::Enable, Some(bank)) =
+                    (&self.ram_mode, self.ram_banks.get_mut(bank))
+                {
+                    bank[(address - 0xA000) as usize] = data;
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cartridges::BANK_SIZE_ROM;
+
+    // 各バンクの先頭にバンク番号を書いた ROM
+    fn numbered_banks(n: usize) -> Vec<u8> {
+        let mut rom = vec![0; n * BANK_SIZE_ROM];
+        for i in 0..n {
+            rom[i * BANK_SIZE_ROM] = i as u8;
+        }
+        rom
+    }
+
+    #[test]
+    fn test_bank_mapping() {
+        let mut mbc = Mbc1::new(Rom::new(numbered_banks(128)), &RamSize::KB8);
+        assert!(!mbc.multicart);
+        // 0 は 1 として扱う
+        mbc.write(0x2000, 0x00);
+        assert_eq!(mbc.read(0x4000), 0x01);
+        mbc.write(0x2000, 0x12);
+        mbc.write(0x4000, 0x02);
+        assert_eq!(mbc.read(0x4000), 0x52);
+        assert_eq!(mbc.read(0x0000), 0x00);
+        // 下位5bitが 0 になるので 0x40 ではなく 0x41 が選ばれる
+        mbc.write(0x2000, 0x20);
+        assert_eq!(mbc.read(0x4000), 0x41);
+        // モード1では 0x0000 - 0x3FFF も bank2 で切り替わる
+        mbc.write(0x6000, 0x01);
+        assert_eq!(mbc.read(0x0000), 0x40);
+
+        // ROM に無いバンクは上位のビットが無視される
+        let mut mbc = Mbc1::new(Rom::new(numbered_banks(8)), &RamSize::KB8);
+        mbc.write(0x2000, 0x1F);
+        mbc.write(0x4000, 0x03);
+        assert_eq!(mbc.read(0x4000), 0x07);
+        mbc.write(0x6000, 0x01);
+        assert_eq!(mbc.read(0x0000), 0x00);
+    }
+
+    #[test]
+    fn test_bank_out_of_range() {
+        // 64KB の ROM でバンク 0x1F を選ぶと、下位2bitのバンク 3 が見える
+        let mut mbc = Mbc1::new(Rom::new(numbered_banks(4)), &RamSize::KB8);
+        mbc.write(0x2000, 0x1F);
+        assert_eq!(mbc.current_rom_bank(), 3);
+        assert_eq!(mbc.read(0x4000), 0x03);
+        assert_eq!(mbc.read(0x7FFF), 0x00);
+
+        // 8KB の RAM では RAM バンク番号が無視される
+        mbc.write(0x0000, 0x0A);
+        mbc.write(0x6000, 0x01);
+        mbc.write(0x4000, 0x03);
+        mbc.write(0xA000, 0x12);
+        assert_eq!(mbc.current_ram_bank(), 0);
+        mbc.write(0x4000, 0x00);
+        assert_eq!(mbc.read(0xA000), 0x12);
+
+        // RAM が無ければ 0xFF が読めて、書き込みは無視される
+        let mut mbc = Mbc1::new(Rom::new(numbered_banks(4)), &RamSize::NoRam);
+        mbc.write(0x0000, 0x0A);
+        mbc.write(0x6000, 0x01);
+        mbc.write(0x4000, 0x03);
+        mbc.write(0xA000, 0x12);
+        assert_eq!(mbc.read(0xA000), 0xFF);
+    }
+
+    #[test]
+    fn test_multicart() {
+        let mut banks = numbered_banks(64);
+        for bank in [0x00, 0x10, 0x20, 0x30] {
+            let header = bank * BANK_SIZE_ROM;
+            banks[header + 0x0104..header + 0x0134].copy_from_slice(&NINTENDO_LOGO);
+        }
+        let mut mbc = Mbc1::new(Rom::new(banks.clone()), &RamSize::NoRam);
+        assert!(mbc.multicart);
+        // 2つ目のゲームを選ぶとバンク 0x10 が 0x0000 - 0x3FFF に見える
+        mbc.write(0x4000, 0x01);
+        mbc.write(0x6000, 0x01);
+        assert_eq!(mbc.read(0x0000), 0x10);
+        assert_eq!(mbc.read(0x4000), 0x11);
+        // bank1 の最上位ビットは無視される
+        mbc.write(0x2000, 0x13);
+        assert_eq!(mbc.read(0x4000), 0x13);
+        mbc.write(0x2000, 0x10);
+        assert_eq!(mbc.read(0x4000), 0x10);
+        assert_eq!(mbc.current_rom_bank(), 0x10);
+
+        // 2つ目のゲームのロゴが無ければ通常の MBC1
+        banks[0x10 * BANK_SIZE_ROM + 0x0104] = 0x00;
+        assert!(!Mbc1::new(Rom::new(banks), &RamSize::NoRam).multicart);
+        assert!(!Mbc1::new(Rom::new(numbered_banks(64)), &RamSize::NoRam).multicart);
+    }
+}
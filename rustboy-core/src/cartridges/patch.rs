@@ -0,0 +1,303 @@
+// ROM に当てるパッチ(IPS / BPS)
+// 翻訳やバグ修正のパッチをパッチ済みのファイルを作らずに使えるようにする
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::crc32::crc32;
+
+const IPS_MAGIC: &[u8] = b"PATCH";
+const IPS_EOF: &[u8] = b"EOF";
+const BPS_MAGIC: &[u8] = b"BPS1";
+// BPS の末尾に付く元の ROM、パッチ後の ROM、パッチ自身の CRC32
+const BPS_FOOTER_SIZE: usize = 12;
+// ゲームボーイの ROM の最大サイズ。パッチ後の大きさがこれを超えたら確保する前にエラーにする
+const MAX_ROM_SIZE: usize = 8 * 1024 * 1024;
+
+/// 先頭のマジックナンバーで形式を判定してパッチを当てる
+pub fn apply_patch(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, String> {
+    if patch.starts_with(IPS_MAGIC) {
+        apply_ips(rom, patch)
+    } else if patch.starts_with(BPS_MAGIC) {
+        apply_bps(rom, patch)
+    } else {
+        Err("Unknown patch format (expected IPS or BPS)".to_string())
+    }
+}
+
+// パッチを先頭から読む
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], pos: usize) -> Self {
+        Self { data, pos }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + len;
+        if end > self.data.len() {
+            return Err(format!("Patch is truncated at 0x{:X}", self.pos));
+        }
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    // ビッグエンディアン
+    fn number(&mut self, len: usize) -> Result<usize, String> {
+        Ok(self.bytes(len)?.iter().fold(0, |n, &b| n << 8 | b as usize))
+    }
+
+    // BPS の可変長整数。続きがあれば最上位ビットが 0 で、重複しないように続きの分を足す
+    fn varint(&mut self) -> Result<usize, String> {
+        let mut data: usize = 0;
+        let mut shift: usize = 1;
+        loop {
+            let x = self.byte()?;
+            data = (x as usize & 0x7F)
+                .checked_mul(shift)
+                .and_then(|n| n.checked_add(data))
+                .ok_or("Patch has a number that is too large")?;
+            if x & 0x80 != 0 {
+                return Ok(data);
+            }
+            shift <<= 7;
+            data += shift;
+        }
+    }
+}
+
+// https://zerosoft.zophar.net/ips.php
+// レコードは 3 バイトのオフセットと 2 バイトの長さ。長さが 0 なら同じ値の繰り返し(RLE)
+// EOF の後に 3 バイトあれば、その長さに切り詰める
+fn apply_ips(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = rom.to_vec();
+    let mut reader = Reader::new(patch, IPS_MAGIC.len());
+    loop {
+        if reader.data[reader.pos..].starts_with(IPS_EOF)
+            && matches!(patch.len() - reader.pos, 3 | 6)
+        {
+            reader.pos += IPS_EOF.len();
+            break;
+        }
+        let offset = reader.number(3)?;
+        let bytes = match reader.number(2)? {
+            0 => {
+                let len = reader.number(2)?;
+                vec![reader.byte()?; len]
+            }
+            len => reader.bytes(len)?.to_vec(),
+        };
+        let end = offset + bytes.len();
+        if end > out.len() {
+            out.resize(end, 0);
+        }
+        out[offset..end].copy_from_slice(&bytes);
+    }
+    if reader.pos < patch.len() {
+        out.truncate(reader.number(3)?);
+    }
+    Ok(out)
+}
+
+// https://www.romhacking.net/documents/746/
+fn apply_bps(rom: &[u8], patch: &[u8]) -> Result<Vec<u8>, String> {
+    if patch.len() < BPS_MAGIC.len() + BPS_FOOTER_SIZE {
+        return Err("Patch is truncated".to_string());
+    }
+    let body = patch.len() - BPS_FOOTER_SIZE;
+    let footer = |i: usize| {
+        let start = body + i * 4;
+        u32::from_le_bytes(patch[start..start + 4].try_into().unwrap())
+    };
+    if crc32(&patch[..body + 8]) != footer(2) {
+        return Err("Patch checksum mismatch".to_string());
+    }
+    // 別の ROM 向けのパッチを当てると壊れるので確認する
+    if crc32(rom) != footer(0) {
+        return Err("Patch is for a different ROM (source checksum mismatch)".to_string());
+    }
+
+    let mut reader = Reader::new(&patch[..body], BPS_MAGIC.len());
+    let source_size = reader.varint()?;
+    let target_size = reader.varint()?;
+    let metadata_size = reader.varint()?;
+    reader.bytes(metadata_size)?;
+    if source_size != rom.len() {
+        return Err(format!(
+            "Patch expects a {} byte ROM, got {}",
+            source_size,
+            rom.len()
+        ));
+    }
+
+    if target_size > MAX_ROM_SIZE {
+        return Err(format!("Patch target is too large ({} bytes)", target_size));
+    }
+    let mut out: Vec<u8> = Vec::with_capacity(target_size);
+    let mut source_offset: usize = 0;
+    let mut target_offset: usize = 0;
+    while reader.pos < body {
+        let data = reader.varint()?;
+        let len = (data >> 2) + 1;
+        if out.len() + len > target_size {
+            return Err("Patch writes past the end of the ROM".to_string());
+        }
+        match data & 3 {
+            // SourceRead: 元の ROM の同じ位置
+            0 => {
+                let start = out.len();
+                let bytes = rom
+                    .get(start..start + len)
+                    .ok_or("Patch reads past the end of the ROM")?;
+                out.extend_from_slice(bytes);
+            }
+            // TargetRead: パッチの中のデータ
+            1 => out.extend_from_slice(reader.bytes(len)?),
+            // SourceCopy: 元の ROM の任意の位置
+            2 => {
+                source_offset = relative(source_offset, reader.varint()?)?;
+                let bytes = rom
+                    .get(source_offset..source_offset + len)
+                    .ok_or("Patch reads past the end of the ROM")?;
+                out.extend_from_slice(bytes);
+                source_offset += len;
+            }
+            // TargetCopy: 書き出し済みの位置。重なっていれば繰り返しになるので 1 バイトずつ
+            _ => {
+                target_offset = relative(target_offset, reader.varint()?)?;
+                for _ in 0..len {
+                    let byte = *out
+                        .get(target_offset)
+                        .ok_or("Patch reads past the end of the output")?;
+                    out.push(byte);
+                    target_offset += 1;
+                }
+            }
+        }
+    }
+    if out.len() != target_size || crc32(&out) != footer(1) {
+        return Err("Patched ROM checksum mismatch".to_string());
+    }
+    Ok(out)
+}
+
+// 最下位ビットが符号で、残りが移動量
+fn relative(offset: usize, data: usize) -> Result<usize, String> {
+    let delta = data >> 1;
+    let moved = if data & 1 == 1 {
+        offset.checked_sub(delta)
+    } else {
+        offset.checked_add(delta)
+    };
+    moved.ok_or_else(|| "Patch has an invalid offset".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ips() {
+        let rom = vec![0u8; 8];
+        let mut patch = b"PATCH".to_vec();
+        // 0x0002 に 2 バイト
+        patch.extend([0, 0, 2, 0, 2, 0xAA, 0xBB]);
+        // 0x0006 から 0xCC を 4 バイト(ROM より長くなる)
+        patch.extend([0, 0, 6, 0, 0, 0, 4, 0xCC]);
+        patch.extend(b"EOF");
+        assert_eq!(
+            apply_patch(&rom, &patch).unwrap(),
+            [0, 0, 0xAA, 0xBB, 0, 0, 0xCC, 0xCC, 0xCC, 0xCC]
+        );
+        // EOF の後の長さで切り詰める
+        patch.extend([0, 0, 3]);
+        assert_eq!(apply_patch(&rom, &patch).unwrap(), [0, 0, 0xAA]);
+        // オフセット 0x454F46 ("EOF") のレコードは終端と区別する
+        let mut patch = b"PATCH".to_vec();
+        patch.extend(b"EOF");
+        patch.extend([0, 1, 0x11]);
+        patch.extend(b"EOF");
+        assert_eq!(apply_patch(&rom, &patch).unwrap().len(), 0x454F47);
+        assert!(apply_patch(&rom, b"PATCH\x00\x00\x01\x00\x05\x01").is_err());
+    }
+
+    fn encode(mut n: usize, out: &mut Vec<u8>) {
+        loop {
+            let x = (n & 0x7F) as u8;
+            n >>= 7;
+            if n == 0 {
+                out.push(0x80 | x);
+                return;
+            }
+            out.push(x);
+            n -= 1;
+        }
+    }
+
+    fn bps(source: &[u8], target: &[u8], actions: &[(usize, usize, &[u8])]) -> Vec<u8> {
+        let mut patch = b"BPS1".to_vec();
+        encode(source.len(), &mut patch);
+        encode(target.len(), &mut patch);
+        encode(0, &mut patch);
+        for &(command, len, data) in actions {
+            encode((len - 1) << 2 | command, &mut patch);
+            patch.extend(data);
+        }
+        patch.extend(crc32(source).to_le_bytes());
+        patch.extend(crc32(target).to_le_bytes());
+        let crc = crc32(&patch);
+        patch.extend(crc.to_le_bytes());
+        patch
+    }
+
+    #[test]
+    fn test_bps() {
+        let source = [1, 2, 3, 4, 5, 6];
+        let target = [1, 2, 9, 5, 6, 9, 5, 6, 9, 3];
+        let patch = bps(
+            &source,
+            &target,
+            &[
+                // 1, 2
+                (0, 2, &[]),
+                // 9
+                (1, 1, &[9]),
+                // 元の ROM の 4 から 5, 6
+                (2, 2, &[0x80 | 4 << 1]),
+                // 書き出し済みの 9, 5, 6 から。書きながら読むので 9 が続く
+                (3, 4, &[0x80 | 2 << 1]),
+                // 元の ROM の 2 に戻って 3
+                (2, 1, &[0x80 | (4 << 1 | 1)]),
+            ],
+        );
+        assert_eq!(apply_patch(&source, &patch).unwrap(), target);
+        // 別の ROM には当てられない
+        assert!(apply_patch(&[1, 2, 3], &patch).is_err());
+        let mut broken = patch.clone();
+        broken[5] ^= 1;
+        assert!(apply_patch(&source, &broken).is_err());
+        assert!(apply_patch(&source, b"UPS1").is_err());
+
+        // パッチ後の大きさが大きすぎれば確保する前にエラーにする
+        let mut huge = b"BPS1".to_vec();
+        encode(source.len(), &mut huge);
+        encode(1 << 40, &mut huge);
+        encode(0, &mut huge);
+        huge.extend(crc32(&source).to_le_bytes());
+        huge.extend(0u32.to_le_bytes());
+        let crc = crc32(&huge);
+        huge.extend(crc.to_le_bytes());
+        assert!(apply_patch(&source, &huge)
+            .unwrap_err()
+            .contains("too large"));
+    }
+}
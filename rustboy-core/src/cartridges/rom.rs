@@ -0,0 +1,124 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use alloc::{format, string::String};
+
+use super::BANK_SIZE_ROM;
+
+/// カートリッジの ROM。バンクに分けてコピーせず、1つの連続した領域のまま MBC から読む
+/// mmap フィーチャでは ROM ファイルをメモリにマップするので、大きな ROM でも読み込みを待たず、
+/// 実際に読んだバンクだけがメモリに載る
+pub struct Rom {
+    data: RomData,
+    // MBC から見えるバンク数。ファイルより小さければ切り詰め、大きければ足りない分を 0xFF とする
+    banks: usize,
+}
+
+enum RomData {
+    Owned(Vec<u8>),
+    // 実行中にファイルを書き換えると読める内容が変わる
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl Rom {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self::with_data(RomData::Owned(data))
+    }
+
+    /// ファイルから読む。mmap フィーチャが有効ならメモリにマップする
+    #[cfg(feature = "std")]
+    pub fn open(filename: &str) -> Result<Self, String> {
+        let error = |e: std::io::Error| format!("{}: {}", filename, e);
+        #[cfg(feature = "mmap")]
+        {
+            let file = std::fs::File::open(filename).map_err(error)?;
+            // SAFETY: マップしている間に他のプロセスがファイルを切り詰めると SIGBUS になる
+            // ROM ファイルは実行中に書き換えない前提とする
+            let map = unsafe { memmap2::Mmap::map(&file) }.map_err(error)?;
+            Ok(Self::with_data(RomData::Mapped(map)))
+        }
+        #[cfg(not(feature = "mmap"))]
+        {
+            std::fs::read(filename).map(Self::new).map_err(error)
+        }
+    }
+
+    fn with_data(data: RomData) -> Self {
+        let mut rom = Self { data, banks: 0 };
+        rom.banks = rom.bytes().len().div_ceil(BANK_SIZE_ROM);
+        rom
+    }
+
+    /// MBC から見えるバンク数を変える。データはコピーしない
+    pub(crate) fn set_num_of_banks(&mut self, banks: usize) {
+        self.banks = banks;
+    }
+
+    /// ファイルの中身そのもの
+    pub fn bytes(&self) -> &[u8] {
+        match &self.data {
+            RomData::Owned(data) => data,
+            #[cfg(feature = "mmap")]
+            RomData::Mapped(map) => map,
+        }
+    }
+
+    pub fn num_of_banks(&self) -> usize {
+        self.banks
+    }
+
+    /// bank 番目のバンクの offset バイト目。ファイルの外は 0xFF
+    pub fn read(&self, bank: usize, offset: usize) -> u8 {
+        self.bank(bank).get(offset).copied().unwrap_or(0xFF)
+    }
+
+    /// bank 番目のバンク。ファイルの終わりにかかる場合は短くなる
+    pub fn bank(&self, bank: usize) -> &[u8] {
+        let bytes = self.bytes();
+        if bank >= self.banks {
+            return &[];
+        }
+        let start = (bank * BANK_SIZE_ROM).min(bytes.len());
+        let end = (start + BANK_SIZE_ROM).min(bytes.len());
+        &bytes[start..end]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_read() {
+        let mut data = vec![0x11; BANK_SIZE_ROM];
+        data.extend([0x22; 0x10]);
+        let mut rom = Rom::new(data);
+        assert_eq!(rom.num_of_banks(), 2);
+        assert_eq!(rom.read(0, 0x3FFF), 0x11);
+        assert_eq!(rom.read(1, 0x000F), 0x22);
+        // ファイルの外は 0xFF
+        assert_eq!(rom.read(1, 0x0010), 0xFF);
+        assert_eq!(rom.bank(1).len(), 0x10);
+        rom.set_num_of_banks(4);
+        assert_eq!(rom.read(3, 0), 0xFF);
+        // 切り詰めたバンクは読めない
+        rom.set_num_of_banks(1);
+        assert_eq!(rom.read(1, 0), 0xFF);
+        assert_eq!(rom.bytes().len(), BANK_SIZE_ROM + 0x10);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_open() {
+        let path = std::env::temp_dir().join(format!("rustboy-rom-{}.gb", std::process::id()));
+        let data: Vec<u8> = (0..BANK_SIZE_ROM * 2).map(|i| (i / 0x100) as u8).collect();
+        std::fs::write(&path, &data).unwrap();
+        let rom = Rom::open(path.to_str().unwrap()).unwrap();
+        assert_eq!(rom.bytes(), &data[..]);
+        assert_eq!(rom.read(1, 0x0100), 0x41);
+        drop(rom);
+        std::fs::remove_file(&path).unwrap();
+        assert!(Rom::open(path.to_str().unwrap()).is_err());
+    }
+}
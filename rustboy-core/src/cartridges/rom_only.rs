@@ -0,0 +1,94 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use log::debug;
+
+use super::{Mbc, MbcState, RamBank, RamSize, Rom, BANK_SIZE_RAM};
+use crate::Address;
+
+pub struct RomOnly {
+    rom: Rom,
+    ram_banks: Vec<RamBank>,
+    current_bank: usize,
+}
+
+impl RomOnly {
+    pub fn new(rom: Rom, ram_size: &RamSize) -> Self {
+        Self {
+            rom,
+            ram_banks: vec![[0; BANK_SIZE_RAM]; ram_size.num_of_banks()],
+            current_bank: 1,
+        }
+    }
+}
+
+impl Mbc for RomOnly {
+    fn current_rom_bank(&self) -> usize {
+        self.current_bank
+    }
+    fn current_ram_bank(&self) -> usize {
+        0
+    }
+    fn state(&self) -> MbcState {
+        MbcState {
+            rom_bank: self.current_bank,
+            ram_bank: 0,
+            rom_banks: self.rom.num_of_banks(),
+            ram_banks: self.ram_banks.len(),
+            ram_enabled: !self.ram_banks.is_empty(),
+            banking_mode: None,
+        }
+    }
+    fn force_rom_bank(&mut self, bank: usize) {
+        self.current_bank = bank;
+    }
+    // RAM は1バンクしか無い
+    fn force_ram_bank(&mut self, _bank: usize) {}
+    fn reset(&mut self) {
+        self.current_bank = 1;
+    }
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            // ROMバンク0から読み込み
+            0x0000..=0x3FFF => self.rom.read(0, address as usize),
+            // ROMバンク1から読み込み。デバッガで切り替えた場合はそのバンク
+            0x4000..=0x7FFF => self
+                .rom
+                .read(self.current_bank, (address - 0x4000) as usize),
+            // RAMから読み込み。RAM が無い場合は 0xFF
+            0xA000..=0xBFFF => match self.ram_banks.first() {
+                Some(bank) => bank[(address - 0xA000) as usize],
+                None => 0xFF,
+            },
+            _ => unreachable!(),
+        }
+    }
+    fn ram(&self) -> Vec<u8> {
+        self.ram_banks.concat()
+    }
+    fn load_ram(&mut self, data: &[u8]) {
+        for (bank, chunk) in self.ram_banks.iter_mut().zip(data.chunks(BANK_SIZE_RAM)) {
+            bank.copy_from_slice(chunk);
+        }
+    }
+    fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend(self.current_bank.to_le_bytes());
+        for bank in &self.ram_banks {
+            state.extend(bank);
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            // RAMへの書き込み。RAM が無い場合は無視する
+            0xA000..=0xBFFF => {
+                if let Some(bank) = self.ram_banks.first_mut() {
+                    bank[(address - 0xA000) as usize] = data;
+                }
+            }
+            _ => {
+                debug!(target: "mbc", "ignored: {:X?}", address);
+                // unreachable!()
+            },
+        }
+    }
+}
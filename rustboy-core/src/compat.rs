@@ -0,0 +1,108 @@
+use crate::cartridges::MbcSelect;
+use crate::ppu::Colorization;
+use crate::quirks::Accuracy;
+
+/// 動作を確かめたゲームごとの設定
+/// フロントエンドは読み込み時に既定値の代わりに使い、設定ファイルや引数で上書きできるようにする
+/// None の項目はフロントエンドの既定値のまま
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompatEntry {
+    // ヘッダのタイトル
+    pub title: &'static str,
+    // グローバルチェックサム。None ならタイトルだけで探す
+    pub checksum: Option<u16>,
+    // ヘッダと ROM が食い違うときの MBC の選び方
+    pub mbc: Option<MbcSelect>,
+    // 再現が必要な実機の不具合
+    pub accuracy: Option<Accuracy>,
+    // ボタンを選択せずに STOP するゲーム
+    pub stop_as_nop: Option<bool>,
+    // CGB のブート ROM のように DMG のゲームに付ける色
+    pub colorization: Option<Colorization>,
+    // 描画のヒント。点滅で半透明に見せるゲームで前のフレームを混ぜる割合(%)
+    pub ghosting: Option<u8>,
+}
+
+impl CompatEntry {
+    const fn new(title: &'static str) -> Self {
+        Self {
+            title,
+            checksum: None,
+            mbc: None,
+            accuracy: None,
+            stop_as_nop: None,
+            colorization: None,
+            ghosting: None,
+        }
+    }
+}
+
+// 組み込みのデータベース。確かめたものだけを足す
+const DATABASE: &[CompatEntry] = &[
+    // Blargg の OAM 破壊のテスト
+    CompatEntry {
+        accuracy: Some(Accuracy::Accurate),
+        ..CompatEntry::new("OAM_BUG")
+    },
+    // タイトルに合わせた色
+    CompatEntry {
+        colorization: Some(Colorization::RED),
+        ..CompatEntry::new("POKEMON RED")
+    },
+    CompatEntry {
+        colorization: Some(Colorization::BLUE),
+        ..CompatEntry::new("POKEMON BLUE")
+    },
+];
+
+/// ヘッダのタイトルとグローバルチェックサムに合う設定
+/// チェックサムまで一致するものを優先し、無ければタイトルだけが一致するものを使う
+/// タイトルの後ろの CGB フラグなど、表示できない文字は無視する
+pub fn lookup(title: &str, checksum: u16) -> Option<&'static CompatEntry> {
+    find(DATABASE, title, checksum)
+}
+
+fn find<'a>(database: &'a [CompatEntry], title: &str, checksum: u16) -> Option<&'a CompatEntry> {
+    let end = title
+        .find(|c: char| !c.is_ascii() || c.is_ascii_control())
+        .unwrap_or(title.len());
+    let title = title[..end].trim();
+    let entries = database.iter().filter(|e| e.title == title);
+    entries
+        .clone()
+        .find(|e| e.checksum == Some(checksum))
+        .or_else(|| entries.clone().find(|e| e.checksum.is_none()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let database = [
+            CompatEntry {
+                ghosting: Some(50),
+                ..CompatEntry::new("GAME")
+            },
+            CompatEntry {
+                checksum: Some(0x1234),
+                mbc: Some(MbcSelect::Auto),
+                ..CompatEntry::new("GAME")
+            },
+        ];
+        // チェックサムが一致するものを優先する
+        let entry = find(&database, "GAME", 0x1234).unwrap();
+        assert_eq!(entry.mbc, Some(MbcSelect::Auto));
+        assert_eq!(entry.ghosting, None);
+        let entry = find(&database, "GAME", 0x5678).unwrap();
+        assert_eq!(entry.ghosting, Some(50));
+        // CGB フラグと空白は無視する
+        assert_eq!(find(&database, "GAME \u{80}", 0x5678), Some(&database[0]));
+        assert_eq!(find(&database, "GAMES", 0x1234), None);
+        assert_eq!(
+            lookup("OAM_BUG", 0).unwrap().accuracy,
+            Some(Accuracy::Accurate)
+        );
+    }
+}
@@ -0,0 +1,800 @@
+use alloc::format;
+use alloc::string::String;
+
+use super::CPU;
+
+// 0xCB に続く命令の 0x00 - 0x3F の操作とオペランドのレジスタ
+const CB_OPERATIONS: [&str; 8] = ["RLC", "RRC", "RL", "RR", "SLA", "SRA", "SWAP", "SRL"];
+const CB_REGISTERS: [&str; 8] = ["B", "C", "D", "E", "H", "L", "(HL)", "A"];
+
+/// 命令のニーモニックとバイト数。未定義の命令は None
+/// オペランドは d8, d16, a8, a16, r8 のまま返すので、逆アセンブラが値に置き換える
+pub fn mnemonic(opcode: u8) -> Option<(&'static str, u8)> {
+    let instruction = match opcode {
+        0x00 => ("NOP", 1),
+        0x01 => ("LD BC, d16", 3),
+        0x02 => ("LD (BC), A", 1),
+        0x03 => ("INC BC", 1),
+        0x04 => ("INC B", 1),
+        0x05 => ("DEC B", 1),
+        0x06 => ("LD B, d8", 2),
+        0x07 => ("RLCA", 1),
+        0x08 => ("LD (a16), SP", 3),
+        0x09 => ("ADD HL, BC", 1),
+        0x0A => ("LD A, (BC)", 1),
+        0x0B => ("DEC BC", 1),
+        0x0C => ("INC C", 1),
+        0x0D => ("DEC C", 1),
+        0x0E => ("LD C, d8", 2),
+        0x0F => ("RRCA", 1),
+        0x10 => ("STOP", 2),
+        0x11 => ("LD DE, d16", 3),
+        0x12 => ("LD (DE), A", 1),
+        0x13 => ("INC DE", 1),
+        0x14 => ("INC D", 1),
+        0x15 => ("DEC D", 1),
+        0x16 => ("LD D, d8", 2),
+        0x17 => ("RLA", 1),
+        0x18 => ("JR r8", 2),
+        0x19 => ("ADD HL, DE", 1),
+        0x1A => ("LD A, (DE)", 1),
+        0x1B => ("DEC DE", 1),
+        0x1C => ("INC E", 1),
+        0x1D => ("DEC E", 1),
+        0x1E => ("LD E, d8", 2),
+        0x1F => ("RRA", 1),
+        0x20 => ("JR NZ, r8", 2),
+        0x21 => ("LD HL, d16", 3),
+        0x22 => ("LD (HL+), A", 1),
+        0x23 => ("INC HL", 1),
+        0x24 => ("INC H", 1),
+        0x25 => ("DEC H", 1),
+        0x26 => ("LD H, d8", 2),
+        0x27 => ("DAA", 1),
+        0x28 => ("JR Z, r8", 2),
+        0x29 => ("ADD HL, HL", 1),
+        0x2A => ("LD A, (HL+)", 1),
+        0x2B => ("DEC HL", 1),
+        0x2C => ("INC L", 1),
+        0x2D => ("DEC L", 1),
+        0x2E => ("LD L, d8", 2),
+        0x2F => ("CPL", 1),
+        0x30 => ("JR NC, r8", 2),
+        0x31 => ("LD SP, d16", 3),
+        0x32 => ("LD (HL-), A", 1),
+        0x33 => ("INC SP", 1),
+        0x34 => ("INC (HL)", 1),
+        0x35 => ("DEC (HL)", 1),
+        0x36 => ("LD (HL), d8", 2),
+        0x37 => ("SCF", 1),
+        0x38 => ("JR C, r8", 2),
+        0x39 => ("ADD HL, SP", 1),
+        0x3A => ("LD A, (HL-)", 1),
+        0x3B => ("DEC SP", 1),
+        0x3C => ("INC A", 1),
+        0x3D => ("DEC A", 1),
+        0x3E => ("LD A, d8", 2),
+        0x3F => ("CCF", 1),
+        0x40 => ("LD B, B", 1),
+        0x41 => ("LD B, C", 1),
+        0x42 => ("LD B, D", 1),
+        0x43 => ("LD B, E", 1),
+        0x44 => ("LD B, H", 1),
+        0x45 => ("LD B, L", 1),
+        0x46 => ("LD B, (HL)", 1),
+        0x47 => ("LD B, A", 1),
+        0x48 => ("LD C, B", 1),
+        0x49 => ("LD C, C", 1),
+        0x4A => ("LD C, D", 1),
+        0x4B => ("LD C, E", 1),
+        0x4C => ("LD C, H", 1),
+        0x4D => ("LD C, L", 1),
+        0x4E => ("LD C, (HL)", 1),
+        0x4F => ("LD C, A", 1),
+        0x50 => ("LD D, B", 1),
+        0x51 => ("LD D, C", 1),
+        0x52 => ("LD D, D", 1),
+        0x53 => ("LD D, E", 1),
+        0x54 => ("LD D, H", 1),
+        0x55 => ("LD D, L", 1),
+        0x56 => ("LD D, (HL)", 1),
+        0x57 => ("LD D, A", 1),
+        0x58 => ("LD E, B", 1),
+        0x59 => ("LD E, C", 1),
+        0x5A => ("LD E, D", 1),
+        0x5B => ("LD E, E", 1),
+        0x5C => ("LD E, H", 1),
+        0x5D => ("LD E, L", 1),
+        0x5E => ("LD E, (HL)", 1),
+        0x5F => ("LD E, A", 1),
+        0x60 => ("LD H, B", 1),
+        0x61 => ("LD H, C", 1),
+        0x62 => ("LD H, D", 1),
+        0x63 => ("LD H, E", 1),
+        0x64 => ("LD H, H", 1),
+        0x65 => ("LD H, L", 1),
+        0x66 => ("LD H, (HL)", 1),
+        0x67 => ("LD H, A", 1),
+        0x68 => ("LD L, B", 1),
+        0x69 => ("LD L, C", 1),
+        0x6A => ("LD L, D", 1),
+        0x6B => ("LD L, E", 1),
+        0x6C => ("LD L, H", 1),
+        0x6D => ("LD L, L", 1),
+        0x6E => ("LD L, (HL)", 1),
+        0x6F => ("LD L, A", 1),
+        0x70 => ("LD (HL), B", 1),
+        0x71 => ("LD (HL), C", 1),
+        0x72 => ("LD (HL), D", 1),
+        0x73 => ("LD (HL), E", 1),
+        0x74 => ("LD (HL), H", 1),
+        0x75 => ("LD (HL), L", 1),
+        0x76 => ("HALT", 1),
+        0x77 => ("LD (HL), A", 1),
+        0x78 => ("LD A, B", 1),
+        0x79 => ("LD A, C", 1),
+        0x7A => ("LD A, D", 1),
+        0x7B => ("LD A, E", 1),
+        0x7C => ("LD A, H", 1),
+        0x7D => ("LD A, L", 1),
+        0x7E => ("LD A, (HL)", 1),
+        0x7F => ("LD A, A", 1),
+        0x80 => ("ADD A, B", 1),
+        0x81 => ("ADD A, C", 1),
+        0x82 => ("ADD A, D", 1),
+        0x83 => ("ADD A, E", 1),
+        0x84 => ("ADD A, H", 1),
+        0x85 => ("ADD A, L", 1),
+        0x86 => ("ADD A, (HL)", 1),
+        0x87 => ("ADD A, A", 1),
+        0x88 => ("ADC A, B", 1),
+        0x89 => ("ADC A, C", 1),
+        0x8A => ("ADC A, D", 1),
+        0x8B => ("ADC A, E", 1),
+        0x8C => ("ADC A, H", 1),
+        0x8D => ("ADC A, L", 1),
+        0x8E => ("ADC A, (HL)", 1),
+        0x8F => ("ADC A, A", 1),
+        0x90 => ("SUB B", 1),
+        0x91 => ("SUB C", 1),
+        0x92 => ("SUB D", 1),
+        0x93 => ("SUB E", 1),
+        0x94 => ("SUB H", 1),
+        0x95 => ("SUB L", 1),
+        0x96 => ("SUB (HL)", 1),
+        0x97 => ("SUB A", 1),
+        0x98 => ("SBC A, B", 1),
+        0x99 => ("SBC A, C", 1),
+        0x9A => ("SBC A, D", 1),
+        0x9B => ("SBC A, E", 1),
+        0x9C => ("SBC A, H", 1),
+        0x9D => ("SBC A, L", 1),
+        0x9E => ("SBC A, (HL)", 1),
+        0x9F => ("SBC A, A", 1),
+        0xA0 => ("AND B", 1),
+        0xA1 => ("AND C", 1),
+        0xA2 => ("AND D", 1),
+        0xA3 => ("AND E", 1),
+        0xA4 => ("AND H", 1),
+        0xA5 => ("AND L", 1),
+        0xA6 => ("AND (HL)", 1),
+        0xA7 => ("AND A", 1),
+        0xA8 => ("XOR B", 1),
+        0xA9 => ("XOR C", 1),
+        0xAA => ("XOR D", 1),
+        0xAB => ("XOR E", 1),
+        0xAC => ("XOR H", 1),
+        0xAD => ("XOR L", 1),
+        0xAE => ("XOR (HL)", 1),
+        0xAF => ("XOR A", 1),
+        0xB0 => ("OR B", 1),
+        0xB1 => ("OR C", 1),
+        0xB2 => ("OR D", 1),
+        0xB3 => ("OR E", 1),
+        0xB4 => ("OR H", 1),
+        0xB5 => ("OR L", 1),
+        0xB6 => ("OR (HL)", 1),
+        0xB7 => ("OR A", 1),
+        0xB8 => ("CP B", 1),
+        0xB9 => ("CP C", 1),
+        0xBA => ("CP D", 1),
+        0xBB => ("CP E", 1),
+        0xBC => ("CP H", 1),
+        0xBD => ("CP L", 1),
+        0xBE => ("CP (HL)", 1),
+        0xBF => ("CP A", 1),
+        0xC0 => ("RET NZ", 1),
+        0xC1 => ("POP BC", 1),
+        0xC2 => ("JP NZ, a16", 3),
+        0xC3 => ("JP a16", 3),
+        0xC4 => ("CALL NZ, a16", 3),
+        0xC5 => ("PUSH BC", 1),
+        0xC6 => ("ADD A, d8", 2),
+        0xC7 => ("RST 00H", 1),
+        0xC8 => ("RET Z", 1),
+        0xC9 => ("RET", 1),
+        0xCA => ("JP Z, a16", 3),
+        0xCB => ("PREFIX CB", 1),
+        0xCC => ("CALL Z, a16", 3),
+        0xCD => ("CALL a16", 3),
+        0xCE => ("ADC A, d8", 2),
+        0xCF => ("RST 08H", 1),
+        0xD0 => ("RET NC", 1),
+        0xD1 => ("POP DE", 1),
+        0xD2 => ("JP NC, a16", 3),
+        0xD4 => ("CALL NC, a16", 3),
+        0xD5 => ("PUSH DE", 1),
+        0xD6 => ("SUB d8", 2),
+        0xD7 => ("RST 10H", 1),
+        0xD8 => ("RET C", 1),
+        0xD9 => ("RETI", 1),
+        0xDA => ("JP C, a16", 3),
+        0xDC => ("CALL C, a16", 3),
+        0xDE => ("SBC A, d8", 2),
+        0xDF => ("RST 18H", 1),
+        0xE0 => ("LDH (a8), A", 2),
+        0xE1 => ("POP HL", 1),
+        0xE2 => ("LD (C), A", 1),
+        0xE5 => ("PUSH HL", 1),
+        0xE6 => ("AND d8", 2),
+        0xE7 => ("RST 20H", 1),
+        0xE8 => ("ADD SP, r8", 2),
+        0xE9 => ("JP HL", 1),
+        0xEA => ("LD (a16), A", 3),
+        0xEE => ("XOR d8", 2),
+        0xEF => ("RST 28H", 1),
+        0xF0 => ("LDH A, (a8)", 2),
+        0xF1 => ("POP AF", 1),
+        0xF2 => ("LD A, (C)", 1),
+        0xF3 => ("DI", 1),
+        0xF5 => ("PUSH AF", 1),
+        0xF6 => ("OR d8", 2),
+        0xF7 => ("RST 30H", 1),
+        0xF8 => ("LD HL, SP+r8", 2),
+        0xF9 => ("LD SP, HL", 1),
+        0xFA => ("LD A, (a16)", 3),
+        0xFB => ("EI", 1),
+        0xFE => ("CP d8", 2),
+        0xFF => ("RST 38H", 1),
+        _ => return None,
+    };
+    Some(instruction)
+}
+
+/// 0xCB に続く命令のニーモニック。どれも 0xCB を含めて2バイト
+pub fn cb_mnemonic(opcode: u8) -> String {
+    let register = CB_REGISTERS[(opcode & 0x07) as usize];
+    let bit = (opcode >> 3) & 0x07;
+    match opcode >> 6 {
+        0 => format!("{} {}", CB_OPERATIONS[bit as usize], register),
+        1 => format!("BIT {}, {}", bit, register),
+        2 => format!("RES {}, {}", bit, register),
+        _ => format!("SET {}, {}", bit, register),
+    }
+}
+
+impl CPU {
+    // https://gbdev.io/gb-opcodes/optables/
+    pub(super) fn execute(&mut self, opcode: u8) -> u8 {
+        match opcode {
+            0x00 => self.nop_0x00(),
+            0x01 => self.ld_bc_d16_0x01(),
+            0x02 => self.ld_bc_a_0x02(),
+            0x03 => self.inc_bc_0x03(),
+            0x04 => self.inc_b_0x04(),
+            0x05 => self.dec_b_0x05(),
+            0x06 => self.ld_b_d8_0x06(),
+            0x07 => self.rlca_0x07(),
+            0x08 => self.ld_a16_sp_0x08(),
+            0x09 => self.add_hl_bc_0x09(),
+            0x0A => self.ld_a_bc_0x0a(),
+            0x0B => self.dec_bc_0x0b(),
+            0x0C => self.inc_c_0x0c(),
+            0x0D => self.dec_c_0x0d(),
+            0x0E => self.ld_c_d8_0x0e(),
+            0x0F => self.rrca_0x0f(),
+            0x10 => self.stop_d8_0x10(),
+            0x11 => self.ld_de_d16_0x11(),
+            0x12 => self.ld_de_a_0x12(),
+            0x13 => self.inc_de_0x13(),
+            0x14 => self.inc_d_0x14(),
+            0x15 => self.dec_d_0x15(),
+            0x16 => self.ld_d_d8_0x16(),
+            0x17 => self.rla_0x17(),
+            0x18 => self.jr_r8_0x18(),
+            0x19 => self.add_hl_de_0x19(),
+            0x1A => self.ld_a_de_0x1a(),
+            0x1B => self.dec_de_0x1b(),
+            0x1C => self.inc_e_0x1c(),
+            0x1D => self.dec_e_0x1d(),
+            0x1E => self.ld_e_d8_0x1e(),
+            0x1F => self.rra_0x1f(),
+            0x20 => self.jr_nz_r8_0x20(),
+            0x21 => self.ld_hl_d16_0x21(),
+            0x22 => self.ld_hl_a_0x22(),
+            0x23 => self.inc_hl_0x23(),
+            0x24 => self.inc_h_0x24(),
+            0x25 => self.dec_h_0x25(),
+            0x26 => self.ld_h_d8_0x26(),
+            0x27 => self.daa_0x27(),
+            0x28 => self.jr_z_r8_0x28(),
+            0x29 => self.add_hl_hl_0x29(),
+            0x2A => self.ld_a_hl_0x2a(),
+            0x2B => self.dec_hl_0x2b(),
+            0x2C => self.inc_l_0x2c(),
+            0x2D => self.dec_l_0x2d(),
+            0x2E => self.ld_l_d8_0x2e(),
+            0x2F => self.cpl_0x2f(),
+            0x30 => self.jr_nc_r8_0x30(),
+            0x31 => self.ld_sp_d16_0x31(),
+            0x32 => self.ld_hl_a_0x32(),
+            0x33 => self.inc_sp_0x33(),
+            0x34 => self.inc_hl_0x34(),
+            0x35 => self.dec_hl_0x35(),
+            0x36 => self.ld_hl_d8_0x36(),
+            0x37 => self.scf_0x37(),
+            0x38 => self.jr_c_r8_0x38(),
+            0x39 => self.add_hl_sp_0x39(),
+            0x3A => self.ld_a_hl_0x3a(),
+            0x3B => self.dec_sp_0x3b(),
+            0x3C => self.inc_a_0x3c(),
+            0x3D => self.dec_a_0x3d(),
+            0x3E => self.ld_a_d8_0x3e(),
+            0x3F => self.ccf_0x3f(),
+            0x40 => self.ld_b_b_0x40(),
+            0x41 => self.ld_b_c_0x41(),
+            0x42 => self.ld_b_d_0x42(),
+            0x43 => self.ld_b_e_0x43(),
+            0x44 => self.ld_b_h_0x44(),
+            0x45 => self.ld_b_l_0x45(),
+            0x46 => self.ld_b_hl_0x46(),
+            0x47 => self.ld_b_a_0x47(),
+            0x48 => self.ld_c_b_0x48(),
+            0x49 => self.ld_c_c_0x49(),
+            0x4A => self.ld_c_d_0x4a(),
+            0x4B => self.ld_c_e_0x4b(),
+            0x4C => self.ld_c_h_0x4c(),
+            0x4D => self.ld_c_l_0x4d(),
+            0x4E => self.ld_c_hl_0x4e(),
+            0x4F => self.ld_c_a_0x4f(),
+            0x50 => self.ld_d_b_0x50(),
+            0x51 => self.ld_d_c_0x51(),
+            0x52 => self.ld_d_d_0x52(),
+            0x53 => self.ld_d_e_0x53(),
+            0x54 => self.ld_d_h_0x54(),
+            0x55 => self.ld_d_l_0x55(),
+            0x56 => self.ld_d_hl_0x56(),
+            0x57 => self.ld_d_a_0x57(),
+            0x58 => self.ld_e_b_0x58(),
+            0x59 => self.ld_e_c_0x59(),
+            0x5A => self.ld_e_d_0x5a(),
+            0x5B => self.ld_e_e_0x5b(),
+            0x5C => self.ld_e_h_0x5c(),
+            0x5D => self.ld_e_l_0x5d(),
+            0x5E => self.ld_e_hl_0x5e(),
+            0x5F => self.ld_e_a_0x5f(),
+            0x60 => self.ld_h_b_0x60(),
+            0x61 => self.ld_h_c_0x61(),
+            0x62 => self.ld_h_d_0x62(),
+            0x63 => self.ld_h_e_0x63(),
+            0x64 => self.ld_h_h_0x64(),
+            0x65 => self.ld_h_l_0x65(),
+            0x66 => self.ld_h_hl_0x66(),
+            0x67 => self.ld_h_a_0x67(),
+            0x68 => self.ld_l_b_0x68(),
+            0x69 => self.ld_l_c_0x69(),
+            0x6A => self.ld_l_d_0x6a(),
+            0x6B => self.ld_l_e_0x6b(),
+            0x6C => self.ld_l_h_0x6c(),
+            0x6D => self.ld_l_l_0x6d(),
+            0x6E => self.ld_l_hl_0x6e(),
+            0x6F => self.ld_l_a_0x6f(),
+            0x70 => self.ld_hl_b_0x70(),
+            0x71 => self.ld_hl_c_0x71(),
+            0x72 => self.ld_hl_d_0x72(),
+            0x73 => self.ld_hl_e_0x73(),
+            0x74 => self.ld_hl_h_0x74(),
+            0x75 => self.ld_hl_l_0x75(),
+            0x76 => self.halt_0x76(),
+            0x77 => self.ld_hl_a_0x77(),
+            0x78 => self.ld_a_b_0x78(),
+            0x79 => self.ld_a_c_0x79(),
+            0x7A => self.ld_a_d_0x7a(),
+            0x7B => self.ld_a_e_0x7b(),
+            0x7C => self.ld_a_h_0x7c(),
+            0x7D => self.ld_a_l_0x7d(),
+            0x7E => self.ld_a_hl_0x7e(),
+            0x7F => self.ld_a_a_0x7f(),
+            0x80 => self.add_a_b_0x80(),
+            0x81 => self.add_a_c_0x81(),
+            0x82 => self.add_a_d_0x82(),
+            0x83 => self.add_a_e_0x83(),
+            0x84 => self.add_a_h_0x84(),
+            0x85 => self.add_a_l_0x85(),
+            0x86 => self.add_a_hl_0x86(),
+            0x87 => self.add_a_a_0x87(),
+            0x88 => self.adc_a_b_0x88(),
+            0x89 => self.adc_a_c_0x89(),
+            0x8A => self.adc_a_d_0x8a(),
+            0x8B => self.adc_a_e_0x8b(),
+            0x8C => self.adc_a_h_0x8c(),
+            0x8D => self.adc_a_l_0x8d(),
+            0x8E => self.adc_a_hl_0x8e(),
+            0x8F => self.adc_a_a_0x8f(),
+            0x90 => self.sub_b_0x90(),
+            0x91 => self.sub_c_0x91(),
+            0x92 => self.sub_d_0x92(),
+            0x93 => self.sub_e_0x93(),
+            0x94 => self.sub_h_0x94(),
+            0x95 => self.sub_l_0x95(),
+            0x96 => self.sub_hl_0x96(),
+            0x97 => self.sub_a_0x97(),
+            0x98 => self.sbc_a_b_0x98(),
+            0x99 => self.sbc_a_c_0x99(),
+            0x9A => self.sbc_a_d_0x9a(),
+            0x9B => self.sbc_a_e_0x9b(),
+            0x9C => self.sbc_a_h_0x9c(),
+            0x9D => self.sbc_a_l_0x9d(),
+            0x9E => self.sbc_a_hl_0x9e(),
+            0x9F => self.sbc_a_a_0x9f(),
+            0xA0 => self.and_b_0xa0(),
+            0xA1 => self.and_c_0xa1(),
+            0xA2 => self.and_d_0xa2(),
+            0xA3 => self.and_e_0xa3(),
+            0xA4 => self.and_h_0xa4(),
+            0xA5 => self.and_l_0xa5(),
+            0xA6 => self.and_hl_0xa6(),
+            0xA7 => self.and_a_0xa7(),
+            0xA8 => self.xor_b_0xa8(),
+            0xA9 => self.xor_c_0xa9(),
+            0xAA => self.xor_d_0xaa(),
+            0xAB => self.xor_e_0xab(),
+            0xAC => self.xor_h_0xac(),
+            0xAD => self.xor_l_0xad(),
+            0xAE => self.xor_hl_0xae(),
+            0xAF => self.xor_a_0xaf(),
+            0xB0 => self.or_b_0xb0(),
+            0xB1 => self.or_c_0xb1(),
+            0xB2 => self.or_d_0xb2(),
+            0xB3 => self.or_e_0xb3(),
+            0xB4 => self.or_h_0xb4(),
+            0xB5 => self.or_l_0xb5(),
+            0xB6 => self.or_hl_0xb6(),
+            0xB7 => self.or_a_0xb7(),
+            0xB8 => self.cp_b_0xb8(),
+            0xB9 => self.cp_c_0xb9(),
+            0xBA => self.cp_d_0xba(),
+            0xBB => self.cp_e_0xbb(),
+            0xBC => self.cp_h_0xbc(),
+            0xBD => self.cp_l_0xbd(),
+            0xBE => self.cp_hl_0xbe(),
+            0xBF => self.cp_a_0xbf(),
+            0xC0 => self.ret_nz_0xc0(),
+            0xC1 => self.pop_bc_0xc1(),
+            0xC2 => self.jp_nz_a16_0xc2(),
+            0xC3 => self.jp_a16_0xc3(),
+            0xC4 => self.call_nz_a16_0xc4(),
+            0xC5 => self.push_bc_0xc5(),
+            0xC6 => self.add_a_d8_0xc6(),
+            0xC7 => self.rst_00h_0xc7(),
+            0xC8 => self.ret_z_0xc8(),
+            0xC9 => self.ret_0xc9(),
+            0xCA => self.jp_z_a16_0xca(),
+            0xCB => self.prefix_0xcb(),
+            0xCC => self.call_z_a16_0xcc(),
+            0xCD => self.call_a16_0xcd(),
+            0xCE => self.adc_a_d8_0xce(),
+            0xCF => self.rst_08h_0xcf(),
+            0xD0 => self.ret_nc_0xd0(),
+            0xD1 => self.pop_de_0xd1(),
+            0xD2 => self.jp_nc_a16_0xd2(),
+            0xD3 => self.illegal_d3_0xd3(),
+            0xD4 => self.call_nc_a16_0xd4(),
+            0xD5 => self.push_de_0xd5(),
+            0xD6 => self.sub_d8_0xd6(),
+            0xD7 => self.rst_10h_0xd7(),
+            0xD8 => self.ret_c_0xd8(),
+            0xD9 => self.reti_0xd9(),
+            0xDA => self.jp_c_a16_0xda(),
+            0xDB => self.illegal_db_0xdb(),
+            0xDC => self.call_c_a16_0xdc(),
+            0xDD => self.illegal_dd_0xdd(),
+            0xDE => self.sbc_a_d8_0xde(),
+            0xDF => self.rst_18h_0xdf(),
+            0xE0 => self.ldh_a8_a_0xe0(),
+            0xE1 => self.pop_hl_0xe1(),
+            0xE2 => self.ld_c_a_0xe2(),
+            0xE3 => self.illegal_e3_0xe3(),
+            0xE4 => self.illegal_e4_0xe4(),
+            0xE5 => self.push_hl_0xe5(),
+            0xE6 => self.and_d8_0xe6(),
+            0xE7 => self.rst_20h_0xe7(),
+            0xE8 => self.add_sp_r8_0xe8(),
+            0xE9 => self.jp_hl_0xe9(),
+            0xEA => self.ld_a16_a_0xea(),
+            0xEB => self.illegal_eb_0xeb(),
+            0xEC => self.illegal_ec_0xec(),
+            0xED => self.illegal_ed_0xed(),
+            0xEE => self.xor_d8_0xee(),
+            0xEF => self.rst_28h_0xef(),
+            0xF0 => self.ldh_a_a8_0xf0(),
+            0xF1 => self.pop_af_0xf1(),
+            0xF2 => self.ld_a_c_0xf2(),
+            0xF3 => self.di_0xf3(),
+            0xF4 => self.illegal_f4_0xf4(),
+            0xF5 => self.push_af_0xf5(),
+            0xF6 => self.or_d8_0xf6(),
+            0xF7 => self.rst_30h_0xf7(),
+            0xF8 => self.ld_hl_sp_r8_0xf8(),
+            0xF9 => self.ld_sp_hl_0xf9(),
+            0xFA => self.ld_a_a16_0xfa(),
+            0xFB => self.ei_0xfb(),
+            0xFC => self.illegal_fc_0xfc(),
+            0xFD => self.illegal_fd_0xfd(),
+            0xFE => self.cp_d8_0xfe(),
+            0xFF => self.rst_38h_0xff(),
+            _ => unreachable!(),
+        }
+    }
+    pub(super) fn execute_cb(&mut self, opcode: u8) -> u8 {
+        match opcode {
+            0x00 => self.rlc_b_0xcb00(),
+            0x01 => self.rlc_c_0xcb01(),
+            0x02 => self.rlc_d_0xcb02(),
+            0x03 => self.rlc_e_0xcb03(),
+            0x04 => self.rlc_h_0xcb04(),
+            0x05 => self.rlc_l_0xcb05(),
+            0x06 => self.rlc_hl_0xcb06(),
+            0x07 => self.rlc_a_0xcb07(),
+            0x08 => self.rrc_b_0xcb08(),
+            0x09 => self.rrc_c_0xcb09(),
+            0x0A => self.rrc_d_0xcb0a(),
+            0x0B => self.rrc_e_0xcb0b(),
+            0x0C => self.rrc_h_0xcb0c(),
+            0x0D => self.rrc_l_0xcb0d(),
+            0x0E => self.rrc_hl_0xcb0e(),
+            0x0F => self.rrc_a_0xcb0f(),
+            0x10 => self.rl_b_0xcb10(),
+            0x11 => self.rl_c_0xcb11(),
+            0x12 => self.rl_d_0xcb12(),
+            0x13 => self.rl_e_0xcb13(),
+            0x14 => self.rl_h_0xcb14(),
+            0x15 => self.rl_l_0xcb15(),
+            0x16 => self.rl_hl_0xcb16(),
+            0x17 => self.rl_a_0xcb17(),
+            0x18 => self.rr_b_0xcb18(),
+            0x19 => self.rr_c_0xcb19(),
+            0x1A => self.rr_d_0xcb1a(),
+            0x1B => self.rr_e_0xcb1b(),
+            0x1C => self.rr_h_0xcb1c(),
+            0x1D => self.rr_l_0xcb1d(),
+            0x1E => self.rr_hl_0xcb1e(),
+            0x1F => self.rr_a_0xcb1f(),
+            0x20 => self.sla_b_0xcb20(),
+            0x21 => self.sla_c_0xcb21(),
+            0x22 => self.sla_d_0xcb22(),
+            0x23 => self.sla_e_0xcb23(),
+            0x24 => self.sla_h_0xcb24(),
+            0x25 => self.sla_l_0xcb25(),
+            0x26 => self.sla_hl_0xcb26(),
+            0x27 => self.sla_a_0xcb27(),
+            0x28 => self.sra_b_0xcb28(),
+            0x29 => self.sra_c_0xcb29(),
+            0x2A => self.sra_d_0xcb2a(),
+            0x2B => self.sra_e_0xcb2b(),
+            0x2C => self.sra_h_0xcb2c(),
+            0x2D => self.sra_l_0xcb2d(),
+            0x2E => self.sra_hl_0xcb2e(),
+            0x2F => self.sra_a_0xcb2f(),
+            0x30 => self.swap_b_0xcb30(),
+            0x31 => self.swap_c_0xcb31(),
+            0x32 => self.swap_d_0xcb32(),
+            0x33 => self.swap_e_0xcb33(),
+            0x34 => self.swap_h_0xcb34(),
+            0x35 => self.swap_l_0xcb35(),
+            0x36 => self.swap_hl_0xcb36(),
+            0x37 => self.swap_a_0xcb37(),
+            0x38 => self.srl_b_0xcb38(),
+            0x39 => self.srl_c_0xcb39(),
+            0x3A => self.srl_d_0xcb3a(),
+            0x3B => self.srl_e_0xcb3b(),
+            0x3C => self.srl_h_0xcb3c(),
+            0x3D => self.srl_l_0xcb3d(),
+            0x3E => self.srl_hl_0xcb3e(),
+            0x3F => self.srl_a_0xcb3f(),
+            0x40 => self.bit_0_b_0xcb40(),
+            0x41 => self.bit_0_c_0xcb41(),
+            0x42 => self.bit_0_d_0xcb42(),
+            0x43 => self.bit_0_e_0xcb43(),
+            0x44 => self.bit_0_h_0xcb44(),
+            0x45 => self.bit_0_l_0xcb45(),
+            0x46 => self.bit_0_hl_0xcb46(),
+            0x47 => self.bit_0_a_0xcb47(),
+            0x48 => self.bit_1_b_0xcb48(),
+            0x49 => self.bit_1_c_0xcb49(),
+            0x4A => self.bit_1_d_0xcb4a(),
+            0x4B => self.bit_1_e_0xcb4b(),
+            0x4C => self.bit_1_h_0xcb4c(),
+            0x4D => self.bit_1_l_0xcb4d(),
+            0x4E => self.bit_1_hl_0xcb4e(),
+            0x4F => self.bit_1_a_0xcb4f(),
+            0x50 => self.bit_2_b_0xcb50(),
+            0x51 => self.bit_2_c_0xcb51(),
+            0x52 => self.bit_2_d_0xcb52(),
+            0x53 => self.bit_2_e_0xcb53(),
+            0x54 => self.bit_2_h_0xcb54(),
+            0x55 => self.bit_2_l_0xcb55(),
+            0x56 => self.bit_2_hl_0xcb56(),
+            0x57 => self.bit_2_a_0xcb57(),
+            0x58 => self.bit_3_b_0xcb58(),
+            0x59 => self.bit_3_c_0xcb59(),
+            0x5A => self.bit_3_d_0xcb5a(),
+            0x5B => self.bit_3_e_0xcb5b(),
+            0x5C => self.bit_3_h_0xcb5c(),
+            0x5D => self.bit_3_l_0xcb5d(),
+            0x5E => self.bit_3_hl_0xcb5e(),
+            0x5F => self.bit_3_a_0xcb5f(),
+            0x60 => self.bit_4_b_0xcb60(),
+            0x61 => self.bit_4_c_0xcb61(),
+            0x62 => self.bit_4_d_0xcb62(),
+            0x63 => self.bit_4_e_0xcb63(),
+            0x64 => self.bit_4_h_0xcb64(),
+            0x65 => self.bit_4_l_0xcb65(),
+            0x66 => self.bit_4_hl_0xcb66(),
+            0x67 => self.bit_4_a_0xcb67(),
+            0x68 => self.bit_5_b_0xcb68(),
+            0x69 => self.bit_5_c_0xcb69(),
+            0x6A => self.bit_5_d_0xcb6a(),
+            0x6B => self.bit_5_e_0xcb6b(),
+            0x6C => self.bit_5_h_0xcb6c(),
+            0x6D => self.bit_5_l_0xcb6d(),
+            0x6E => self.bit_5_hl_0xcb6e(),
+            0x6F => self.bit_5_a_0xcb6f(),
+            0x70 => self.bit_6_b_0xcb70(),
+            0x71 => self.bit_6_c_0xcb71(),
+            0x72 => self.bit_6_d_0xcb72(),
+            0x73 => self.bit_6_e_0xcb73(),
+            0x74 => self.bit_6_h_0xcb74(),
+            0x75 => self.bit_6_l_0xcb75(),
+            0x76 => self.bit_6_hl_0xcb76(),
+            0x77 => self.bit_6_a_0xcb77(),
+            0x78 => self.bit_7_b_0xcb78(),
+            0x79 => self.bit_7_c_0xcb79(),
+            0x7A => self.bit_7_d_0xcb7a(),
+            0x7B => self.bit_7_e_0xcb7b(),
+            0x7C => self.bit_7_h_0xcb7c(),
+            0x7D => self.bit_7_l_0xcb7d(),
+            0x7E => self.bit_7_hl_0xcb7e(),
+            0x7F => self.bit_7_a_0xcb7f(),
+            0x80 => self.res_0_b_0xcb80(),
+            0x81 => self.res_0_c_0xcb81(),
+            0x82 => self.res_0_d_0xcb82(),
+            0x83 => self.res_0_e_0xcb83(),
+            0x84 => self.res_0_h_0xcb84(),
+            0x85 => self.res_0_l_0xcb85(),
+            0x86 => self.res_0_hl_0xcb86(),
+            0x87 => self.res_0_a_0xcb87(),
+            0x88 => self.res_1_b_0xcb88(),
+            0x89 => self.res_1_c_0xcb89(),
+            0x8A => self.res_1_d_0xcb8a(),
+            0x8B => self.res_1_e_0xcb8b(),
+            0x8C => self.res_1_h_0xcb8c(),
+            0x8D => self.res_1_l_0xcb8d(),
+            0x8E => self.res_1_hl_0xcb8e(),
+            0x8F => self.res_1_a_0xcb8f(),
+            0x90 => self.res_2_b_0xcb90(),
+            0x91 => self.res_2_c_0xcb91(),
+            0x92 => self.res_2_d_0xcb92(),
+            0x93 => self.res_2_e_0xcb93(),
+            0x94 => self.res_2_h_0xcb94(),
+            0x95 => self.res_2_l_0xcb95(),
+            0x96 => self.res_2_hl_0xcb96(),
+            0x97 => self.res_2_a_0xcb97(),
+            0x98 => self.res_3_b_0xcb98(),
+            0x99 => self.res_3_c_0xcb99(),
+            0x9A => self.res_3_d_0xcb9a(),
+            0x9B => self.res_3_e_0xcb9b(),
+            0x9C => self.res_3_h_0xcb9c(),
+            0x9D => self.res_3_l_0xcb9d(),
+            0x9E => self.res_3_hl_0xcb9e(),
+            0x9F => self.res_3_a_0xcb9f(),
+            0xA0 => self.res_4_b_0xcba0(),
+            0xA1 => self.res_4_c_0xcba1(),
+            0xA2 => self.res_4_d_0xcba2(),
+            0xA3 => self.res_4_e_0xcba3(),
+            0xA4 => self.res_4_h_0xcba4(),
+            0xA5 => self.res_4_l_0xcba5(),
+            0xA6 => self.res_4_hl_0xcba6(),
+            0xA7 => self.res_4_a_0xcba7(),
+            0xA8 => self.res_5_b_0xcba8(),
+            0xA9 => self.res_5_c_0xcba9(),
+            0xAA => self.res_5_d_0xcbaa(),
+            0xAB => self.res_5_e_0xcbab(),
+            0xAC => self.res_5_h_0xcbac(),
+            0xAD => self.res_5_l_0xcbad(),
+            0xAE => self.res_5_hl_0xcbae(),
+            0xAF => self.res_5_a_0xcbaf(),
+            0xB0 => self.res_6_b_0xcbb0(),
+            0xB1 => self.res_6_c_0xcbb1(),
+            0xB2 => self.res_6_d_0xcbb2(),
+            0xB3 => self.res_6_e_0xcbb3(),
+            0xB4 => self.res_6_h_0xcbb4(),
+            0xB5 => self.res_6_l_0xcbb5(),
+            0xB6 => self.res_6_hl_0xcbb6(),
+            0xB7 => self.res_6_a_0xcbb7(),
+            0xB8 => self.res_7_b_0xcbb8(),
+            0xB9 => self.res_7_c_0xcbb9(),
+            0xBA => self.res_7_d_0xcbba(),
+            0xBB => self.res_7_e_0xcbbb(),
+            0xBC => self.res_7_h_0xcbbc(),
+            0xBD => self.res_7_l_0xcbbd(),
+            0xBE => self.res_7_hl_0xcbbe(),
+            0xBF => self.res_7_a_0xcbbf(),
+            0xC0 => self.set_0_b_0xcbc0(),
+            0xC1 => self.set_0_c_0xcbc1(),
+            0xC2 => self.set_0_d_0xcbc2(),
+            0xC3 => self.set_0_e_0xcbc3(),
+            0xC4 => self.set_0_h_0xcbc4(),
+            0xC5 => self.set_0_l_0xcbc5(),
+            0xC6 => self.set_0_hl_0xcbc6(),
+            0xC7 => self.set_0_a_0xcbc7(),
+            0xC8 => self.set_1_b_0xcbc8(),
+            0xC9 => self.set_1_c_0xcbc9(),
+            0xCA => self.set_1_d_0xcbca(),
+            0xCB => self.set_1_e_0xcbcb(),
+            0xCC => self.set_1_h_0xcbcc(),
+            0xCD => self.set_1_l_0xcbcd(),
+            0xCE => self.set_1_hl_0xcbce(),
+            0xCF => self.set_1_a_0xcbcf(),
+            0xD0 => self.set_2_b_0xcbd0(),
+            0xD1 => self.set_2_c_0xcbd1(),
+            0xD2 => self.set_2_d_0xcbd2(),
+            0xD3 => self.set_2_e_0xcbd3(),
+            0xD4 => self.set_2_h_0xcbd4(),
+            0xD5 => self.set_2_l_0xcbd5(),
+            0xD6 => self.set_2_hl_0xcbd6(),
+            0xD7 => self.set_2_a_0xcbd7(),
+            0xD8 => self.set_3_b_0xcbd8(),
+            0xD9 => self.set_3_c_0xcbd9(),
+            0xDA => self.set_3_d_0xcbda(),
+            0xDB => self.set_3_e_0xcbdb(),
+            0xDC => self.set_3_h_0xcbdc(),
+            0xDD => self.set_3_l_0xcbdd(),
+            0xDE => self.set_3_hl_0xcbde(),
+            0xDF => self.set_3_a_0xcbdf(),
+            0xE0 => self.set_4_b_0xcbe0(),
+            0xE1 => self.set_4_c_0xcbe1(),
+            0xE2 => self.set_4_d_0xcbe2(),
+            0xE3 => self.set_4_e_0xcbe3(),
+            0xE4 => self.set_4_h_0xcbe4(),
+            0xE5 => self.set_4_l_0xcbe5(),
+            0xE6 => self.set_4_hl_0xcbe6(),
+            0xE7 => self.set_4_a_0xcbe7(),
+            0xE8 => self.set_5_b_0xcbe8(),
+            0xE9 => self.set_5_c_0xcbe9(),
+            0xEA => self.set_5_d_0xcbea(),
+            0xEB => self.set_5_e_0xcbeb(),
+            0xEC => self.set_5_h_0xcbec(),
+            0xED => self.set_5_l_0xcbed(),
+            0xEE => self.set_5_hl_0xcbee(),
+            0xEF => self.set_5_a_0xcbef(),
+            0xF0 => self.set_6_b_0xcbf0(),
+            0xF1 => self.set_6_c_0xcbf1(),
+            0xF2 => self.set_6_d_0xcbf2(),
+            0xF3 => self.set_6_e_0xcbf3(),
+            0xF4 => self.set_6_h_0xcbf4(),
+            0xF5 => self.set_6_l_0xcbf5(),
+            0xF6 => self.set_6_hl_0xcbf6(),
+            0xF7 => self.set_6_a_0xcbf7(),
+            0xF8 => self.set_7_b_0xcbf8(),
+            0xF9 => self.set_7_c_0xcbf9(),
+            0xFA => self.set_7_d_0xcbfa(),
+            0xFB => self.set_7_e_0xcbfb(),
+            0xFC => self.set_7_h_0xcbfc(),
+            0xFD => self.set_7_l_0xcbfd(),
+            0xFE => self.set_7_hl_0xcbfe(),
+            0xFF => self.set_7_a_0xcbff(),
+            _ => unreachable!(),
+        }
+    }
+}
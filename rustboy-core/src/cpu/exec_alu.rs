@@ -0,0 +1,1228 @@
+use log::trace;
+
+use super::CPU;
+use crate::arithmetic::{add16_hl, add_sp_e8, ArithmeticUtil};
+
+// 8bit/16bit の算術論理演算 (INC, DEC, ADD, ADC, SUB, SBC, AND, XOR, OR, CP など)
+impl CPU {
+    // bytes: 1 cycles: [8]
+    pub(super) fn inc_bc_0x03(&mut self) -> u8 {
+        trace!(target: "cpu", "INC BC");
+        self.corrupt_oam(self.registers.bc());
+        self.registers.set_bc(self.registers.bc().wrapping_add(1));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_b_0x04(&mut self) -> u8 {
+        trace!(target: "cpu", "INC B");
+        self.registers.f.h = self.registers.b.calc_half_carry(1);
+        self.registers.b = self.registers.b.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.b == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_b_0x05(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC B");
+        self.registers.f.h = self.registers.b.calc_half_borrow(1);
+        self.registers.b = self.registers.b.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.b == 0;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn add_hl_bc_0x09(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD HL, BC");
+        let (hl, h, c) = add16_hl(self.registers.hl(), self.registers.bc());
+        self.registers.set_hl(hl);
+        self.registers.f.h = h;
+        self.registers.f.c = c;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn dec_bc_0x0b(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC BC");
+        self.corrupt_oam(self.registers.bc());
+        self.registers.set_bc(self.registers.bc().wrapping_sub(1));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_c_0x0c(&mut self) -> u8 {
+        trace!(target: "cpu", "INC C");
+        self.registers.f.h = self.registers.c.calc_half_carry(1);
+        self.registers.c = self.registers.c.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.c == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_c_0x0d(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC C");
+        self.registers.f.h = self.registers.c.calc_half_borrow(1);
+        self.registers.c = self.registers.c.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.c == 0;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn inc_de_0x13(&mut self) -> u8 {
+        trace!(target: "cpu", "INC DE");
+        self.corrupt_oam(self.registers.de());
+        self.registers.set_de(self.registers.de().wrapping_add(1));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_d_0x14(&mut self) -> u8 {
+        trace!(target: "cpu", "INC D");
+        self.registers.f.h = self.registers.d.calc_half_carry(1);
+        self.registers.d = self.registers.d.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.d == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_d_0x15(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC D");
+        self.registers.f.h = self.registers.d.calc_half_borrow(1);
+        self.registers.d = self.registers.d.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.d == 0;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn add_hl_de_0x19(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD HL, DE");
+        let (hl, h, c) = add16_hl(self.registers.hl(), self.registers.de());
+        self.registers.set_hl(hl);
+        self.registers.f.h = h;
+        self.registers.f.c = c;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn dec_de_0x1b(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC DE");
+        self.corrupt_oam(self.registers.de());
+        self.registers.set_de(self.registers.de().wrapping_sub(1));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_e_0x1c(&mut self) -> u8 {
+        trace!(target: "cpu", "INC E");
+        self.registers.f.h = self.registers.e.calc_half_carry(1);
+        self.registers.e = self.registers.e.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.e == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_e_0x1d(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC E");
+        self.registers.f.h = self.registers.e.calc_half_borrow(1);
+        self.registers.e = self.registers.e.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.e == 0;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn inc_hl_0x23(&mut self) -> u8 {
+        trace!(target: "cpu", "INC HL");
+        self.corrupt_oam(self.registers.hl());
+        self.registers.set_hl(self.registers.hl().wrapping_add(1));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_h_0x24(&mut self) -> u8 {
+        trace!(target: "cpu", "INC H");
+        self.registers.f.h = self.registers.h.calc_half_carry(1);
+        self.registers.h = self.registers.h.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.h == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_h_0x25(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC H");
+        self.registers.f.h = self.registers.h.calc_half_borrow(1);
+        self.registers.h = self.registers.h.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.h == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn daa_0x27(&mut self) -> u8 {
+        // https://github.com/Baekalfen/PyBoy/blob/96d2b3d54fe73a6030ff61b6c70952b8e6aa6299/pyboy/core/opcodes.py#L412
+        let mut al = self.registers.a;
+        let mut corr = 0x00;
+        if self.registers.f.h {
+            corr |= 0x06;
+        } else {
+            corr |= 0x00;
+        }
+        if self.registers.f.c {
+            corr |= 0x60;
+        } else {
+            corr |= 0x00;
+        }
+        if self.registers.f.n {
+            al = al.wrapping_sub(corr);
+        } else {
+            if (al & 0x0F) > 0x09 {
+                corr |= 0x06;
+            } else {
+                corr |= 0x00;
+            }
+            if al > 0x99 {
+                corr |= 0x60;
+            } else {
+                corr |= 0x00;
+            }
+            al = al.wrapping_add(corr);
+        }
+        self.registers.a = al;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.c = (corr & 0x60) != 0;
+        self.registers.f.h = false;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn add_hl_hl_0x29(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD HL, HL");
+        let (hl, h, c) = add16_hl(self.registers.hl(), self.registers.hl());
+        self.registers.set_hl(hl);
+        self.registers.f.h = h;
+        self.registers.f.c = c;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn dec_hl_0x2b(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC HL");
+        self.corrupt_oam(self.registers.hl());
+        self.registers.set_hl(self.registers.hl().wrapping_sub(1));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_l_0x2c(&mut self) -> u8 {
+        trace!(target: "cpu", "INC L");
+        self.registers.f.h = self.registers.l.calc_half_carry(1);
+        self.registers.l = self.registers.l.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.l == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_l_0x2d(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC L");
+        self.registers.f.h = self.registers.l.calc_half_borrow(1);
+        self.registers.l = self.registers.l.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.l == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cpl_0x2f(&mut self) -> u8 {
+        trace!(target: "cpu", "CPL");
+        self.registers.a = !self.registers.a;
+        self.registers.f.n = true;
+        self.registers.f.h = true;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn inc_sp_0x33(&mut self) -> u8 {
+        trace!(target: "cpu", "INC SP");
+        self.corrupt_oam(self.registers.sp);
+        self.registers.sp = self.registers.sp.wrapping_add(1);
+        8
+    }
+    // bytes: 1 cycles: [12]
+    pub(super) fn inc_hl_0x34(&mut self) -> u8 {
+        trace!(target: "cpu", "INC (HL)");
+        let hl = self.read(self.registers.hl());
+        self.registers.f.h = hl.calc_half_carry(1);
+        self.write(self.registers.hl(), hl.wrapping_add(1));
+        self.registers.f.n = false;
+        self.registers.f.z = hl.wrapping_add(1) == 0;
+        12
+    }
+    // bytes: 1 cycles: [12]
+    pub(super) fn dec_hl_0x35(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC (HL)");
+        let hl = self.read(self.registers.hl());
+        self.registers.f.h = hl.calc_half_borrow(1);
+        self.write(self.registers.hl(), hl.wrapping_sub(1));
+        self.registers.f.n = true;
+        self.registers.f.z = hl.wrapping_sub(1) == 0;
+        12
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn scf_0x37(&mut self) -> u8 {
+        trace!(target: "cpu", "SCF");
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = true;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn add_hl_sp_0x39(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD HL, SP");
+        let (hl, h, c) = add16_hl(self.registers.hl(), self.registers.sp);
+        self.registers.set_hl(hl);
+        self.registers.f.h = h;
+        self.registers.f.c = c;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn dec_sp_0x3b(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC SP");
+        self.corrupt_oam(self.registers.sp);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn inc_a_0x3c(&mut self) -> u8 {
+        trace!(target: "cpu", "INC A");
+        self.registers.f.h = self.registers.a.calc_half_carry(1);
+        self.registers.a = self.registers.a.wrapping_add(1);
+        self.registers.f.n = false;
+        self.registers.f.z = self.registers.a == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn dec_a_0x3d(&mut self) -> u8 {
+        trace!(target: "cpu", "DEC A");
+        self.registers.f.h = self.registers.a.calc_half_borrow(1);
+        self.registers.a = self.registers.a.wrapping_sub(1);
+        self.registers.f.n = true;
+        self.registers.f.z = self.registers.a == 0;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ccf_0x3f(&mut self) -> u8 {
+        trace!(target: "cpu", "CCF");
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = !self.registers.f.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_b_0x80(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, B");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.b);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.b);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.b);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_c_0x81(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, C");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.c);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.c);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.c);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_d_0x82(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, D");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.d);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.d);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.d);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_e_0x83(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, E");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.e);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.e);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.e);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_h_0x84(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, H");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.h);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.h);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.h);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_l_0x85(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, L");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.l);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.l);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.l);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn add_a_hl_0x86(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, (HL)");
+        let hl = self.read(self.registers.hl());
+        self.registers.f.h = self.registers.a.calc_half_carry(hl);
+        self.registers.f.c = self.registers.a.calc_carry(hl);
+        self.registers.a = self.registers.a.wrapping_add(hl);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn add_a_a_0x87(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, A");
+        self.registers.f.h = self.registers.a.calc_half_carry(self.registers.a);
+        self.registers.f.c = self.registers.a.calc_carry(self.registers.a);
+        self.registers.a = self.registers.a.wrapping_add(self.registers.a);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_b_0x88(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, B");
+        let h = self.registers.b.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.b.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.b.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_c_0x89(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, C");
+        let h = self.registers.c.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.c.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.c.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_d_0x8a(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, D");
+        let h = self.registers.d.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.d.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.d.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_e_0x8b(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, E");
+        let h = self.registers.e.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.e.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.e.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_h_0x8c(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, H");
+        let h = self.registers.h.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.h.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.h.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_l_0x8d(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, L");
+        let h = self.registers.l.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.l.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.l.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn adc_a_hl_0x8e(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, (HL)");
+        let d8 = self.read(self.registers.hl());
+        let h = d8.calc_half_carry(self.registers.f.c as u8);
+        let c = d8.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = d8.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn adc_a_a_0x8f(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, A");
+        let h = self.registers.a.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.a.calc_carry(self.registers.f.c as u8);
+        let rhs = self.registers.a.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_b_0x90(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB B");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.b);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.b);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.b);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_c_0x91(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB C");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.c);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.c);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.c);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_d_0x92(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB D");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.d);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.d);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.d);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_e_0x93(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB E");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.e);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.e);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.e);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_h_0x94(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB H");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.h);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.h);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.h);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_l_0x95(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB L");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.l);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.l);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.l);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn sub_hl_0x96(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB (HL)");
+        let hl = self.read(self.registers.hl());
+        self.registers.f.h = self.registers.a.calc_half_borrow(hl);
+        self.registers.f.c = self.registers.a.calc_borrow(hl);
+        self.registers.a = self.registers.a.wrapping_sub(hl);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sub_a_0x97(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB A");
+        self.registers.f.h = self.registers.a.calc_half_borrow(self.registers.a);
+        self.registers.f.c = self.registers.a.calc_borrow(self.registers.a);
+        self.registers.a = self.registers.a.wrapping_sub(self.registers.a);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_b_0x98(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, B");
+        let h = self.registers.b.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.b.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.b.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.b.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_c_0x99(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, C");
+        let h = self.registers.c.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.c.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.c.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.c.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_d_0x9a(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, D");
+        let h = self.registers.d.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.d.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.d.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.d.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_e_0x9b(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB A, E");
+        let h = self.registers.e.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.e.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.e.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.e.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_h_0x9c(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, H");
+        let h = self.registers.h.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.h.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.h.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.h.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_l_0x9d(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, L");
+        let h = self.registers.l.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.l.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.l.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.l.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn sbc_a_hl_0x9e(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, (HL)");
+        let d8 = self.read(self.registers.hl());
+        let h = d8.calc_half_carry(self.registers.f.c as u8);
+        let c = d8.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = d8.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if d8.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn sbc_a_a_0x9f(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, A");
+        let h = self.registers.a.calc_half_carry(self.registers.f.c as u8);
+        let c = self.registers.a.calc_carry(self.registers.f.c as u8);
+        let rhs: u8 = self.registers.a.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if self.registers.a.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < (rhs & 0x0F)
+            }
+        };
+        self.registers.f.c = if c { true } else { self.registers.a < rhs };
+        self.registers.a = self.registers.a.wrapping_sub(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_b_0xa0(&mut self) -> u8 {
+        trace!(target: "cpu", "AND B");
+        self.registers.a &= self.registers.b;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_c_0xa1(&mut self) -> u8 {
+        trace!(target: "cpu", "AND C");
+        self.registers.a &= self.registers.c;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_d_0xa2(&mut self) -> u8 {
+        trace!(target: "cpu", "AND D");
+        self.registers.a &= self.registers.d;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_e_0xa3(&mut self) -> u8 {
+        trace!(target: "cpu", "AND E");
+        self.registers.a &= self.registers.e;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_h_0xa4(&mut self) -> u8 {
+        trace!(target: "cpu", "AND H");
+        self.registers.a &= self.registers.h;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_l_0xa5(&mut self) -> u8 {
+        trace!(target: "cpu", "AND L");
+        self.registers.a &= self.registers.l;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn and_hl_0xa6(&mut self) -> u8 {
+        trace!(target: "cpu", "AND (HL)");
+        self.registers.a &= self.read(self.registers.hl());
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn and_a_0xa7(&mut self) -> u8 {
+        trace!(target: "cpu", "AND A");
+        self.registers.a &= self.registers.a;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_b_0xa8(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR B");
+        self.registers.a ^= self.registers.b;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_c_0xa9(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR C");
+        self.registers.a ^= self.registers.c;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_d_0xaa(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR D");
+        self.registers.a ^= self.registers.d;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_e_0xab(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR E");
+        self.registers.a ^= self.registers.e;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_h_0xac(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR H");
+        self.registers.a ^= self.registers.h;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_l_0xad(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR L");
+        self.registers.a ^= self.registers.l;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn xor_hl_0xae(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR (HL)");
+        self.registers.a ^= self.read(self.registers.hl());
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn xor_a_0xaf(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR A");
+        self.registers.a ^= self.registers.a;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_b_0xb0(&mut self) -> u8 {
+        trace!(target: "cpu", "OR B");
+        self.registers.a |= self.registers.b;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_c_0xb1(&mut self) -> u8 {
+        trace!(target: "cpu", "OR C");
+        self.registers.a |= self.registers.c;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_d_0xb2(&mut self) -> u8 {
+        trace!(target: "cpu", "OR D");
+        self.registers.a |= self.registers.d;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_e_0xb3(&mut self) -> u8 {
+        trace!(target: "cpu", "OR E");
+        self.registers.a |= self.registers.e;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_h_0xb4(&mut self) -> u8 {
+        trace!(target: "cpu", "OR H");
+        self.registers.a |= self.registers.h;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_l_0xb5(&mut self) -> u8 {
+        trace!(target: "cpu", "OR L");
+        self.registers.a |= self.registers.l;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn or_hl_0xb6(&mut self) -> u8 {
+        trace!(target: "cpu", "OR (HL)");
+        self.registers.a |= self.read(self.registers.hl());
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn or_a_0xb7(&mut self) -> u8 {
+        trace!(target: "cpu", "OR A");
+        self.registers.a |= self.registers.a;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_b_0xb8(&mut self) -> u8 {
+        trace!(target: "cpu", "CP B");
+        self.compare(self.registers.b);
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_c_0xb9(&mut self) -> u8 {
+        trace!(target: "cpu", "CP C");
+        self.compare(self.registers.c);
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_d_0xba(&mut self) -> u8 {
+        trace!(target: "cpu", "CP D");
+        self.compare(self.registers.d);
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_e_0xbb(&mut self) -> u8 {
+        trace!(target: "cpu", "CP E");
+        self.compare(self.registers.e);
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_h_0xbc(&mut self) -> u8 {
+        trace!(target: "cpu", "CP H");
+        self.compare(self.registers.h);
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_l_0xbd(&mut self) -> u8 {
+        trace!(target: "cpu", "CP L");
+        self.compare(self.registers.l);
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn cp_hl_0xbe(&mut self) -> u8 {
+        trace!(target: "cpu", "CP (HL)");
+        self.compare(self.read(self.registers.hl()));
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn cp_a_0xbf(&mut self) -> u8 {
+        trace!(target: "cpu", "CP A");
+        self.compare(self.registers.a);
+        4
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn add_a_d8_0xc6(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD A, d8");
+        let d8: u8 = self.fetch();
+        self.registers.f.h = self.registers.a.calc_half_carry(d8);
+        self.registers.f.c = self.registers.a.calc_carry(d8);
+        self.registers.a = self.registers.a.wrapping_add(d8);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn adc_a_d8_0xce(&mut self) -> u8 {
+        trace!(target: "cpu", "ADC A, d8");
+        let d8 = self.fetch();
+        let h = d8.calc_half_carry(self.registers.f.c as u8);
+        let c = d8.calc_carry(self.registers.f.c as u8);
+        let rhs = d8.wrapping_add(self.registers.f.c as u8);
+        self.registers.f.h = if h {
+            true
+        } else {
+            self.registers.a.calc_half_carry(rhs)
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            self.registers.a.calc_carry(rhs)
+        };
+        self.registers.a = self.registers.a.wrapping_add(rhs);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sub_d8_0xd6(&mut self) -> u8 {
+        trace!(target: "cpu", "SUB d8");
+        let d8 = self.fetch();
+        self.registers.f.h = self.registers.a.calc_half_borrow(d8);
+        self.registers.f.c = self.registers.a.calc_borrow(d8);
+        self.registers.a = self.registers.a.wrapping_sub(d8);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sbc_a_d8_0xde(&mut self) -> u8 {
+        trace!(target: "cpu", "SBC A, d8");
+        let d8 = self.fetch();
+        trace!(target: "cpu", "d8: 0b{:08b}", d8);
+        let h = d8.calc_half_carry(self.registers.f.c as u8);
+        let c = d8.calc_carry(self.registers.f.c as u8);
+        let rhs: u16 = (d8 as u16).wrapping_add(self.registers.f.c as u16);
+        self.registers.f.h = if h {
+            true
+        } else {
+            if d8.calc_half_carry(self.registers.f.c as u8) {
+                true
+            } else {
+                (self.registers.a & 0x0F) < ((rhs as u8) & 0x0F)
+            }
+        };
+        self.registers.f.c = if c {
+            true
+        } else {
+            (self.registers.a as u16) < rhs
+        };
+        self.registers.a = self.registers.a.wrapping_sub(rhs as u8);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn and_d8_0xe6(&mut self) -> u8 {
+        trace!(target: "cpu", "AND d8");
+        self.registers.a &= self.fetch();
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn add_sp_r8_0xe8(&mut self) -> u8 {
+        trace!(target: "cpu", "ADD SP, r8");
+        let r8 = self.fetch();
+        let (sp, h, c) = add_sp_e8(self.registers.sp, r8);
+        self.registers.sp = sp;
+        self.registers.f.h = h;
+        self.registers.f.c = c;
+        self.registers.f.z = false;
+        self.registers.f.n = false;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn xor_d8_0xee(&mut self) -> u8 {
+        trace!(target: "cpu", "XOR d8");
+        self.registers.a ^= self.fetch();
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn or_d8_0xf6(&mut self) -> u8 {
+        trace!(target: "cpu", "OR d8");
+        self.registers.a |= self.fetch();
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn cp_d8_0xfe(&mut self) -> u8 {
+        trace!(target: "cpu", "CP d8");
+        let rhs = self.fetch();
+        trace!(target: "cpu", "CP d8: 0b{:08b}", rhs);
+        self.compare(rhs);
+        8
+    }
+
+    // CP は A - rhs のフラグだけを立てる。SUB と違い A は変えない
+    fn compare(&mut self, rhs: u8) {
+        let a = self.registers.a;
+        self.registers.f.z = a.wrapping_sub(rhs) == 0;
+        self.registers.f.n = true;
+        self.registers.f.h = a.calc_half_borrow(rhs);
+        self.registers.f.c = a.calc_borrow(rhs);
+    }
+}
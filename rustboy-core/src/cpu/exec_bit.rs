@@ -0,0 +1,2122 @@
+use log::trace;
+
+use super::CPU;
+
+// ローテート、シフトとビット操作 (RLCA などと CB プレフィックスの命令)
+impl CPU {
+    // bytes: 1 cycles: [4]
+    pub(super) fn rlca_0x07(&mut self) -> u8 {
+        trace!(target: "cpu", "RLCA");
+        let c = (self.registers.a >> 7) == 1;
+        self.registers.a = self.registers.a << 1 | c as u8;
+        // GBCPUman.pdf だと Set if result is zero. だが、
+        // https://gbdev.io/gb-opcodes/optables/ では 0
+        self.registers.f.z = false;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn rrca_0x0f(&mut self) -> u8 {
+        trace!(target: "cpu", "RRCA");
+        let c = (self.registers.a & 0x1) == 1;
+        self.registers.a = (c as u8) << 7 | self.registers.a >> 1;
+        // GBCPUman.pdf だと Set if result is zero. だが、
+        // https://gbdev.io/gb-opcodes/optables/ では 0
+        self.registers.f.z = false;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn rla_0x17(&mut self) -> u8 {
+        trace!(target: "cpu", "RLA");
+        let c = (self.registers.a >> 7) == 1;
+        self.registers.a = (self.registers.a << 1) | self.registers.f.c as u8;
+        // GBCPUman.pdf だと Set if result is zero. だが、
+        // https://gbdev.io/gb-opcodes/optables/ では 0
+        self.registers.f.z = false;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn rra_0x1f(&mut self) -> u8 {
+        trace!(target: "cpu", "RRA");
+        let c = (self.registers.a & 0x1) == 1;
+        self.registers.a = ((self.registers.f.c as u8) << 7) | (self.registers.a >> 1);
+        // GBCPUman.pdf だと Set if result is zero. だが、
+        // https://gbdev.io/gb-opcodes/optables/ では 0
+        self.registers.f.z = false;
+
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        4
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_b_0xcb00(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC B");
+        let c = (self.registers.b >> 7) == 0x1;
+        self.registers.b = self.registers.b << 1 | c as u8;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_c_0xcb01(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC C");
+        let c = (self.registers.c >> 7) == 0x1;
+        self.registers.c = self.registers.c << 1 | c as u8;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_d_0xcb02(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC D");
+        let c = (self.registers.d >> 7) == 0x1;
+        self.registers.d = self.registers.d << 1 | c as u8;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_e_0xcb03(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC E");
+        let c = (self.registers.e >> 7) == 0x1;
+        self.registers.e = self.registers.e << 1 | c as u8;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_h_0xcb04(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC H");
+        let c = (self.registers.h >> 7) == 0x1;
+        self.registers.h = self.registers.h << 1 | c as u8;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_l_0xcb05(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC L");
+        let c = (self.registers.l >> 7) == 0x1;
+        self.registers.l = self.registers.l << 1 | c as u8;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn rlc_hl_0xcb06(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value >> 7 == 0x1;
+        let result = value << 1 | c as u8;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rlc_a_0xcb07(&mut self) -> u8 {
+        trace!(target: "cpu", "RLC A");
+        let c = (self.registers.a >> 7) == 0x1;
+        self.registers.a = self.registers.a << 1 | c as u8;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_b_0xcb08(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC B");
+        let c = (self.registers.b & 0x01) == 1;
+        self.registers.b = (c as u8) << 7 | self.registers.b >> 1;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_c_0xcb09(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC C");
+        let c = (self.registers.c & 0x01) == 1;
+        self.registers.c = (c as u8) << 7 | self.registers.c >> 1;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_d_0xcb0a(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC D");
+        let c = (self.registers.d & 0x01) == 1;
+        self.registers.d = (c as u8) << 7 | self.registers.d >> 1;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_e_0xcb0b(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC E");
+        let c = (self.registers.e & 0x01) == 1;
+        self.registers.e = (c as u8) << 7 | self.registers.e >> 1;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_h_0xcb0c(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC H");
+        let c = (self.registers.h & 0x01) == 1;
+        self.registers.h = (c as u8) << 7 | self.registers.h >> 1;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_l_0xcb0d(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC L");
+        let c = (self.registers.l & 0x01) == 1;
+        self.registers.l = (c as u8) << 7 | self.registers.l >> 1;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn rrc_hl_0xcb0e(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value & 0x01 == 0x01;
+        let result = (c as u8) << 7 | value >> 1;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rrc_a_0xcb0f(&mut self) -> u8 {
+        trace!(target: "cpu", "RRC A");
+        let c = (self.registers.a & 0x01) == 1;
+        self.registers.a = (c as u8) << 7 | self.registers.a >> 1;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_b_0xcb10(&mut self) -> u8 {
+        trace!(target: "cpu", "RL B");
+        let c = (self.registers.b >> 7) == 0x1;
+        self.registers.b = self.registers.b << 1 | self.registers.f.c as u8;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_c_0xcb11(&mut self) -> u8 {
+        trace!(target: "cpu", "RL C");
+        let c = (self.registers.c >> 7) == 0x1;
+        self.registers.c = self.registers.c << 1 | self.registers.f.c as u8;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_d_0xcb12(&mut self) -> u8 {
+        trace!(target: "cpu", "RL D");
+        let c = (self.registers.d >> 7) == 0x1;
+        self.registers.d = self.registers.d << 1 | self.registers.f.c as u8;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_e_0xcb13(&mut self) -> u8 {
+        trace!(target: "cpu", "RL E");
+        let c = (self.registers.e >> 7) == 0x1;
+        self.registers.e = self.registers.e << 1 | self.registers.f.c as u8;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_h_0xcb14(&mut self) -> u8 {
+        trace!(target: "cpu", "RL H");
+        let c = (self.registers.h >> 7) == 0x1;
+        self.registers.h = self.registers.h << 1 | self.registers.f.c as u8;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_l_0xcb15(&mut self) -> u8 {
+        trace!(target: "cpu", "RL L");
+        let c = (self.registers.l >> 7) == 0x1;
+        self.registers.l = self.registers.l << 1 | self.registers.f.c as u8;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn rl_hl_0xcb16(&mut self) -> u8 {
+        trace!(target: "cpu", "RL (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value >> 7 == 0x1;
+        let result = value << 1 | self.registers.f.c as u8;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rl_a_0xcb17(&mut self) -> u8 {
+        trace!(target: "cpu", "RL A");
+        let c = (self.registers.a >> 7) == 0x1;
+        self.registers.a = self.registers.a << 1 | (self.registers.f.c as u8);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_b_0xcb18(&mut self) -> u8 {
+        trace!(target: "cpu", "RR B");
+        let c = (self.registers.b & 0x01) == 0x01;
+        self.registers.b = (self.registers.f.c as u8) << 7 | self.registers.b >> 1;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_c_0xcb19(&mut self) -> u8 {
+        trace!(target: "cpu", "RR C");
+        let c = (self.registers.c & 0x01) == 0x01;
+        self.registers.c = (self.registers.f.c as u8) << 7 | self.registers.c >> 1;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_d_0xcb1a(&mut self) -> u8 {
+        trace!(target: "cpu", "RR D");
+        let c = (self.registers.d & 0x01) == 0x01;
+        self.registers.d = (self.registers.f.c as u8) << 7 | self.registers.d >> 1;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_e_0xcb1b(&mut self) -> u8 {
+        trace!(target: "cpu", "RR E");
+        let c = (self.registers.e & 0x01) == 0x01;
+        self.registers.e = (self.registers.f.c as u8) << 7 | self.registers.e >> 1;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_h_0xcb1c(&mut self) -> u8 {
+        trace!(target: "cpu", "RR H");
+        let c = (self.registers.h & 0x01) == 0x01;
+        self.registers.h = (self.registers.f.c as u8) << 7 | self.registers.h >> 1;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_l_0xcb1d(&mut self) -> u8 {
+        trace!(target: "cpu", "RR L");
+        let c = (self.registers.l & 0x01) == 0x01;
+        self.registers.l = (self.registers.f.c as u8) << 7 | self.registers.l >> 1;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn rr_hl_0xcb1e(&mut self) -> u8 {
+        trace!(target: "cpu", "RR (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value & 0x01 == 0x01;
+        let result = (self.registers.f.c as u8) << 7 | value >> 1;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn rr_a_0xcb1f(&mut self) -> u8 {
+        trace!(target: "cpu", "RR A");
+        let c = (self.registers.a & 0x01) == 0x01;
+        self.registers.a = (self.registers.f.c as u8) << 7 | self.registers.a >> 1;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_b_0xcb20(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA B");
+        self.registers.f.c = (self.registers.b >> 7) == 0x1;
+        self.registers.b = self.registers.b << 1;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_c_0xcb21(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA C");
+        self.registers.f.c = (self.registers.c >> 7) == 0x1;
+        self.registers.c = self.registers.c << 1;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_d_0xcb22(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA D");
+        self.registers.f.c = (self.registers.d >> 7) == 0x1;
+        self.registers.d = self.registers.d << 1;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_e_0xcb23(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA E");
+        self.registers.f.c = (self.registers.e >> 7) == 0x1;
+        self.registers.e = self.registers.e << 1;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_h_0xcb24(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA H");
+        self.registers.f.c = (self.registers.h >> 7) == 0x1;
+        self.registers.h = self.registers.h << 1;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_l_0xcb25(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA L");
+        self.registers.f.c = (self.registers.l >> 7) == 0x1;
+        self.registers.l = self.registers.l << 1;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn sla_hl_0xcb26(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value >> 7 == 0x1;
+        let result = value << 1;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sla_a_0xcb27(&mut self) -> u8 {
+        trace!(target: "cpu", "SLA A");
+        self.registers.f.c = (self.registers.a >> 7) == 0x1;
+        self.registers.a = self.registers.a << 1;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_b_0xcb28(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA B");
+        let c = self.registers.b & 0x1 == 0x1;
+        let smb = self.registers.b & 0x80;
+        self.registers.b = smb | (self.registers.b >> 1);
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_c_0xcb29(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA C");
+        let c = self.registers.c & 0x1 == 0x1;
+        let smb = self.registers.c & 0x80;
+        self.registers.c = smb | (self.registers.c >> 1);
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_d_0xcb2a(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA D");
+        let c = self.registers.d & 0x1 == 0x1;
+        let smd = self.registers.d & 0x80;
+        self.registers.d = smd | (self.registers.d >> 1);
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_e_0xcb2b(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA E");
+        let c = self.registers.e & 0x1 == 0x1;
+        let smd = self.registers.e & 0x80;
+        self.registers.e = smd | (self.registers.e >> 1);
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_h_0xcb2c(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA H");
+        let c = self.registers.h & 0x1 == 0x1;
+        let smb = self.registers.h & 0x80;
+        self.registers.h = smb | (self.registers.h >> 1);
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_l_0xcb2d(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA L");
+        let c = self.registers.l & 0x1 == 0x1;
+        let smb = self.registers.l & 0x80;
+        self.registers.l = smb | (self.registers.l >> 1);
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn sra_hl_0xcb2e(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value & 0x01 == 0x01;
+        let result = value & 0x80 | value >> 1;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn sra_a_0xcb2f(&mut self) -> u8 {
+        trace!(target: "cpu", "SRA A");
+        let c = self.registers.a & 0x1 == 0x1;
+        let smb = self.registers.a & 0x80;
+        self.registers.a = smb | (self.registers.a >> 1);
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_b_0xcb30(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP B");
+        let upper = (self.registers.b & 0xF0) >> 4;
+        let lower = self.registers.b & 0x0F;
+        self.registers.b = (lower << 4) | upper;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_c_0xcb31(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP C");
+        let upper = (self.registers.c & 0xF0) >> 4;
+        let lower = self.registers.c & 0x0F;
+        self.registers.c = (lower << 4) | upper;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_d_0xcb32(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP D");
+        let upper = (self.registers.d & 0xF0) >> 4;
+        let lower = self.registers.d & 0x0F;
+        self.registers.d = (lower << 4) | upper;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_e_0xcb33(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP E");
+        let upper = (self.registers.e & 0xF0) >> 4;
+        let lower = self.registers.e & 0x0F;
+        self.registers.e = (lower << 4) | upper;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_h_0xcb34(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP H");
+        let upper = (self.registers.h & 0xF0) >> 4;
+        let lower = self.registers.h & 0x0F;
+        self.registers.h = (lower << 4) | upper;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_l_0xcb35(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP L");
+        let upper = (self.registers.l & 0xF0) >> 4;
+        let lower = self.registers.l & 0x0F;
+        self.registers.l = (lower << 4) | upper;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn swap_hl_0xcb36(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let result = value.rotate_left(4);
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn swap_a_0xcb37(&mut self) -> u8 {
+        trace!(target: "cpu", "SWAP A");
+        let upper = (self.registers.a & 0xF0) >> 4;
+        let lower = self.registers.a & 0x0F;
+        self.registers.a = (lower << 4) | upper;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = false;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_b_0xcb38(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL B");
+        let c = (self.registers.b & 0x01) == 0x01;
+        self.registers.b = self.registers.b >> 1;
+        self.registers.f.z = self.registers.b == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_c_0xcb39(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL C");
+        let c = (self.registers.c & 0x01) == 0x01;
+        self.registers.c = self.registers.c >> 1;
+        self.registers.f.z = self.registers.c == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_d_0xcb3a(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL D");
+        let c = (self.registers.d & 0x01) == 0x01;
+        self.registers.d = self.registers.d >> 1;
+        self.registers.f.z = self.registers.d == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_e_0xcb3b(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL E");
+        let c = (self.registers.e & 0x01) == 0x01;
+        self.registers.e = self.registers.e >> 1;
+        self.registers.f.z = self.registers.e == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_h_0xcb3c(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL H");
+        let c = (self.registers.h & 0x01) == 0x01;
+        self.registers.h = self.registers.h >> 1;
+        self.registers.f.z = self.registers.h == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_l_0xcb3d(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL L");
+        let c = (self.registers.l & 0x01) == 0x01;
+        self.registers.l = self.registers.l >> 1;
+        self.registers.f.z = self.registers.l == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn srl_hl_0xcb3e(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL (HL)");
+        // 読み込みと書き込みは 1 回ずつ。フラグは書き込んだ値で決まる
+        let value = self.read(self.registers.hl());
+        let c = value & 0x01 == 0x01;
+        let result = value >> 1;
+        self.write(self.registers.hl(), result);
+        self.registers.f.z = result == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn srl_a_0xcb3f(&mut self) -> u8 {
+        trace!(target: "cpu", "SRL A");
+        let c = (self.registers.a & 0x01) == 0x01;
+        self.registers.a = self.registers.a >> 1;
+        self.registers.f.z = self.registers.a == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = false;
+        self.registers.f.c = c;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_b_0xcb40(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_c_0xcb41(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_d_0xcb42(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_e_0xcb43(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_h_0xcb44(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_l_0xcb45(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_0_hl_0xcb46(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_0_a_0xcb47(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 0, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 0) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_b_0xcb48(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_c_0xcb49(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_d_0xcb4a(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_e_0xcb4b(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_h_0xcb4c(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_l_0xcb4d(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_1_hl_0xcb4e(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_1_a_0xcb4f(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 1, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 1) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_b_0xcb50(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_c_0xcb51(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_d_0xcb52(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_e_0xcb53(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_h_0xcb54(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_l_0xcb55(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_2_hl_0xcb56(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_2_a_0xcb57(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 2, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 2) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_b_0xcb58(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_c_0xcb59(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_d_0xcb5a(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_e_0xcb5b(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_h_0xcb5c(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_l_0xcb5d(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_3_hl_0xcb5e(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_3_a_0xcb5f(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 3, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 3) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_b_0xcb60(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_c_0xcb61(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_d_0xcb62(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_e_0xcb63(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_h_0xcb64(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_l_0xcb65(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_4_hl_0xcb66(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_4_a_0xcb67(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 4, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 4) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_b_0xcb68(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_c_0xcb69(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_d_0xcb6a(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_e_0xcb6b(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_h_0xcb6c(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_l_0xcb6d(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_5_hl_0xcb6e(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_5_a_0xcb6f(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 5, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 5) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_b_0xcb70(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_c_0xcb71(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_d_0xcb72(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_e_0xcb73(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_h_0xcb74(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_l_0xcb75(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_6_hl_0xcb76(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_6_a_0xcb77(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 6, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 6) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_b_0xcb78(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, B");
+        self.registers.f.z = (self.registers.b & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_c_0xcb79(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, C");
+        self.registers.f.z = (self.registers.c & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_d_0xcb7a(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, D");
+        self.registers.f.z = (self.registers.d & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_e_0xcb7b(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, E");
+        self.registers.f.z = (self.registers.e & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_h_0xcb7c(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, H");
+        self.registers.f.z = (self.registers.h & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_l_0xcb7d(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, L");
+        self.registers.f.z = (self.registers.l & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn bit_7_hl_0xcb7e(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, (HL)");
+        self.registers.f.z = (self.read(self.registers.hl()) & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        12
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn bit_7_a_0xcb7f(&mut self) -> u8 {
+        trace!(target: "cpu", "BIT 7, A");
+        self.registers.f.z = (self.registers.a & 0b1 << 7) == 0;
+        self.registers.f.n = false;
+        self.registers.f.h = true;
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_b_0xcb80(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, B");
+        self.registers.b = self.registers.b & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_c_0xcb81(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, C");
+        self.registers.c = self.registers.c & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_d_0xcb82(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, D");
+        self.registers.d = self.registers.d & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_e_0xcb83(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, E");
+        self.registers.e = self.registers.e & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_h_0xcb84(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, H");
+        self.registers.h = self.registers.h & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_l_0xcb85(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, L");
+        self.registers.l = self.registers.l & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_0_hl_0xcb86(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 0),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_0_a_0xcb87(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 0, A");
+        self.registers.a = self.registers.a & !(0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_b_0xcb88(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, B");
+        self.registers.b = self.registers.b & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_c_0xcb89(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, C");
+        self.registers.c = self.registers.c & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_d_0xcb8a(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, D");
+        self.registers.d = self.registers.d & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_e_0xcb8b(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, E");
+        self.registers.e = self.registers.e & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_h_0xcb8c(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, H");
+        self.registers.h = self.registers.h & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_l_0xcb8d(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, L");
+        self.registers.l = self.registers.l & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_1_hl_0xcb8e(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 1),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_1_a_0xcb8f(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 1, A");
+        self.registers.a = self.registers.a & !(0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_b_0xcb90(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, B");
+        self.registers.b = self.registers.b & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_c_0xcb91(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, C");
+        self.registers.c = self.registers.c & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_d_0xcb92(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, D");
+        self.registers.d = self.registers.d & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_e_0xcb93(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, E");
+        self.registers.e = self.registers.e & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_h_0xcb94(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, H");
+        self.registers.h = self.registers.h & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_l_0xcb95(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, L");
+        self.registers.l = self.registers.l & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_2_hl_0xcb96(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 2),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_2_a_0xcb97(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 2, A");
+        self.registers.a = self.registers.a & !(0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_b_0xcb98(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, B");
+        self.registers.b = self.registers.b & !(0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_c_0xcb99(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, C");
+        self.registers.c = self.registers.c & !(0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_d_0xcb9a(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, D");
+        self.registers.d = self.registers.d & !(0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_e_0xcb9b(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, E");
+        self.registers.e = self.registers.e & !(0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_h_0xcb9c(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, H");
+        self.registers.h = self.registers.h & !(0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_l_0xcb9d(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, L");
+        self.registers.l = self.registers.l & !(0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_3_hl_0xcb9e(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 3),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_3_a_0xcb9f(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 3, A");
+        self.registers.a = self.registers.a & !(0b1 << 3);
+        0
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_b_0xcba0(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, B");
+        self.registers.b = self.registers.b & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_c_0xcba1(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, C");
+        self.registers.c = self.registers.c & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_d_0xcba2(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, D");
+        self.registers.d = self.registers.d & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_e_0xcba3(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, E");
+        self.registers.e = self.registers.e & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_h_0xcba4(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, H");
+        self.registers.h = self.registers.h & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_l_0xcba5(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, L");
+        self.registers.l = self.registers.l & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_4_hl_0xcba6(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 4),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_4_a_0xcba7(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 4, A");
+        self.registers.a = self.registers.a & !(0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_b_0xcba8(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, B");
+        self.registers.b = self.registers.b & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_c_0xcba9(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, C");
+        self.registers.c = self.registers.c & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_d_0xcbaa(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, D");
+        self.registers.d = self.registers.d & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_e_0xcbab(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, E");
+        self.registers.e = self.registers.e & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_h_0xcbac(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, H");
+        self.registers.h = self.registers.h & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_l_0xcbad(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, L");
+        self.registers.l = self.registers.l & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_5_hl_0xcbae(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 5),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_5_a_0xcbaf(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 5, A");
+        self.registers.a = self.registers.a & !(0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_b_0xcbb0(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, B");
+        self.registers.b = self.registers.b & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_c_0xcbb1(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, C");
+        self.registers.c = self.registers.c & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_d_0xcbb2(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, D");
+        self.registers.d = self.registers.d & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_e_0xcbb3(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, E");
+        self.registers.e = self.registers.e & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_h_0xcbb4(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, H");
+        self.registers.h = self.registers.h & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_l_0xcbb5(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, L");
+        self.registers.l = self.registers.l & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_6_hl_0xcbb6(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 6),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_6_a_0xcbb7(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 6, A");
+        self.registers.a = self.registers.a & !(0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_b_0xcbb8(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, B");
+        self.registers.b = self.registers.b & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_c_0xcbb9(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, C");
+        self.registers.c = self.registers.c & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_d_0xcbba(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, D");
+        self.registers.d = self.registers.d & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_e_0xcbbb(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, E");
+        self.registers.e = self.registers.e & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_h_0xcbbc(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, H");
+        self.registers.h = self.registers.h & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_l_0xcbbd(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, L");
+        self.registers.l = self.registers.l & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn res_7_hl_0xcbbe(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) & !(0b1 << 7),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn res_7_a_0xcbbf(&mut self) -> u8 {
+        trace!(target: "cpu", "RES 7, A");
+        self.registers.a = self.registers.a & !(0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_b_0xcbc0(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, B");
+        self.registers.b = self.registers.b | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_c_0xcbc1(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, C");
+        self.registers.c = self.registers.c | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_d_0xcbc2(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, D");
+        self.registers.d = self.registers.d | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_e_0xcbc3(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, E");
+        self.registers.e = self.registers.e | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_h_0xcbc4(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, H");
+        self.registers.h = self.registers.h | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_l_0xcbc5(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, L");
+        self.registers.l = self.registers.l | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_0_hl_0xcbc6(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 0),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_0_a_0xcbc7(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 0, A");
+        self.registers.a = self.registers.a | (0b1 << 0);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_b_0xcbc8(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, B");
+        self.registers.b = self.registers.b | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_c_0xcbc9(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, C");
+        self.registers.c = self.registers.c | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_d_0xcbca(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, D");
+        self.registers.d = self.registers.d | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_e_0xcbcb(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, E");
+        self.registers.e = self.registers.e | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_h_0xcbcc(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, H");
+        self.registers.h = self.registers.h | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_l_0xcbcd(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, L");
+        self.registers.l = self.registers.l | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_1_hl_0xcbce(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 1),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_1_a_0xcbcf(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 1, A");
+        self.registers.a = self.registers.a | (0b1 << 1);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_b_0xcbd0(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, B");
+        self.registers.b = self.registers.b | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_c_0xcbd1(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, C");
+        self.registers.c = self.registers.c | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_d_0xcbd2(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, D");
+        self.registers.d = self.registers.d | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_e_0xcbd3(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, E");
+        self.registers.e = self.registers.e | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_h_0xcbd4(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, H");
+        self.registers.h = self.registers.h | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_l_0xcbd5(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, L");
+        self.registers.l = self.registers.l | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_2_hl_0xcbd6(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 2),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_2_a_0xcbd7(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 2, A");
+        self.registers.a = self.registers.a | (0b1 << 2);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_b_0xcbd8(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, B");
+        self.registers.b = self.registers.b | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_c_0xcbd9(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, C");
+        self.registers.c = self.registers.c | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_d_0xcbda(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, D");
+        self.registers.d = self.registers.d | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_e_0xcbdb(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, E");
+        self.registers.e = self.registers.e | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_h_0xcbdc(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, H");
+        self.registers.h = self.registers.h | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_l_0xcbdd(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, L");
+        self.registers.l = self.registers.l | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_3_hl_0xcbde(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 3),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_3_a_0xcbdf(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 3, A");
+        self.registers.a = self.registers.a | (0b1 << 3);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_b_0xcbe0(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, B");
+        self.registers.b = self.registers.b | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_c_0xcbe1(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, C");
+        self.registers.c = self.registers.c | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_d_0xcbe2(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, D");
+        self.registers.d = self.registers.d | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_e_0xcbe3(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, E");
+        self.registers.e = self.registers.e | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_h_0xcbe4(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, H");
+        self.registers.h = self.registers.h | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_l_0xcbe5(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, L");
+        self.registers.l = self.registers.l | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_4_hl_0xcbe6(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 4),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_4_a_0xcbe7(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 4, A");
+        self.registers.a = self.registers.a | (0b1 << 4);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_b_0xcbe8(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, B");
+        self.registers.b = self.registers.b | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_c_0xcbe9(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, C");
+        self.registers.c = self.registers.c | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_d_0xcbea(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, D");
+        self.registers.d = self.registers.d | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_e_0xcbeb(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, E");
+        self.registers.e = self.registers.e | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_h_0xcbec(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, H");
+        self.registers.h = self.registers.h | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_l_0xcbed(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, L");
+        self.registers.l = self.registers.l | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_5_hl_0xcbee(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 5),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_5_a_0xcbef(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 5, A");
+        self.registers.a = self.registers.a | (0b1 << 5);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_b_0xcbf0(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, B");
+        self.registers.b = self.registers.b | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_c_0xcbf1(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, C");
+        self.registers.c = self.registers.c | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_d_0xcbf2(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, D");
+        self.registers.d = self.registers.d | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_e_0xcbf3(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, E");
+        self.registers.e = self.registers.e | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_h_0xcbf4(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, H");
+        self.registers.h = self.registers.h | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_l_0xcbf5(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, L");
+        self.registers.l = self.registers.l | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_6_hl_0xcbf6(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 6),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_6_a_0xcbf7(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 6, A");
+        self.registers.a = self.registers.a | (0b1 << 6);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_b_0xcbf8(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, B");
+        self.registers.b = self.registers.b | (0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_c_0xcbf9(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, C");
+        self.registers.c = self.registers.c | (0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_d_0xcbfa(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, D");
+        self.registers.d = self.registers.d | (0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_e_0xcbfb(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, E");
+        self.registers.e = self.registers.e | (0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_h_0xcbfc(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, H");
+        self.registers.h = self.registers.h | (0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_l_0xcbfd(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, L");
+        self.registers.l = self.registers.l | (0b1 << 7);
+        8
+    }
+    // bytes: 2 cycles: [16]
+    pub(super) fn set_7_hl_0xcbfe(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, (HL)");
+        self.write(
+            self.registers.hl(),
+            self.read(self.registers.hl()) | (0b1 << 7),
+        );
+        16
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn set_7_a_0xcbff(&mut self) -> u8 {
+        trace!(target: "cpu", "SET 7, A");
+        self.registers.a = self.registers.a | (0b1 << 7);
+        8
+    }
+}
@@ -0,0 +1,409 @@
+use log::trace;
+
+use super::{Ime, CPU};
+use crate::arithmetic::AddSigned;
+
+// 分岐と CPU の制御 (JP, JR, CALL, RET, RST, HALT, EI/DI など)
+impl CPU {
+    // bytes: 1 cycles: [4]
+    pub(super) fn nop_0x00(&mut self) -> u8 {
+        trace!(target: "cpu", "NOP");
+        4
+    }
+    // bytes: 2 cycles: [4]
+    pub(super) fn stop_d8_0x10(&mut self) -> u8 {
+        trace!(target: "cpu", "STOP");
+        let _ = self.fetch();
+        if self.stop_as_nop {
+            return 4;
+        }
+        // CGB では準備されていれば倍速モードを切り替える
+        // 切り替えでなければボタンが押されるまで CPU と LCD を止める
+        if !self.bus.upgrade().unwrap().borrow().stop() {
+            self.is_stopped = true;
+        }
+        4
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn jr_r8_0x18(&mut self) -> u8 {
+        trace!(target: "cpu", "JR r8");
+        let r8 = self.fetch();
+        self.registers.pc = self.registers.pc.add_signed_u8(r8);
+        12
+    }
+    // bytes: 2 cycles: [12, 8]
+    pub(super) fn jr_nz_r8_0x20(&mut self) -> u8 {
+        trace!(target: "cpu", "JR NZ, r8");
+        let r8 = self.fetch();
+        if !self.registers.f.z {
+            self.registers.pc = self.registers.pc.add_signed_u8(r8);
+            12
+        } else {
+            8
+        }
+    }
+    // bytes: 2 cycles: [12, 8]
+    pub(super) fn jr_z_r8_0x28(&mut self) -> u8 {
+        trace!(target: "cpu", "JR Z, r8");
+        let r8 = self.fetch();
+        if self.registers.f.z {
+            self.registers.pc = self.registers.pc.add_signed_u8(r8);
+            12
+        } else {
+            8
+        }
+    }
+    // bytes: 2 cycles: [12, 8]
+    pub(super) fn jr_nc_r8_0x30(&mut self) -> u8 {
+        trace!(target: "cpu", "JR NC, r8");
+        let r8 = self.fetch();
+        if !self.registers.f.c {
+            self.registers.pc = self.registers.pc.add_signed_u8(r8);
+            12
+        } else {
+            8
+        }
+    }
+    // bytes: 2 cycles: [12, 8]
+    pub(super) fn jr_c_r8_0x38(&mut self) -> u8 {
+        trace!(target: "cpu", "JR C, r8");
+        let r8 = self.fetch();
+        if self.registers.f.c {
+            self.registers.pc = self.registers.pc.add_signed_u8(r8);
+            12
+        } else {
+            8
+        }
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn halt_0x76(&mut self) -> u8 {
+        trace!(target: "cpu", "HALT");
+        // 割り込みが来るまで待機
+        self.is_halted = true;
+        // haltの直後の命令はスキップされる(GBCPUman.pdf page 20)
+        self.registers.pc = self.registers.pc.wrapping_add(1);
+        4
+    }
+    // bytes: 1 cycles: [20, 8]
+    pub(super) fn ret_nz_0xc0(&mut self) -> u8 {
+        trace!(target: "cpu", "RET NZ");
+        // 条件の判定に 1 M-Cycle かかる
+        self.cycle(4);
+        if !self.registers.f.z {
+            self.registers.pc = self.pop();
+            20
+        } else {
+            8
+        }
+    }
+    // bytes: 3 cycles: [16, 12]
+    pub(super) fn jp_nz_a16_0xc2(&mut self) -> u8 {
+        trace!(target: "cpu", "JP NZ, a16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        if !self.registers.f.z {
+            self.registers.pc = a16;
+            16
+        } else {
+            12
+        }
+    }
+    // bytes: 3 cycles: [16]
+    pub(super) fn jp_a16_0xc3(&mut self) -> u8 {
+        trace!(target: "cpu", "JP a16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        self.registers.pc = a16;
+        16
+    }
+    // bytes: 3 cycles: [24, 12]
+    pub(super) fn call_nz_a16_0xc4(&mut self) -> u8 {
+        trace!(target: "cpu", "CALL NZ, a16");
+        let lower: u16 = self.fetch().into();
+        let upper: u16 = self.fetch().into();
+        if !self.registers.f.z {
+            self.push(self.registers.pc);
+            self.registers.pc = upper << 8 | lower;
+            24
+        } else {
+            12
+        }
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_00h_0xc7(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 00H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0000;
+        16
+    }
+    // bytes: 1 cycles: [20, 8]
+    pub(super) fn ret_z_0xc8(&mut self) -> u8 {
+        trace!(target: "cpu", "RET Z");
+        // 条件の判定に 1 M-Cycle かかる
+        self.cycle(4);
+        if self.registers.f.z {
+            self.registers.pc = self.pop();
+            20
+        } else {
+            8
+        }
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn ret_0xc9(&mut self) -> u8 {
+        trace!(target: "cpu", "RET");
+        self.registers.pc = self.pop();
+        16
+    }
+    // bytes: 3 cycles: [16, 12]
+    pub(super) fn jp_z_a16_0xca(&mut self) -> u8 {
+        trace!(target: "cpu", "JP Z, a16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        if self.registers.f.z {
+            self.registers.pc = a16;
+            16
+        } else {
+            12
+        }
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn prefix_0xcb(&mut self) -> u8 {
+        // CBの場合は先に処理している
+        unreachable!();
+    }
+    // bytes: 3 cycles: [24, 12]
+    pub(super) fn call_z_a16_0xcc(&mut self) -> u8 {
+        trace!(target: "cpu", "CALL Z, a16");
+        let lower: u16 = self.fetch().into();
+        let upper: u16 = self.fetch().into();
+        if self.registers.f.z {
+            self.push(self.registers.pc);
+            self.registers.pc = upper << 8 | lower;
+            24
+        } else {
+            12
+        }
+    }
+    // bytes: 3 cycles: [24]
+    pub(super) fn call_a16_0xcd(&mut self) -> u8 {
+        trace!(target: "cpu", "CALL a16");
+        let lower: u16 = self.fetch().into();
+        let upper: u16 = self.fetch().into();
+        self.push(self.registers.pc);
+        self.registers.pc = upper << 8 | lower;
+        24
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_08h_0xcf(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 08H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0008;
+        16
+    }
+    // bytes: 1 cycles: [20, 8]
+    pub(super) fn ret_nc_0xd0(&mut self) -> u8 {
+        trace!(target: "cpu", "RET NC");
+        // 条件の判定に 1 M-Cycle かかる
+        self.cycle(4);
+        if !self.registers.f.c {
+            self.registers.pc = self.pop();
+            20
+        } else {
+            8
+        }
+    }
+    // bytes: 3 cycles: [16, 12]
+    pub(super) fn jp_nc_a16_0xd2(&mut self) -> u8 {
+        trace!(target: "cpu", "JP NC, a16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        if !self.registers.f.c {
+            self.registers.pc = a16;
+            16
+        } else {
+            12
+        }
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_d3_0xd3(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 3 cycles: [24, 12]
+    pub(super) fn call_nc_a16_0xd4(&mut self) -> u8 {
+        trace!(target: "cpu", "CALL NC, a16");
+        let lower: u16 = self.fetch().into();
+        let upper: u16 = self.fetch().into();
+        if !self.registers.f.c {
+            self.push(self.registers.pc);
+            self.registers.pc = upper << 8 | lower;
+            24
+        } else {
+            12
+        }
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_10h_0xd7(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 10H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0010;
+        16
+    }
+    // bytes: 1 cycles: [20, 8]
+    pub(super) fn ret_c_0xd8(&mut self) -> u8 {
+        trace!(target: "cpu", "RET C");
+        // 条件の判定に 1 M-Cycle かかる
+        self.cycle(4);
+        if self.registers.f.c {
+            self.registers.pc = self.pop();
+            20
+        } else {
+            8
+        }
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn reti_0xd9(&mut self) -> u8 {
+        trace!(target: "cpu", "RETI");
+        self.registers.pc = self.pop();
+        // RETI は EI と違い、すぐに有効にする
+        self.ime = Ime::Enabled;
+        16
+    }
+    // bytes: 3 cycles: [16, 12]
+    pub(super) fn jp_c_a16_0xda(&mut self) -> u8 {
+        trace!(target: "cpu", "JP C, a16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        if self.registers.f.c {
+            self.registers.pc = a16;
+            16
+        } else {
+            12
+        }
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_db_0xdb(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 3 cycles: [24, 12]
+    pub(super) fn call_c_a16_0xdc(&mut self) -> u8 {
+        trace!(target: "cpu", "CALL C, a16");
+        let lower: u16 = self.fetch().into();
+        let upper: u16 = self.fetch().into();
+        if self.registers.f.c {
+            self.push(self.registers.pc);
+            self.registers.pc = upper << 8 | lower;
+            24
+        } else {
+            12
+        }
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_dd_0xdd(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_18h_0xdf(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 18H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0018;
+        16
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_e3_0xe3(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_e4_0xe4(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_20h_0xe7(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 20H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0020;
+        16
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn jp_hl_0xe9(&mut self) -> u8 {
+        trace!(target: "cpu", "JP (HL)");
+        self.registers.pc = self.registers.hl();
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_eb_0xeb(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_ec_0xec(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_ed_0xed(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_28h_0xef(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 28H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0028;
+        16
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn di_0xf3(&mut self) -> u8 {
+        trace!(target: "cpu", "DI");
+        // EI の直後なら有効になる前に取り消す
+        self.ime = Ime::Disabled;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_f4_0xf4(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_30h_0xf7(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 30H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0030;
+        16
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ei_0xfb(&mut self) -> u8 {
+        trace!(target: "cpu", "EI");
+        // 次の命令を実行し終えてから有効になる。EI が続いても遅れは延びない
+        if self.ime == Ime::Disabled {
+            self.ime = Ime::Pending;
+        }
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_fc_0xfc(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn illegal_fd_0xfd(&mut self) -> u8 {
+        // tick で弾くので呼ばれない
+        unreachable!()
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn rst_38h_0xff(&mut self) -> u8 {
+        trace!(target: "cpu", "RST 38H");
+        self.push(self.registers.pc);
+        self.registers.pc = 0x0000 + 0x0038;
+        16
+    }
+}
@@ -0,0 +1,655 @@
+use log::trace;
+
+use super::CPU;
+use crate::arithmetic::add_sp_e8;
+
+// ロード命令 (LD, LDH, PUSH, POP)
+impl CPU {
+    // bytes: 3 cycles: [12]
+    pub(super) fn ld_bc_d16_0x01(&mut self) -> u8 {
+        trace!(target: "cpu", "LD BC, d16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let d16 = h << 8 | l;
+        self.registers.set_bc(d16);
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_bc_a_0x02(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (BC), A");
+        self.write(self.registers.bc(), self.registers.a);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_b_d8_0x06(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, d8");
+        let d8 = self.fetch();
+        self.registers.b = d8;
+        8
+    }
+    // bytes: 3 cycles: [20]
+    pub(super) fn ld_a16_sp_0x08(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (a16), SP");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        self.write(a16, (self.registers.sp & 0x00FF) as u8);
+        self.write(a16.wrapping_add(1), (self.registers.sp >> 8) as u8);
+        20
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_a_bc_0x0a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (BC)");
+        self.registers.a = self.read(self.registers.bc());
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_c_d8_0x0e(&mut self) -> u8 {
+        trace!(target: "cpu", "ld C, d8");
+        let d8 = self.fetch();
+        self.registers.c = d8;
+        8
+    }
+    // bytes: 3 cycles: [12]
+    pub(super) fn ld_de_d16_0x11(&mut self) -> u8 {
+        trace!(target: "cpu", "ld DE, d16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let d16 = h << 8 | l;
+        self.registers.set_de(d16);
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_de_a_0x12(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (DE), A");
+        self.write(self.registers.de(), self.registers.a);
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_d_d8_0x16(&mut self) -> u8 {
+        trace!(target: "cpu", "ld D, d8");
+        let d8 = self.fetch();
+        self.registers.d = d8;
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_a_de_0x1a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (DE)");
+        self.registers.a = self.read(self.registers.de());
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_e_d8_0x1e(&mut self) -> u8 {
+        trace!(target: "cpu", "ld E, d8");
+        let d8 = self.fetch();
+        self.registers.e = d8;
+        8
+    }
+    // bytes: 3 cycles: [12]
+    pub(super) fn ld_hl_d16_0x21(&mut self) -> u8 {
+        trace!(target: "cpu", "ld HL, d16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let d16 = h << 8 | l;
+        self.registers.set_hl(d16);
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_a_0x22(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL+), A");
+        self.write(self.registers.hl(), self.registers.a);
+        self.registers.set_hl(self.registers.hl().wrapping_add(1));
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_h_d8_0x26(&mut self) -> u8 {
+        trace!(target: "cpu", "ld H, d8");
+        let d8 = self.fetch();
+        self.registers.h = d8;
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_a_hl_0x2a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (HL+)");
+        self.registers.a = self.read(self.registers.hl());
+        self.registers.set_hl(self.registers.hl().wrapping_add(1));
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_l_d8_0x2e(&mut self) -> u8 {
+        trace!(target: "cpu", "ld L, d8");
+        let d8 = self.fetch();
+        self.registers.l = d8;
+        8
+    }
+    // bytes: 3 cycles: [12]
+    pub(super) fn ld_sp_d16_0x31(&mut self) -> u8 {
+        trace!(target: "cpu", "ld SP, d16");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let d16 = h << 8 | l;
+        self.registers.sp = d16;
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_a_0x32(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL-), A");
+        self.write(self.registers.hl(), self.registers.a);
+        self.registers.set_hl(self.registers.hl().wrapping_sub(1));
+        8
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn ld_hl_d8_0x36(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), n");
+        let d8 = self.fetch();
+        self.write(self.registers.hl(), d8);
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_a_hl_0x3a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (HL-)");
+        self.registers.a = self.read(self.registers.hl());
+        self.registers.set_hl(self.registers.hl().wrapping_sub(1));
+        8
+    }
+    // bytes: 2 cycles: [8]
+    pub(super) fn ld_a_d8_0x3e(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, d8");
+        let d8 = self.fetch();
+        self.registers.a = d8;
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_b_0x40(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, B");
+        self.registers.b = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_c_0x41(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, C");
+        self.registers.b = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_d_0x42(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, D");
+        self.registers.b = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_e_0x43(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, E");
+        self.registers.b = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_h_0x44(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, H");
+        self.registers.b = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_l_0x45(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, L");
+        self.registers.b = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_b_hl_0x46(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, (HL)");
+        self.registers.b = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_b_a_0x47(&mut self) -> u8 {
+        trace!(target: "cpu", "LD B, A");
+        self.registers.b = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_b_0x48(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, B");
+        self.registers.c = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_c_0x49(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, C");
+        self.registers.c = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_d_0x4a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, D");
+        self.registers.c = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_e_0x4b(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, E");
+        self.registers.c = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_h_0x4c(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, H");
+        self.registers.c = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_l_0x4d(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, B");
+        self.registers.c = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_c_hl_0x4e(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, (HL)");
+        self.registers.c = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_c_a_0x4f(&mut self) -> u8 {
+        trace!(target: "cpu", "LD C, A");
+        self.registers.c = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_b_0x50(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, B");
+        self.registers.d = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_c_0x51(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, C");
+        self.registers.d = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_d_0x52(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, D");
+        self.registers.d = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_e_0x53(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, E");
+        self.registers.d = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_h_0x54(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, H");
+        self.registers.d = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_l_0x55(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, L");
+        self.registers.d = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_d_hl_0x56(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, (HL)");
+        self.registers.d = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_d_a_0x57(&mut self) -> u8 {
+        trace!(target: "cpu", "LD D, A");
+        self.registers.d = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_b_0x58(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, B");
+        self.registers.e = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_c_0x59(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, C");
+        self.registers.e = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_d_0x5a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, D");
+        self.registers.e = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_e_0x5b(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, E");
+        self.registers.e = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_h_0x5c(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, H");
+        self.registers.e = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_l_0x5d(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, L");
+        self.registers.e = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_e_hl_0x5e(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, (HL)");
+        self.registers.e = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_e_a_0x5f(&mut self) -> u8 {
+        trace!(target: "cpu", "LD E, A");
+        self.registers.e = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_b_0x60(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, B");
+        self.registers.h = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_c_0x61(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, C");
+        self.registers.h = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_d_0x62(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, D");
+        self.registers.h = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_e_0x63(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, E");
+        self.registers.h = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_h_0x64(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, H");
+        self.registers.h = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_l_0x65(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, L");
+        self.registers.h = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_h_hl_0x66(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, (HL)");
+        self.registers.h = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_h_a_0x67(&mut self) -> u8 {
+        trace!(target: "cpu", "LD H, A");
+        self.registers.h = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_b_0x68(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, B");
+        self.registers.l = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_c_0x69(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, C");
+        self.registers.l = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_d_0x6a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, D");
+        self.registers.l = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_e_0x6b(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, E");
+        self.registers.l = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_h_0x6c(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, H");
+        self.registers.l = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_l_0x6d(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, L");
+        self.registers.l = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_l_hl_0x6e(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, (HL)");
+        self.registers.l = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_l_a_0x6f(&mut self) -> u8 {
+        trace!(target: "cpu", "LD L, A");
+        self.registers.l = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_b_0x70(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), B");
+        self.write(self.registers.hl(), self.registers.b);
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_c_0x71(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), C");
+        self.write(self.registers.hl(), self.registers.c);
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_d_0x72(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), D");
+        self.write(self.registers.hl(), self.registers.d);
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_e_0x73(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), E");
+        self.write(self.registers.hl(), self.registers.e);
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_h_0x74(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), H");
+        self.write(self.registers.hl(), self.registers.h);
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_l_0x75(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), L");
+        self.write(self.registers.hl(), self.registers.l);
+        8
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_hl_a_0x77(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (HL), A");
+        self.write(self.registers.hl(), self.registers.a);
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_b_0x78(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, B");
+        self.registers.a = self.registers.b;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_c_0x79(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, C");
+        self.registers.a = self.registers.c;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_d_0x7a(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, D");
+        self.registers.a = self.registers.d;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_e_0x7b(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, E");
+        self.registers.a = self.registers.e;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_h_0x7c(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, H");
+        self.registers.a = self.registers.h;
+        4
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_l_0x7d(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, L");
+        self.registers.a = self.registers.l;
+        4
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_a_hl_0x7e(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (HL)");
+        self.registers.a = self.read(self.registers.hl());
+        8
+    }
+    // bytes: 1 cycles: [4]
+    pub(super) fn ld_a_a_0x7f(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, A");
+        self.registers.a = self.registers.a;
+        4
+    }
+    // bytes: 1 cycles: [12]
+    pub(super) fn pop_bc_0xc1(&mut self) -> u8 {
+        trace!(target: "cpu", "POP BC");
+        let v = self.pop();
+        self.registers.set_bc(v);
+        12
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn push_bc_0xc5(&mut self) -> u8 {
+        trace!(target: "cpu", "PUSH BC");
+        self.push(self.registers.bc());
+        16
+    }
+    // bytes: 1 cycles: [12]
+    pub(super) fn pop_de_0xd1(&mut self) -> u8 {
+        trace!(target: "cpu", "POP DE");
+        let v = self.pop();
+        self.registers.set_de(v);
+        12
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn push_de_0xd5(&mut self) -> u8 {
+        trace!(target: "cpu", "PUSH DE");
+        self.push(self.registers.de());
+        16
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn ldh_a8_a_0xe0(&mut self) -> u8 {
+        trace!(target: "cpu", "LDH (a8), A");
+        let a8: u16 = self.fetch().into();
+        self.write(0xFF00 + a8, self.registers.a);
+        12
+    }
+    // bytes: 1 cycles: [12]
+    pub(super) fn pop_hl_0xe1(&mut self) -> u8 {
+        trace!(target: "cpu", "POP HL");
+        let v = self.pop();
+        self.registers.set_hl(v);
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_c_a_0xe2(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (C), A");
+        self.write(0xFF00 + self.registers.c as u16, self.registers.a);
+        8
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn push_hl_0xe5(&mut self) -> u8 {
+        trace!(target: "cpu", "PUSH HL");
+        self.push(self.registers.hl());
+        16
+    }
+    // bytes: 3 cycles: [16]
+    pub(super) fn ld_a16_a_0xea(&mut self) -> u8 {
+        trace!(target: "cpu", "LD (a16), A");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        self.write(a16, self.registers.a);
+        16
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn ldh_a_a8_0xf0(&mut self) -> u8 {
+        trace!(target: "cpu", "LDH A, (a8)");
+        let a8: u16 = self.fetch().into();
+        self.registers.a = self.read(0xFF00 + a8);
+        12
+    }
+    // bytes: 1 cycles: [12]
+    pub(super) fn pop_af_0xf1(&mut self) -> u8 {
+        trace!(target: "cpu", "POP AF");
+        // 下位4ビットは捨てられる
+        let v = self.pop();
+        self.registers.set_af(v);
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_a_c_0xf2(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (C)");
+        self.registers.a = self.read(0xFF00 + self.registers.c as u16);
+        8
+    }
+    // bytes: 1 cycles: [16]
+    pub(super) fn push_af_0xf5(&mut self) -> u8 {
+        trace!(target: "cpu", "PUSH AF");
+        self.push(self.registers.af());
+        16
+    }
+    // bytes: 2 cycles: [12]
+    pub(super) fn ld_hl_sp_r8_0xf8(&mut self) -> u8 {
+        trace!(target: "cpu", "LD HL, SP+r8");
+        let r8 = self.fetch();
+        let (hl, h, c) = add_sp_e8(self.registers.sp, r8);
+        self.registers.set_hl(hl);
+        self.registers.f.z = false;
+        self.registers.f.n = false;
+        self.registers.f.h = h;
+        self.registers.f.c = c;
+        12
+    }
+    // bytes: 1 cycles: [8]
+    pub(super) fn ld_sp_hl_0xf9(&mut self) -> u8 {
+        trace!(target: "cpu", "LD SP, HL");
+        self.registers.sp = self.registers.hl();
+        8
+    }
+    // bytes: 3 cycles: [16]
+    pub(super) fn ld_a_a16_0xfa(&mut self) -> u8 {
+        trace!(target: "cpu", "LD A, (a16)");
+        let l: u16 = self.fetch().into();
+        let h: u16 = self.fetch().into();
+        let a16 = h << 8 | l;
+        self.registers.a = self.read(a16);
+        16
+    }
+}
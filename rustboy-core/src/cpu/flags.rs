@@ -0,0 +1,54 @@
+#[derive(Default, Copy, Clone, Debug)]
+pub struct Flags {
+    // 外部クレートを使う場合は bitflags が良さそう
+    // 7bit: Zero flag
+    // 結果が0のときにセットする
+    // jump で利用される
+    pub z: bool,
+    // 6bit: Subtraction flag(BCD)
+    // BCD Number は 0x00 - 0x99 のこと
+    // 1つ前の命令が減算のときにセットする
+    // DAA命令でのみ利用される
+    pub n: bool,
+    // 5bit: Half Carry flag(BCD)
+    // 下位4bitに対する Carry flag
+    // DAA命令でのみ利用される
+    pub h: bool,
+    // 4bit: Carry flag
+    // 8bit加算で0xFF、16bit加算で0xFFFFを超えたとき、減算で0未満のときにセットされる
+    // jump といくつかの命令(ADC, SBC, RL, RLAなど)で利用される
+    pub c: bool,
+}
+
+// F の下位4ビットは常に 0 で、書き込んでも無視される
+impl From<u8> for Flags {
+    fn from(v: u8) -> Self {
+        Self {
+            z: (v & 0b_1000_0000) == 0b_1000_0000,
+            n: (v & 0b_0100_0000) == 0b_0100_0000,
+            h: (v & 0b_0010_0000) == 0b_0010_0000,
+            c: (v & 0b_0001_0000) == 0b_0001_0000,
+        }
+    }
+}
+
+impl From<Flags> for u8 {
+    fn from(flags: Flags) -> Self {
+        let mut v;
+        if flags.z {
+            v = 0b_1000_0000;
+        } else {
+            v = 0b_0000_0000;
+        }
+        if flags.n {
+            v |= 0b_0100_0000;
+        }
+        if flags.h {
+            v |= 0b_0010_0000;
+        }
+        if flags.c {
+            v |= 0b_0001_0000;
+        }
+        v
+    }
+}
@@ -0,0 +1,1304 @@
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::rc::Weak;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::default::Default;
+use core::fmt::Debug;
+use core::fmt::{Display, Formatter};
+
+use log::debug;
+
+use crate::interruption::Peripheral;
+use crate::io::Bus;
+use crate::metrics::InterruptCounts;
+use crate::quirks::HardwareQuirks;
+use crate::Address;
+pub use decode::{cb_mnemonic, mnemonic};
+use registers::Registers;
+
+mod decode;
+mod exec_alu;
+mod exec_bit;
+mod exec_ctrl;
+mod exec_ld;
+mod flags;
+mod registers;
+#[cfg(test)]
+pub(crate) mod testing;
+
+// 未定義の命令
+const ILLEGAL_OPCODES: [u8; 11] = [
+    0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD,
+];
+// エラー時に表示する直前の命令数
+const BACKTRACE_SIZE: usize = 8;
+// 実行履歴として保持する命令数
+const HISTORY_SIZE: usize = 256;
+
+/// 実行した命令の記録。レジスタは実行前の値
+#[derive(Debug, Clone, Copy)]
+pub struct History {
+    pc: Address,
+    opcode: u16,
+    registers: Registers,
+}
+
+impl Display for History {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let r = &self.registers;
+        write!(
+            f,
+            "0x{:04X}: 0x{:04X}  AF={:02X}{:02X} BC={:04X} DE={:04X} HL={:04X} SP={:04X}",
+            self.pc,
+            self.opcode,
+            r.a,
+            u8::from(r.f),
+            r.bc(),
+            r.de(),
+            r.hl(),
+            r.sp
+        )
+    }
+}
+
+/// 命令の実行に失敗した
+#[derive(Debug, Clone, PartialEq)]
+pub enum CpuError {
+    IllegalOpcode {
+        pc: Address,
+        opcode: u16,
+        // 直前に実行した命令の PC と opcode(古い順)
+        backtrace: Vec<(Address, u16)>,
+    },
+}
+
+impl Display for CpuError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CpuError::IllegalOpcode {
+                pc,
+                opcode,
+                backtrace,
+            } => {
+                writeln!(f, "illegal opcode 0x{:02X} at 0x{:04X}", opcode, pc)?;
+                write!(f, "backtrace:")?;
+                for (pc, opcode) in backtrace {
+                    write!(f, "\n  0x{:04X}: 0x{:04X}", pc, opcode)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl core::error::Error for CpuError {}
+
+/// Interrupt Master Enable Flag の状態
+/// EI は次の命令を実行し終えてから割り込みを有効にする
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Ime {
+    Disabled,
+    // EI の直後。次の命令の後に Enabled になる。その前に DI すれば取り消される
+    Pending,
+    Enabled,
+}
+
+/// 外から参照するレジスタと状態
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpuState {
+    pub a: u8,
+    pub f: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+    pub sp: Address,
+    pub pc: Address,
+    pub ime: bool,
+    pub halted: bool,
+    pub stopped: bool,
+}
+
+#[derive(Debug)]
+pub struct CPU {
+    registers: Registers,
+    bus: Weak<RefCell<dyn Bus>>,
+    // halt() 呼び出し後は割り込みが来るまで停止する
+    is_halted: bool,
+    // STOP 実行後は選択中のボタンが押されるまで停止する
+    is_stopped: bool,
+    // STOP を NOP として扱う。STOP の後にボタンを待たない壊れた ROM 用
+    stop_as_nop: bool,
+
+    // Interrupt Master Enable Flag
+    ime: Ime,
+
+    // 直前に実行した命令(古い順)
+    history: VecDeque<History>,
+    // 実行中の命令で進めた T-Cycle 数
+    ticked: Cell<u8>,
+    // 実行した命令数と処理した割り込みの回数
+    instructions: u64,
+    interrupts: InterruptCounts,
+    // 再現する実機の不具合
+    quirks: HardwareQuirks,
+    // 0xFE00 - 0xFE9F スプライト属性テーブル (Object Attribute Memory)
+    // oam: Box<dyn IO>,
+
+    // 以下はIOレジスタ
+    // 0xFF00 コントロールパッド情報/機種タイプ
+    // p1: u8,
+    // 0xFF04 - 0xFF07
+    // timer: Box<dyn IO>,
+
+    // 0xFF10 - FF3F
+    // sound: Box<dyn IO>,
+
+    // 0xFF46 DMA(Direct Memory Access)
+    // dma: u8,
+    // 0xFF40 - 0xFF4B
+    // lcd: Box<dyn IO>,
+
+    // 0xFF80 - 0xFFFE はSPが指すスタック領域
+    // stack: [u8; 0xFFFE - 0xFF80 + 1],
+}
+
+impl CPU {
+    pub const CLOCK: u32 = 4194304;
+
+    pub fn new(bus: Weak<RefCell<dyn Bus>>) -> Self {
+        Self {
+            bus,
+            registers: Registers::new(),
+            is_halted: false,
+            is_stopped: false,
+            stop_as_nop: false,
+            ime: Ime::Disabled,
+            history: VecDeque::with_capacity(HISTORY_SIZE),
+            ticked: Cell::new(0),
+            instructions: 0,
+            interrupts: InterruptCounts::default(),
+            quirks: HardwareQuirks::default(),
+        }
+    }
+    /// 1命令(または割り込み処理)を実行する
+    /// メモリアクセスのたびに周辺機器を 1 M-Cycle 進めるので、戻り値の cycle は進めた後の値
+    pub fn tick(&mut self) -> Result<(u16, u8), CpuError> {
+        self.ticked.set(0);
+        if self.is_stopped {
+            // 割り込みでは目覚めない。ボタンが押されれば次の tick から再開する
+            if self.bus.upgrade().unwrap().borrow().stopped(4) {
+                self.is_stopped = false;
+            }
+            return Ok((0x0000, 4));
+        }
+        // 割り込み処理
+        self.handle_interruption();
+        let dispatched = self.ticked.get();
+        // EI の直後の命令を実行し終えたら有効にする
+        let pending = self.ime == Ime::Pending;
+        if self.is_halted {
+            // NOP
+            self.idle(dispatched + 4);
+            return Ok((0x0000 as u16, self.ticked.get()));
+        }
+        // fetch
+        let pc = self.registers.pc;
+        let registers = self.registers;
+        let opcode = self.fetch();
+        if ILLEGAL_OPCODES.contains(&opcode) {
+            // 調べられるように PC は命令の位置に戻しておく
+            self.registers.pc = pc;
+            return Err(CpuError::IllegalOpcode {
+                pc,
+                opcode: opcode as u16,
+                backtrace: self
+                    .history
+                    .iter()
+                    .skip(self.history.len().saturating_sub(BACKTRACE_SIZE))
+                    .map(|h| (h.pc, h.opcode))
+                    .collect(),
+            });
+        }
+        // decode & execute
+        let (opcode, cycle) = if opcode == 0xCB {
+            // CBの場合は16bit命令になる
+            let opcode = self.fetch();
+            (0xCB00 | opcode as u16, self.execute_cb(opcode))
+        } else {
+            (opcode as u16, self.execute(opcode))
+        };
+        if pending && self.ime == Ime::Pending {
+            self.ime = Ime::Enabled;
+        }
+        // メモリアクセスを伴わない内部処理の分を進める
+        self.idle(dispatched + cycle);
+        let cycle = self.ticked.get();
+        self.instructions += 1;
+        if self.history.len() == HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.history.push_back(History {
+            pc,
+            opcode,
+            registers,
+        });
+        Ok((opcode, cycle))
+    }
+    pub fn state(&self) -> CpuState {
+        let r = &self.registers;
+        CpuState {
+            a: r.a,
+            f: u8::from(r.f),
+            b: r.b,
+            c: r.c,
+            d: r.d,
+            e: r.e,
+            h: r.h,
+            l: r.l,
+            sp: r.sp,
+            pc: r.pc,
+            ime: self.ime == Ime::Enabled,
+            halted: self.is_halted,
+            stopped: self.is_stopped,
+        }
+    }
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        let r = &self.registers;
+        state.extend([r.a, u8::from(r.f), r.b, r.c, r.d, r.e, r.h, r.l]);
+        state.extend(r.sp.to_le_bytes());
+        state.extend(r.pc.to_le_bytes());
+        state.extend([self.is_halted as u8, self.is_stopped as u8, self.ime as u8]);
+    }
+    pub fn dump_registers(&self) -> String {
+        format!(
+            "{:?}, ime: {:?}, is_halted: {}, is_stopped: {}",
+            &self.registers, self.ime, self.is_halted, self.is_stopped
+        )
+    }
+    pub fn set_quirks(&mut self, quirks: HardwareQuirks) {
+        self.quirks = quirks;
+    }
+    pub fn set_stop_as_nop(&mut self, stop_as_nop: bool) {
+        self.stop_as_nop = stop_as_nop;
+    }
+    /// 実行した命令数。リセットしても戻さない
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+    pub fn interrupts(&self) -> InterruptCounts {
+        self.interrupts
+    }
+    /// 直近 n 命令の実行履歴(古い順)
+    #[cfg(feature = "std")]
+    pub fn history(&self, n: usize) -> String {
+        self.history
+            .iter()
+            .skip(self.history.len().saturating_sub(n))
+            .map(|h| h.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    // PCの位置から1バイト読み取り、PCをインクリメントする
+    fn fetch(&mut self) -> u8 {
+        let byte = self.read(self.registers.pc);
+        // opcode実行前にインクリメントしておく
+        self.registers.pc = self.registers.pc.wrapping_add(1);
+        byte
+    }
+    // 割り込み処理
+    fn handle_interruption(&mut self) {
+        if let Some(interrupt) = self.check_interrupt() {
+            self.is_halted = false;
+            // imeフラグは割り込みフラグより優先される
+            if self.ime != Ime::Enabled {
+                return;
+            };
+            // 2 M-Cycle 待ってからスタックにリターンアドレスを保存する
+            self.cycle(4);
+            self.cycle(4);
+            let pc = self.registers.pc;
+            self.registers.sp = self.registers.sp.wrapping_sub(1);
+            self.write(self.registers.sp, (pc >> 8) as u8);
+            // 飛び先は上位バイトを積んだ後に決まる。SP が 0x0000 なら IE を書き換えている
+            // 要求された割り込みが無くなっていたら 0x0000 に飛ぶ
+            let interrupt = if self.quirks.ie_push {
+                self.check_interrupt()
+            } else {
+                Some(interrupt)
+            };
+            self.registers.sp = self.registers.sp.wrapping_sub(1);
+            self.write(self.registers.sp, (pc & 0x00FF) as u8);
+            // 割り込み処理中は他の割り込みを禁止。通常は RETI で戻される
+            self.ime = Ime::Disabled;
+            // フラグをリセットしてPCを更新
+            self.registers.pc = match interrupt {
+                Some(interrupt) => {
+                    self.reset_interrupt(&interrupt);
+                    self.interrupts.count(&interrupt);
+                    interrupt.jump_address()
+                }
+                None => 0x0000,
+            };
+            // 割り込み処理には 5 M-Cycle かかる
+            self.idle(20);
+        }
+    }
+    fn check_interrupt(&self) -> Option<Peripheral> {
+        Peripheral::highest(self.read_untimed(0xFF0F), self.read_untimed(0xFFFF))
+    }
+    // 処理する割り込みのビットだけを IF から下ろす。同時に要求された他の割り込みは残る
+    fn reset_interrupt(&mut self, p: &Peripheral) {
+        let data = self.read_untimed(0xFF0F) & 0b_0001_1111 & !p.bit();
+        self.write_untimed(0xFF0F, data);
+    }
+    // クロックを進めずにメモリを読み込む。割り当ては MotherBoard の MemoryMap で決まる
+    fn read_untimed(&self, address: Address) -> u8 {
+        self.bus.upgrade().unwrap().borrow().read(address)
+    }
+    // クロックを進めずにメモリに書き込む
+    fn write_untimed(&mut self, address: Address, data: u8) {
+        self.bus.upgrade().unwrap().borrow().write(address, data);
+    }
+    // メモリアクセスは 1 M-Cycle かかるので、アクセスの前に周辺機器を進める
+    fn read(&self, address: Address) -> u8 {
+        self.cycle(4);
+        self.read_untimed(address)
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        self.cycle(4);
+        self.write_untimed(address, data);
+    }
+    // 周辺機器を進める
+    fn cycle(&self, cycles: u8) {
+        self.bus.upgrade().unwrap().borrow().tick(cycles);
+        self.ticked.set(self.ticked.get() + cycles);
+    }
+    // 命令全体の T-Cycle 数のうち、まだ進めていない分を進める
+    fn idle(&self, cycles: u8) {
+        if cycles > self.ticked.get() {
+            self.cycle(cycles - self.ticked.get());
+        }
+    }
+    // 16bit の INC/DEC の内部処理でアドレスバスに値を出す
+    fn corrupt_oam(&self, address: Address) {
+        self.bus.upgrade().unwrap().borrow().corrupt_oam(address);
+    }
+    // SP をデクリメントしてから上位、下位の順にスタックに積む
+    // PUSH、CALL、RST のいずれも書き込みの前に 1 M-Cycle の内部処理がある
+    fn push(&mut self, v: u16) {
+        self.cycle(4);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        self.write(self.registers.sp, (v >> 8) as u8);
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        self.write(self.registers.sp, (v & 0x00FF) as u8);
+    }
+    // 下位、上位の順にスタックから取り出す
+    fn pop(&mut self) -> u16 {
+        let lower = self.read(self.registers.sp) as u16;
+        self.registers.sp = self.registers.sp.wrapping_add(1);
+        let upper = self.read(self.registers.sp) as u16;
+        self.registers.sp = self.registers.sp.wrapping_add(1);
+        upper << 8 | lower
+    }
+    pub fn reset(&mut self) {
+        debug!(target: "cpu", "Reset");
+        self.write_untimed(0xFF00, 0xCF); // P1
+        self.write_untimed(0xFF01, 0x00); // SB
+        self.write_untimed(0xFF02, 0x7E); // SC
+        self.write_untimed(0xFF04, 0x18); // DIV
+        self.write_untimed(0xFF05, 0x00); // TIMA
+        self.write_untimed(0xFF06, 0x00); // TMA
+        self.write_untimed(0xFF07, 0x00); // TAC
+        self.write_untimed(0xFF0F, 0xE1); // IF
+        self.write_untimed(0xFF10, 0x80); // NR10
+        self.write_untimed(0xFF11, 0xBF); // NR11
+        self.write_untimed(0xFF12, 0xF3); // NR12
+        self.write_untimed(0xFF13, 0xFF); // NR13
+        self.write_untimed(0xFF14, 0xBF); // NR14
+        self.write_untimed(0xFF16, 0x3F); // NR21
+        self.write_untimed(0xFF17, 0x00); // NR22
+        self.write_untimed(0xFF18, 0xFF); // NR23
+        self.write_untimed(0xFF19, 0xBF); // NR24
+        self.write_untimed(0xFF1A, 0x7F); // NR30
+        self.write_untimed(0xFF1B, 0xFF); // NR31
+        self.write_untimed(0xFF1C, 0x9F); // NR32
+        self.write_untimed(0xFF1D, 0xFF); // NR33
+        self.write_untimed(0xFF1E, 0xBF); // NR34
+        self.write_untimed(0xFF20, 0xFF); // NR41
+        self.write_untimed(0xFF21, 0x00); // NR42
+        self.write_untimed(0xFF22, 0x00); // NR43
+        self.write_untimed(0xFF23, 0xBF); // NR44
+        self.write_untimed(0xFF24, 0x77); // NR50
+        self.write_untimed(0xFF25, 0xF3); // NR51
+        self.write_untimed(0xFF26, 0xF1); // NR52
+        self.write_untimed(0xFF40, 0x91); // LCDC
+        self.write_untimed(0xFF41, 0x81); // STAT
+        self.write_untimed(0xFF42, 0x00); // SCY
+        self.write_untimed(0xFF43, 0x00); // SCX
+        self.bus.upgrade().unwrap().borrow().poke(0xFF44, 0x91); // LY は読み込み専用なので poke
+        self.write_untimed(0xFF45, 0x00); // LYC
+        self.write_untimed(0xFF47, 0xFC); // BGP
+        self.write_untimed(0xFF48, 0xFF); // OBP0
+        self.write_untimed(0xFF49, 0xFF); // OBP1
+        self.write_untimed(0xFF4A, 0x00); // WY
+        self.write_untimed(0xFF4B, 0x00); // WX
+        self.write_untimed(0xFFFF, 0x00); // IE
+
+        self.registers.reset();
+        self.history.clear();
+        self.is_stopped = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::flags::Flags;
+    use super::testing::{assemble, flags, run, setup, RamBus};
+    use super::*;
+    use crate::quirks::Accuracy;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_tick_per_memory_access() {
+        // LD (HL),d8 は 3 M-Cycle で、各アクセスの前に 1 M-Cycle 進める
+        let (bus, mut cpu) = setup(&[0x36, 0x12]);
+        let (opcode, cycle) = cpu.tick().unwrap();
+        assert_eq!((opcode, cycle), (0x36, 12));
+        let log = bus.borrow().log.borrow().clone();
+        // 割り込みの確認はクロックを進めないので除く
+        assert_eq!(
+            log.into_iter()
+                .filter(|l| l != "RFF0F" && l != "RFFFF")
+                .collect::<Vec<String>>(),
+            vec!["T4", "R0100", "T4", "R0101", "T4", "W014D"]
+        );
+        assert_eq!(bus.borrow().memory.borrow()[0x014D], 0x12);
+
+        // NOP はメモリアクセスが命令の読み込みだけ
+        bus.borrow().log.borrow_mut().clear();
+        assert_eq!(cpu.tick().unwrap(), (0x00, 4));
+    }
+
+    // 割り込みの確認と解除はクロックを進めないので除いたアクセスの記録
+    fn timed_log(bus: &Rc<RefCell<RamBus>>) -> Vec<String> {
+        let log = bus.borrow().log.take();
+        log.into_iter()
+            .filter(|l| !["RFF0F", "RFFFF", "WFF0F"].contains(&l.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_internal_cycle_positions() {
+        // PUSH BC は書き込みの前に 1 M-Cycle 待つ
+        let (bus, mut cpu) = setup(&[0xC5]);
+        assert_eq!(cpu.tick().unwrap(), (0xC5, 16));
+        assert_eq!(
+            timed_log(&bus),
+            vec!["T4", "R0100", "T4", "T4", "WFFFD", "T4", "WFFFC"]
+        );
+
+        // RET Z は条件の判定の後に読み込み、最後に PC を設定する
+        let (bus, mut cpu) = setup(&[0xC8]);
+        assert!(cpu.registers.f.z);
+        cpu.registers.sp = 0xC000;
+        assert_eq!(cpu.tick().unwrap(), (0xC8, 20));
+        assert_eq!(
+            timed_log(&bus),
+            vec!["T4", "R0100", "T4", "T4", "RC000", "T4", "RC001", "T4"]
+        );
+
+        // 割り込みは 2 M-Cycle 待ってから書き込み、続けて 0x0040 の NOP を実行する
+        let (bus, mut cpu) = setup(&[0x00]);
+        bus.borrow().memory.borrow_mut()[0xFF0F] = 0b_0000_0001;
+        bus.borrow().memory.borrow_mut()[0xFFFF] = 0b_0000_0001;
+        cpu.ime = Ime::Enabled;
+        assert_eq!(cpu.tick().unwrap(), (0x00, 24));
+        assert_eq!(
+            timed_log(&bus),
+            vec!["T4", "T4", "T4", "WFFFD", "T4", "WFFFC", "T4", "T4", "R0040"]
+        );
+    }
+
+    // VBlank 割り込みを要求して許可した状態で program を始める
+    fn setup_interrupt(program: &[u8]) -> (Rc<RefCell<RamBus>>, CPU) {
+        let (bus, mut cpu) = setup(program);
+        bus.borrow().memory.borrow_mut()[0xFF0F] = 0b_0000_0001;
+        bus.borrow().memory.borrow_mut()[0xFFFF] = 0b_0000_0001;
+        cpu.registers.sp = 0xD000;
+        (bus, cpu)
+    }
+
+    #[test]
+    fn test_ei_delay() {
+        // EI; NOP; NOP
+        let (bus, mut cpu) = setup_interrupt(&[0xFB, 0x00, 0x00]);
+        assert_eq!(cpu.tick().unwrap().0, 0xFB);
+        assert!(!cpu.state().ime);
+        // EI の次の命令は割り込まれずに実行される
+        assert_eq!(cpu.tick().unwrap().0, 0x00);
+        assert_eq!(cpu.registers.pc, 0x0102);
+        assert!(cpu.state().ime);
+        // 0x0040 の NOP まで実行する
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0041);
+        assert_eq!(bus.borrow().memory.borrow()[0xCFFE..0xD000], [0x02, 0x01]);
+        assert!(!cpu.state().ime);
+
+        // EI; EI; NOP は2つ目の EI の後に有効になる
+        let (_bus, mut cpu) = setup_interrupt(&[0xFB, 0xFB, 0x00]);
+        cpu.tick().unwrap();
+        cpu.tick().unwrap();
+        assert!(cpu.state().ime);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0041);
+    }
+
+    #[test]
+    fn test_ei_di() {
+        // EI; DI; NOP は有効になる前に取り消される
+        let (_bus, mut cpu) = setup_interrupt(&[0xFB, 0xF3, 0x00]);
+        for _ in 0..3 {
+            cpu.tick().unwrap();
+        }
+        assert_eq!(cpu.registers.pc, 0x0103);
+        assert!(!cpu.state().ime);
+    }
+
+    #[test]
+    fn test_ei_halt() {
+        // EI; HALT は HALT の後に有効になるので、停止せずにすぐ割り込みを処理する
+        let (_bus, mut cpu) = setup_interrupt(&[0xFB, 0x76]);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.tick().unwrap().0, 0x76);
+        assert!(cpu.state().ime);
+        cpu.tick().unwrap();
+        assert!(!cpu.state().halted);
+        assert_eq!(cpu.registers.pc, 0x0041);
+    }
+
+    #[test]
+    fn test_ei_reti() {
+        // EI; RETI は RETI ですぐに有効になり、戻り先の命令より前に割り込む
+        let (bus, mut cpu) = setup_interrupt(&[0xFB, 0xD9]);
+        bus.borrow().memory.borrow_mut()[0xD000..0xD002].copy_from_slice(&[0x00, 0x02]);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.tick().unwrap().0, 0xD9);
+        assert_eq!(cpu.registers.pc, 0x0200);
+        assert!(cpu.state().ime);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0041);
+        assert_eq!(bus.borrow().memory.borrow()[0xD000..0xD002], [0x00, 0x02]);
+    }
+
+    #[test]
+    fn test_ie_push() {
+        // SP = 0x0000 だと PC の上位バイト 0x02 が IE に書き込まれる
+        for (accuracy, requested, jump) in [
+            (Accuracy::Fast, 0b_0000_0001, 0x0040),
+            (Accuracy::Accurate, 0b_0000_0001, 0x0000),
+            (Accuracy::Accurate, 0b_0000_0011, 0x0048),
+        ] {
+            let (bus, mut cpu) = setup(&[]);
+            cpu.set_quirks(HardwareQuirks::from(accuracy));
+            bus.borrow().memory.borrow_mut()[0xFF0F] = requested;
+            bus.borrow().memory.borrow_mut()[0xFFFF] = 0b_0000_0011;
+            cpu.ime = Ime::Enabled;
+            cpu.registers.pc = 0x0200;
+            cpu.registers.sp = 0x0000;
+            cpu.tick().unwrap();
+            // 割り込み先の NOP を実行した後
+            assert_eq!(cpu.registers.pc, jump + 1);
+            assert_eq!(bus.borrow().memory.borrow()[0xFFFF], 0x02);
+            assert_eq!(bus.borrow().memory.borrow()[0xFFFE], 0x00);
+        }
+    }
+
+    #[test]
+    fn test_interrupt_priority() {
+        // 各割り込みの飛び先は RETI。同時に要求された割り込みを優先度の高い順に1つずつ処理する
+        let (bus, mut cpu) = setup(&[0x00]);
+        {
+            let bus = bus.borrow();
+            let mut memory = bus.memory.borrow_mut();
+            for address in [0x0040, 0x0048, 0x0050, 0x0058, 0x0060] {
+                memory[address] = 0xD9;
+            }
+            // Joypad, Timer, LCD STAT, VBlank を要求し、VBlank は許可しない
+            memory[0xFF0F] = 0b_0001_0111;
+            memory[0xFFFF] = 0b_0001_1110;
+        }
+        cpu.ime = Ime::Enabled;
+        cpu.registers.sp = 0xD000;
+        for remaining in [0b_0001_0101, 0b_0001_0001, 0b_0000_0001] {
+            cpu.tick().unwrap();
+            assert_eq!(cpu.registers.pc, 0x0100);
+            // 処理した割り込みのビットだけが下りる
+            assert_eq!(bus.borrow().memory.borrow()[0xFF0F], remaining);
+        }
+        let counts = cpu.interrupts();
+        assert_eq!(
+            (counts.v_blank, counts.lcd_stat, counts.timer, counts.joypad),
+            (0, 1, 1, 1)
+        );
+        // 許可されていない VBlank は処理しない
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0101);
+        assert_eq!(Peripheral::highest(0b_0001_1111, 0b_0000_0000), None);
+        assert_eq!(
+            Peripheral::highest(0b_0001_1000, 0b_0001_1111),
+            Some(Peripheral::Serial)
+        );
+    }
+
+    #[test]
+    fn test_pc_wrap() {
+        // 0xFFFE: LD A, d8 (d8 は IE の 0xFFFF)
+        // 0x0000: JR -4
+        let (bus, mut cpu) = setup(&[]);
+        {
+            let bus = bus.borrow();
+            let mut memory = bus.memory.borrow_mut();
+            memory[0xFFFE] = 0x3E;
+            memory[0xFFFF] = 0x00;
+            memory[0x0000] = 0x18;
+            memory[0x0001] = 0xFC;
+        }
+        cpu.registers.pc = 0xFFFE;
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.a, 0x00);
+        assert_eq!(cpu.registers.pc, 0x0000);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0xFFFE);
+
+        // 0xFFFF: RST 00H は PC を 0x0000 として積む
+        cpu.registers.pc = 0xFFFF;
+        bus.borrow().memory.borrow_mut()[0xFFFF] = 0xC7;
+        cpu.registers.sp = 0xD000;
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0000);
+        assert_eq!(bus.borrow().memory.borrow()[0xCFFE..0xD000], [0x00, 0x00]);
+
+        // SP も 0xFFFF から 0x0000 に戻る
+        cpu.registers.sp = 0xFFFF;
+        bus.borrow().memory.borrow_mut()[0x0000] = 0xC1;
+        cpu.registers.pc = 0x0000;
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.bc(), 0xC1C7);
+        assert_eq!(cpu.registers.sp, 0x0001);
+    }
+
+    #[test]
+    fn test_jr() {
+        // 分岐したときだけ PC を書き換える内部の 1 M-Cycle が入る
+        let (bus, mut cpu) = setup(&assemble("JR NZ, 5"));
+        bus.borrow().log.take();
+        cpu.registers.f.z = false;
+        assert_eq!(cpu.tick().unwrap(), (0x20, 12));
+        assert_eq!(cpu.registers.pc, 0x0107);
+        assert_eq!(timed_log(&bus), vec!["T4", "R0100", "T4", "R0101", "T4"]);
+        let (bus, mut cpu) = setup(&assemble("JR NZ, 5"));
+        bus.borrow().log.take();
+        cpu.registers.f.z = true;
+        assert_eq!(cpu.tick().unwrap(), (0x20, 8));
+        assert_eq!(cpu.registers.pc, 0x0102);
+        assert_eq!(timed_log(&bus), vec!["T4", "R0100", "T4", "R0101"]);
+
+        // 飛び先は JR の次の命令からの差。(JR を置く場所, r8, 飛び先)
+        let cases: [(u16, i8, u16); 11] = [
+            (0x0100, 127, 0x0181),
+            (0x0100, -128, 0x0082),
+            (0x0100, -2, 0x0100),
+            // ROM のバンク 0 と切り替えられるバンクの境界をまたぐ
+            (0x3FFE, 0, 0x4000),
+            (0x3FF0, 127, 0x4071),
+            (0x4000, -3, 0x3FFF),
+            (0x4010, -128, 0x3F92),
+            // アドレス空間の端で折り返す
+            (0xFFF0, 127, 0x0071),
+            (0xFFFE, 0, 0x0000),
+            (0x0000, -128, 0xFF82),
+            (0x0010, -19, 0xFFFF),
+        ];
+        for (at, r8, expected) in cases {
+            let (bus, mut cpu) = setup(&[]);
+            {
+                let bus = bus.borrow();
+                let mut memory = bus.memory.borrow_mut();
+                memory[at as usize] = 0x18;
+                memory[at.wrapping_add(1) as usize] = r8 as u8;
+            }
+            cpu.registers.pc = at;
+            assert_eq!(cpu.tick().unwrap(), (0x18, 12));
+            assert_eq!(cpu.registers.pc, expected, "JR {} at {:04X}", r8, at);
+        }
+    }
+
+    #[test]
+    fn test_flags_round_trip() {
+        for v in 0..=0xFF {
+            assert_eq!(u8::from(Flags::from(v)), v & 0xF0);
+        }
+        let flags = Flags {
+            z: true,
+            n: false,
+            h: true,
+            c: false,
+        };
+        assert_eq!(u8::from(flags), 0b_1010_0000);
+    }
+
+    #[test]
+    fn test_push_pop_af() {
+        // LD BC, 0x12FF; PUSH BC; POP AF; PUSH AF; POP DE
+        let (bus, mut cpu) = setup(&[0x01, 0xFF, 0x12, 0xC5, 0xF1, 0xF5, 0xD1]);
+        for _ in 0..3 {
+            cpu.tick().unwrap();
+        }
+        // F の下位4ビットは 0 になる
+        assert_eq!(cpu.registers.a, 0x12);
+        assert_eq!(u8::from(cpu.registers.f), 0xF0);
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+        assert_eq!((cpu.state().a, cpu.state().f), (0x12, 0xF0));
+        cpu.tick().unwrap();
+        assert_eq!(bus.borrow().memory.borrow()[0xFFFC..0xFFFE], [0xF0, 0x12]);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.de(), 0x12F0);
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+    }
+
+    #[test]
+    fn test_call_ret() {
+        // LD SP, 0xD000; CALL 0x0200; NOP
+        // 0x0200: RST 08H
+        // 0x0008: RET
+        // 0x0201: RET
+        let (bus, mut cpu) = setup(&[0x31, 0x00, 0xD0, 0xCD, 0x00, 0x02, 0x00]);
+        {
+            let bus = bus.borrow();
+            let mut memory = bus.memory.borrow_mut();
+            memory[0x0200..0x0202].copy_from_slice(&[0xCF, 0xC9]);
+            memory[0x0008] = 0xC9;
+        }
+        cpu.tick().unwrap();
+        bus.borrow().log.borrow_mut().clear();
+        assert_eq!(cpu.tick().unwrap(), (0xCD, 24));
+        assert_eq!(cpu.registers.pc, 0x0200);
+        assert_eq!(cpu.registers.sp, 0xCFFE);
+        // SP をデクリメントしてから上位バイト、下位バイトの順に書き込む
+        let writes: Vec<String> = bus
+            .borrow()
+            .log
+            .borrow()
+            .iter()
+            .filter(|l| l.starts_with('W'))
+            .cloned()
+            .collect();
+        assert_eq!(writes, vec!["WCFFF", "WCFFE"]);
+        assert_eq!(bus.borrow().memory.borrow()[0xCFFE..0xD000], [0x06, 0x01]);
+
+        assert_eq!(cpu.tick().unwrap(), (0xCF, 16));
+        assert_eq!(cpu.registers.pc, 0x0008);
+        assert_eq!(cpu.registers.sp, 0xCFFC);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0201);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.pc, 0x0106);
+        assert_eq!(cpu.registers.sp, 0xD000);
+    }
+
+    #[test]
+    fn test_push_pop() {
+        // LD BC, 0x1234; LD DE, 0x5678; LD HL, 0x9ABC
+        // PUSH BC; PUSH DE; PUSH HL; POP BC; POP DE; POP HL
+        let (_bus, mut cpu) = setup(&[
+            0x01, 0x34, 0x12, 0x11, 0x78, 0x56, 0x21, 0xBC, 0x9A, 0xC5, 0xD5, 0xE5, 0xC1, 0xD1,
+            0xE1,
+        ]);
+        for _ in 0..6 {
+            cpu.tick().unwrap();
+        }
+        assert_eq!(cpu.registers.sp, 0xFFFE - 6);
+        for _ in 0..3 {
+            cpu.tick().unwrap();
+        }
+        assert_eq!(cpu.registers.bc(), 0x9ABC);
+        assert_eq!(cpu.registers.de(), 0x5678);
+        assert_eq!(cpu.registers.hl(), 0x1234);
+        assert_eq!(cpu.registers.sp, 0xFFFE);
+    }
+
+    #[test]
+    fn test_stack_wrapping() {
+        // LD SP, 0x0001; PUSH BC; POP DE
+        let (bus, mut cpu) = setup(&[0x31, 0x01, 0x00, 0xC5, 0xD1]);
+        for _ in 0..2 {
+            cpu.tick().unwrap();
+        }
+        assert_eq!(cpu.registers.sp, 0xFFFF);
+        assert_eq!(bus.borrow().memory.borrow()[0x0000], 0x00);
+        assert_eq!(bus.borrow().memory.borrow()[0xFFFF], 0x13);
+        cpu.tick().unwrap();
+        assert_eq!(cpu.registers.de(), 0x0013);
+        assert_eq!(cpu.registers.sp, 0x0001);
+    }
+
+    // LD 命令を命令表(https://gbdev.io/pandocs/CPU_Instruction_Set.html)と照らし合わせる
+    mod ld {
+        use super::*;
+
+        // B, C, D, E, H, L, (HL), A の順。オペコードの下位3ビットと同じ並び
+        fn operands(cpu: &CPU, bus: &Rc<RefCell<RamBus>>, hl: Address) -> [u8; 8] {
+            let r = &cpu.registers;
+            let m = bus.borrow().memory.borrow()[hl as usize];
+            [r.b, r.c, r.d, r.e, r.h, r.l, m, r.a]
+        }
+
+        #[test]
+        fn test_ld_r_r() {
+            for opcode in (0x40..=0x7F).filter(|op| *op != 0x76) {
+                let (bus, mut cpu) = setup(&[opcode]);
+                cpu.registers.b = 0x11;
+                cpu.registers.c = 0x22;
+                cpu.registers.d = 0x33;
+                cpu.registers.e = 0x44;
+                cpu.registers.set_hl(0xC055);
+                cpu.registers.a = 0x66;
+                bus.borrow().memory.borrow_mut()[0xC055] = 0x77;
+                let before = operands(&cpu, &bus, 0xC055);
+                let (dst, src) = (((opcode >> 3) & 0x07) as usize, (opcode & 0x07) as usize);
+                let cycles = if dst == 6 || src == 6 { 8 } else { 4 };
+                assert_eq!(
+                    cpu.tick().unwrap(),
+                    (opcode as u16, cycles),
+                    "{:02X}",
+                    opcode
+                );
+                let mut expected = before;
+                expected[dst] = before[src];
+                assert_eq!(operands(&cpu, &bus, 0xC055), expected, "{:02X}", opcode);
+            }
+        }
+
+        #[test]
+        fn test_ld_r_d8() {
+            for opcode in [0x06, 0x0E, 0x16, 0x1E, 0x26, 0x2E, 0x36, 0x3E] {
+                let (bus, mut cpu) = setup(&[opcode, 0x5A]);
+                cpu.registers.set_hl(0xC000);
+                // 即値をアドレスとして読むと 0xEE になる
+                bus.borrow().memory.borrow_mut()[0x005A] = 0xEE;
+                let before = operands(&cpu, &bus, 0xC000);
+                let dst = ((opcode >> 3) & 0x07) as usize;
+                let cycles = if dst == 6 { 12 } else { 8 };
+                assert_eq!(
+                    cpu.tick().unwrap(),
+                    (opcode as u16, cycles),
+                    "{:02X}",
+                    opcode
+                );
+                assert_eq!(cpu.registers.pc, 0x0102);
+                let mut expected = before;
+                expected[dst] = 0x5A;
+                assert_eq!(operands(&cpu, &bus, 0xC000), expected, "{:02X}", opcode);
+            }
+        }
+
+        #[test]
+        fn test_ld_hl_increment() {
+            // LD (HL+), A; LD (HL-), A
+            for (opcode, next) in [(0x22, 0xC001), (0x32, 0xBFFF)] {
+                let (bus, mut cpu) = setup(&[opcode]);
+                cpu.registers.a = 0x42;
+                cpu.registers.set_hl(0xC000);
+                bus.borrow().memory.borrow_mut()[next as usize] = 0x99;
+                assert_eq!(cpu.tick().unwrap(), (opcode as u16, 8));
+                assert_eq!(bus.borrow().memory.borrow()[0xC000], 0x42);
+                // 書き込んだ後に A を読み直さない
+                assert_eq!(cpu.registers.a, 0x42);
+                assert_eq!(cpu.registers.hl(), next);
+            }
+            // LD A, (HL+); LD A, (HL-)
+            for (opcode, next) in [(0x2A, 0xC001), (0x3A, 0xBFFF)] {
+                let (bus, mut cpu) = setup(&[opcode]);
+                cpu.registers.set_hl(0xC000);
+                bus.borrow().memory.borrow_mut()[0xC000] = 0x77;
+                bus.borrow().memory.borrow_mut()[next as usize] = 0x99;
+                assert_eq!(cpu.tick().unwrap(), (opcode as u16, 8));
+                assert_eq!(cpu.registers.a, 0x77);
+                assert_eq!(cpu.registers.hl(), next);
+            }
+        }
+
+        #[test]
+        fn test_ld_indirect() {
+            // (命令, A を書き込むアドレス, T-Cycle 数)
+            let stores: [(&[u8], Address, u8); 5] = [
+                (&[0x02], 0xC012, 8),              // LD (BC), A
+                (&[0x12], 0xC034, 8),              // LD (DE), A
+                (&[0xE0, 0x80], 0xFF80, 12),       // LDH (a8), A
+                (&[0xE2], 0xFF12, 8),              // LD (C), A
+                (&[0xEA, 0x00, 0xD0], 0xD000, 16), // LD (a16), A
+            ];
+            for (program, address, cycles) in stores {
+                let (bus, mut cpu) = setup(program);
+                cpu.registers.set_bc(0xC012);
+                cpu.registers.set_de(0xC034);
+                cpu.registers.a = 0x42;
+                assert_eq!(cpu.tick().unwrap(), (program[0] as u16, cycles));
+                assert_eq!(bus.borrow().memory.borrow()[address as usize], 0x42);
+                assert_eq!(cpu.registers.pc, 0x0100 + program.len() as u16);
+            }
+            // (命令, A に読み込むアドレス, T-Cycle 数)
+            let loads: [(&[u8], Address, u8); 5] = [
+                (&[0x0A], 0xC012, 8),              // LD A, (BC)
+                (&[0x1A], 0xC034, 8),              // LD A, (DE)
+                (&[0xF0, 0x80], 0xFF80, 12),       // LDH A, (a8)
+                (&[0xF2], 0xFF12, 8),              // LD A, (C)
+                (&[0xFA, 0x00, 0xD0], 0xD000, 16), // LD A, (a16)
+            ];
+            for (program, address, cycles) in loads {
+                let (bus, mut cpu) = setup(program);
+                cpu.registers.set_bc(0xC012);
+                cpu.registers.set_de(0xC034);
+                bus.borrow().memory.borrow_mut()[address as usize] = 0x77;
+                assert_eq!(cpu.tick().unwrap(), (program[0] as u16, cycles));
+                assert_eq!(cpu.registers.a, 0x77);
+                assert_eq!(cpu.registers.pc, 0x0100 + program.len() as u16);
+            }
+        }
+
+        #[test]
+        fn test_ld_16bit() {
+            // LD BC, d16; LD DE, d16; LD HL, d16; LD SP, d16
+            let (_bus, mut cpu) = setup(&[
+                0x01, 0x34, 0x12, 0x11, 0x78, 0x56, 0x21, 0xBC, 0x9A, 0x31, 0xF0, 0xDE,
+            ]);
+            for _ in 0..4 {
+                assert_eq!(cpu.tick().unwrap().1, 12);
+            }
+            assert_eq!(cpu.registers.bc(), 0x1234);
+            assert_eq!(cpu.registers.de(), 0x5678);
+            assert_eq!(cpu.registers.hl(), 0x9ABC);
+            assert_eq!(cpu.registers.sp, 0xDEF0);
+
+            // LD (a16), SP は下位バイトから書き込む
+            let (bus, mut cpu) = setup(&[0x08, 0x00, 0xC0]);
+            cpu.registers.sp = 0xBEEF;
+            assert_eq!(cpu.tick().unwrap(), (0x08, 20));
+            assert_eq!(bus.borrow().memory.borrow()[0xC000..0xC002], [0xEF, 0xBE]);
+            assert_eq!(
+                timed_log(&bus),
+                ["T4", "R0100", "T4", "R0101", "T4", "R0102", "T4", "WC000", "T4", "WC001"]
+            );
+            // a16 が 0xFFFF なら上位バイトは 0x0000 に書き込む
+            let (bus, mut cpu) = setup(&[0x08, 0xFF, 0xFF]);
+            cpu.registers.sp = 0x1234;
+            cpu.tick().unwrap();
+            let memory = &bus.borrow().memory;
+            assert_eq!(memory.borrow()[0xFFFF], 0x34);
+            assert_eq!(memory.borrow()[0x0000], 0x12);
+
+            // LD SP, HL
+            let (_bus, mut cpu) = setup(&[0xF9]);
+            cpu.registers.set_hl(0xC123);
+            assert_eq!(cpu.tick().unwrap(), (0xF9, 8));
+            assert_eq!(cpu.registers.sp, 0xC123);
+        }
+
+        #[test]
+        fn test_ld_hl_sp_r8() {
+            // (SP, r8, HL, H, C)。キャリーは下位バイト同士の加算で決まる
+            let cases = [
+                (0xFFF8, 0x08, 0x0000, true, true),
+                (0x0000, 0xFF, 0xFFFF, false, false),
+                (0x00FF, 0x01, 0x0100, true, true),
+                (0x1000, 0x80, 0x0F80, false, false),
+            ];
+            for (sp, r8, hl, h, c) in cases {
+                let (_bus, mut cpu) = setup(&[0xF8, r8]);
+                cpu.registers.sp = sp;
+                assert_eq!(cpu.tick().unwrap(), (0xF8, 12));
+                assert_eq!(cpu.registers.hl(), hl);
+                let f = cpu.registers.f;
+                assert_eq!((f.z, f.n, f.h, f.c), (false, false, h, c), "{:04X}", sp);
+            }
+        }
+    }
+
+    // 16bit の INC, DEC, ADD HL, rr
+    mod alu16 {
+        use super::*;
+
+        // BC, DE, HL, SP の順。オペコードの bit 4 - 5 と同じ並び
+        fn set_pair(cpu: &mut CPU, index: u8, v: u16) {
+            match index {
+                0 => cpu.registers.set_bc(v),
+                1 => cpu.registers.set_de(v),
+                2 => cpu.registers.set_hl(v),
+                _ => cpu.registers.sp = v,
+            }
+        }
+
+        fn pair(cpu: &CPU, index: u8) -> u16 {
+            match index {
+                0 => cpu.registers.bc(),
+                1 => cpu.registers.de(),
+                2 => cpu.registers.hl(),
+                _ => cpu.registers.sp,
+            }
+        }
+
+        fn set_flags(cpu: &mut CPU, value: bool) {
+            let f = &mut cpu.registers.f;
+            (f.z, f.n, f.h, f.c) = (value, value, value, value);
+        }
+
+        #[test]
+        fn test_inc_dec_16bit() {
+            // (値, INC の結果, DEC の結果)。フラグは変わらない
+            let cases = [
+                (0x0000, 0x0001, 0xFFFF),
+                (0x00FF, 0x0100, 0x00FE),
+                (0x0FFF, 0x1000, 0x0FFE),
+                (0xFFFF, 0x0000, 0xFFFE),
+            ];
+            for index in 0..4 {
+                let inc = 0x03 | index << 4;
+                let dec = 0x0B | index << 4;
+                for (v, incremented, decremented) in cases {
+                    for flags in [false, true] {
+                        for (opcode, expected) in [(inc, incremented), (dec, decremented)] {
+                            let (_bus, mut cpu) = setup(&[opcode]);
+                            set_pair(&mut cpu, index, v);
+                            set_flags(&mut cpu, flags);
+                            assert_eq!(cpu.tick().unwrap(), (opcode as u16, 8));
+                            assert_eq!(pair(&cpu, index), expected, "{:02X} {:04X}", opcode, v);
+                            let f = cpu.registers.f;
+                            assert_eq!((f.z, f.n, f.h, f.c), (flags, flags, flags, flags));
+                        }
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_add_hl_rr() {
+            // (HL, rr, 結果, H, C)。H は bit 11、C は bit 15 からの桁上がり
+            let cases = [
+                (0x0000, 0x0000, 0x0000, false, false),
+                (0x0FFF, 0x0001, 0x1000, true, false),
+                (0x00FF, 0x0001, 0x0100, false, false),
+                (0x0F00, 0x0100, 0x1000, true, false),
+                (0x8000, 0x8000, 0x0000, false, true),
+                (0xFFFF, 0x0001, 0x0000, true, true),
+                (0x7000, 0x1000, 0x8000, false, false),
+            ];
+            for index in [0, 1, 3] {
+                let opcode = 0x09 | index << 4;
+                for (hl, rr, result, h, c) in cases {
+                    for z in [false, true] {
+                        let (_bus, mut cpu) = setup(&[opcode]);
+                        cpu.registers.set_hl(hl);
+                        set_pair(&mut cpu, index, rr);
+                        set_flags(&mut cpu, true);
+                        cpu.registers.f.z = z;
+                        assert_eq!(cpu.tick().unwrap(), (opcode as u16, 8));
+                        assert_eq!(cpu.registers.hl(), result, "{:02X} {:04X}", opcode, hl);
+                        // Z は変わらず、N は 0
+                        let f = cpu.registers.f;
+                        assert_eq!((f.z, f.n, f.h, f.c), (z, false, h, c), "{:02X}", opcode);
+                    }
+                }
+            }
+            // ADD HL, HL は HL を 2 倍する
+            let doubles = [(0x0800, 0x1000, true, false), (0x8001, 0x0002, false, true)];
+            for (hl, result, h, c) in doubles {
+                let (_bus, mut cpu) = setup(&[0x29]);
+                cpu.registers.set_hl(hl);
+                assert_eq!(cpu.tick().unwrap(), (0x29, 8));
+                assert_eq!(cpu.registers.hl(), result);
+                let f = cpu.registers.f;
+                assert_eq!((f.n, f.h, f.c), (false, h, c), "{:04X}", hl);
+            }
+        }
+    }
+
+    // CP は引き算のフラグだけを立てて A を変えない
+    mod compare {
+        use super::*;
+
+        // B, C, D, E, H, L, (HL), A の順。オペコードの下位 3bit と同じ並び
+        fn set_operand(cpu: &mut CPU, bus: &Rc<RefCell<RamBus>>, index: u8, v: u8) {
+            match index {
+                0 => cpu.registers.b = v,
+                1 => cpu.registers.c = v,
+                2 => cpu.registers.d = v,
+                3 => cpu.registers.e = v,
+                4 => cpu.registers.h = v,
+                5 => cpu.registers.l = v,
+                6 => {
+                    cpu.registers.set_hl(0xC000);
+                    bus.borrow().memory.borrow_mut()[0xC000] = v;
+                }
+                _ => cpu.registers.a = v,
+            }
+        }
+
+        #[test]
+        fn test_cp() {
+            // (A, 比べる値, Z, H, C)
+            let cases = [
+                (0x3C, 0x2F, false, true, false),
+                (0x3C, 0x3C, true, false, false),
+                (0x3C, 0x40, false, false, true),
+                (0x00, 0x01, false, true, true),
+                (0x00, 0x00, true, false, false),
+                (0x80, 0x7F, false, true, false),
+            ];
+            for (a, rhs, z, h, c) in cases {
+                for index in 0..7 {
+                    let opcode = 0xB8 | index;
+                    let (bus, mut cpu) = setup(&[opcode]);
+                    cpu.registers.a = a;
+                    set_operand(&mut cpu, &bus, index, rhs);
+                    cpu.tick().unwrap();
+                    assert_eq!(cpu.registers.a, a, "{:02X}", opcode);
+                    let f = cpu.registers.f;
+                    assert_eq!((f.z, f.n, f.h, f.c), (z, true, h, c), "{:02X}", opcode);
+                }
+                // CP d8
+                let (_bus, mut cpu) = setup(&[0xFE, rhs]);
+                cpu.registers.a = a;
+                assert_eq!(cpu.tick().unwrap(), (0xFE, 8));
+                assert_eq!(cpu.registers.a, a);
+                let f = cpu.registers.f;
+                assert_eq!((f.z, f.n, f.h, f.c), (z, true, h, c));
+            }
+            // CP A は常に等しい
+            let (_bus, mut cpu) = setup(&assemble("LD A, 0x5A; CP A"));
+            run(&mut cpu, 2);
+            assert_eq!(cpu.registers.a, 0x5A);
+            assert_eq!(flags(&cpu), "ZN--");
+        }
+
+        #[test]
+        fn test_cp_loop() {
+            // CP で A が壊れると 0x10 に届かずに回り続ける
+            let (_bus, mut cpu) = setup(&assemble("INC A; CP 0x10; JR NZ, -5; LD B, A"));
+            cpu.registers.a = 0x00;
+            run(&mut cpu, 0x10 * 3);
+            assert_eq!(cpu.registers.pc, 0x0105);
+            assert_eq!(cpu.tick().unwrap(), (0x47, 4));
+            assert_eq!(cpu.registers.b, 0x10);
+        }
+    }
+
+    // 命令ごとの T-Cycle 数を命令表(https://gbdev.io/pandocs/CPU_Instruction_Set.html)と照らし合わせる
+    mod timing {
+        use super::*;
+
+        // M-Cycle 数。条件付きの命令は分岐したときの値で、未定義の命令と CB は 0
+        // STOP は次のバイトも読み、速度の切り替えで止まるので除く
+        #[rustfmt::skip]
+        const CYCLES: [u8; 256] = [
+            1, 3, 2, 2, 1, 1, 2, 1, 5, 2, 2, 2, 1, 1, 2, 1,
+            0, 3, 2, 2, 1, 1, 2, 1, 3, 2, 2, 2, 1, 1, 2, 1,
+            3, 3, 2, 2, 1, 1, 2, 1, 3, 2, 2, 2, 1, 1, 2, 1,
+            3, 3, 2, 2, 3, 3, 3, 1, 3, 2, 2, 2, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            2, 2, 2, 2, 2, 2, 1, 2, 1, 1, 1, 1, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            1, 1, 1, 1, 1, 1, 2, 1, 1, 1, 1, 1, 1, 1, 2, 1,
+            5, 3, 4, 4, 6, 4, 2, 4, 5, 4, 4, 0, 6, 6, 2, 4,
+            5, 3, 4, 0, 6, 4, 2, 4, 5, 4, 4, 0, 6, 0, 2, 4,
+            3, 3, 2, 0, 0, 4, 2, 4, 4, 1, 4, 0, 0, 0, 2, 4,
+            3, 3, 2, 1, 0, 4, 2, 4, 3, 2, 4, 1, 0, 0, 2, 4,
+        ];
+
+        // 分岐しなかったときの M-Cycle 数と、分岐する条件のフラグ(Z か C)と値
+        fn branch(opcode: u8) -> Option<(u8, char, bool)> {
+            let condition = match (opcode >> 3) & 0x03 {
+                0 => ('Z', false),
+                1 => ('Z', true),
+                2 => ('C', false),
+                _ => ('C', true),
+            };
+            let cycles = match opcode {
+                0x20 | 0x28 | 0x30 | 0x38 => 2,
+                0xC0 | 0xC8 | 0xD0 | 0xD8 => 2,
+                0xC2 | 0xCA | 0xD2 | 0xDA => 3,
+                0xC4 | 0xCC | 0xD4 | 0xDC => 3,
+                _ => return None,
+            };
+            Some((cycles, condition.0, condition.1))
+        }
+
+        // オペランドは全て 0 なので、読み書きは 0x0000 付近と 0xC000 のスタックに収まる
+        fn run(program: &[u8], z: bool, c: bool) -> (u16, u8) {
+            let (_bus, mut cpu) = setup(program);
+            cpu.registers.sp = 0xC000;
+            cpu.registers.f.z = z;
+            cpu.registers.f.c = c;
+            cpu.tick().unwrap()
+        }
+
+        #[test]
+        fn test_cycles() {
+            for opcode in (0..=0xFF).filter(|op| CYCLES[*op as usize] != 0) {
+                let expected = CYCLES[opcode as usize] * 4;
+                match branch(opcode) {
+                    Some((not_taken, flag, value)) => {
+                        let (z, c) = if flag == 'Z' {
+                            (value, false)
+                        } else {
+                            (false, value)
+                        };
+                        let taken = run(&[opcode, 0, 0], z, c);
+                        assert_eq!(taken, (opcode as u16, expected), "{:02X} taken", opcode);
+                        let (z, c) = if flag == 'Z' { (!z, c) } else { (z, !c) };
+                        let skipped = run(&[opcode, 0, 0], z, c);
+                        assert_eq!(
+                            skipped,
+                            (opcode as u16, not_taken * 4),
+                            "{:02X} not taken",
+                            opcode
+                        );
+                    }
+                    None => {
+                        let actual = run(&[opcode, 0, 0], false, false);
+                        assert_eq!(actual, (opcode as u16, expected), "{:02X}", opcode);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_cb_cycles() {
+            // レジスタは 2、(HL) は読んで書くので 4、BIT b, (HL) は書かないので 3 M-Cycle
+            for opcode in 0..=0xFF_u8 {
+                let expected = match (opcode & 0x07, opcode >> 6) {
+                    (6, 1) => 12,
+                    (6, _) => 16,
+                    _ => 8,
+                };
+                let actual = run(&[0xCB, opcode], false, false);
+                assert_eq!(
+                    actual,
+                    (0xCB00 | opcode as u16, expected),
+                    "CB {:02X}",
+                    opcode
+                );
+            }
+        }
+    }
+}
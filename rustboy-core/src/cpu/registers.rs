@@ -0,0 +1,83 @@
+use super::flags::Flags;
+use crate::Address;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Registers {
+    // https://w.atwiki.jp/gbspec/pages/34.html
+    // 8ビットレジスタは AF、BC、DE、HL の組み合わせで
+    // 16 ビットのペアレジスタとしても扱う
+    // アキュームレータ
+    pub a: u8,
+    // フラグ
+    pub f: Flags,
+    // 汎用
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+    // スタックポインタ
+    pub sp: Address,
+    // プログラムカウンタ
+    pub pc: Address,
+}
+
+impl Registers {
+    pub fn new() -> Self {
+        Self {
+            a: 0x01,
+            f: Flags::from(0xB0),
+            b: 0x00,
+            c: 0x13,
+            d: 0x00,
+            e: 0xD8,
+            h: 0x01,
+            l: 0x4D,
+            sp: 0xFFFF,
+            pc: 0x0100,
+        }
+    }
+    pub fn reset(&mut self) {
+        // https://gbdev.io/pandocs/Power_Up_Sequence.html
+        self.a = 0x01;
+        self.f = Flags::from(0xB0);
+        self.b = 0x00;
+        self.c = 0x13;
+        self.d = 0x00;
+        self.e = 0xD8;
+        self.h = 0x01;
+        self.l = 0x4D;
+        self.pc = 0x0100;
+        self.sp = 0xFFFE;
+    }
+    pub fn af(&self) -> u16 {
+        ((self.a as u16) << 8) | u8::from(self.f) as u16
+    }
+    pub fn set_af(&mut self, v: u16) {
+        self.a = ((v & 0xFF00) >> 8) as u8;
+        // 下位4ビットは捨てられる
+        self.f = Flags::from((v & 0x00F0) as u8);
+    }
+    pub fn bc(&self) -> u16 {
+        ((self.b as u16) << 8) | self.c as u16
+    }
+    pub fn set_bc(&mut self, v: u16) {
+        self.b = ((v & 0xFF00) >> 8) as u8;
+        self.c = (v & 0x00FF) as u8;
+    }
+    pub fn de(&self) -> u16 {
+        ((self.d as u16) << 8) | self.e as u16
+    }
+    pub fn set_de(&mut self, v: u16) {
+        self.d = ((v & 0xFF00) >> 8) as u8;
+        self.e = (v & 0x00FF) as u8;
+    }
+    pub fn hl(&self) -> u16 {
+        ((self.h as u16) << 8) | self.l as u16
+    }
+    pub fn set_hl(&mut self, v: u16) {
+        self.h = ((v & 0xFF00) >> 8) as u8;
+        self.l = (v & 0x00FF) as u8;
+    }
+}
@@ -0,0 +1,188 @@
+// CPU の単体テストで使うバスと、プログラムの組み立て、状態の確認の補助
+// 命令のテストを足すときは setup と assemble で始めると、MotherBoard を組み立てずに済む
+use alloc::format;
+use alloc::rc::{Rc, Weak};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use super::decode::{cb_mnemonic, mnemonic};
+use super::CPU;
+use crate::io::Bus;
+use crate::Address;
+
+// オペランドの書き方。長いものから順に探す
+const OPERANDS: [&str; 5] = ["d16", "a16", "d8", "a8", "r8"];
+
+/// 64KB を平らに並べただけのバス。アクセスと tick の順番を記録する
+/// IO レジスタも普通のメモリとして読み書きする
+pub(crate) struct RamBus {
+    pub(crate) memory: RefCell<Vec<u8>>,
+    pub(crate) log: RefCell<Vec<String>>,
+}
+
+impl RamBus {
+    pub(crate) fn new() -> Self {
+        Self {
+            memory: RefCell::new(vec![0; 0x10000]),
+            log: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// address から data を書き込む。記録には残さない
+    pub(crate) fn load(&self, address: Address, data: &[u8]) {
+        let start = address as usize;
+        self.memory.borrow_mut()[start..start + data.len()].copy_from_slice(data);
+    }
+}
+
+impl Bus for RamBus {
+    fn read(&self, address: Address) -> u8 {
+        self.log.borrow_mut().push(format!("R{:04X}", address));
+        self.memory.borrow()[address as usize]
+    }
+    fn write(&self, address: Address, data: u8) {
+        self.log.borrow_mut().push(format!("W{:04X}", address));
+        self.memory.borrow_mut()[address as usize] = data;
+    }
+    fn tick(&self, cycles: u8) {
+        self.log.borrow_mut().push(format!("T{}", cycles));
+    }
+}
+
+/// 0x0100 から program を配置して、リセット直後のレジスタで始める
+pub(crate) fn setup(program: &[u8]) -> (Rc<RefCell<RamBus>>, CPU) {
+    let bus = Rc::new(RefCell::new(RamBus::new()));
+    bus.borrow().load(0x0100, program);
+    let weak: Weak<RefCell<dyn Bus>> = Rc::<RefCell<RamBus>>::downgrade(&bus);
+    let mut cpu = CPU::new(weak);
+    cpu.registers.reset();
+    (bus, cpu)
+}
+
+/// 1行に1命令、または ';' で区切ったアセンブリを機械語にする
+/// 書き方は逆アセンブラ(decode::mnemonic)の表と同じで、d8 などのオペランドに数値を書く
+/// 数値は 0x を付けると16進数。r8 は "JR NZ, -5" のように飛び先までの差を書く
+pub(crate) fn assemble(source: &str) -> Vec<u8> {
+    source
+        .split(['\n', ';'])
+        .filter(|line| !line.trim().is_empty())
+        .flat_map(|line| {
+            encode(line.trim()).unwrap_or_else(|| panic!("Cannot assemble: {}", line.trim()))
+        })
+        .collect()
+}
+
+// 命令表を順に探して、最初に一致した命令にする
+fn encode(instruction: &str) -> Option<Vec<u8>> {
+    let given = tokens(instruction);
+    for opcode in 0..=0xFF {
+        let (template, size) = match mnemonic(opcode) {
+            Some(m) => m,
+            None => continue,
+        };
+        let template = tokens(template);
+        if template.len() != given.len() {
+            continue;
+        }
+        let mut bytes = vec![opcode];
+        let matched = template
+            .iter()
+            .zip(&given)
+            .all(|(t, g)| match operand(t, g) {
+                Some(operand) => {
+                    bytes.extend(operand);
+                    true
+                }
+                None => t.eq_ignore_ascii_case(g),
+            });
+        if matched {
+            // STOP は2バイト目を 0x00 で埋める
+            bytes.resize(size as usize, 0x00);
+            return Some(bytes);
+        }
+    }
+    (0..=0xFF)
+        .find(|&opcode| tokens(&cb_mnemonic(opcode)) == given)
+        .map(|opcode| vec![0xCB, opcode])
+}
+
+fn tokens(instruction: &str) -> Vec<String> {
+    instruction
+        .split([' ', ','])
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_uppercase())
+        .collect()
+}
+
+// template の d8 などの部分に数値が書かれていればそのバイト列(リトルエンディアン)
+fn operand(template: &str, given: &str) -> Option<Vec<u8>> {
+    let kind = OPERANDS
+        .iter()
+        .find(|kind| template.contains(&kind.to_uppercase()))?;
+    let (prefix, suffix) = template.split_once(&kind.to_uppercase())?;
+    let value = given.strip_prefix(prefix)?.strip_suffix(suffix)?;
+    let value = match value.strip_prefix("0X") {
+        Some(hex) => i32::from_str_radix(hex, 16).ok()?,
+        None => value.parse().ok()?,
+    };
+    match *kind {
+        "d16" | "a16" => Some((value as u16).to_le_bytes().to_vec()),
+        // LDH の a8 は 0xFF44 のように書いても下位バイトを使う
+        _ => Some(vec![value as u8]),
+    }
+}
+
+/// フラグを "ZNHC" の順に並べた文字列。立っていないフラグは '-'
+pub(crate) fn flags(cpu: &CPU) -> String {
+    let f = cpu.registers.f;
+    [(f.z, 'Z'), (f.n, 'N'), (f.h, 'H'), (f.c, 'C')]
+        .iter()
+        .map(|&(set, c)| if set { c } else { '-' })
+        .collect()
+}
+
+/// n 命令実行する
+pub(crate) fn run(cpu: &mut CPU, n: usize) {
+    for _ in 0..n {
+        cpu.tick().unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assemble() {
+        assert_eq!(assemble("NOP"), [0x00]);
+        assert_eq!(assemble("ld a, 0x3C; CP 16"), [0x3E, 0x3C, 0xFE, 0x10]);
+        assert_eq!(assemble("JR NZ, -5"), [0x20, 0xFB]);
+        assert_eq!(assemble("LD HL, 0xC000"), [0x21, 0x00, 0xC0]);
+        assert_eq!(assemble("LD (0xC000), SP"), [0x08, 0x00, 0xC0]);
+        assert_eq!(assemble("LDH A, (0x44)"), [0xF0, 0x44]);
+        assert_eq!(assemble("LD HL, SP+-2"), [0xF8, 0xFE]);
+        assert_eq!(assemble("LD A, (HL+)\nJP HL"), [0x2A, 0xE9]);
+        assert_eq!(assemble("STOP"), [0x10, 0x00]);
+        assert_eq!(assemble("SWAP A; BIT 7, (HL)"), [0xCB, 0x37, 0xCB, 0x7E]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot assemble: LD A, Z")]
+    fn test_assemble_error() {
+        assemble("LD A, Z");
+    }
+
+    #[test]
+    fn test_setup() {
+        let (bus, mut cpu) = setup(&assemble("XOR A; SCF"));
+        run(&mut cpu, 2);
+        assert_eq!(flags(&cpu), "Z--C");
+        assert_eq!(cpu.state().pc, 0x0102);
+        // 割り込みの確認を除くと、tick してから命令を読み込む
+        let log = bus.borrow().log.take();
+        let timed: Vec<&String> = log.iter().filter(|l| !l.starts_with("RFF")).collect();
+        assert_eq!(timed[0..4], ["T4", "R0100", "T4", "R0101"]);
+    }
+}
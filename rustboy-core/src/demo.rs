@@ -0,0 +1,184 @@
+// --demo で動かす小さな自作 ROM
+// このリポジトリのために書いたもので、パブリックドメインとして扱う
+// 十字キーで背景をスクロールし、A ボタンで矩形波を鳴らす
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+// ROM のタイトル
+const TITLE: &[u8] = b"RUSTBOY DEMO";
+
+// タイルデータの配置先
+const TILES_ADDRESS: usize = 0x0200;
+
+// 0: 白、1: 枠付きのブロック、2: 市松模様
+const TILES: [u8; 48] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+    0xFF, 0xFF, 0xFF, 0x81, 0xFF, 0x81, 0xFF, 0x81, //
+    0xFF, 0x81, 0xFF, 0x81, 0xFF, 0x81, 0xFF, 0xFF, //
+    0x00, 0xAA, 0x00, 0x55, 0x00, 0xAA, 0x00, 0x55, //
+    0x00, 0xAA, 0x00, 0x55, 0x00, 0xAA, 0x00, 0x55, //
+];
+
+// 0x0150 から配置する
+const PROGRAM: [u8; 172] = [
+    // start:
+    0xF3, // DI
+    0x31, 0xFE, 0xFF, // LD SP, 0xFFFE
+    // wait_lcd_off:
+    0xF0, 0x44, // LDH A, (0x44)
+    0xFE, 0x90, // CP 144
+    0x20, 0xFA, // JR NZ, wait_lcd_off
+    0xAF, // XOR A
+    0xE0, 0x40, // LDH (0x40), A   LCD を止める
+    0x21, 0x00, 0x80, // LD HL, 0x8000
+    0x11, 0x00, 0x02, // LD DE, 0x0200   タイルデータ
+    0x0E, 0x30, // LD C, 48
+    // copy:
+    0x1A, // LD A, (DE)
+    0x22, // LD (HL+), A
+    0x13, // INC DE
+    0x0D, // DEC C
+    0x20, 0xFA, // JR NZ, copy
+    0x21, 0x00, 0x98, // LD HL, 0x9800
+    0x01, 0x00, 0x04, // LD BC, 0x0400
+    0x1E, 0x00, // LD E, 0
+    // fill:
+    0x7B, // LD A, E         タイル 0, 1, 2 を順に並べる
+    0x22, // LD (HL+), A
+    0x1C, // INC E
+    0x7B, // LD A, E
+    0xFE, 0x03, // CP 3
+    0x20, 0x02, // JR NZ, next
+    0x1E, 0x00, // LD E, 0
+    // next:
+    0x0B, // DEC BC
+    0x78, // LD A, B
+    0xB1, // OR C
+    0x20, 0xF1, // JR NZ, fill
+    0x3E, 0xE4, // LD A, 0xE4
+    0xE0, 0x47, // LDH (0x47), A   BGP
+    0x3E, 0x80, // LD A, 0x80
+    0xE0, 0x26, // LDH (0x26), A   NR52 電源
+    0x3E, 0x77, // LD A, 0x77
+    0xE0, 0x24, // LDH (0x24), A   NR50 音量
+    0x3E, 0xFF, // LD A, 0xFF
+    0xE0, 0x25, // LDH (0x25), A   NR51 全チャンネルを左右に出力
+    0x3E, 0x91, // LD A, 0x91
+    0xE0, 0x40, // LDH (0x40), A   LCD と BG を有効にする
+    // main:
+    0xF0, 0x44, // LDH A, (0x44)   1 フレームに 1 回だけ処理する
+    0xFE, 0x90, // CP 144
+    0x28, 0xFA, // JR Z, main
+    // wait_vblank:
+    0xF0, 0x44, // LDH A, (0x44)
+    0xFE, 0x90, // CP 144
+    0x20, 0xFA, // JR NZ, wait_vblank
+    0x3E, 0x20, // LD A, 0x20
+    0xE0, 0x00, // LDH (0x00), A   十字キーを選択
+    0xF0, 0x00, // LDH A, (0x00)
+    0xF0, 0x00, // LDH A, (0x00)
+    0x2F, // CPL
+    0xE6, 0x0F, // AND 0x0F
+    0x47, // LD B, A
+    0x3E, 0x10, // LD A, 0x10
+    0xE0, 0x00, // LDH (0x00), A   ボタンを選択
+    0xF0, 0x00, // LDH A, (0x00)
+    0xF0, 0x00, // LDH A, (0x00)
+    0x2F, // CPL
+    0xE6, 0x0F, // AND 0x0F
+    0x4F, // LD C, A
+    0x3E, 0x30, // LD A, 0x30
+    0xE0, 0x00, // LDH (0x00), A   選択を解除
+    0xCB, 0x40, // BIT 0, B        右
+    0x28, 0x06, // JR Z, not_right
+    0xF0, 0x43, // LDH A, (0x43)   SCX
+    0xC6, 0x08, // ADD A, 8
+    0xE0, 0x43, // LDH (0x43), A
+    // not_right:
+    0xCB, 0x48, // BIT 1, B        左
+    0x28, 0x06, // JR Z, not_left
+    0xF0, 0x43, // LDH A, (0x43)   SCX
+    0xD6, 0x08, // SUB 8
+    0xE0, 0x43, // LDH (0x43), A
+    // not_left:
+    0xCB, 0x50, // BIT 2, B        上
+    0x28, 0x06, // JR Z, not_up
+    0xF0, 0x42, // LDH A, (0x42)   SCY
+    0xD6, 0x08, // SUB 8
+    0xE0, 0x42, // LDH (0x42), A
+    // not_up:
+    0xCB, 0x58, // BIT 3, B        下
+    0x28, 0x06, // JR Z, not_down
+    0xF0, 0x42, // LDH A, (0x42)   SCY
+    0xC6, 0x08, // ADD A, 8
+    0xE0, 0x42, // LDH (0x42), A
+    // not_down:
+    0xCB, 0x41, // BIT 0, C        A ボタン
+    0x28, 0xAC, // JR Z, main
+    0x3E, 0x80, // LD A, 0x80
+    0xE0, 0x11, // LDH (0x11), A   NR11 デューティ比 50%
+    0x3E, 0xF3, // LD A, 0xF3
+    0xE0, 0x12, // LDH (0x12), A   NR12 最大音量から減衰
+    0x3E, 0x83, // LD A, 0x83
+    0xE0, 0x13, // LDH (0x13), A   NR13
+    0x3E, 0x87, // LD A, 0x87
+    0xE0, 0x14, // LDH (0x14), A   NR14 約 1kHz で鳴らす
+    0x18, 0x9A, // JR main
+];
+
+/// 32KB の ROM イメージを作る。統合テストの入力にも使える
+pub fn rom() -> Vec<u8> {
+    let mut rom = build_rom(0x8000, TITLE, &PROGRAM);
+    rom[TILES_ADDRESS..TILES_ADDRESS + TILES.len()].copy_from_slice(&TILES);
+    rom
+}
+
+/// 0x0150 から program を配置した len バイトの ROM イメージを作る。テストやベンチマークの入力に使う
+/// カートリッジの種類などヘッダを書き換えたら set_header_checksum を呼び直すこと
+pub fn build_rom(len: usize, title: &[u8], program: &[u8]) -> Vec<u8> {
+    let mut rom = vec![0; len];
+    // NOP; JP 0x0150
+    rom[0x100..0x104].copy_from_slice(&[0x00, 0xC3, 0x50, 0x01]);
+    rom[0x134..0x134 + title.len()].copy_from_slice(title);
+    rom[0x150..0x150 + program.len()].copy_from_slice(program);
+    set_header_checksum(&mut rom);
+    rom
+}
+
+/// 0x0134 から 0x014C のヘッダのチェックサムを 0x014D に書き込む
+pub fn set_header_checksum(rom: &mut [u8]) {
+    let mut x: u8 = 0;
+    for b in &rom[0x134..=0x14C] {
+        x = x.wrapping_sub(*b).wrapping_sub(1);
+    }
+    rom[0x14D] = x;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Bus;
+    use crate::mother_board::MotherBoard;
+
+    #[test]
+    fn test_demo() {
+        // プログラムとタイルデータが重ならない
+        assert!(0x150 + PROGRAM.len() <= TILES_ADDRESS);
+        let mb = MotherBoard::headless(rom());
+        let mb = mb.borrow();
+        mb.reset();
+        // 背景の初期化に 1 フレーム以上かかる
+        for _ in 0..3 {
+            mb.run_until_vblank().unwrap();
+        }
+        let frame = mb.run_until_vblank().unwrap();
+        assert_eq!(mb.read(0xFF40), 0x91);
+        // 3 種類のタイルが描画されている
+        let mut colors: Vec<u8> = frame.iter().flatten().map(|p| p.luminance()).collect();
+        colors.sort();
+        colors.dedup();
+        assert_eq!(colors.len(), 4);
+    }
+}
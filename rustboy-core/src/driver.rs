@@ -0,0 +1,378 @@
+// エミュレーションを別スレッドで動かし、フロントエンドとはチャンネルだけでやり取りする
+// MotherBoard は Rc と RefCell で部品を共有していて Send ではないので、スレッドの中で組み立てる
+// スレッドを跨ぐのは ROM のバイト列とメッセージだけになる
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::thread::{self, sleep, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender, TryRecvError};
+
+use crate::cartridges::Cartridge;
+use crate::cpu::CPU;
+use crate::joypad::{ButtonState, KeySource};
+use crate::mother_board::MotherBoard;
+use crate::ppu::{FrameBuffer, Headless};
+
+/// AudioChunk のサンプリング周波数
+pub const SAMPLE_RATE: u32 = 44100;
+
+// フロントエンドが読まずに溜まったら、それ以上の画面と音は捨てる
+const OUTPUT_QUEUE: usize = 16;
+
+/// フロントエンドからの入力
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    /// KeyBindings の既定の文字(a, b, 改行, 空白, h, j, k, l)で一時的に押す
+    Key(char),
+    /// 押し続けているボタン。キーを離したことが分かる GUI 向けで、一度送ると Key は使われない
+    Buttons(ButtonState),
+}
+
+/// 実行の操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum Control {
+    Pause,
+    Resume,
+    /// 1フレーム進めて止まる
+    StepFrame,
+    /// 1.0 で実機と同じ速さ。0 なら待たない。負の数や NaN なら Output::Error を送って今の速さのまま続ける
+    Speed(f64),
+    Reset,
+    /// カートリッジを差し替えてリセットする。壊れた ROM なら Output::Error を送って今の ROM のまま続ける
+    LoadRom(Vec<u8>),
+    Quit,
+}
+
+/// 描画し終えたフレーム
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub buffer: Arc<FrameBuffer>,
+    /// 描画したフレーム数(このフレームを含む)
+    pub frame: u64,
+    /// リセットからの経過 T-Cycle 数
+    pub cycles: u64,
+}
+
+/// 1フレームの間に鳴った音。8bit モノラルの PCM で、今はチャンネル3(波形メモリ)だけを鳴らす
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioChunk {
+    pub sample_rate: u32,
+    pub samples: Vec<u8>,
+}
+
+/// エミュレーションのスレッドからの出力
+#[derive(Debug, Clone)]
+pub enum Output {
+    Frame(Frame),
+    Audio(AudioChunk),
+    /// 実行できない命令に遭遇したので止まった。Reset か LoadRom で再開できる
+    /// 受け付けられない Control でも送る。その場合は止まらずに続ける
+    Error(String),
+}
+
+/// 別スレッドで動くエミュレータ。drop でスレッドを止める
+pub struct Driver {
+    inputs: Sender<InputEvent>,
+    controls: Sender<Control>,
+    outputs: Receiver<Output>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Driver {
+    /// ROM を確かめてからスレッドを起動する。起動するとすぐに実機と同じ速さで動き始める
+    pub fn spawn(rom: Vec<u8>) -> Result<Self, String> {
+        Cartridge::check_rom(&rom)?;
+        let (inputs, input_rx) = unbounded();
+        let (controls, control_rx) = unbounded();
+        let (output_tx, outputs) = bounded(OUTPUT_QUEUE);
+        let thread = thread::spawn(move || {
+            let keys = ChannelKeys::new(input_rx);
+            let mb = MotherBoard::with_frontend(rom, Box::new(Headless), Box::new(keys));
+            let mb = mb.borrow();
+            mb.reset();
+            Runner::new(&mb, control_rx, output_tx).run();
+        });
+        Ok(Self {
+            inputs,
+            controls,
+            outputs,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn send_input(&self, event: InputEvent) {
+        // スレッドが終わっていれば捨てる
+        let _ = self.inputs.send(event);
+    }
+
+    pub fn send_control(&self, control: Control) {
+        let _ = self.controls.send(control);
+    }
+
+    /// 画面と音の受信側。crossbeam_channel::select! で他のイベントと一緒に待てる
+    pub fn outputs(&self) -> &Receiver<Output> {
+        &self.outputs
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        self.send_control(Control::Quit);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// チャンネルで届いた入力を JoyPad に渡す
+struct ChannelKeys {
+    events: Receiver<InputEvent>,
+    keys: RefCell<VecDeque<char>>,
+    buttons: Cell<Option<ButtonState>>,
+}
+
+impl ChannelKeys {
+    fn new(events: Receiver<InputEvent>) -> Self {
+        Self {
+            events,
+            keys: RefCell::new(VecDeque::new()),
+            buttons: Cell::new(None),
+        }
+    }
+
+    fn receive(&self) {
+        for event in self.events.try_iter() {
+            match event {
+                InputEvent::Key(c) => self.keys.borrow_mut().push_back(c),
+                InputEvent::Buttons(state) => self.buttons.set(Some(state)),
+            }
+        }
+    }
+}
+
+impl KeySource for ChannelKeys {
+    fn next_key(&self) -> Option<char> {
+        self.receive();
+        self.keys.borrow_mut().pop_front()
+    }
+    fn buttons(&self) -> Option<ButtonState> {
+        self.receive();
+        self.buttons.get()
+    }
+}
+
+// スレッドの中で MotherBoard を動かす
+struct Runner<'a> {
+    mb: &'a MotherBoard,
+    controls: Receiver<Control>,
+    outputs: Sender<Output>,
+    paused: bool,
+    // 止まっていても次のフレームまで進める
+    advance: bool,
+    speed: f64,
+    // 基準とする時刻と、その時点の経過時間(等速の T-Cycle 数)
+    origin: Instant,
+    origin_elapsed: u64,
+    // リセットから送った音のサンプル数
+    samples: u64,
+}
+
+impl<'a> Runner<'a> {
+    fn new(mb: &'a MotherBoard, controls: Receiver<Control>, outputs: Sender<Output>) -> Self {
+        Self {
+            mb,
+            controls,
+            outputs,
+            paused: false,
+            advance: false,
+            speed: 1.0,
+            origin: Instant::now(),
+            origin_elapsed: 0,
+            samples: 0,
+        }
+    }
+
+    fn run(&mut self) {
+        loop {
+            // 1フレーム進める操作は、次の操作を受け取る前に済ませる
+            if self.advance {
+                self.run_frame();
+                continue;
+            }
+            // 止まっている間は操作が届くまで待つ
+            let control = if self.paused {
+                self.controls.recv().ok()
+            } else {
+                match self.controls.try_recv() {
+                    Ok(control) => Some(control),
+                    Err(TryRecvError::Empty) => {
+                        self.run_frame();
+                        continue;
+                    }
+                    Err(TryRecvError::Disconnected) => None,
+                }
+            };
+            match control {
+                Some(Control::Quit) | None => return,
+                Some(control) => self.handle(control),
+            }
+        }
+    }
+
+    fn handle(&mut self, control: Control) {
+        match control {
+            Control::Pause => self.paused = true,
+            Control::Resume => self.paused = false,
+            Control::StepFrame => {
+                self.paused = true;
+                self.advance = true;
+            }
+            Control::Speed(speed) if speed.is_finite() && speed >= 0.0 => self.speed = speed,
+            Control::Speed(speed) => self.send(Output::Error(format!("Invalid speed: {}", speed))),
+            Control::Reset => self.reset(),
+            Control::LoadRom(rom) => match Cartridge::check_rom(&rom) {
+                Ok(_) => {
+                    self.mb.load_cartridge(Cartridge::from_bytes(rom));
+                    self.reset();
+                }
+                Err(e) => self.send(Output::Error(e)),
+            },
+            Control::Quit => {}
+        }
+        // 止まっていた分や速さが変わる前の分を待たないように基準を取り直す
+        self.rebase();
+    }
+
+    fn reset(&mut self) {
+        self.mb.reset();
+        self.paused = false;
+        self.samples = 0;
+    }
+
+    fn rebase(&mut self) {
+        self.origin = Instant::now();
+        self.origin_elapsed = self.mb.elapsed();
+    }
+
+    fn run_frame(&mut self) {
+        self.advance = false;
+        let buffer = match self.mb.run_until_vblank() {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                self.paused = true;
+                self.send(Output::Error(e.to_string()));
+                return;
+            }
+        };
+        self.send(Output::Frame(Frame {
+            buffer,
+            frame: self.mb.frames(),
+            cycles: self.mb.cycles(),
+        }));
+        let samples = self.mb.elapsed() * SAMPLE_RATE as u64 / CPU::CLOCK as u64;
+        let len = samples.saturating_sub(self.samples) as usize;
+        let pcm = self.mb.sound().mixed_pcm(SAMPLE_RATE, self.samples, len);
+        self.samples = samples;
+        self.send(Output::Audio(AudioChunk {
+            sample_rate: SAMPLE_RATE,
+            samples: pcm,
+        }));
+        self.wait();
+    }
+
+    // 実機と同じ速さになるまで待つ
+    fn wait(&self) {
+        if self.speed <= 0.0 {
+            return;
+        }
+        let emulated = self.mb.elapsed().saturating_sub(self.origin_elapsed);
+        let seconds = emulated as f64 / (CPU::CLOCK as f64 * self.speed);
+        // 極端に遅くしたときは Duration に収まらないので待たない
+        let Ok(target) = Duration::try_from_secs_f64(seconds) else {
+            return;
+        };
+        let elapsed = self.origin.elapsed();
+        if target > elapsed {
+            sleep(target - elapsed);
+        }
+    }
+
+    // フロントエンドが読んでいなければ捨てる
+    fn send(&self, output: Output) {
+        let _ = self.outputs.try_send(output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo;
+
+    fn next_frame(driver: &Driver) -> Frame {
+        loop {
+            match driver
+                .outputs()
+                .recv_timeout(Duration::from_secs(10))
+                .unwrap()
+            {
+                Output::Frame(frame) => return frame,
+                Output::Audio(chunk) => assert_eq!(chunk.sample_rate, SAMPLE_RATE),
+                Output::Error(e) => panic!("{}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_driver() {
+        assert!(Driver::spawn(vec![0; 0x100]).is_err());
+        let driver = Driver::spawn(demo::rom()).unwrap();
+        let first = next_frame(&driver);
+        assert!(first.cycles > 0);
+        driver.send_input(InputEvent::Buttons(ButtonState::from_bits(0x01)));
+        // 壊れた ROM は読み込まずにエラーを送る。それまでに描画したフレームは読み飛ばす
+        driver.send_control(Control::LoadRom(vec![0; 0x10]));
+        loop {
+            let output = driver.outputs().recv_timeout(Duration::from_secs(10));
+            if matches!(output.unwrap(), Output::Error(_)) {
+                break;
+            }
+        }
+        // 差し替えるとリセットされるので、経過サイクル数が1フレーム分に戻る
+        driver.send_control(Control::LoadRom(demo::rom()));
+        while next_frame(&driver).cycles > 70224 {}
+    }
+
+    #[test]
+    fn test_step_frame() {
+        // スレッドを使わずに、操作を全て積んでから実行する
+        let mb = MotherBoard::headless(demo::rom());
+        let mb = mb.borrow();
+        mb.reset();
+        let (controls, control_rx) = unbounded();
+        let (output_tx, outputs) = bounded(OUTPUT_QUEUE);
+        for control in [
+            Control::Speed(0.0),
+            Control::Pause,
+            // 止まっていても1フレームずつ進む
+            Control::StepFrame,
+            Control::StepFrame,
+            // 受け付けずにエラーを送る
+            Control::Speed(f64::NAN),
+            Control::Speed(-1.0),
+            Control::Quit,
+        ] {
+            controls.send(control).unwrap();
+        }
+        Runner::new(&mb, control_rx, output_tx).run();
+        let outputs: Vec<Output> = outputs.try_iter().collect();
+        assert_eq!(outputs.len(), 6);
+        assert!(matches!(&outputs[0], Output::Frame(frame) if frame.frame == 1));
+        assert!(matches!(&outputs[1], Output::Audio(_)));
+        assert!(matches!(&outputs[2], Output::Frame(frame) if frame.frame == 2));
+        assert!(matches!(&outputs[3], Output::Audio(_)));
+        assert!(matches!(&outputs[4], Output::Error(e) if e == "Invalid speed: NaN"));
+        assert!(matches!(&outputs[5], Output::Error(_)));
+    }
+}
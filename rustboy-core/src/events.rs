@@ -0,0 +1,107 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::fmt::{Debug, Formatter};
+use core::mem;
+
+use crate::ppu::PPUMode;
+
+/// 部品から通知される出来事
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    /// VBlank に入った。裏のバッファに描画し終えたフレームが表になっている
+    VBlank,
+    /// LCD のモード(STAT の下位2ビット)が変わった
+    ModeChange(PPUMode),
+    /// シリアル通信で1バイトの転送が終わった。値は受信したデータ
+    SerialTransferred(u8),
+    /// シリアル通信の転送を始めた。値は送信するデータ
+    /// Blargg のテスト ROM はこれで結果の文字列を出力する
+    SerialSent(u8),
+    /// ROM/RAM バンクが切り替わった。値は切り替え後の ROM バンク
+    BankSwitch(usize),
+    /// OAM DMA 転送が終わった
+    DmaDone,
+}
+
+pub type Subscriber = Box<dyn FnMut(&Event)>;
+
+/// 部品が publish した出来事をフロントエンドに配る
+/// 部品を借用している最中に呼び出さないように、出来事は溜めておいて MotherBoard::step の最後に配る
+/// 割り込みと同じように Rc で部品と共有する
+#[derive(Default)]
+pub struct EventBus {
+    queue: RefCell<Vec<Event>>,
+    subscribers: RefCell<Vec<Subscriber>>,
+    // 購読者がいなければ溜めない
+    active: Cell<bool>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 配っている最中(購読者の中)には呼べない
+    pub fn subscribe(&self, subscriber: Subscriber) {
+        self.subscribers.borrow_mut().push(subscriber);
+        self.active.set(true);
+    }
+
+    pub fn publish(&self, event: Event) {
+        if self.active.get() {
+            self.queue.borrow_mut().push(event);
+        }
+    }
+
+    /// 溜まっている出来事を publish された順に全ての購読者に配る
+    pub fn dispatch(&self) {
+        if self.queue.borrow().is_empty() {
+            return;
+        }
+        let events = mem::take(&mut *self.queue.borrow_mut());
+        let mut subscribers = self.subscribers.borrow_mut();
+        for event in &events {
+            for subscriber in subscribers.iter_mut() {
+                subscriber(event);
+            }
+        }
+    }
+}
+
+impl Debug for EventBus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "EventBus: {{ subscribers: {}, queued: {} }}",
+            self.subscribers.borrow().len(),
+            self.queue.borrow().len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::rc::Rc;
+
+    #[test]
+    fn test_dispatch() {
+        let bus = EventBus::new();
+        // 購読者がいなければ捨てる
+        bus.publish(Event::VBlank);
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let r = Rc::clone(&received);
+        bus.subscribe(Box::new(move |e| r.borrow_mut().push(*e)));
+        bus.publish(Event::DmaDone);
+        bus.publish(Event::SerialTransferred(0x42));
+        assert!(received.borrow().is_empty());
+        bus.dispatch();
+        assert_eq!(
+            *received.borrow(),
+            [Event::DmaDone, Event::SerialTransferred(0x42)]
+        );
+        bus.dispatch();
+        assert_eq!(received.borrow().len(), 2);
+    }
+}
@@ -0,0 +1,349 @@
+// C など Rust 以外のフロントエンドから使うための API
+// 共有ライブラリは cargo rustc --release --lib --features ffi --crate-type cdylib で作る
+// 宣言は include/rustboy.h にある
+use std::cell::{Cell, RefCell};
+use std::os::raw::c_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::rc::Rc;
+use std::slice;
+
+use log::warn;
+
+use crate::cartridges::Cartridge;
+use crate::cpu::CpuError;
+use crate::io::Bus;
+use crate::joypad::{ButtonState, KeySource};
+use crate::mother_board::MotherBoard;
+use crate::ppu::Headless;
+
+/// rustboy_set_buttons に渡すボタンのビット
+pub const RUSTBOY_A: u8 = 1 << 0;
+pub const RUSTBOY_B: u8 = 1 << 1;
+pub const RUSTBOY_SELECT: u8 = 1 << 2;
+pub const RUSTBOY_START: u8 = 1 << 3;
+pub const RUSTBOY_RIGHT: u8 = 1 << 4;
+pub const RUSTBOY_LEFT: u8 = 1 << 5;
+pub const RUSTBOY_UP: u8 = 1 << 6;
+pub const RUSTBOY_DOWN: u8 = 1 << 7;
+
+// RGBA で 160 x 144
+const FRAME_SIZE: usize = 160 * 144 * 4;
+
+// rustboy_set_buttons で設定されたボタンを押し続けている扱いにする
+// RUSTBOY_A などのビットの並びは ButtonState::bits と同じ
+struct Buttons(Rc<Cell<u8>>);
+
+impl KeySource for Buttons {
+    fn next_key(&self) -> Option<char> {
+        None
+    }
+    fn buttons(&self) -> Option<ButtonState> {
+        Some(ButtonState::from_bits(self.0.get()))
+    }
+}
+
+/// C からは中身の見えないハンドルとして扱う。Python のモジュールもこれを包む
+pub struct Emulator {
+    mother_board: Rc<RefCell<MotherBoard>>,
+    buttons: Rc<Cell<u8>>,
+    frame: Vec<u8>,
+}
+
+impl Emulator {
+    /// ROM は Cartridge::check_rom で確かめておくこと
+    pub(crate) fn new(rom: Vec<u8>) -> Self {
+        let buttons = Rc::new(Cell::new(0));
+        let mother_board = MotherBoard::with_frontend(
+            rom,
+            Box::new(Headless),
+            Box::new(Buttons(Rc::clone(&buttons))),
+        );
+        mother_board.borrow().reset();
+        Self {
+            mother_board,
+            buttons,
+            frame: vec![0xFF; FRAME_SIZE],
+        }
+    }
+
+    pub(crate) fn step_frame(&mut self) -> Result<(), CpuError> {
+        let frame_buffer = self.mother_board.borrow().run_until_vblank()?;
+        for (rgba, pixel) in self.frame.chunks_mut(4).zip(frame_buffer.iter().flatten()) {
+            // アルファは使っていないので不透明にする
+            rgba.copy_from_slice(&[pixel.0, pixel.1, pixel.2, 0xFF]);
+        }
+        Ok(())
+    }
+
+    /// 最後に実行したフレームの画面。RGBA で FRAME_SIZE バイト
+    pub fn frame(&self) -> &[u8] {
+        &self.frame
+    }
+
+    /// RUSTBOY_A などの論理和。次に指定するまで押したままになる
+    pub fn set_buttons(&self, buttons: u8) {
+        self.buttons.set(buttons);
+    }
+
+    /// CPU と同じように読む
+    pub fn read(&self, address: u16) -> u8 {
+        self.mother_board.borrow().read(address)
+    }
+
+    /// CPU と同じように書き込む
+    pub fn write(&self, address: u16, data: u8) {
+        self.mother_board.borrow().write(address, data);
+    }
+
+    /// address から len バイト読み込む。IO レジスタは状態を変えずに読む
+    pub fn read_range(&self, address: u16, len: usize) -> Vec<u8> {
+        self.mother_board.borrow().read_range(address, len)
+    }
+
+    /// data を address から順に CPU と同じように書き込む
+    pub fn write_range(&self, address: u16, data: &[u8]) {
+        self.mother_board.borrow().write_range(address, data);
+    }
+}
+
+/// ROM イメージをコピーしてエミュレータを作る。ROM が壊れていれば NULL
+///
+/// # Safety
+/// rom は len バイト読める領域を指していること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_create(rom: *const u8, len: usize) -> *mut Emulator {
+    if rom.is_null() {
+        return ptr::null_mut();
+    }
+    let rom = slice::from_raw_parts(rom, len).to_vec();
+    if let Err(e) = Cartridge::check_rom(&rom) {
+        warn!(target: "mbc", "{}", e);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(Emulator::new(rom)))
+}
+
+/// 次の VBlank まで実行する。成功すれば 0、CPU が停止すれば -1
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルであること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_step_frame(emulator: *mut Emulator) -> c_int {
+    let emulator = match emulator.as_mut() {
+        Some(emulator) => emulator,
+        None => return -1,
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| emulator.step_frame())) {
+        Ok(Ok(())) => 0,
+        Ok(Err(e)) => {
+            warn!(target: "cpu", "{}", e);
+            -1
+        }
+        Err(_) => -1,
+    }
+}
+
+/// 最後に実行したフレームの画面。RGBA 160 x 144 で 92160 バイト
+/// 次に rustboy_step_frame か rustboy_destroy を呼ぶまで有効
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルであること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_framebuffer(emulator: *const Emulator) -> *const u8 {
+    match emulator.as_ref() {
+        Some(emulator) => emulator.frame().as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+/// 押されているボタンを RUSTBOY_A などの論理和で指定する。次に指定するまで押したままになる
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルであること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_set_buttons(emulator: *mut Emulator, buttons: u8) {
+    if let Some(emulator) = emulator.as_ref() {
+        emulator.set_buttons(buttons);
+    }
+}
+
+/// address のメモリを CPU と同じように読む。emulator が NULL か、読み込みで panic したら 0xFF
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルであること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_read(emulator: *const Emulator, address: u16) -> u8 {
+    match emulator.as_ref() {
+        Some(emulator) => {
+            panic::catch_unwind(AssertUnwindSafe(|| emulator.read(address))).unwrap_or(0xFF)
+        }
+        None => 0xFF,
+    }
+}
+
+/// address のメモリに CPU と同じように書き込む
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルであること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_write(emulator: *mut Emulator, address: u16, data: u8) {
+    if let Some(emulator) = emulator.as_ref() {
+        // panic は C 側に伝えられないので、書き込まなかったことにする
+        panic::catch_unwind(AssertUnwindSafe(|| emulator.write(address, data))).ok();
+    }
+}
+
+/// address から len バイトを buffer に読み込む。メモリビューア向けに IO レジスタは状態を変えずに読む
+/// 0xFFFF の次は 0x0000 に戻る。成功すれば 0、NULL か読み込みで panic したら -1(buffer は 0xFF で埋める)
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルで、buffer は len バイト書き込めること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_read_range(
+    emulator: *const Emulator,
+    address: u16,
+    buffer: *mut u8,
+    len: usize,
+) -> c_int {
+    if buffer.is_null() {
+        return -1;
+    }
+    let buffer = slice::from_raw_parts_mut(buffer, len);
+    let data = emulator.as_ref().and_then(|emulator| {
+        panic::catch_unwind(AssertUnwindSafe(|| emulator.read_range(address, len))).ok()
+    });
+    match data {
+        Some(data) => {
+            buffer.copy_from_slice(&data);
+            0
+        }
+        None => {
+            buffer.fill(0xFF);
+            -1
+        }
+    }
+}
+
+/// data の len バイトを address から順に CPU と同じように書き込む
+/// 成功すれば 0、NULL か書き込みで panic したら -1(途中まで書き込まれていることがある)
+///
+/// # Safety
+/// emulator は rustboy_create が返した破棄前のハンドルで、data は len バイト読めること
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_write_range(
+    emulator: *mut Emulator,
+    address: u16,
+    data: *const u8,
+    len: usize,
+) -> c_int {
+    let emulator = match (emulator.as_ref(), data.is_null()) {
+        (Some(emulator), false) => emulator,
+        _ => return -1,
+    };
+    let data = slice::from_raw_parts(data, len);
+    match panic::catch_unwind(AssertUnwindSafe(|| emulator.write_range(address, data))) {
+        Ok(()) => 0,
+        Err(_) => -1,
+    }
+}
+
+/// エミュレータを破棄する。NULL なら何もしない
+///
+/// # Safety
+/// emulator は rustboy_create が返したハンドルで、まだ破棄していないこと
+#[no_mangle]
+pub unsafe extern "C" fn rustboy_destroy(emulator: *mut Emulator) {
+    if !emulator.is_null() {
+        drop(Box::from_raw(emulator));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo;
+
+    #[test]
+    fn test_emulator() {
+        let rom = demo::rom();
+        unsafe {
+            let emulator = rustboy_create(rom.as_ptr(), rom.len());
+            assert!(!emulator.is_null());
+            rustboy_set_buttons(emulator, RUSTBOY_RIGHT);
+            for _ in 0..10 {
+                assert_eq!(rustboy_step_frame(emulator), 0);
+            }
+            let frame = slice::from_raw_parts(rustboy_framebuffer(emulator), FRAME_SIZE);
+            assert!(frame.chunks(4).all(|rgba| rgba[3] == 0xFF));
+            rustboy_write(emulator, 0xC000, 0x42);
+            assert_eq!(rustboy_read(emulator, 0xC000), 0x42);
+            assert_eq!(rustboy_read(emulator, 0xE000), 0x42);
+            // 0xFFFF から 0x0000 に戻る
+            assert_eq!(
+                rustboy_write_range(emulator, 0xFFFF, [0x05, 0x00, 0x11].as_ptr(), 3),
+                0
+            );
+            let mut buffer = [0; 4];
+            assert_eq!(
+                rustboy_read_range(emulator, 0xFFFE, buffer.as_mut_ptr(), 4),
+                0
+            );
+            assert_eq!(buffer[1], 0x05);
+            assert_eq!(buffer[2..], rom[0..2]);
+            rustboy_read_range(emulator, 0xC000, buffer.as_mut_ptr(), 2);
+            assert_eq!(buffer[0], 0x42);
+            rustboy_destroy(emulator);
+        }
+    }
+
+    #[test]
+    fn test_invalid_rom() {
+        let rom = [0; 0x100];
+        unsafe {
+            assert!(rustboy_create(rom.as_ptr(), rom.len()).is_null());
+            assert!(rustboy_create(ptr::null(), 0).is_null());
+            // ヘッダに無い ROM サイズ
+            let mut rom = demo::rom();
+            rom[0x148] = 0x40;
+            demo::set_header_checksum(&mut rom);
+            assert!(rustboy_create(rom.as_ptr(), rom.len()).is_null());
+            assert_eq!(rustboy_step_frame(ptr::null_mut()), -1);
+            assert_eq!(rustboy_read(ptr::null(), 0xC000), 0xFF);
+            let mut buffer = [0; 2];
+            assert_eq!(
+                rustboy_read_range(ptr::null(), 0xC000, buffer.as_mut_ptr(), 2),
+                -1
+            );
+            assert_eq!(buffer, [0xFF, 0xFF]);
+            assert_eq!(
+                rustboy_write_range(ptr::null_mut(), 0xC000, buffer.as_ptr(), 2),
+                -1
+            );
+            rustboy_destroy(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_buttons() {
+        let buttons = Buttons(Rc::new(Cell::new(0)));
+        assert_eq!(buttons.buttons(), Some(ButtonState::default()));
+        buttons.0.set(RUSTBOY_UP);
+        assert!(buttons.buttons().unwrap().up);
+        // 同時に押したボタンはすべて P1 から読める
+        let rom = demo::rom();
+        unsafe {
+            let gb = rustboy_create(rom.as_ptr(), rom.len());
+            rustboy_set_buttons(gb, RUSTBOY_A | RUSTBOY_START | RUSTBOY_RIGHT);
+            rustboy_write(gb, 0xFF00, 0x10);
+            assert_eq!(rustboy_read(gb, 0xFF00), 0xD6);
+            rustboy_write(gb, 0xFF00, 0x20);
+            assert_eq!(rustboy_read(gb, 0xFF00), 0xEE);
+            // 離したボタンはすぐに読めなくなる
+            rustboy_set_buttons(gb, RUSTBOY_RIGHT);
+            rustboy_write(gb, 0xFF00, 0x10);
+            assert_eq!(rustboy_read(gb, 0xFF00), 0xDF);
+            rustboy_destroy(gb);
+        }
+    }
+}
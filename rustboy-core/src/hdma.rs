@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+
+use crate::io::IO;
+use crate::Address;
+
+/// CGB の VRAM DMA (0xFF51 - 0xFF55)
+/// ROM や RAM から VRAM に 16 バイトずつ転送する。読み書きは MotherBoard が行う
+/// https://gbdev.io/pandocs/CGB_Registers.html#lcd-vram-dma-transfers
+#[derive(Debug)]
+pub struct Hdma {
+    // 転送元。下位4bitは無視される
+    source: u16,
+    // 転送先の VRAM 内のオフセット。0x0000 - 0x1FF0
+    destination: u16,
+    // 残りのブロック数 - 1。HDMA5 の下位7bit
+    length: u8,
+    mode: Mode,
+    active: bool,
+    // HBlank に入ったので1ブロック転送できる
+    ready: bool,
+    // 直前に HBlank だったか
+    hblank: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    // 全てのブロックをすぐに転送する(GDMA)
+    General,
+    // HBlank ごとに1ブロックずつ転送する(HDMA)
+    HBlank,
+}
+
+impl Hdma {
+    pub fn new() -> Self {
+        Self {
+            source: 0,
+            destination: 0,
+            // 転送していないときは 0xFF が読める
+            length: 0x7F,
+            mode: Mode::General,
+            active: false,
+            ready: false,
+            hblank: false,
+        }
+    }
+
+    /// PPU のモードを伝える。HBlank に入ったら HDMA の1ブロックを転送できるようになる
+    pub fn hblank(&mut self, hblank: bool) {
+        if hblank && !self.hblank && self.active && self.mode == Mode::HBlank {
+            self.ready = true;
+        }
+        self.hblank = hblank;
+    }
+
+    /// 転送するブロックがあれば (転送元, 転送先) を返して次のブロックに進む
+    pub fn next_block(&mut self) -> Option<(Address, Address)> {
+        if !self.active || (self.mode == Mode::HBlank && !self.ready) {
+            return None;
+        }
+        self.ready = false;
+        let block = (self.source, 0x8000 | self.destination);
+        self.source = self.source.wrapping_add(0x10);
+        self.destination = (self.destination + 0x10) & 0x1FF0;
+        if self.length == 0 {
+            self.active = false;
+            self.length = 0x7F;
+        } else {
+            self.length -= 1;
+        }
+        Some(block)
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend(self.source.to_le_bytes());
+        state.extend(self.destination.to_le_bytes());
+        state.push(self.length);
+        state.push(self.mode as u8);
+        state.push(self.active as u8);
+        state.push(self.ready as u8);
+        state.push(self.hblank as u8);
+    }
+}
+
+impl Default for Hdma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IO for Hdma {
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            // 転送元と転送先は書き込み専用
+            0xFF51..=0xFF54 => 0xFF,
+            // 転送中は bit 7 が 0。中断すると bit 7 が 1 で残りのブロック数が読める
+            0xFF55 => {
+                if self.active {
+                    self.length
+                } else {
+                    0x80 | self.length
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF51 => self.source = (data as u16) << 8 | (self.source & 0x00FF),
+            0xFF52 => self.source = (self.source & 0xFF00) | (data & 0xF0) as u16,
+            0xFF53 => self.destination = ((data & 0x1F) as u16) << 8 | (self.destination & 0x00FF),
+            0xFF54 => self.destination = (self.destination & 0x1F00) | (data & 0xF0) as u16,
+            0xFF55 => {
+                // HDMA の転送中に bit 7 を 0 にすると中断する
+                if self.active && self.mode == Mode::HBlank && data & 0x80 == 0 {
+                    self.active = false;
+                    self.ready = false;
+                    return;
+                }
+                self.length = data & 0x7F;
+                self.mode = if data & 0x80 == 0 {
+                    Mode::General
+                } else {
+                    Mode::HBlank
+                };
+                self.active = true;
+                self.ready = false;
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start(hdma: &mut Hdma, source: u16, destination: u16, hdma5: u8) {
+        hdma.write(0xFF51, (source >> 8) as u8);
+        hdma.write(0xFF52, source as u8);
+        hdma.write(0xFF53, (destination >> 8) as u8);
+        hdma.write(0xFF54, destination as u8);
+        hdma.write(0xFF55, hdma5);
+    }
+
+    #[test]
+    fn test_general() {
+        let mut hdma = Hdma::new();
+        assert_eq!(hdma.read(0xFF55), 0xFF);
+        // 下位4bitと転送先の上位3bitは無視される
+        start(&mut hdma, 0xC123, 0xE456, 0x01);
+        assert_eq!(hdma.read(0xFF55), 0x01);
+        assert_eq!(hdma.next_block(), Some((0xC120, 0x8450)));
+        assert_eq!(hdma.next_block(), Some((0xC130, 0x8460)));
+        assert_eq!(hdma.next_block(), None);
+        assert_eq!(hdma.read(0xFF55), 0xFF);
+        assert_eq!(hdma.read(0xFF51), 0xFF);
+    }
+
+    #[test]
+    fn test_hblank() {
+        let mut hdma = Hdma::new();
+        start(&mut hdma, 0x4000, 0x9FF0, 0x82);
+        assert_eq!(hdma.read(0xFF55), 0x02);
+        assert_eq!(hdma.next_block(), None);
+        // HBlank に入ったときに1ブロックだけ
+        hdma.hblank(true);
+        assert_eq!(hdma.next_block(), Some((0x4000, 0x9FF0)));
+        assert_eq!(hdma.next_block(), None);
+        hdma.hblank(true);
+        assert_eq!(hdma.next_block(), None);
+        // 転送先は VRAM の先頭に戻る
+        hdma.hblank(false);
+        hdma.hblank(true);
+        assert_eq!(hdma.next_block(), Some((0x4010, 0x8000)));
+        assert_eq!(hdma.read(0xFF55), 0x00);
+
+        // 中断すると残りのブロック数が読める
+        hdma.write(0xFF55, 0x00);
+        assert_eq!(hdma.read(0xFF55), 0x80);
+        hdma.hblank(false);
+        hdma.hblank(true);
+        assert_eq!(hdma.next_block(), None);
+    }
+}
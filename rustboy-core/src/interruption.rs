@@ -1,9 +1,13 @@
-use std::convert::Into;
-use std::fmt::{Debug, Formatter};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::Into;
+use core::fmt::{Debug, Formatter};
 
 use crate::io::IO;
 use crate::Address;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Peripheral {
     Joypad,
     Serial,
@@ -13,6 +17,34 @@ pub enum Peripheral {
 }
 
 impl Peripheral {
+    // ビット 0 (V-Blank) が最高、ビット 4 (Joypad) が最低の優先度
+    const PRIORITY: [Peripheral; 5] = [
+        Peripheral::VBlank,
+        Peripheral::LcdStatus,
+        Peripheral::Timer,
+        Peripheral::Serial,
+        Peripheral::Joypad,
+    ];
+
+    /// IF と IE の対応するビット
+    pub fn bit(&self) -> u8 {
+        match self {
+            Peripheral::Joypad => 0b_0001_0000,
+            Peripheral::Serial => 0b_0000_1000,
+            Peripheral::Timer => 0b_0000_0100,
+            Peripheral::LcdStatus => 0b_0000_0010,
+            Peripheral::VBlank => 0b_0000_0001,
+        }
+    }
+
+    /// 要求されていて(IF)許可されている(IE)割り込みのうち、最も優先度の高いもの
+    /// 同時に要求されていても1度に処理するのは1つだけで、残りは IF に立ったまま次の機会を待つ
+    pub fn highest(flags: u8, enables: u8) -> Option<Peripheral> {
+        Self::PRIORITY
+            .into_iter()
+            .find(|p| flags & enables & p.bit() != 0)
+    }
+
     pub fn jump_address(&self) -> Address {
         match self {
             Peripheral::Joypad => 0x0060,
@@ -136,11 +168,24 @@ impl Interruption {
             enables: InterruptEnables::default(),
         }
     }
-    pub fn print_interrupt_flags(&self) {
-        println!("InterruptFlags: 0b{:08b}", u8::from(self.interrupts));
+    /// 周辺機器からの割り込み要求。IF の対応するビットを立てる
+    pub fn request(&mut self, p: Peripheral) {
+        match p {
+            Peripheral::Joypad => self.interrupts.joypad = true,
+            Peripheral::Serial => self.interrupts.serial = true,
+            Peripheral::Timer => self.interrupts.timer = true,
+            Peripheral::LcdStatus => self.interrupts.lcd_stat = true,
+            Peripheral::VBlank => self.interrupts.v_blank = true,
+        }
+    }
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend([u8::from(self.interrupts), u8::from(self.enables)]);
+    }
+    pub fn dump_interrupt_flags(&self) -> String {
+        format!("InterruptFlags: 0b{:08b}", u8::from(self.interrupts))
     }
-    pub fn print_interrupt_enables(&self) {
-        println!("InterruptEnables: 0b{:08b}", u8::from(self.enables));
+    pub fn dump_interrupt_enables(&self) -> String {
+        format!("InterruptEnables: 0b{:08b}", u8::from(self.enables))
     }
 }
 
@@ -166,7 +211,7 @@ impl IO for Interruption {
 }
 
 impl Debug for Interruption {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "Interruption")
     }
 }
@@ -0,0 +1,59 @@
+use core::fmt::{Debug, Formatter};
+
+use crate::Address;
+
+pub trait IO {
+    fn read(&self, address: Address) -> u8;
+    fn write(&mut self, address: Address, data: u8);
+    // 状態を変えずに読む。デバッガやメモリビューアが使う
+    // 読み込みに副作用(キー入力の消費など)が無ければ read と同じ
+    fn peek(&self, address: Address) -> u8 {
+        self.read(address)
+    }
+    // 書き込みに伴う動作(カウンタのリセットなど)をせずに値だけを書き換える
+    // 書き込みに副作用が無ければ write と同じ
+    fn poke(&mut self, address: Address, data: u8) {
+        self.write(address, data)
+    }
+}
+
+impl Debug for dyn IO {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "IO")
+    }
+}
+
+// アドレスバスは16bit
+// データバスは8bit
+pub trait Bus {
+    fn read(&self, _address: Address) -> u8;
+    fn write(&self, _address: Address, _data: u8);
+    // 周辺機器の状態を変えずに読む
+    fn peek(&self, address: Address) -> u8 {
+        self.read(address)
+    }
+    // 周辺機器を動かさずに書き込む
+    fn poke(&self, address: Address, data: u8) {
+        self.write(address, data)
+    }
+    // CPU のメモリアクセスに合わせて周辺機器を進める
+    fn tick(&self, _cycles: u8) {}
+    // 16bit の INC/DEC は内部処理の間に address をアドレスバスに出す
+    // OAM を指していると OAM が壊れることがある
+    fn corrupt_oam(&self, _address: Address) {}
+    // STOP 命令。CGB で速度の切り替えが準備されていれば切り替えて true を返す
+    fn stop(&self) -> bool {
+        false
+    }
+    // STOP で止まっている間、CPU の代わりに時間を進める
+    // 選択中のボタンが押されていれば true を返して目覚める
+    fn stopped(&self, _cycles: u8) -> bool {
+        true
+    }
+}
+
+impl Debug for dyn Bus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "")
+    }
+}
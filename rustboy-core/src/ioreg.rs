@@ -0,0 +1,91 @@
+use crate::Address;
+
+/// 0xFF00 - 0xFFFF の IO レジスタの名前。デバッガのメモリ表示や逆アセンブルの注釈に使う
+/// CGB のレジスタも含む。アドレス順に並べる
+/// https://gbdev.io/pandocs/Hardware_Reg_List.html
+pub const IO_REGISTERS: [(Address, &str); 59] = [
+    (0xFF00, "P1"),
+    (0xFF01, "SB"),
+    (0xFF02, "SC"),
+    (0xFF04, "DIV"),
+    (0xFF05, "TIMA"),
+    (0xFF06, "TMA"),
+    (0xFF07, "TAC"),
+    (0xFF0F, "IF"),
+    (0xFF10, "NR10"),
+    (0xFF11, "NR11"),
+    (0xFF12, "NR12"),
+    (0xFF13, "NR13"),
+    (0xFF14, "NR14"),
+    (0xFF16, "NR21"),
+    (0xFF17, "NR22"),
+    (0xFF18, "NR23"),
+    (0xFF19, "NR24"),
+    (0xFF1A, "NR30"),
+    (0xFF1B, "NR31"),
+    (0xFF1C, "NR32"),
+    (0xFF1D, "NR33"),
+    (0xFF1E, "NR34"),
+    (0xFF20, "NR41"),
+    (0xFF21, "NR42"),
+    (0xFF22, "NR43"),
+    (0xFF23, "NR44"),
+    (0xFF24, "NR50"),
+    (0xFF25, "NR51"),
+    (0xFF26, "NR52"),
+    (0xFF40, "LCDC"),
+    (0xFF41, "STAT"),
+    (0xFF42, "SCY"),
+    (0xFF43, "SCX"),
+    (0xFF44, "LY"),
+    (0xFF45, "LYC"),
+    (0xFF46, "DMA"),
+    (0xFF47, "BGP"),
+    (0xFF48, "OBP0"),
+    (0xFF49, "OBP1"),
+    (0xFF4A, "WY"),
+    (0xFF4B, "WX"),
+    (0xFF4D, "KEY1"),
+    (0xFF4F, "VBK"),
+    (0xFF50, "BOOT"),
+    (0xFF51, "HDMA1"),
+    (0xFF52, "HDMA2"),
+    (0xFF53, "HDMA3"),
+    (0xFF54, "HDMA4"),
+    (0xFF55, "HDMA5"),
+    (0xFF56, "RP"),
+    (0xFF68, "BCPS"),
+    (0xFF69, "BCPD"),
+    (0xFF6A, "OCPS"),
+    (0xFF6B, "OCPD"),
+    (0xFF6C, "OPRI"),
+    (0xFF70, "SVBK"),
+    (0xFF76, "PCM12"),
+    (0xFF77, "PCM34"),
+    (0xFFFF, "IE"),
+];
+
+/// 名前の付いた IO レジスタならその名前を返す。波形メモリや HRAM、未使用のアドレスは None
+pub fn io_register_name(address: Address) -> Option<&'static str> {
+    IO_REGISTERS
+        .binary_search_by_key(&address, |(a, _)| *a)
+        .ok()
+        .map(|i| IO_REGISTERS[i].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_register_name() {
+        // 二分探索するのでアドレス順に並んでいること
+        assert!(IO_REGISTERS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(io_register_name(0xFF00), Some("P1"));
+        assert_eq!(io_register_name(0xFF40), Some("LCDC"));
+        assert_eq!(io_register_name(0xFFFF), Some("IE"));
+        assert_eq!(io_register_name(0xFF03), None);
+        assert_eq!(io_register_name(0xFF80), None);
+        assert_eq!(io_register_name(0xC000), None);
+    }
+}
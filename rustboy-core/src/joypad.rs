@@ -0,0 +1,438 @@
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::{Debug, Formatter};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::mpsc::Receiver;
+
+use crate::cpu::CPU;
+use crate::interruption::{Interruption, Peripheral};
+use crate::io::IO;
+use crate::Address;
+
+/// 押されているボタン
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ButtonState {
+    pub a: bool,
+    pub b: bool,
+    pub start: bool,
+    pub select: bool,
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl ButtonState {
+    /// 下位から A, B, Select, Start, 右, 左, 上, 下 のビット。押されていれば 1
+    pub fn bits(&self) -> u8 {
+        self.direction_bits() << 4 | self.action_bits()
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        let bit = |i: u8| bits >> i & 1 == 1;
+        Self {
+            a: bit(0),
+            b: bit(1),
+            select: bit(2),
+            start: bit(3),
+            right: bit(4),
+            left: bit(5),
+            up: bit(6),
+            down: bit(7),
+        }
+    }
+
+    // 押されているボタンのビットを 1 にする(P1 レジスタとは逆)
+    fn action_bits(&self) -> u8 {
+        (self.start as u8) << 3 | (self.select as u8) << 2 | (self.b as u8) << 1 | self.a as u8
+    }
+
+    fn direction_bits(&self) -> u8 {
+        (self.down as u8) << 3 | (self.up as u8) << 2 | (self.left as u8) << 1 | self.right as u8
+    }
+}
+
+/// 0xFF00: P1 レジスタ
+/// Bit 5, 4 は CPU からの出力で、0 にしたボタン群を選択する(両方選択することもできる)
+/// Bit 3 - 0 は入力で、選択中のボタン群のうち押されているボタンのビットが 0 になる
+/// Bit 7, 6 は未使用で常に 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct JoypadRegister {
+    select: u8,
+}
+
+impl JoypadRegister {
+    // Bit 5: A, B, Select, Start
+    const SELECT_ACTION: u8 = 0b_0010_0000;
+    // Bit 4: 十字キー
+    const SELECT_DIRECTION: u8 = 0b_0001_0000;
+
+    fn new() -> Self {
+        Self {
+            select: Self::SELECT_ACTION | Self::SELECT_DIRECTION,
+        }
+    }
+
+    fn write(&mut self, data: u8) {
+        self.select = data & (Self::SELECT_ACTION | Self::SELECT_DIRECTION);
+    }
+
+    fn read(&self, state: &ButtonState) -> u8 {
+        let mut pressed = 0;
+        if self.select & Self::SELECT_ACTION == 0 {
+            pressed |= state.action_bits();
+        }
+        if self.select & Self::SELECT_DIRECTION == 0 {
+            pressed |= state.direction_bits();
+        }
+        0b_1100_0000 | self.select | (!pressed & 0x0F)
+    }
+}
+
+/// ボタンに対応させるキー
+/// 1文字が1つのボタンに対応する
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyBindings {
+    pub a: char,
+    pub b: char,
+    pub start: char,
+    pub select: char,
+    pub up: char,
+    pub down: char,
+    pub left: char,
+    pub right: char,
+}
+
+impl KeyBindings {
+    // 1文字で押せるのは1つのボタンだけ
+    fn button_state(&self, c: char) -> ButtonState {
+        ButtonState {
+            a: c == self.a,
+            b: c == self.b,
+            start: c == self.start,
+            select: c == self.select,
+            up: c == self.up,
+            down: c == self.down,
+            left: c == self.left,
+            right: c == self.right,
+        }
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            a: 'a',
+            b: 'b',
+            start: '\n',
+            select: ' ',
+            up: 'k',
+            down: 'j',
+            left: 'h',
+            right: 'l',
+        }
+    }
+}
+
+/// キー入力の送信元
+/// std ではフロントエンドが標準入力を読むスレッドから Receiver で受け取る。マイコンではボタンを読む実装を渡す
+pub trait KeySource {
+    /// 新しく押されたキー。何も押されていなければ None
+    fn next_key(&self) -> Option<char>;
+    /// 押し続けているボタン。ボタンを離したことが分かる送信元だけが返す
+    /// None ならキー入力で押されたボタンを一定時間押し続けている扱いにする
+    fn buttons(&self) -> Option<ButtonState> {
+        None
+    }
+}
+
+/// キー入力が無い。ヘッドレス実行用
+pub struct NoKeys;
+
+impl KeySource for NoKeys {
+    fn next_key(&self) -> Option<char> {
+        None
+    }
+}
+
+// 1つの文字列を1つのキーとする。送信側が閉じている場合は何も押されていない扱いになる
+#[cfg(feature = "std")]
+impl KeySource for Receiver<String> {
+    fn next_key(&self) -> Option<char> {
+        self.try_recv().ok().and_then(|key| key.chars().next())
+    }
+}
+
+// 1度の読み込みで受け取るキーの数の上限。押したままのキーを返し続ける送信元でも止まるようにする
+const MAX_KEYS_PER_READ: usize = 8;
+
+/// キーを押してから自動で離すまでの時間の既定値
+/// 端末からはキーを離したことが分からないので、この間に同じキーが届けば押し続けている扱いにする
+pub const DEFAULT_KEY_HOLD: Duration = Duration::from_millis(150);
+
+pub struct JoyPad {
+    register: JoypadRegister,
+    keys: KeyBindings,
+    source: Box<dyn KeySource>,
+    // ボタンごとに離す時刻(clock と同じ単位)。ButtonState::bits のビット順
+    // 複数のボタンを同時に押していられる
+    release_at: RefCell<[u64; 8]>,
+    // キー入力1回でボタンを押し続ける T-Cycle 数(等速)
+    hold: u64,
+    // リセットからの経過 T-Cycle 数(等速)
+    clock: u64,
+    interruption: Rc<RefCell<Interruption>>,
+}
+
+impl JoyPad {
+    /// 任意の送信元からキー入力を受け付ける
+    pub fn new(
+        source: Box<dyn KeySource>,
+        keys: KeyBindings,
+        interruption: Rc<RefCell<Interruption>>,
+    ) -> Self {
+        Self {
+            source,
+            keys,
+            interruption,
+            register: JoypadRegister::new(),
+            release_at: RefCell::new([0; 8]),
+            hold: Self::to_cycles(DEFAULT_KEY_HOLD),
+            clock: 0,
+        }
+    }
+
+    /// キーを押してから自動で離すまでの時間
+    pub fn set_hold(&mut self, hold: Duration) {
+        self.hold = Self::to_cycles(hold);
+    }
+
+    /// 押しているボタンを全て離す。キー入力の送信元と設定はそのまま
+    pub fn reset(&mut self) {
+        self.register = JoypadRegister::new();
+        *self.release_at.borrow_mut() = [0; 8];
+        self.clock = 0;
+    }
+
+    fn to_cycles(duration: Duration) -> u64 {
+        (duration.as_secs_f64() * CPU::CLOCK as f64) as u64
+    }
+
+    /// 等速の T-Cycle 数で時間を進める
+    pub fn tick(&mut self, cycles: u8) {
+        self.clock += cycles as u64;
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.push(self.register.select);
+        state.extend(self.clock.to_le_bytes());
+        for release_at in self.release_at.borrow().iter() {
+            state.extend(release_at.to_le_bytes());
+        }
+    }
+
+    /// 入力済みのキーで押されているボタン。ゲームが最後に読んだ状態になる
+    pub fn pressed(&self) -> ButtonState {
+        self.peek_button_state()
+    }
+
+    // 入力済みのキーだけを見る。キー入力を読み進めず、割り込みも要求しない
+    fn peek_button_state(&self) -> ButtonState {
+        let release_at = self.release_at.borrow();
+        let bits = (0..8)
+            .filter(|&i| release_at[i] > self.clock)
+            .fold(0, |bits, i| bits | 1 << i);
+        ButtonState::from_bits(bits)
+    }
+
+    // キー入力を読んで押されているボタンを更新する
+    fn button_state(&self) -> ButtonState {
+        let before = self.peek_button_state();
+        match self.source.buttons() {
+            Some(state) => {
+                let bits = state.bits();
+                for (i, release_at) in self.release_at.borrow_mut().iter_mut().enumerate() {
+                    *release_at = if bits >> i & 1 == 1 { u64::MAX } else { 0 };
+                }
+            }
+            None => {
+                // 押している間に届いた同じキー(キーリピート)は押し直さずに離す時刻を延ばす
+                for _ in 0..MAX_KEYS_PER_READ {
+                    let c = match self.source.next_key() {
+                        Some(c) => c,
+                        None => break,
+                    };
+                    let bits = self.keys.button_state(c).bits();
+                    for (i, release_at) in self.release_at.borrow_mut().iter_mut().enumerate() {
+                        if bits >> i & 1 == 1 {
+                            *release_at = self.clock + self.hold;
+                        }
+                    }
+                }
+            }
+        }
+        let state = self.peek_button_state();
+        // 新しく押されたボタンが選択中なら割り込み
+        let pressed = ButtonState::from_bits(state.bits() & !before.bits());
+        if self.register.read(&pressed) & 0x0F != 0x0F {
+            self.interruption.borrow_mut().request(Peripheral::Joypad);
+        }
+        state
+    }
+}
+
+impl IO for JoyPad {
+    // 0xFF00 のみ
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0xFF00 => self.register.read(&self.button_state()),
+            _ => unreachable!(),
+        }
+    }
+    fn peek(&self, address: Address) -> u8 {
+        match address {
+            0xFF00 => self.register.read(&self.peek_button_state()),
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, _address: Address, data: u8) {
+        self.register.write(data);
+    }
+}
+
+impl Debug for JoyPad {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // rom_data は表示しない
+        write!(f, "JoyPad")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_register_select() {
+        let mut register = JoypadRegister::new();
+        assert_eq!(register.read(&ButtonState::default()), 0xFF);
+        // 下位4ビットと未使用のビットへの書き込みは無視される
+        register.write(0b_1101_0101);
+        assert_eq!(register.select, 0b_0001_0000);
+        assert_eq!(register.read(&ButtonState::default()), 0b_1101_1111);
+    }
+
+    #[test]
+    fn test_register_truth_table() {
+        let state = ButtonState {
+            a: true,
+            start: true,
+            left: true,
+            ..ButtonState::default()
+        };
+        let mut register = JoypadRegister::new();
+        // (Bit 5, 4 に書き込む値, 読み込まれる値)
+        let table = [
+            // どちらも選択しなければ何も押されていない
+            (0b_0011_0000, 0b_1111_1111),
+            // ボタン: Start と A
+            (0b_0001_0000, 0b_1101_0110),
+            // 十字キー: 左
+            (0b_0010_0000, 0b_1110_1101),
+            // 両方: 論理積になる
+            (0b_0000_0000, 0b_1100_0100),
+        ];
+        for (select, expected) in table {
+            register.write(select);
+            assert_eq!(register.read(&state), expected, "select: {:08b}", select);
+        }
+    }
+
+    #[test]
+    fn test_read_key() {
+        let (tx, rx) = mpsc::channel::<String>();
+        let interruption = Rc::new(RefCell::new(Interruption::new()));
+        let mut joypad = JoyPad::new(
+            Box::new(rx),
+            KeyBindings::default(),
+            Rc::clone(&interruption),
+        );
+        joypad.set_hold(Duration::from_millis(1));
+        tx.send("a\n".to_string()).unwrap();
+        joypad.write(0xFF00, 0x20);
+        // 十字キーを選択中は A は読めないが、次の選択まで入力は保持される
+        assert_eq!(joypad.read(0xFF00), 0xEF);
+        assert_eq!(interruption.borrow().read(0xFF0F), 0);
+        joypad.write(0xFF00, 0x10);
+        assert_eq!(joypad.read(0xFF00), 0xDE);
+        assert_eq!(interruption.borrow().read(0xFF0F), 0);
+        // 選択中のボタンが押されると割り込み。A も押したまま
+        tx.send("b\n".to_string()).unwrap();
+        joypad.write(0xFF00, 0x30);
+        joypad.write(0xFF00, 0x10);
+        assert_eq!(joypad.read(0xFF00), 0xDC);
+        assert_eq!(interruption.borrow().read(0xFF0F), 0b_0001_0000);
+        // 一定時間が経つと入力が離される
+        for _ in 0..1000 {
+            joypad.tick(8);
+        }
+        assert_eq!(joypad.read(0xFF00), 0xDF);
+    }
+
+    #[test]
+    fn test_simultaneous_keys() {
+        let (tx, rx) = mpsc::channel::<String>();
+        let interruption = Rc::new(RefCell::new(Interruption::new()));
+        let mut joypad = JoyPad::new(
+            Box::new(rx),
+            KeyBindings::default(),
+            Rc::clone(&interruption),
+        );
+        joypad.set_hold(Duration::from_millis(1));
+        // 右を押したまま A を押せる
+        tx.send("l".to_string()).unwrap();
+        tx.send("a".to_string()).unwrap();
+        joypad.write(0xFF00, 0x00);
+        assert_eq!(joypad.read(0xFF00), 0xC0 | 0x0E);
+        assert_eq!(interruption.borrow().read(0xFF0F), 0b_0001_0000);
+        assert_eq!(joypad.pressed().bits(), 0b_0001_0001);
+        interruption.borrow_mut().write(0xFF0F, 0);
+        // キーリピートは押し直しにならず、離す時刻だけが延びる
+        for _ in 0..3 {
+            for _ in 0..500 {
+                joypad.tick(4);
+            }
+            tx.send("l".to_string()).unwrap();
+            assert_eq!(joypad.read(0xFF00) & 0x0F, 0x0E);
+            assert_eq!(interruption.borrow().read(0xFF0F), 0);
+        }
+        // A は離されていて右だけが残る
+        assert_eq!(joypad.pressed().bits(), 0b_0001_0000);
+        for _ in 0..1100 {
+            joypad.tick(4);
+        }
+        assert_eq!(joypad.read(0xFF00), 0xCF);
+    }
+
+    #[test]
+    fn test_peek() {
+        let (tx, rx) = mpsc::channel::<String>();
+        let interruption = Rc::new(RefCell::new(Interruption::new()));
+        let mut joypad = JoyPad::new(
+            Box::new(rx),
+            KeyBindings::default(),
+            Rc::clone(&interruption),
+        );
+        tx.send("a\n".to_string()).unwrap();
+        joypad.write(0xFF00, 0x10);
+        // peek はキー入力を読み進めず、割り込みも要求しない
+        assert_eq!(joypad.peek(0xFF00), 0xDF);
+        assert_eq!(interruption.borrow().read(0xFF0F), 0);
+        assert_eq!(joypad.read(0xFF00), 0xDE);
+        // 入力済みのキーは見える
+        assert_eq!(joypad.peek(0xFF00), 0xDE);
+    }
+}
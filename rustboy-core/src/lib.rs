@@ -0,0 +1,86 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// エミュレーションだけを行うコア。端末への描画や標準入力、設定ファイルの読み込みは
+// rustboy-cli などのフロントエンドが LCD と KeySource を通して受け持つ
+
+extern crate alloc;
+
+pub use braille::lines as braille_lines;
+pub use cartridges::{apply_patch, Cartridge, CartridgeHeader, MbcSelect, Rom, BANK_SIZE_ROM};
+pub use compat::{lookup as lookup_compat, CompatEntry};
+pub use cpu::{cb_mnemonic, mnemonic, CpuError, CpuState, CPU};
+pub use demo::{build_rom, rom as demo_rom, set_header_checksum};
+pub use events::Event;
+pub use io::Bus;
+pub use ioreg::{io_register_name, IO_REGISTERS};
+pub use joypad::{ButtonState, KeyBindings, KeySource, DEFAULT_KEY_HOLD};
+#[cfg(feature = "std")]
+pub use link::{Link, LinkMode};
+#[cfg(feature = "metrics")]
+pub use metrics::serve as serve_metrics;
+pub use metrics::{InterruptCounts, Metrics};
+pub use mother_board::MotherBoard;
+pub use png::encode as encode_png;
+pub use ppu::{
+    Colorization, DebugView, FrameBuffer, FrameInfo, OamEntry, PPUMode, PixelData, PixelSource,
+    TileData, TileMapEntry, Viewport, DEFAULT_PALETTE, LCD,
+};
+pub use preview::{preview, PREVIEW_FRAMES};
+pub use quirks::{Accuracy, HardwareQuirks};
+pub use ram_init::RamInit;
+#[cfg(feature = "recorder")]
+pub use recorder::Recorder;
+pub use serial::Cable;
+pub use sgb::Screen;
+#[cfg(feature = "std")]
+pub use sound::write_wav;
+pub use stats::FrameStats;
+#[cfg(feature = "std")]
+pub use stats::StatsCollector;
+
+mod arithmetic;
+mod braille;
+mod cartridges;
+mod compat;
+mod cpu;
+mod crc32;
+mod demo;
+#[cfg(feature = "driver")]
+pub mod driver;
+mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod hdma;
+mod interruption;
+mod io;
+mod ioreg;
+mod joypad;
+#[cfg(feature = "std")]
+mod link;
+mod memory_map;
+mod metrics;
+mod mother_board;
+mod oam_dma;
+mod png;
+mod ppu;
+mod preview;
+#[cfg(feature = "pyo3")]
+mod python;
+mod quirks;
+mod ram_init;
+#[cfg(feature = "recorder")]
+mod recorder;
+mod serial;
+mod sgb;
+mod sound;
+mod speed;
+mod stats;
+mod timer;
+
+type Address = u16;
+
+/// ログの target。フロントエンドはこの部品ごとにレベルを決める
+/// mbc はカートリッジ、serial は通信ケーブルを含む
+pub const LOG_TARGETS: [&str; 8] = [
+    "cpu", "ppu", "timer", "mbc", "joypad", "serial", "sound", "sgb",
+];
@@ -0,0 +1,200 @@
+use std::io::{Read, Result, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+
+use crate::serial::Cable;
+
+// 相手からの返信をこれ以上待たない
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 通信ケーブルの接続方法
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkMode {
+    // host:port に接続する
+    Connect(String),
+    // port で接続を待つ
+    Listen(u16),
+}
+
+/// TCP でやりとりする 3 バイトのフレーム
+/// 1バイト目が種類、2バイト目が転送ごとの番号、3バイト目が SB の値
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frame {
+    // 内部クロック側が転送を開始した
+    Transfer { seq: u8, data: u8 },
+    // 外部クロック側からの返信
+    Reply { seq: u8, data: u8 },
+}
+
+impl Frame {
+    fn to_bytes(self) -> [u8; 3] {
+        match self {
+            Frame::Transfer { seq, data } => [0x01, seq, data],
+            Frame::Reply { seq, data } => [0x02, seq, data],
+        }
+    }
+
+    fn from_bytes(bytes: [u8; 3]) -> Option<Self> {
+        match bytes[0] {
+            0x01 => Some(Frame::Transfer {
+                seq: bytes[1],
+                data: bytes[2],
+            }),
+            0x02 => Some(Frame::Reply {
+                seq: bytes[1],
+                data: bytes[2],
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// TCP で繋いだ通信ケーブル
+/// 受信はスレッドで行い、チャネル経由で受け取る
+pub struct Link {
+    stream: TcpStream,
+    rx: Receiver<Frame>,
+    // 内部クロック側で最後に開始した転送の番号
+    seq: u8,
+    // 外部クロック側で、まだ返信していない転送
+    pending: Option<(u8, u8)>,
+    // 切断されたら以降は相手がいない扱いにする
+    connected: bool,
+}
+
+impl Link {
+    pub fn open(mode: &LinkMode) -> Result<Self> {
+        let stream = match mode {
+            LinkMode::Connect(address) => TcpStream::connect(address)?,
+            LinkMode::Listen(port) => {
+                let listener = TcpListener::bind(("0.0.0.0", *port))?;
+                info!(target: "serial", "Waiting for link on port {}", port);
+                listener.accept()?.0
+            }
+        };
+        Self::new(stream)
+    }
+
+    fn new(stream: TcpStream) -> Result<Self> {
+        stream.set_nodelay(true)?;
+        let mut reader = stream.try_clone()?;
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || loop {
+            let mut buf = [0; 3];
+            if reader.read_exact(&mut buf).is_err() {
+                break;
+            }
+            match Frame::from_bytes(buf) {
+                Some(frame) => {
+                    if tx.send(frame).is_err() {
+                        break;
+                    }
+                }
+                None => {
+                    warn!(target: "serial", "link: broken frame {:02X?}", buf);
+                }
+            }
+        });
+        Ok(Self {
+            stream,
+            rx,
+            seq: 0,
+            pending: Option::None,
+            connected: true,
+        })
+    }
+
+    fn send(&mut self, frame: Frame) {
+        if !self.connected {
+            return;
+        }
+        if self.stream.write_all(&frame.to_bytes()).is_err() {
+            warn!(target: "serial", "link: disconnected");
+            self.connected = false;
+        }
+    }
+}
+
+impl Cable for Link {
+    fn start(&mut self, data: u8) {
+        self.seq = self.seq.wrapping_add(1);
+        self.send(Frame::Transfer {
+            seq: self.seq,
+            data,
+        });
+    }
+
+    // 相手がいない、または応答が無ければ 0xFF
+    fn finish(&mut self) -> u8 {
+        while self.connected {
+            match self.rx.recv_timeout(TIMEOUT) {
+                Ok(Frame::Reply { seq, data }) if seq == self.seq => return data,
+                // 古い転送への返信は捨てる
+                Ok(Frame::Reply { .. }) => {}
+                // 両方が内部クロックの場合は相手の転送を待たせておく
+                Ok(Frame::Transfer { seq, data }) => self.pending = Some((seq, data)),
+                Err(RecvTimeoutError::Timeout) => {
+                    debug!(target: "serial", "link: timeout");
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => self.connected = false,
+            }
+        }
+        0xFF
+    }
+
+    fn exchange(&mut self, data: u8) -> Option<u8> {
+        while self.pending.is_none() {
+            match self.rx.try_recv() {
+                Ok(Frame::Transfer { seq, data }) => self.pending = Some((seq, data)),
+                Ok(Frame::Reply { .. }) => {}
+                Err(_) => return None,
+            }
+        }
+        let (seq, received) = self.pending.take().unwrap();
+        self.send(Frame::Reply { seq, data });
+        Some(received)
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        // 受信スレッドも止まるように閉じる
+        self.stream.shutdown(Shutdown::Both).ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exchange() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let mut master = Link::open(&LinkMode::Connect(address)).unwrap();
+        let mut slave = Link::new(listener.accept().unwrap().0).unwrap();
+
+        // 転送が始まるまでは何も受け取らない
+        assert_eq!(slave.exchange(0x22), None);
+        master.start(0x11);
+        let received = loop {
+            if let Some(data) = slave.exchange(0x22) {
+                break data;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+        assert_eq!(received, 0x11);
+        assert_eq!(master.finish(), 0x22);
+
+        // 切断されたら相手がいない扱いになる
+        drop(slave);
+        master.start(0x33);
+        assert_eq!(master.finish(), 0xFF);
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+use core::ops::RangeInclusive;
+
+use crate::Address;
+
+/// アドレスを割り当てる部品
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Device {
+    // ROM と外部 RAM
+    Cartridge,
+    // VRAM、OAM と LCD のレジスタ
+    Ppu,
+    // 作業 RAM(メインメモリ)
+    WorkRam,
+    // 上位RAM スタック用の領域
+    HighRam,
+    // 割り込みフラグと割り込み有効
+    Interruption,
+    Joypad,
+    Serial,
+    Timer,
+    Sound,
+    // OAM DMA
+    OamDma,
+    // CGB の VRAM DMA
+    Hdma,
+    // CGB の倍速モード
+    Speed,
+}
+
+/// アドレスの範囲に対する処理
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Handler {
+    Device(Device),
+    // 指定したバイト数だけ前のアドレスと同じ内容
+    Mirror(Address),
+    // 何も繋がっていない。読むと 0xFF で、書き込みは無視する
+    OpenBus,
+}
+
+// 上位 8bit ごとのページの処理
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Page {
+    // ページ全体が同じ処理
+    Whole(Handler),
+    // IO レジスタのように 1 バイトごとに処理が違う。split の添字
+    Split(usize),
+}
+
+/// (範囲, 処理) の対応表
+/// 後から追加した範囲が優先されるので、ブート ROM のように一部を覆う領域も追加できる
+// resolve はメモリアクセスのたびに呼ばれるので、範囲を探さずにページの表を引く
+#[derive(Debug, Clone)]
+pub struct MemoryMap {
+    regions: Vec<(RangeInclusive<Address>, Handler)>,
+    // address >> 8 で引く。map のたびに regions から作り直す
+    pages: [Page; 256],
+    split: Vec<[Handler; 256]>,
+}
+
+impl Default for MemoryMap {
+    fn default() -> Self {
+        Self {
+            regions: Vec::new(),
+            pages: [Page::Whole(Handler::OpenBus); 256],
+            split: Vec::new(),
+        }
+    }
+}
+
+impl MemoryMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// DMG の割り当て
+    /// https://w.atwiki.jp/gbspec/pages/13.html
+    pub fn dmg() -> Self {
+        let mut map = Self::new();
+        // 0x0000 - 0x3FFF: 16KB ROM バンク0
+        // 0x4000 - 0x7FFF: 16KB ROM バンク1 から N
+        map.map(0x0000..=0x7FFF, Handler::Device(Device::Cartridge));
+        // 0x8000 - 0x9FFF: 8KB VRAM
+        map.map(0x8000..=0x9FFF, Handler::Device(Device::Ppu));
+        // 0xA000 - 0xBFFF: 8KB カートリッジ RAM バンク0 から N
+        map.map(0xA000..=0xBFFF, Handler::Device(Device::Cartridge));
+        // 0xC000 - 0xCFFF: 4KB 作業 RAM(メインメモリ)
+        // 0xD000 - 0xDFFF: 4KB 作業 RAM(メインメモリ)
+        map.map(0xC000..=0xDFFF, Handler::Device(Device::WorkRam));
+        // 0xE000 - 0xFDFF: 0xC000 - 0xDDFF と同じ内容
+        map.map(0xE000..=0xFDFF, Handler::Mirror(0x2000));
+        // 以降はシステム領域（WR信号は外部に出力されずCPU内部で処理される）
+        // 0xFE00 - 0xFE9F: スプライト属性テーブル (OAM)
+        map.map(0xFE00..=0xFE9F, Handler::Device(Device::Ppu));
+        // 以下はI/Oポート
+        map.map(0xFF00..=0xFF00, Handler::Device(Device::Joypad));
+        map.map(0xFF01..=0xFF02, Handler::Device(Device::Serial));
+        map.map(0xFF04..=0xFF07, Handler::Device(Device::Timer));
+        map.map(0xFF0F..=0xFF0F, Handler::Device(Device::Interruption));
+        map.map(0xFF10..=0xFF3F, Handler::Device(Device::Sound));
+        map.map(0xFF40..=0xFF4B, Handler::Device(Device::Ppu));
+        // 0xFF46: OAM DMA
+        map.map(0xFF46..=0xFF46, Handler::Device(Device::OamDma));
+        // 0xFF80 - 0xFFFE: 上位RAM スタック用の領域
+        map.map(0xFF80..=0xFFFE, Handler::Device(Device::HighRam));
+        map.map(0xFFFF..=0xFFFF, Handler::Device(Device::Interruption));
+        map
+    }
+
+    /// CGB の割り当て。DMG の割り当てに CGB だけのレジスタを加える
+    pub fn cgb() -> Self {
+        let mut map = Self::dmg();
+        // 0xFF4D: 倍速モードの切り替え
+        map.map(0xFF4D..=0xFF4D, Handler::Device(Device::Speed));
+        // 0xFF51 - 0xFF55: VRAM DMA
+        map.map(0xFF51..=0xFF55, Handler::Device(Device::Hdma));
+        map
+    }
+
+    /// range を handler に割り当てる。既存の範囲と重なる部分は上書きされる
+    pub fn map(&mut self, range: RangeInclusive<Address>, handler: Handler) {
+        self.regions.push((range, handler));
+        self.rebuild();
+    }
+
+    // 追加した順に範囲を塗って、ページの表を作り直す
+    fn rebuild(&mut self) {
+        self.pages = [Page::Whole(Handler::OpenBus); 256];
+        self.split.clear();
+        for (range, handler) in &self.regions {
+            let (start, end) = (*range.start() as usize, *range.end() as usize);
+            for page in (start >> 8)..=(end >> 8) {
+                let first = start.max(page << 8) & 0xFF;
+                let last = end.min((page << 8) | 0xFF) & 0xFF;
+                if first == 0x00 && last == 0xFF {
+                    self.pages[page] = Page::Whole(*handler);
+                    continue;
+                }
+                let index = match self.pages[page] {
+                    Page::Split(index) => index,
+                    Page::Whole(whole) => {
+                        self.split.push([whole; 256]);
+                        self.pages[page] = Page::Split(self.split.len() - 1);
+                        self.split.len() - 1
+                    }
+                };
+                self.split[index][first..=last].fill(*handler);
+            }
+        }
+    }
+
+    fn handler(&self, address: Address) -> Handler {
+        match self.pages[(address >> 8) as usize] {
+            Page::Whole(handler) => handler,
+            Page::Split(index) => self.split[index][(address & 0xFF) as usize],
+        }
+    }
+
+    /// ミラーを辿って、アクセスする部品とアドレスを返す。None ならオープンバス
+    pub fn resolve(&self, address: Address) -> Option<(Device, Address)> {
+        let mut address = address;
+        loop {
+            match self.handler(address) {
+                Handler::Device(device) => return Some((device, address)),
+                Handler::Mirror(offset) => address -= offset,
+                Handler::OpenBus => return None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dmg() {
+        let map = MemoryMap::dmg();
+        assert_eq!(map.resolve(0x4000), Some((Device::Cartridge, 0x4000)));
+        assert_eq!(map.resolve(0xFE9F), Some((Device::Ppu, 0xFE9F)));
+        assert_eq!(map.resolve(0xFFFF), Some((Device::Interruption, 0xFFFF)));
+        assert_eq!(map.resolve(0xFF46), Some((Device::OamDma, 0xFF46)));
+        // ミラーは元のアドレスに読み替える
+        assert_eq!(map.resolve(0xE123), Some((Device::WorkRam, 0xC123)));
+        // 何も繋がっていない
+        assert_eq!(map.resolve(0xFEA0), None);
+        assert_eq!(map.resolve(0xFF4C), None);
+        assert_eq!(map.resolve(0xFF55), None);
+        assert_eq!(map.resolve(0xFF4D), None);
+    }
+
+    #[test]
+    fn test_cgb() {
+        let map = MemoryMap::cgb();
+        assert_eq!(map.resolve(0xFF51), Some((Device::Hdma, 0xFF51)));
+        assert_eq!(map.resolve(0xFF55), Some((Device::Hdma, 0xFF55)));
+        assert_eq!(map.resolve(0xFF4D), Some((Device::Speed, 0xFF4D)));
+        assert_eq!(map.resolve(0xFF50), None);
+        assert_eq!(map.resolve(0xFF4B), Some((Device::Ppu, 0xFF4B)));
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut map = MemoryMap::dmg();
+        // ブート ROM のように ROM の先頭だけを覆う
+        map.map(0x0000..=0x00FF, Handler::OpenBus);
+        map.map(0x4080..=0x417F, Handler::OpenBus);
+        assert_eq!(map.resolve(0x00FF), None);
+        assert_eq!(map.resolve(0x0100), Some((Device::Cartridge, 0x0100)));
+        assert_eq!(map.resolve(0x407F), Some((Device::Cartridge, 0x407F)));
+        assert_eq!(map.resolve(0x4080), None);
+        assert_eq!(map.resolve(0x417F), None);
+        assert_eq!(map.resolve(0x4180), Some((Device::Cartridge, 0x4180)));
+        // 覆った範囲の上にさらに重ねられる
+        map.map(0x4100..=0x4100, Handler::Device(Device::WorkRam));
+        assert_eq!(map.resolve(0x4100), Some((Device::WorkRam, 0x4100)));
+        assert_eq!(map.resolve(0x4101), None);
+    }
+
+    #[test]
+    fn test_pages() {
+        // ページの表で引いた結果が、範囲を後ろから探した結果と一致する
+        let map = MemoryMap::cgb();
+        for address in 0..=0xFFFF {
+            let expected = map
+                .regions
+                .iter()
+                .rev()
+                .find(|(range, _)| range.contains(&address))
+                .map_or(Handler::OpenBus, |(_, handler)| *handler);
+            assert_eq!(map.handler(address), expected, "0x{:04X}", address);
+        }
+    }
+}
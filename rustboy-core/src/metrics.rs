@@ -0,0 +1,128 @@
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "metrics")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "metrics")]
+use std::net::TcpListener;
+#[cfg(feature = "metrics")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "metrics")]
+use std::thread;
+
+use crate::interruption::Peripheral;
+
+/// 種類ごとの割り込みを処理した回数
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterruptCounts {
+    pub v_blank: u64,
+    pub lcd_stat: u64,
+    pub timer: u64,
+    pub serial: u64,
+    pub joypad: u64,
+}
+
+impl InterruptCounts {
+    pub fn count(&mut self, p: &Peripheral) {
+        match p {
+            Peripheral::VBlank => self.v_blank += 1,
+            Peripheral::LcdStatus => self.lcd_stat += 1,
+            Peripheral::Timer => self.timer += 1,
+            Peripheral::Serial => self.serial += 1,
+            Peripheral::Joypad => self.joypad += 1,
+        }
+    }
+}
+
+/// ヘッドレスで長時間動かすときに監視するためのカウンタ
+/// 各部品が持つ値を MotherBoard::metrics で集める
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Metrics {
+    // 描画したフレーム数
+    pub frames: u64,
+    // リセットからの経過 T-Cycle 数
+    pub cycles: u64,
+    // 実行した命令数。HALT 中と割り込み処理は含まない
+    pub instructions: u64,
+    pub interrupts: InterruptCounts,
+    // ROM/RAM バンクの切り替え回数
+    pub bank_switches: u64,
+}
+
+impl Metrics {
+    /// Prometheus のテキスト形式
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        let counters = [
+            ("frames", "Frames rendered.", self.frames),
+            ("cycles", "T-cycles since reset.", self.cycles),
+            ("instructions", "Instructions executed.", self.instructions),
+            ("bank_switches", "Bank switches.", self.bank_switches),
+        ];
+        for (name, help, value) in counters {
+            text += &format!("# HELP rustboy_{}_total {}\n", name, help);
+            text += &format!("# TYPE rustboy_{}_total counter\n", name);
+            text += &format!("rustboy_{}_total {}\n", name, value);
+        }
+        text += "# HELP rustboy_interrupts_total Interrupts serviced.\n";
+        text += "# TYPE rustboy_interrupts_total counter\n";
+        let i = &self.interrupts;
+        let interrupts = [
+            ("vblank", i.v_blank),
+            ("lcd_stat", i.lcd_stat),
+            ("timer", i.timer),
+            ("serial", i.serial),
+            ("joypad", i.joypad),
+        ];
+        for (kind, value) in interrupts {
+            text += &format!("rustboy_interrupts_total{{type=\"{}\"}} {}\n", kind, value);
+        }
+        text
+    }
+}
+
+/// port で HTTP を待ち受け、どのパスへのリクエストにも最新の Metrics を返す
+/// 返された Metrics をメインループで更新する
+#[cfg(feature = "metrics")]
+pub fn serve(port: u16) -> io::Result<Arc<Mutex<Metrics>>> {
+    let listener = TcpListener::bind(("0.0.0.0", port))?;
+    let metrics = Arc::new(Mutex::new(Metrics::default()));
+    let published = Arc::clone(&metrics);
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            // リクエストの内容は見ない
+            let mut buf = [0; 1024];
+            if stream.read(&mut buf).is_err() {
+                continue;
+            }
+            let body = published.lock().unwrap().to_prometheus();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).ok();
+        }
+    });
+    Ok(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_prometheus() {
+        let mut metrics = Metrics {
+            frames: 3,
+            instructions: 100,
+            ..Metrics::default()
+        };
+        metrics.interrupts.count(&Peripheral::Timer);
+        metrics.interrupts.count(&Peripheral::Timer);
+        let text = metrics.to_prometheus();
+        assert!(text.contains("rustboy_frames_total 3\n"));
+        assert!(text.contains("rustboy_instructions_total 100\n"));
+        assert!(text.contains("rustboy_interrupts_total{type=\"timer\"} 2\n"));
+        assert!(text.contains("rustboy_interrupts_total{type=\"vblank\"} 0\n"));
+    }
+}
@@ -0,0 +1,1220 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::{Cell, Ref, RefCell, RefMut};
+use core::time::Duration;
+
+use log::{debug, info};
+
+#[cfg(feature = "std")]
+use crate::cartridges::MbcSelect;
+use crate::cartridges::Cartridge;
+use crate::cpu::{CpuError, CpuState, CPU};
+use crate::events::{Event, EventBus};
+use crate::hdma::Hdma;
+use crate::interruption::Interruption;
+use crate::io::{Bus, IO};
+use crate::joypad::{JoyPad, KeyBindings, KeySource, NoKeys};
+use crate::memory_map::{Device, MemoryMap};
+use crate::metrics::Metrics;
+use crate::oam_dma::OamDma;
+use crate::ppu::{FrameBuffer, Headless, LCD, PPU};
+use crate::quirks::HardwareQuirks;
+use crate::ram_init::RamInit;
+use crate::serial::{Cable, Serial};
+use crate::sound::Sound;
+use crate::speed::Speed;
+use crate::timer::Timer;
+use crate::Address;
+
+// 0xFFFE - 0xFF80
+pub type Stack = [u8; 128];
+
+#[derive(Debug)]
+pub struct MotherBoard {
+    cpu: Option<RefCell<CPU>>,
+    cartridge: RefCell<Cartridge>,
+    ram: RefCell<[u8; 4 * 1024 * 2]>,
+    stack: RefCell<Stack>,
+    ppu: Option<RefCell<Box<PPU>>>,
+    // 周辺機器と共有して割り込みを要求してもらう
+    interruption: Rc<RefCell<Interruption>>,
+    // 部品からフロントエンドへの通知
+    events: Rc<EventBus>,
+    timer: Option<RefCell<Timer>>,
+    serial: Option<RefCell<Serial>>,
+    sound: RefCell<Box<Sound>>,
+    joypad: RefCell<Box<JoyPad>>,
+    // OAM DMA
+    oam_dma: RefCell<OamDma>,
+    // CGB の VRAM DMA
+    hdma: RefCell<Hdma>,
+    // CGB の倍速モード
+    speed: RefCell<Speed>,
+    // アドレスと部品の対応表。カートリッジを差し替えると CGB 用に変わることがある
+    memory_map: RefCell<MemoryMap>,
+    // リセット時の RAM の中身
+    ram_init: Cell<RamInit>,
+    // リセットからの経過 T-Cycle 数
+    cycles: Cell<u64>,
+    // リセットからの経過時間を等速の T-Cycle 数で数えたもの
+    elapsed: Cell<u64>,
+    // VBlank ごとに書き込み直すアドレスと値(チート)
+    freezes: RefCell<Vec<(Address, u8)>>,
+    // 最後に固定した値を書き込んだときの VBlank の回数
+    frozen_at: Cell<u64>,
+}
+
+impl MotherBoard {
+    /// 描画も標準入力も使わずに構築する
+    /// 時刻や乱数、スレッドに依存しないので、同じROMと入力からは常に同じ状態になる
+    pub fn headless(rom: Vec<u8>) -> Rc<RefCell<Self>> {
+        Self::with_frontend(rom, Box::new(Headless), Box::new(NoKeys))
+    }
+
+    /// 描画先とキー入力を渡して構築する。マイコンの LCD やボタンに繋ぐ場合に使う
+    /// keys は KeyBindings の既定の文字(a, b, 改行, 空白, h, j, k, l)で押されたボタンを返す
+    pub fn with_frontend(
+        rom: Vec<u8>,
+        lcd: Box<dyn LCD>,
+        keys: Box<dyn KeySource>,
+    ) -> Rc<RefCell<Self>> {
+        Self::with_cartridge(
+            Cartridge::from_bytes(rom),
+            lcd,
+            keys,
+            KeyBindings::default(),
+        )
+    }
+
+    /// 読み込んだカートリッジとキー配置を渡して構築する。端末などのフロントエンドが使う
+    pub fn with_cartridge(
+        cartridge: Cartridge,
+        lcd: Box<dyn LCD>,
+        keys: Box<dyn KeySource>,
+        bindings: KeyBindings,
+    ) -> Rc<RefCell<Self>> {
+        info!(target: "mbc", "{:?}", cartridge);
+        if let Some(entry) = cartridge.compat() {
+            info!(target: "mbc", "compat: {:?}", entry);
+        }
+        let sgb = cartridge.supports_sgb();
+        let memory_map = Self::memory_map_for(&cartridge);
+        let interruption = Rc::new(RefCell::new(Interruption::new()));
+        let events = Rc::new(EventBus::new());
+        let joypad = JoyPad::new(keys, bindings, Rc::clone(&interruption));
+        let mb = Rc::new(RefCell::new(Self {
+            cartridge: RefCell::new(cartridge),
+            sound: RefCell::new(Box::new(Sound::new())),
+            joypad: RefCell::new(Box::new(joypad)),
+            oam_dma: RefCell::new(OamDma::new()),
+            hdma: RefCell::new(Hdma::new()),
+            speed: RefCell::new(Speed::new()),
+            interruption: Rc::clone(&interruption),
+            events: Rc::clone(&events),
+            ppu: Option::None,
+            ram: RefCell::new([0; 4 * 1024 * 2]),
+            stack: RefCell::new([0; 128]),
+            timer: Option::None,
+            serial: Option::None,
+            cpu: Option::None,
+            memory_map: RefCell::new(memory_map),
+            ram_init: Cell::new(RamInit::default()),
+            cycles: Cell::new(0),
+            elapsed: Cell::new(0),
+            freezes: RefCell::new(Vec::new()),
+            frozen_at: Cell::new(0),
+        }));
+        let ppu = RefCell::new(Box::new(PPU::new(
+            lcd,
+            Rc::clone(&interruption),
+            Rc::clone(&events),
+        )));
+        if sgb {
+            ppu.borrow_mut().enable_sgb();
+        }
+        let timer = RefCell::new(Timer::new(Rc::clone(&interruption)));
+        let serial = RefCell::new(Serial::new(interruption, events));
+        let cpu = RefCell::new(CPU::new(Rc::<RefCell<MotherBoard>>::downgrade(&mb)));
+        mb.as_ref().borrow_mut().cpu = Option::Some(cpu);
+        mb.as_ref().borrow_mut().ppu = Option::Some(ppu);
+        mb.as_ref().borrow_mut().timer = Option::Some(timer);
+        mb.as_ref().borrow_mut().serial = Option::Some(serial);
+        mb
+    }
+
+    /// 再現する実機の不具合を設定する
+    pub fn set_quirks(&self, quirks: HardwareQuirks) {
+        self.cpu.as_ref().unwrap().borrow_mut().set_quirks(quirks);
+        self.ppu().set_quirks(quirks);
+    }
+
+    /// STOP を NOP として扱う。STOP の後にボタンを選択せずに止まってしまう ROM 用
+    pub fn set_stop_as_nop(&self, stop_as_nop: bool) {
+        self.cpu
+            .as_ref()
+            .unwrap()
+            .borrow_mut()
+            .set_stop_as_nop(stop_as_nop);
+    }
+
+    /// キー入力で押したボタンを自動で離すまでの時間
+    pub fn set_key_hold(&self, hold: Duration) {
+        self.joypad.borrow_mut().set_hold(hold);
+    }
+
+    /// 通信ケーブルを繋ぐ
+    pub fn set_link(&self, cable: Box<dyn Cable>) {
+        self.serial.as_ref().unwrap().borrow_mut().set_link(cable);
+    }
+
+    /// リセット時の作業 RAM、VRAM、HRAM の中身。次の reset から使われる
+    pub fn set_ram_init(&self, init: RamInit) {
+        self.ram_init.set(init);
+    }
+
+    fn memory_map_for(cartridge: &Cartridge) -> MemoryMap {
+        if cartridge.supports_cgb() {
+            MemoryMap::cgb()
+        } else {
+            MemoryMap::dmg()
+        }
+    }
+
+    /// 電源投入直後(ブート ROM の実行後)の状態にする
+    /// 全ての部品をここで初期化する。カートリッジの RAM、描画先やキー入力などの設定は残す
+    pub fn reset(&self) {
+        let sgb = {
+            let mut cartridge = self.cartridge.borrow_mut();
+            cartridge.reset();
+            cartridge.supports_sgb()
+        };
+        self.ppu().reset(sgb);
+        *self.interruption.borrow_mut() = Interruption::new();
+        self.timer.as_ref().unwrap().borrow_mut().reset();
+        self.serial.as_ref().unwrap().borrow_mut().reset();
+        **self.sound.borrow_mut() = Sound::new();
+        self.joypad.borrow_mut().reset();
+        *self.oam_dma.borrow_mut() = OamDma::new();
+        *self.hdma.borrow_mut() = Hdma::new();
+        *self.speed.borrow_mut() = Speed::new();
+        let init = self.ram_init.get();
+        info!(target: "cpu", "RAM init: {}", init);
+        init.fill(&mut [
+            &mut self.ram.borrow_mut()[..],
+            self.ppu().vram_mut(),
+            &mut self.stack.borrow_mut()[..],
+        ]);
+        // IO レジスタの初期値は CPU がバスを通して書き込む
+        self.cpu.as_ref().unwrap().borrow_mut().reset();
+        self.cycles.set(0);
+        self.elapsed.set(0);
+    }
+
+    /// カートリッジを差し替えてリセットする。フロントエンドの「ROM を開く」に使う
+    /// 差し替える前のカートリッジのセーブデータは呼び出し側で書き出しておく
+    pub fn load_cartridge(&self, cartridge: Cartridge) {
+        info!(target: "mbc", "{:?}", cartridge);
+        if let Some(entry) = cartridge.compat() {
+            info!(target: "mbc", "compat: {:?}", entry);
+        }
+        *self.memory_map.borrow_mut() = Self::memory_map_for(&cartridge);
+        *self.cartridge.borrow_mut() = cartridge;
+        self.reset();
+    }
+
+    /// ROM ファイルを読み込んで差し替える。読み込めなければ今のカートリッジのまま
+    #[cfg(feature = "std")]
+    pub fn load_rom(&self, path: &str) -> Result<(), String> {
+        let cartridge = Cartridge::open(path, MbcSelect::Header)?;
+        self.load_cartridge(cartridge);
+        Ok(())
+    }
+
+    /// 1命令実行する
+    pub fn step(&self) -> Result<(u16, u8), CpuError> {
+        // 周辺機器は CPU のメモリアクセスごとに Bus::tick で進める
+        let result = self.cpu.as_ref().unwrap().borrow_mut().tick();
+        self.apply_freezes();
+        // 部品の借用が終わってから通知する
+        self.events.dispatch();
+        result
+    }
+
+    /// address の値を value に固定する。すぐに書き込み、その後は VBlank に入るたびに書き込み直す
+    /// 作業 RAM、HRAM、カートリッジの RAM を固定できる。リセットしても固定したまま
+    pub fn freeze(&self, address: Address, value: u8) -> Result<(), String> {
+        if !matches!(address, 0xA000..=0xFDFF | 0xFF80..=0xFFFE) {
+            return Err(format!("Cannot freeze 0x{:04X}: not RAM", address));
+        }
+        let mut freezes = self.freezes.borrow_mut();
+        match freezes.iter_mut().find(|(a, _)| *a == address) {
+            Some(freeze) => freeze.1 = value,
+            None => freezes.push((address, value)),
+        }
+        self.poke(address, value);
+        Ok(())
+    }
+
+    /// 固定をやめる。固定していなければ false
+    pub fn unfreeze(&self, address: Address) -> bool {
+        let mut freezes = self.freezes.borrow_mut();
+        let len = freezes.len();
+        freezes.retain(|(a, _)| *a != address);
+        freezes.len() != len
+    }
+
+    /// 固定しているアドレスと値(固定した順)
+    pub fn freezes(&self) -> Vec<(Address, u8)> {
+        self.freezes.borrow().clone()
+    }
+
+    // VBlank に入っていれば、フレームを LCD に渡す前に固定した値を書き込み直す
+    fn apply_freezes(&self) {
+        let vblanks = self.ppu().vblanks();
+        if vblanks == self.frozen_at.get() {
+            return;
+        }
+        self.frozen_at.set(vblanks);
+        for &(address, value) in self.freezes.borrow().iter() {
+            self.poke(address, value);
+        }
+    }
+
+    /// VBlank、モードの変化、シリアル転送の完了、バンク切り替えなどの通知を受け取る
+    /// 通知は命令の実行ごとにまとめて届くので、購読者から MotherBoard を借用できる
+    pub fn subscribe(&self, subscriber: impl FnMut(&Event) + 'static) {
+        self.events.subscribe(Box::new(subscriber));
+    }
+
+    /// 次の VBlank に入るまで実行して、描画し終えたフレームを返す
+    /// VBlank 割り込みは次の step で処理される
+    pub fn run_until_vblank(&self) -> Result<Arc<FrameBuffer>, CpuError> {
+        let vblanks = self.ppu().vblanks();
+        while self.ppu().vblanks() == vblanks {
+            self.step()?;
+        }
+        Ok(self.ppu().front_buffer())
+    }
+
+    /// リセットからの経過 T-Cycle 数
+    pub fn cycles(&self) -> u64 {
+        self.cycles.get()
+    }
+
+    /// リセットからの経過時間を等速の T-Cycle 数で表したもの
+    /// 倍速モードでは cycles の半分の速さで進むので、実時間との比較にはこちらを使う
+    pub fn elapsed(&self) -> u64 {
+        self.elapsed.get()
+    }
+
+    /// CPU のレジスタ
+    pub fn cpu_state(&self) -> CpuState {
+        self.cpu.as_ref().unwrap().borrow().state()
+    }
+
+    /// 描画したフレーム数
+    pub fn frames(&self) -> u64 {
+        self.ppu.as_ref().unwrap().borrow().frames()
+    }
+
+    /// 監視用のカウンタ
+    pub fn metrics(&self) -> Metrics {
+        let cpu = self.cpu.as_ref().unwrap().borrow();
+        Metrics {
+            frames: self.frames(),
+            cycles: self.cycles(),
+            instructions: cpu.instructions(),
+            interrupts: cpu.interrupts(),
+            bank_switches: self.cartridge.borrow().bank_switches(),
+        }
+    }
+
+    /// address から len バイト読み込む。0xFFFF の次は 0x0000 に戻る
+    /// メモリビューア向けに、IO レジスタは状態を変えずに読む(peek)
+    pub fn read_range(&self, address: Address, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|i| self.peek(address.wrapping_add(i as u16)))
+            .collect()
+    }
+
+    /// data を address から順に CPU と同じように書き込む
+    pub fn write_range(&self, address: Address, data: &[u8]) {
+        for (i, byte) in data.iter().enumerate() {
+            self.write(address.wrapping_add(i as u16), *byte);
+        }
+    }
+
+    /// ROM 以外の全状態をバイト列に書き出す。2つの実行結果の比較に使う
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut state = Vec::new();
+        state.extend(self.cycles().to_le_bytes());
+        // 乱数で埋めた場合も seed から同じ状態を作り直せる
+        self.ram_init.get().save_state(&mut state);
+        self.cpu.as_ref().unwrap().borrow().save_state(&mut state);
+        self.cartridge.borrow().save_state(&mut state);
+        state.extend(self.ram.borrow().iter());
+        state.extend(self.stack.borrow().iter());
+        self.ppu.as_ref().unwrap().borrow().save_state(&mut state);
+        self.interruption.borrow().save_state(&mut state);
+        self.timer.as_ref().unwrap().borrow().save_state(&mut state);
+        self.serial
+            .as_ref()
+            .unwrap()
+            .borrow()
+            .save_state(&mut state);
+        self.sound.borrow().save_state(&mut state);
+        self.joypad.borrow().save_state(&mut state);
+        self.oam_dma.borrow().save_state(&mut state);
+        self.hdma.borrow().save_state(&mut state);
+        self.speed.borrow().save_state(&mut state);
+        state
+    }
+
+    // OAM DMA で 160 バイトを OAM に転送する。転送にかかる時間はまだ再現せず、すぐに終わる
+    // 転送元の読み替えは OamDma が行うので、I/O ポートを読んで副作用が起きることは無い
+    fn transfer_oam_dma(&self) {
+        let sources: Vec<Address> = self.oam_dma.borrow().sources().collect();
+        for (i, source) in sources.into_iter().enumerate() {
+            let data = self.read(source);
+            self.ppu().write(0xFE00 + i as Address, data);
+        }
+        self.events.publish(Event::DmaDone);
+    }
+
+    // VRAM DMA で 16 バイトずつ VRAM に転送する
+    // 転送している間は CPU が止まるので、1ブロックにつき 8 M-Cycle だけ周辺機器を進める
+    // 転送にかかる時間は速度によらないので、倍速モードでは 16 M-Cycle になる
+    fn transfer_hdma(&self) {
+        let mut blocks = 0;
+        loop {
+            let block = self.hdma.borrow_mut().next_block();
+            match block {
+                Some((source, destination)) => {
+                    for i in 0..0x10 {
+                        self.write(destination + i, self.read(source.wrapping_add(i)));
+                    }
+                    blocks += 1;
+                }
+                None => break,
+            }
+        }
+        let factor = self.speed.borrow().factor();
+        for _ in 0..blocks * 8 * factor as usize {
+            self.tick(4);
+        }
+    }
+}
+
+// 以下はデバッガ用
+impl MotherBoard {
+    pub fn cpu(&self) -> Ref<'_, CPU> {
+        self.cpu.as_ref().unwrap().borrow()
+    }
+    pub fn stack(&self) -> Ref<'_, Stack> {
+        self.stack.borrow()
+    }
+    pub fn ppu(&self) -> RefMut<'_, Box<PPU>> {
+        self.ppu.as_ref().unwrap().borrow_mut()
+    }
+    pub fn interruption(&self) -> Ref<'_, Interruption> {
+        self.interruption.borrow()
+    }
+    pub fn cartridge(&self) -> RefMut<'_, Cartridge> {
+        self.cartridge.borrow_mut()
+    }
+    pub fn timer(&self) -> Ref<'_, Timer> {
+        self.timer.as_ref().unwrap().borrow()
+    }
+    pub fn serial(&self) -> Ref<'_, Serial> {
+        self.serial.as_ref().unwrap().borrow()
+    }
+    pub fn sound(&self) -> Ref<'_, Box<Sound>> {
+        self.sound.borrow()
+    }
+}
+
+impl Bus for MotherBoard {
+    // メモリから1バイト読み込む
+    fn read(&self, address: Address) -> u8 {
+        let (device, address) = match self.memory_map.borrow().resolve(address) {
+            Some(resolved) => resolved,
+            // 何も繋がっていない
+            None => {
+                debug!(target: "cpu", "ignored: {:X?}", address);
+                return 0xFF;
+            }
+        };
+        match device {
+            Device::Cartridge => self.cartridge.borrow().read(address),
+            Device::Ppu => self.ppu.as_ref().unwrap().borrow().read(address),
+            Device::WorkRam => self.ram.borrow()[(address - 0xC000) as usize],
+            Device::HighRam => self.stack.borrow()[(address - 0xFF80) as usize],
+            Device::Interruption => self.interruption.borrow().read(address),
+            Device::Joypad => {
+                let joypad = self.joypad.borrow();
+                let data = joypad.read(address);
+                self.ppu().set_input(joypad.pressed());
+                // SGB の複数人モードではコントローラの番号が読める
+                match self.ppu.as_ref().unwrap().borrow().sgb() {
+                    Some(sgb) => sgb.read(data),
+                    None => data,
+                }
+            }
+            Device::Serial => self.serial.as_ref().unwrap().borrow().read(address),
+            Device::Timer => self.timer.as_ref().unwrap().borrow().read(address),
+            Device::Sound => self.sound.borrow().read(address),
+            Device::OamDma => self.oam_dma.borrow().read(address),
+            Device::Hdma => self.hdma.borrow().read(address),
+            Device::Speed => self.speed.borrow().read(address),
+        }
+    }
+
+    // 周辺機器の状態を変えずに1バイト読み込む
+    fn peek(&self, address: Address) -> u8 {
+        match self.memory_map.borrow().resolve(address) {
+            Some((Device::Joypad, address)) => {
+                let data = self.joypad.borrow().peek(address);
+                match self.ppu.as_ref().unwrap().borrow().sgb() {
+                    Some(sgb) => sgb.read(data),
+                    None => data,
+                }
+            }
+            // ジョイパッド以外は読み込みに副作用が無い
+            _ => self.read(address),
+        }
+    }
+
+    // メモリに1バイト書き込む
+    fn write(&self, address: Address, data: u8) {
+        let (device, address) = match self.memory_map.borrow().resolve(address) {
+            Some(resolved) => resolved,
+            None => {
+                debug!(target: "cpu", "ignored: {:X?}", address);
+                return;
+            }
+        };
+        match device {
+            Device::Cartridge => {
+                let mut cartridge = self.cartridge.borrow_mut();
+                let switches = cartridge.bank_switches();
+                cartridge.write(address, data);
+                if cartridge.bank_switches() != switches {
+                    self.events
+                        .publish(Event::BankSwitch(cartridge.current_rom_bank()));
+                }
+            }
+            Device::Ppu => self.ppu.as_ref().unwrap().borrow_mut().write(address, data),
+            Device::WorkRam => self.ram.borrow_mut()[(address - 0xC000) as usize] = data,
+            Device::HighRam => self.stack.borrow_mut()[(address - 0xFF80) as usize] = data,
+            Device::Interruption => self.interruption.borrow_mut().write(address, data),
+            Device::Joypad => {
+                self.joypad.borrow_mut().write(address, data);
+                // SGB のコマンドパケットもジョイパッドのポートから送られる
+                self.ppu.as_ref().unwrap().borrow_mut().write_sgb(data);
+            }
+            Device::Serial => self
+                .serial
+                .as_ref()
+                .unwrap()
+                .borrow_mut()
+                .write(address, data),
+            Device::Timer => self
+                .timer
+                .as_ref()
+                .unwrap()
+                .borrow_mut()
+                .write(address, data),
+            Device::Sound => self.sound.borrow_mut().write(address, data),
+            Device::OamDma => {
+                self.oam_dma.borrow_mut().write(address, data);
+                self.transfer_oam_dma();
+            }
+            Device::Hdma => {
+                self.hdma.borrow_mut().write(address, data);
+                // GDMA はすぐに転送する
+                self.transfer_hdma();
+            }
+            Device::Speed => self.speed.borrow_mut().write(address, data),
+        }
+    }
+    // 周辺機器を動かさずに1バイト書き込む。デバッガがメモリを書き換えるのに使う
+    fn poke(&self, address: Address, data: u8) {
+        match self.memory_map.borrow().resolve(address) {
+            // ROM への書き込みはバンクを切り替えてしまうので無視する
+            Some((Device::Cartridge, 0x0000..=0x7FFF)) => {}
+            // SGB のコマンドパケットとして扱わない
+            Some((Device::Joypad, address)) => self.joypad.borrow_mut().poke(address, data),
+            // 読み込み専用の LY も書き換える
+            Some((Device::Ppu, address)) => {
+                self.ppu.as_ref().unwrap().borrow_mut().poke(address, data)
+            }
+            Some((Device::Timer, address)) => self
+                .timer
+                .as_ref()
+                .unwrap()
+                .borrow_mut()
+                .poke(address, data),
+            _ => self.write(address, data),
+        }
+    }
+    fn corrupt_oam(&self, address: Address) {
+        self.ppu().corrupt_oam(address);
+    }
+    fn stop(&self) -> bool {
+        // STOP を実行すると DIV がリセットされる
+        self.timer
+            .as_ref()
+            .unwrap()
+            .borrow_mut()
+            .write(0xFF04, 0x00);
+        if self.speed.borrow_mut().switch() {
+            return true;
+        }
+        self.ppu().set_stopped(true);
+        false
+    }
+    // STOP 中は CPU、タイマー、シリアル、サウンドの時計が止まる
+    // キーを離せるようにジョイパッドは進め、フロントエンドにフレームを渡せるように LCD も進める
+    fn stopped(&self, cycles: u8) -> bool {
+        let normal = self.speed.borrow().to_normal(cycles);
+        self.ppu.as_ref().unwrap().borrow_mut().tick(normal);
+        self.joypad.borrow_mut().tick(normal);
+        self.elapsed.set(self.elapsed.get() + normal as u64);
+        // P1 で選択中のボタンが押されると入力線が下がって目覚める
+        let woke = self.joypad.borrow().read(0xFF00) & 0x0F != 0x0F;
+        if woke {
+            self.ppu().set_stopped(false);
+        }
+        woke
+    }
+    // 中央のスケジューラ。CPU が 1 M-Cycle 進めるたびに呼ばれる
+    // OAM DMA とサウンドはまだ時間の概念を持たないので進めない
+    // 倍速モードでは PPU、VRAM DMA、ジョイパッドの時計は等速のまま、タイマーとシリアルは CPU と同じ速さで進む
+    fn tick(&self, cycles: u8) {
+        let normal = self.speed.borrow().to_normal(cycles);
+        self.ppu.as_ref().unwrap().borrow_mut().tick(normal);
+        let hblank = self.ppu().in_hblank();
+        self.hdma.borrow_mut().hblank(hblank);
+        self.transfer_hdma();
+        self.joypad.borrow_mut().tick(normal);
+        self.timer.as_ref().unwrap().borrow_mut().tick(cycles);
+        self.serial.as_ref().unwrap().borrow_mut().tick(cycles);
+        self.cycles.set(self.cycles.get() + cycles as u64);
+        self.elapsed.set(self.elapsed.get() + normal as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo;
+    use crate::ppu::PPUMode;
+    use crate::quirks::Accuracy;
+
+    // 0x0150 から program を配置した 32KB の ROM を作る
+    fn build_rom(program: &[u8]) -> Vec<u8> {
+        let mut rom = demo::build_rom(0x8000, b"TESTROM", program);
+        // VBlank 割り込みは RETI するだけ
+        rom[0x40] = 0xD9;
+        // Timer 割り込みも同様
+        rom[0x50] = 0xD9;
+        rom
+    }
+
+    // CGB に対応した ROM
+    fn build_cgb_rom(program: &[u8]) -> Vec<u8> {
+        let mut rom = build_rom(program);
+        rom[0x143] = 0x80;
+        demo::set_header_checksum(&mut rom);
+        rom
+    }
+
+    // タイマーとVBlankの割り込みを有効にして、WRAMに書き込み続ける
+    const PROGRAM: [u8; 21] = [
+        0x3E, 0x05, // LD A, 0x05
+        0xE0, 0xFF, // LDH (0xFF), A   IE = VBlank | Timer
+        0xE0, 0x07, // LDH (0x07), A   TAC = 262144Hz で開始
+        0xFB, // EI
+        0x21, 0x00, 0xC0, // LD HL, 0xC000
+        // loop:
+        0x04, // INC B
+        0x78, // LD A, B
+        0x22, // LD (HL+), A
+        0x7C, // LD A, H
+        0xE6, 0x1F, // AND 0x1F
+        0xF6, 0xC0, // OR 0xC0
+        0x67, // LD H, A
+        0x18, 0xF5, // JR loop
+    ];
+
+    fn run_headless(steps: usize) -> (u64, Vec<u8>) {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        for _ in 0..steps {
+            mb.step().unwrap();
+        }
+        (mb.cycles(), mb.save_state())
+    }
+
+    #[test]
+    fn test_cycles() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        assert_eq!(mb.cycles(), 0);
+        // NOP, JP a16
+        let (_, nop) = mb.step().unwrap();
+        let (_, jp) = mb.step().unwrap();
+        assert_eq!(mb.cycles(), (nop + jp) as u64);
+        assert_eq!(mb.cycles(), 4 + 16);
+    }
+
+    #[test]
+    fn test_branch_cycles() {
+        // XOR A; JR NZ, 0; JR Z, 0
+        let mb = MotherBoard::headless(build_rom(&[0xAF, 0x20, 0x00, 0x28, 0x00]));
+        let mb = mb.borrow();
+        mb.reset();
+        mb.step().unwrap();
+        mb.step().unwrap();
+        let start = mb.cycles();
+        // 分岐しなかった JR は 8、分岐した JR は 12 サイクル進める
+        mb.step().unwrap();
+        mb.step().unwrap();
+        assert_eq!(mb.cycles() - start, 4 + 8);
+        mb.step().unwrap();
+        assert_eq!(mb.cycles() - start, 4 + 8 + 12);
+    }
+
+    #[test]
+    fn test_run_until_vblank() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        mb.run_until_vblank().unwrap();
+        // 144 ライン目に入ったところで止まり、割り込みはまだ処理されていない
+        assert_eq!(mb.read(0xFF44), 144);
+        assert_eq!(mb.read(0xFF0F) & 0b_0000_0001, 0b_0000_0001);
+        let start = mb.cycles();
+        mb.run_until_vblank().unwrap();
+        assert_eq!(mb.read(0xFF44), 144);
+        assert!((mb.cycles() - start).abs_diff(70224) < 24);
+        assert_eq!(mb.ppu().vblanks(), 2);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        for _ in 0..100_000 {
+            mb.step().unwrap();
+        }
+        let metrics = mb.metrics();
+        assert_eq!(metrics.frames, mb.frames());
+        assert_eq!(metrics.cycles, mb.cycles());
+        // 割り込み処理は直後の命令と同じ step で行われる
+        let i = metrics.interrupts;
+        assert_eq!(metrics.instructions, 100_000);
+        assert_eq!(i.v_blank, metrics.frames);
+        assert!(i.timer > 0);
+        assert_eq!((i.lcd_stat, i.serial, i.joypad), (0, 0, 0));
+        assert_eq!(metrics.bank_switches, 0);
+    }
+
+    #[test]
+    fn test_events() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let e = Rc::clone(&events);
+        mb.subscribe(move |event| e.borrow_mut().push(*event));
+        mb.reset();
+        mb.run_until_vblank().unwrap();
+        // VBlank に入った命令の step で届く
+        let events = events.borrow();
+        assert_eq!(
+            events[events.len() - 2..],
+            [Event::VBlank, Event::ModeChange(PPUMode::VBlank)]
+        );
+        // リセット直後は VBlank 中なので、144 ライン分の HBlank を通る
+        let hblanks = events
+            .iter()
+            .filter(|e| **e == Event::ModeChange(PPUMode::HBlank))
+            .count();
+        assert_eq!(hblanks, 144);
+        assert_eq!(
+            events[..3],
+            [
+                Event::ModeChange(PPUMode::VBlank),
+                Event::ModeChange(PPUMode::OAMScan),
+                Event::ModeChange(PPUMode::Drawing),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_serial_event() {
+        let mb = start_serial(0x81);
+        let mb = mb.borrow();
+        let transferred = Rc::new(Cell::new(None));
+        let t = Rc::clone(&transferred);
+        mb.subscribe(move |event| {
+            if let Event::SerialTransferred(data) = event {
+                t.set(Some(*data));
+            }
+        });
+        while transferred.get().is_none() {
+            mb.step().unwrap();
+        }
+        assert_eq!(transferred.get(), Some(0xFF));
+    }
+
+    #[test]
+    fn test_serial_sent() {
+        // Blargg のテスト ROM と同じく SB に書いてから SC に 0x81 を書いて送る
+        let mb = MotherBoard::headless(build_rom(&[
+            0x3E, 0x41, // LD A, 0x41
+            0xE0, 0x01, // LDH (0x01), A
+            0x3E, 0x81, // LD A, 0x81
+            0xE0, 0x02, // LDH (0x02), A
+            0x18, 0xFE, // JR -2
+        ]));
+        let mb = mb.borrow();
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let s = Rc::clone(&sent);
+        mb.subscribe(move |event| {
+            if let Event::SerialSent(data) = event {
+                s.borrow_mut().push(*data);
+            }
+        });
+        mb.reset();
+        // NOP, JP, LD, LDH, LD, LDH
+        for _ in 0..6 {
+            mb.step().unwrap();
+        }
+        assert_eq!(*sent.borrow(), vec![0x41]);
+    }
+
+    #[test]
+    fn test_poke() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        mb.poke(0xC000, 0x42);
+        assert_eq!(mb.peek(0xC000), 0x42);
+        // バンクは切り替わらない
+        mb.poke(0x2000, 0x02);
+        assert_eq!(mb.cartridge().bank_switches(), 0);
+        assert_eq!(mb.peek(0x0150), PROGRAM[0]);
+        // DIV はリセットされない
+        mb.poke(0xFF04, 0x12);
+        assert_eq!(mb.peek(0xFF04), 0x12);
+        mb.write(0xFF04, 0x12);
+        assert_eq!(mb.peek(0xFF04), 0x00);
+    }
+
+    #[test]
+    fn test_open_bus() {
+        // 何も繋がっていないアドレスは CPU から読んでも 0xFF
+        let mb = MotherBoard::headless(build_rom(&[
+            0xFA, 0x4C, 0xFF, // LD A, (0xFF4C)
+            0xEA, 0x00, 0xC0, // LD (0xC000), A
+            0xFA, 0xA0, 0xFE, // LD A, (0xFEA0)
+            0xEA, 0x01, 0xC0, // LD (0xC001), A
+            0x18, 0xFE, // JR -2
+        ]));
+        let mb = mb.borrow();
+        mb.reset();
+        for _ in 0..6 {
+            mb.step().unwrap();
+        }
+        assert_eq!(mb.peek(0xC000), 0xFF);
+        assert_eq!(mb.peek(0xC001), 0xFF);
+    }
+
+    #[test]
+    fn test_oam_dma() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        for i in 0..0xA0 {
+            mb.write(0xC100 + i, i as u8);
+            mb.write(0xDE00 + i, 0xFF - i as u8);
+            mb.write(0x8000 + i, 0x80 | i as u8);
+        }
+        mb.write(0xFF46, 0xC1);
+        assert_eq!(mb.read(0xFF46), 0xC1);
+        assert_eq!(mb.read(0xFE00), 0x00);
+        assert_eq!(mb.read(0xFE9F), 0x9F);
+        // 作業 RAM のミラーからも転送できる
+        mb.write(0xFF46, 0xE1);
+        assert_eq!(mb.read(0xFE9F), 0x9F);
+        // 0xFE00 以降を指定すると OAM 自身ではなく 0xDE00 から読まれる
+        mb.write(0xFF46, 0xFE);
+        assert_eq!(mb.read(0xFE00), 0xFF);
+        assert_eq!(mb.read(0xFE9F), 0x60);
+        // VRAM からも転送できる
+        mb.write(0xFF46, 0x80);
+        assert_eq!(mb.read(0xFE01), 0x81);
+    }
+
+    #[test]
+    fn test_gdma() {
+        let mb = MotherBoard::headless(build_cgb_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        // 0x0150 から 0x8000 に 2 ブロック
+        for (address, data) in [
+            (0xFF51, 0x01),
+            (0xFF52, 0x50),
+            (0xFF53, 0x80),
+            (0xFF54, 0x00),
+        ] {
+            mb.write(address, data);
+        }
+        mb.write(0xFF55, 0x01);
+        assert_eq!(mb.read_range(0x8000, PROGRAM.len()), PROGRAM);
+        assert_eq!(mb.read(0xFF55), 0xFF);
+        // 転送の間 CPU は止まる
+        assert_eq!(mb.cycles(), 2 * 8 * 4);
+
+        // DMG の ROM では何も繋がっていない
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.write(0xFF55, 0x01);
+        assert_eq!(mb.read(0xFF55), 0xFF);
+        assert_eq!(mb.cycles(), 0);
+    }
+
+    #[test]
+    fn test_hdma() {
+        let mb = MotherBoard::headless(build_cgb_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        for (address, data) in [
+            (0xFF51, 0x01),
+            (0xFF52, 0x50),
+            (0xFF53, 0x80),
+            (0xFF54, 0x00),
+        ] {
+            mb.write(address, data);
+        }
+        mb.write(0xFF55, 0x81);
+        assert_eq!(mb.read(0xFF55), 0x01);
+        assert_eq!(mb.peek(0x8000), 0x00);
+        // HBlank ごとに 1 ブロックずつ
+        let hblanks = Rc::new(Cell::new(0));
+        let h = Rc::clone(&hblanks);
+        mb.subscribe(move |event| {
+            if *event == Event::ModeChange(PPUMode::HBlank) {
+                h.set(h.get() + 1);
+            }
+        });
+        while mb.read(0xFF55) != 0xFF {
+            mb.step().unwrap();
+        }
+        assert_eq!(hblanks.get(), 2);
+        assert_eq!(mb.read_range(0x8000, PROGRAM.len()), PROGRAM);
+    }
+
+    #[test]
+    fn test_freeze() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        mb.freeze(0xC000, 0x99).unwrap();
+        mb.freeze(0xFF80, 0x01).unwrap();
+        mb.freeze(0xFF80, 0x02).unwrap();
+        assert_eq!(mb.freezes(), [(0xC000, 0x99), (0xFF80, 0x02)]);
+        assert!(mb.freeze(0x4000, 0x01).is_err());
+        assert!(mb.freeze(0xFF40, 0x01).is_err());
+        // プログラムが作業 RAM を書き換え続けても、VBlank で書き込み直す
+        mb.run_until_vblank().unwrap();
+        mb.run_until_vblank().unwrap();
+        assert_eq!(mb.read(0xC000), 0x99);
+        assert_eq!(mb.read(0xFF80), 0x02);
+        assert!(mb.unfreeze(0xC000));
+        assert!(!mb.unfreeze(0xC000));
+        assert_eq!(mb.freezes(), [(0xFF80, 0x02)]);
+        // 8KB を書き終えるまで進める
+        for _ in 0..8 {
+            mb.run_until_vblank().unwrap();
+        }
+        assert_ne!(mb.read(0xC000), 0x99);
+    }
+
+    #[test]
+    fn test_stop() {
+        let program = [
+            0x3E, 0x10, // LD A, 0x10
+            0xE0, 0x00, // LDH (0x00), A   P1 = ボタンを選択
+            0x10, 0x00, // STOP
+            0x04, // INC B
+            0x18, 0xFE, // JR -2
+        ];
+        let (tx, rx) = std::sync::mpsc::channel::<String>();
+        let mb = MotherBoard::with_frontend(build_rom(&program), Box::new(Headless), Box::new(rx));
+        let mb = mb.borrow();
+        mb.reset();
+        // NOP, JP, LD, LDH
+        for _ in 0..4 {
+            mb.step().unwrap();
+        }
+        mb.poke(0xFF04, 0x12);
+        mb.step().unwrap();
+        assert!(mb.cpu_state().stopped);
+        assert_eq!(mb.read(0xFF04), 0x00);
+        // 止まっている間も LCD はフレームを渡し続けるが、CPU とタイマーは進まない
+        let (cycles, frames) = (mb.cycles(), mb.frames());
+        mb.run_until_vblank().unwrap();
+        mb.run_until_vblank().unwrap();
+        assert!(mb.frames() > frames);
+        assert_eq!(mb.cycles(), cycles);
+        assert_eq!(mb.read(0xFF04), 0x00);
+        assert_eq!(mb.cpu_state().pc, 0x0156);
+        // 選択していない十字キーでは目覚めない
+        tx.send("l".to_string()).unwrap();
+        mb.step().unwrap();
+        assert!(mb.cpu_state().stopped);
+        tx.send("a".to_string()).unwrap();
+        mb.step().unwrap();
+        assert!(!mb.cpu_state().stopped);
+        mb.step().unwrap();
+        assert_eq!(mb.cpu_state().b, 0x01);
+
+        // NOP として扱えば止まらずに次の命令に進む
+        mb.reset();
+        mb.set_stop_as_nop(true);
+        for _ in 0..6 {
+            mb.step().unwrap();
+        }
+        assert!(!mb.cpu_state().stopped);
+        assert_eq!(mb.cpu_state().b, 0x01);
+    }
+
+    #[test]
+    fn test_double_speed() {
+        let program = [
+            0x3E, 0x01, // LD A, 0x01
+            0xE0, 0x4D, // LDH (0x4D), A   KEY1 = 切り替えの準備
+            0x10, 0x00, // STOP
+            0x18, 0xFE, // JR -2
+        ];
+        let mb = MotherBoard::headless(build_cgb_rom(&program));
+        let mb = mb.borrow();
+        mb.reset();
+        // NOP, JP, LD, LDH
+        for _ in 0..4 {
+            mb.step().unwrap();
+        }
+        assert_eq!(mb.read(0xFF4D), 0x7F);
+        assert_eq!(mb.cycles(), mb.elapsed());
+        mb.poke(0xFF04, 0x12);
+        mb.step().unwrap();
+        // 切り替えると DIV がリセットされる
+        assert_eq!(mb.read(0xFF4D), 0xFE);
+        assert_eq!(mb.read(0xFF04), 0x00);
+
+        // 1フレームの間に CPU は 2 倍の T-Cycle を進める
+        mb.run_until_vblank().unwrap();
+        let (cycles, elapsed) = (mb.cycles(), mb.elapsed());
+        mb.run_until_vblank().unwrap();
+        assert!((mb.elapsed() - elapsed).abs_diff(70224) < 24);
+        assert!((mb.cycles() - cycles).abs_diff(70224 * 2) < 24);
+
+        // DMG の ROM では切り替わらない
+        let mb = MotherBoard::headless(build_rom(&program));
+        let mb = mb.borrow();
+        mb.reset();
+        for _ in 0..5 {
+            mb.step().unwrap();
+        }
+        assert_eq!(mb.read(0xFF4D), 0xFF);
+        assert_eq!(mb.cycles(), mb.elapsed());
+    }
+
+    #[test]
+    fn test_stat_write() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        // VBlank 中に STAT に書き込む
+        mb.run_until_vblank().unwrap();
+        mb.write(0xFF0F, 0x00);
+        mb.write(0xFF41, 0x00);
+        assert_eq!(mb.read(0xFF0F) & 0b_0000_0010, 0);
+
+        mb.set_quirks(HardwareQuirks::from(Accuracy::Accurate));
+        mb.write(0xFF41, 0x00);
+        assert_eq!(mb.read(0xFF0F) & 0b_0000_0010, 0b_0000_0010);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        // 数フレーム分実行して、状態が完全に一致することを確認する
+        let (cycles_a, state_a) = run_headless(100_000);
+        let (cycles_b, state_b) = run_headless(100_000);
+        assert!(cycles_a > 70224 * 3);
+        assert_eq!(cycles_a, cycles_b);
+        assert!(state_a == state_b);
+
+        // 実行数が違えば状態も違う
+        let (_, state_c) = run_headless(100_001);
+        assert!(state_a != state_c);
+    }
+
+    // SB に 0x41 を書き込んで SC に control を書き込む
+    fn start_serial(control: u8) -> Rc<RefCell<MotherBoard>> {
+        let mb = MotherBoard::headless(build_rom(&[
+            0x3E, 0x41, // LD A, 0x41
+            0xE0, 0x01, // LDH (0x01), A
+            0x3E, control, // LD A, control
+            0xE0, 0x02, // LDH (0x02), A
+            0x18, 0xFE, // JR -2
+        ]));
+        mb.borrow().reset();
+        // NOP, JP, LD, LDH, LD, LDH
+        for _ in 0..6 {
+            mb.borrow().step().unwrap();
+        }
+        mb
+    }
+
+    #[test]
+    fn test_serial_internal_clock() {
+        let mb = start_serial(0x81);
+        let mb = mb.borrow();
+        let start = mb.cycles();
+        // 8192Hz で 8bit 送るので 4096 T-Cycle かかる
+        while mb.cycles() - start < 4096 - 12 {
+            assert_eq!(mb.read(0xFF0F) & 0b_0000_1000, 0);
+            assert_eq!(mb.read(0xFF02), 0xFF);
+            mb.step().unwrap();
+        }
+        while mb.cycles() - start < 4096 + 12 {
+            mb.step().unwrap();
+        }
+        assert_eq!(mb.read(0xFF0F) & 0b_0000_1000, 0b_0000_1000);
+        assert_eq!(mb.read(0xFF02), 0x7F);
+        // 相手がいないので 0xFF を受信する
+        assert_eq!(mb.read(0xFF01), 0xFF);
+    }
+
+    #[test]
+    fn test_serial_external_clock() {
+        let mb = start_serial(0x80);
+        let mb = mb.borrow();
+        // 外部クロックは相手がいないので転送が終わらない
+        for _ in 0..10000 {
+            mb.step().unwrap();
+        }
+        assert_eq!(mb.read(0xFF0F) & 0b_0000_1000, 0);
+        assert_eq!(mb.read(0xFF02), 0xFE);
+        assert_eq!(mb.read(0xFF01), 0x41);
+    }
+
+    #[test]
+    fn test_ram_init() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.set_ram_init(RamInit::Pattern(0x55));
+        mb.reset();
+        assert_eq!(mb.read_range(0xC000, 2), [0x55, 0x55]);
+        assert_eq!(mb.read_range(0x9FFF, 1), [0x55]);
+        assert_eq!(mb.read_range(0xFF80, 1), [0x55]);
+        // 同じ seed なら同じ状態から始まる
+        mb.set_ram_init(RamInit::Random(1));
+        mb.reset();
+        let random = mb.read_range(0xC000, 0x2000);
+        assert!(random.iter().any(|b| *b != random[0]));
+        mb.write(0xC000, !random[0]);
+        mb.reset();
+        assert_eq!(mb.read_range(0xC000, 0x2000), random);
+    }
+
+    #[test]
+    fn test_reset() {
+        let steps = |mb: &MotherBoard, n: usize| {
+            for _ in 0..n {
+                mb.step().unwrap();
+            }
+        };
+        let fresh = MotherBoard::headless(build_rom(&PROGRAM));
+        let fresh = fresh.borrow();
+        fresh.reset();
+        steps(&fresh, 1000);
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        steps(&mb, 10000);
+        mb.write(0xFF40, 0x00);
+        mb.reset();
+        steps(&mb, 1000);
+        // 初めから動かしたときと同じになる
+        assert_eq!(mb.cycles(), fresh.cycles());
+        assert_eq!(mb.cpu_state(), fresh.cpu_state());
+        assert_eq!(mb.read(0xFF40), fresh.read(0xFF40));
+        assert_eq!(mb.read_range(0xC000, 0x2000), fresh.read_range(0xC000, 0x2000));
+        // 描画したフレーム数は戻さない
+        assert!(mb.frames() > fresh.frames());
+    }
+
+    #[test]
+    fn test_load_cartridge() {
+        let mb = MotherBoard::headless(build_rom(&PROGRAM));
+        let mb = mb.borrow();
+        mb.reset();
+        for _ in 0..100 {
+            mb.step().unwrap();
+        }
+        // NOP; NOP; 0xD3
+        mb.load_cartridge(Cartridge::from_bytes(build_rom(&[0x00, 0x00, 0xD3])));
+        assert_eq!(mb.cpu_state().pc, 0x0100);
+        assert_eq!(mb.cycles(), 0);
+        assert_eq!(mb.read_range(0x0150, 3), [0x00, 0x00, 0xD3]);
+        assert!(mb.load_rom("/nonexistent.gb").is_err());
+        assert_eq!(mb.read_range(0x0150, 3), [0x00, 0x00, 0xD3]);
+    }
+
+    #[test]
+    fn test_illegal_opcode() {
+        // NOP; NOP; 0xD3
+        let mb = MotherBoard::headless(build_rom(&[0x00, 0x00, 0xD3]));
+        let mb = mb.borrow();
+        mb.reset();
+        for _ in 0..4 {
+            mb.step().unwrap();
+        }
+        let e = mb.step().unwrap_err();
+        assert_eq!(
+            e,
+            CpuError::IllegalOpcode {
+                pc: 0x0152,
+                opcode: 0xD3,
+                backtrace: vec![
+                    (0x0100, 0x00),
+                    (0x0101, 0xC3),
+                    (0x0150, 0x00),
+                    (0x0151, 0x00)
+                ],
+            }
+        );
+        // PC は進めず何度実行しても同じエラーになる
+        assert_eq!(mb.step().unwrap_err(), e);
+        // 命令の読み込み(1 M-Cycle)は2回とも進む
+        assert_eq!(mb.cycles(), 4 + 16 + 4 + 4 + 4 + 4);
+
+        let history = mb.cpu.as_ref().unwrap().borrow().history(2);
+        assert_eq!(
+            history,
+            "0x0150: 0x0000  AF=01B0 BC=0013 DE=00D8 HL=014D SP=FFFE\n\
+             0x0151: 0x0000  AF=01B0 BC=0013 DE=00D8 HL=014D SP=FFFE"
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::vec::Vec;
+
+use crate::io::IO;
+use crate::Address;
+
+// OAM は 4 バイト x 40 個
+const OAM_SIZE: u16 = 0xA0;
+
+/// OAM DMA (0xFF46)
+/// 書き込んだ値 XX について XX00 - XX9F を OAM に転送する。読み書きは MotherBoard が行う
+/// https://gbdev.io/pandocs/OAM_DMA_Transfer.html
+#[derive(Debug)]
+pub struct OamDma {
+    // 最後に書き込まれた値。そのまま読み出せる
+    source: u8,
+}
+
+impl OamDma {
+    pub fn new() -> Self {
+        Self { source: 0xFF }
+    }
+
+    /// 転送元のアドレスを OAM の先頭から順に返す
+    /// DMA は作業 RAM のミラーまでしか読めないので、0xE000 以降は 0x2000 前の作業 RAM になる
+    /// 0xFE00 - 0xFFFF を指定しても OAM や I/O ポートではなく 0xDE00 - 0xDFFF が読まれる
+    pub fn sources(&self) -> impl Iterator<Item = Address> {
+        let base = (self.source as Address) << 8;
+        (0..OAM_SIZE).map(move |i| match base + i {
+            address @ 0xE000..=0xFFFF => address - 0x2000,
+            address => address,
+        })
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.push(self.source);
+    }
+}
+
+impl Default for OamDma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IO for OamDma {
+    // 0xFF46 のみ
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0xFF46 => self.source,
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF46 => self.source = data,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources() {
+        let mut dma = OamDma::new();
+        dma.write(0xFF46, 0xC1);
+        assert_eq!(dma.read(0xFF46), 0xC1);
+        let sources: Vec<Address> = dma.sources().collect();
+        assert_eq!(sources.len(), 160);
+        assert_eq!((sources[0], sources[159]), (0xC100, 0xC19F));
+        // 0xE000 以降は作業 RAM のミラー
+        dma.write(0xFF46, 0xE2);
+        assert_eq!(dma.sources().next(), Some(0xC200));
+        dma.write(0xFF46, 0xFE);
+        assert_eq!(dma.sources().last(), Some(0xDE9F));
+        dma.write(0xFF46, 0xFF);
+        assert_eq!(dma.sources().next(), Some(0xDF00));
+    }
+}
@@ -0,0 +1,100 @@
+use alloc::vec::Vec;
+
+use crate::crc32::crc32;
+use crate::ppu::FrameBuffer;
+
+pub(crate) const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// 画面を RGB 8bit の PNG にする。SGB や CGB の色もそのまま残る
+/// 圧縮はしないので 1 枚 70KB ほどになる
+pub fn encode(frame_buffer: &FrameBuffer) -> Vec<u8> {
+    let (width, height) = (frame_buffer[0].len() as u32, frame_buffer.len() as u32);
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // ビット深度8のトゥルーカラー
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+    // 各行の先頭にフィルタ種別(0: None)を置く
+    let mut rows = Vec::with_capacity((width as usize * 3 + 1) * height as usize);
+    for line in frame_buffer.iter() {
+        rows.push(0);
+        for pixel in line.iter() {
+            rows.extend_from_slice(&[pixel.0, pixel.1, pixel.2]);
+        }
+    }
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &ihdr);
+    chunk(&mut png, b"IDAT", &zlib_stored(&rows));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// 長さ、種別、データ、CRC の順に書く
+pub(crate) fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// 無圧縮ブロックだけで構成した zlib ストリーム
+pub(crate) fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut v = Vec::with_capacity(data.len() + data.len() / 0xFFFF * 5 + 11);
+    v.extend_from_slice(&[0x78, 0x01]);
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        v.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        v.push(is_final as u8);
+        let len = block.len() as u16;
+        v.extend_from_slice(&len.to_le_bytes());
+        v.extend_from_slice(&(!len).to_le_bytes());
+        v.extend_from_slice(block);
+    }
+    v.extend_from_slice(&adler32(data).to_be_bytes());
+    v
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ppu::PixelData;
+    use alloc::boxed::Box;
+
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut frame_buffer = Box::new([[PixelData(255, 255, 255, 0); 160]; 144]);
+        frame_buffer[0][1] = PixelData(0x12, 0x34, 0x56, 0);
+        let png = encode(&frame_buffer);
+        assert_eq!(&png[0..8], &SIGNATURE);
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 160, 0, 0, 0, 144]);
+        assert_eq!(
+            &png[png.len() - 12..],
+            &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82]
+        );
+        // IDAT の zlib ヘッダ、ブロックヘッダ、フィルタ種別の後に1行目の画素が並ぶ
+        let idat = 8 + (12 + 13);
+        assert_eq!(&png[idat + 4..idat + 8], b"IDAT");
+        let row = idat + 8 + 2 + 5 + 1;
+        assert_eq!(&png[row..row + 6], &[255, 255, 255, 0x12, 0x34, 0x56]);
+    }
+}
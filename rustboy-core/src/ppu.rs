@@ -0,0 +1,2189 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::{IntoIter, Vec};
+use core::cell::RefCell;
+use core::fmt::{Debug, Formatter};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+#[cfg(feature = "recorder")]
+use log::warn;
+
+use crate::arithmetic::{AddSigned, ToSigned};
+use crate::braille;
+use crate::cpu::CPU;
+use crate::events::{Event, EventBus};
+use crate::interruption::{Interruption, Peripheral};
+use crate::io::IO;
+use crate::joypad::ButtonState;
+use crate::quirks::HardwareQuirks;
+#[cfg(feature = "recorder")]
+use crate::recorder::Recorder;
+use crate::sgb::{Mask, Screen, Sgb, TRANSFER_SIZE};
+use crate::stats::FrameStats;
+use crate::Address;
+
+pub use colorization::Colorization;
+pub use view::{DebugView, OamEntry, TileData, TileMapEntry};
+
+mod colorization;
+mod view;
+
+const WHITE: PixelData = PixelData(255, 255, 255, 0);
+const LIGHT_GRAY: PixelData = PixelData(170, 170, 170, 0);
+const DARK_GRAY: PixelData = PixelData(85, 85, 85, 0);
+const BLACK: PixelData = PixelData(0, 0, 0, 0);
+/// 色番号 0 - 3 に対応する表示色
+pub const DEFAULT_PALETTE: [PixelData; 4] = [WHITE, LIGHT_GRAY, DARK_GRAY, BLACK];
+
+const WIDTH_LCD: u16 = 160;
+const HEIGHT_LCD: u16 = 144;
+const HEIGHT_LCD_MARGIN: u16 = 10;
+const WIDTH_TILE: u16 = 8;
+const HEIGHT_TILE: u16 = 8;
+const WIDTH_BG: u16 = 256;
+const HEIGHT_BG: u16 = 256;
+const WIDTH_WINDOW: u16 = 256;
+const HEIGHT_WINDOW: u16 = 256;
+const SCANLINE_CYCLE: u64 = 456;
+// OAM Scan(mode 2)の長さと、Drawing(mode 3)の最短の長さ
+const OAM_SCAN_CYCLE: u64 = 80;
+const DRAWING_CYCLE: u64 = 172;
+// 1フレームは 70224 T-Cycle
+#[cfg(feature = "std")]
+const FRAME_CYCLE: u64 = SCANLINE_CYCLE * (HEIGHT_LCD + HEIGHT_LCD_MARGIN) as u64;
+// 0x8000 - 0x97FF のタイル数
+const NUM_OF_TILES: usize = 384;
+
+pub type FrameBuffer = [[PixelData; WIDTH_LCD as usize]; HEIGHT_LCD as usize];
+
+/// 描画するフレームの情報。フロントエンドが FPS などを重ねて表示するのに使う
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameInfo {
+    // 起動してから描画したフレーム数(このフレームを含まない)
+    pub frame: u64,
+    // 起動してからの T-Cycle 数
+    pub cycles: u64,
+    // 前のフレームからの実機に対する速さ。1.0 で実機と同じ
+    pub speed: f64,
+    // 状態行を表示する場合のみ Some。1秒ごとに更新される
+    pub stats: Option<FrameStats>,
+    // このフレームでゲームが最後に読んだボタンの状態
+    pub input: ButtonState,
+    // フレーム数、経過時間、入力を重ねて表示する。TAS の作成や記録の確認に使う
+    pub overlay: bool,
+}
+
+impl FrameInfo {
+    /// 実機での経過時間
+    pub fn emulated_time(&self) -> Duration {
+        Duration::from_secs_f64(self.cycles as f64 / CPU::CLOCK as f64)
+    }
+}
+
+/// 画面のピクセルを描画するときに使う背景のタイルとスプライト。PPU のデバッグ用
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelSource {
+    // 背景マップのエントリのアドレスと、そこに書かれたタイル番号
+    pub map_address: u16,
+    pub tile_number: u8,
+    // タイルデータのうち、そのピクセルの行のアドレス
+    pub tile_address: u16,
+    // そのピクセルに重なる最も優先度の高いスプライトの OAM の番号
+    pub sprite: Option<usize>,
+}
+
+/// フロントエンドが表示したい領域
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Viewport {
+    // ゲームボーイの画面(160x144)だけ
+    Screen,
+    // SGB の枠を含めた画面(256x224)
+    SgbBorder,
+}
+
+pub trait LCD {
+    /// 描画が必要なタイミングで実行される
+    fn draw(&self, frame_buffer: &FrameBuffer, info: &FrameInfo);
+    /// SGB の枠が設定されていて、viewport が SgbBorder の場合は枠を合成した画面も渡される
+    fn draw_with_border(&self, frame_buffer: &FrameBuffer, _screen: &Screen, info: &FrameInfo) {
+        self.draw(frame_buffer, info);
+    }
+    /// 表示したい領域。枠を表示できないフロントエンドはゲームボーイの画面だけにする
+    fn viewport(&self) -> Viewport {
+        Viewport::Screen
+    }
+}
+
+/// 何も描画しない。テストやヘッドレス実行用
+pub struct Headless;
+
+impl LCD for Headless {
+    fn draw(&self, _frame_buffer: &FrameBuffer, _info: &FrameInfo) {}
+}
+
+// RGBA
+#[derive(Clone, Copy, PartialEq)]
+pub struct PixelData(pub u8, pub u8, pub u8, pub u8);
+
+impl PixelData {
+    /// 白黒の端末に表示するときの明るさ
+    pub fn luminance(&self) -> u8 {
+        ((self.0 as u16 + self.1 as u16 + self.2 as u16) / 3) as u8
+    }
+}
+
+impl Debug for PixelData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // パレットが変更されていても4段階の明るさで表示する
+        match self.luminance() {
+            213..=255 => write!(f, " "),
+            128..=212 => write!(f, "{}", char::from_u32(0x25A1).unwrap()),
+            43..=127 => write!(f, "{}", char::from_u32(0x25A6).unwrap()),
+            _ => write!(f, "{}", char::from_u32(0x25A0).unwrap()),
+        }
+    }
+}
+
+/// STAT の下位2ビットで表される LCD のモード
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PPUMode {
+    // Drawing後に 456 T-Cycles になるよう調整するための待機
+    HBlank,
+    // 擬似スキャンラインのスキャン中の待機。擬似スキャンラインも456 T-Cycles消費する
+    VBlank,
+    // OAMから描画するスプライトをスプライトバッファに格納
+    OAMScan,
+    // LCDにピクセルを転送
+    Drawing,
+}
+
+impl From<u8> for PPUMode {
+    fn from(v: u8) -> Self {
+        match v & 0b11 {
+            0 => PPUMode::HBlank,
+            1 => PPUMode::VBlank,
+            2 => PPUMode::OAMScan,
+            _ => PPUMode::Drawing,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TileDataSelect {
+    Method8000,
+    Method8800,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TileMapSelect {
+    Method9C00,
+    Method9800,
+}
+
+impl From<TileMapSelect> for Address {
+    fn from(v: TileMapSelect) -> Self {
+        if v == TileMapSelect::Method9C00 {
+            // 0x9C00 - 0x9FFF の1024個
+            0x9C00
+        } else {
+            // 0x9800 - 0x9BFF の1024個
+            0x9800
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpriteSize {
+    Normal,
+    Tall,
+}
+
+impl From<SpriteSize> for u16 {
+    fn from(v: SpriteSize) -> Self {
+        if v == SpriteSize::Tall {
+            16
+        } else {
+            8
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Color {
+    // 00
+    White,
+    // 10
+    LightGray,
+    // 01
+    DarkGray,
+    // 11
+    Black,
+}
+
+impl Color {
+    fn to_rgba(self, palette: &[PixelData; 4]) -> PixelData {
+        match self {
+            Color::White => palette[0],
+            Color::LightGray => palette[1],
+            Color::DarkGray => palette[2],
+            Color::Black => palette[3],
+        }
+    }
+
+    fn from_number(number: u8) -> Self {
+        match number & 0b11 {
+            0 => Color::White,
+            1 => Color::LightGray,
+            2 => Color::DarkGray,
+            _ => Color::Black,
+        }
+    }
+
+    // BGP などのパレットレジスタで色番号を表示する色に変える
+    fn apply_palette(self, palette: u8) -> Self {
+        Color::from_number(palette >> (self.number() * 2))
+    }
+
+    // 色番号(0 - 3)
+    fn number(self) -> u8 {
+        match self {
+            Color::White => 0,
+            Color::LightGray => 1,
+            Color::DarkGray => 2,
+            Color::Black => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pixel {
+    // パレット適用前の値
+    color: Color,
+    // スプライトのパレット。OBP0 なら 0、OBP1 なら 1
+    // レジスタの値ではなく選択を持ち、描画するときの OBP0 / OBP1 を通す
+    palette: u8,
+    // 未使用
+    // sprite: u8,
+    // スプライトの bit7 の値
+    background_priority: bool,
+}
+
+// 1行の描画に使うレジスタ。Drawing(mode 3)に入るときに1度だけ読み取る
+// HBlank 割り込みで書き換えると次の行から反映され、描画中に書き換えてもその行は変わらない
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineRegisters {
+    scy: u16,
+    scx: u16,
+    bgp: u8,
+    obp0: u8,
+    obp1: u8,
+    wy: u8,
+    wx: u8,
+}
+
+impl LineRegisters {
+    // スプライトのパレット選択に対応する OBP0 / OBP1 の値
+    fn obp(&self, palette: u8) -> u8 {
+        match palette {
+            0 => self.obp0,
+            _ => self.obp1,
+        }
+    }
+}
+
+// タイルは 8 x 8 ピクセル。1ピクセルは2bitで4色。
+// 先頭2バイトがタイル内の一番上の行に相当
+// バイトごとのbitの位置が列に相当（0ビット目が一番右）
+type Tile = [u8; 8 * 2];
+
+struct TileLine {
+    low: u8,
+    high: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sprite {
+    // OAM の何番目のエントリか
+    index: usize,
+    y_position: u16,
+    x_position: u16,
+    tile_number: u8,
+    background_priority: bool,
+    // OBP0 なら 0、OBP1 なら 1
+    palette: u8,
+}
+
+impl Sprite {
+    pub const BASE_ADDRESS: Address = 0x8000;
+
+    // ly の行に掛かるスプライトを優先度の高い順に返す
+    fn oam_scan(oam: &[u8; 4 * 40], ly: u16, lcdc: LcdControl) -> Vec<Sprite> {
+        // 1度の ScanLine で表示できるスプライトは OAM の先頭から10個まで
+        // 画面の左右の外にあるスプライトも数に含まれる
+        let mut sprite_buffer: Vec<Sprite> = oam
+            .chunks(4)
+            .enumerate()
+            .filter_map(|(index, bytes)| Sprite::new(index, bytes, ly, lcdc))
+            .take(10)
+            .collect();
+        // X 座標が小さいほど優先度が高く、同じなら OAM の先にある方が高い(安定ソートで順序を保つ)
+        sprite_buffer.sort_by_key(|sprite| sprite.x_position);
+        sprite_buffer
+    }
+    fn new(index: usize, bytes: &[u8], ly: u16, lcdc: LcdControl) -> Option<Self> {
+        let entry = OamEntry::decode(index, bytes.try_into().ok()?);
+        let sprite = Self {
+            index,
+            y_position: entry.y.into(),
+            x_position: entry.x.into(),
+            tile_number: entry.tile_number,
+            background_priority: entry.bg_priority,
+            palette: entry.palette,
+        };
+        if ly + 16 < sprite.y_position {
+            return Option::None;
+        }
+        if ly + 16 >= sprite.y_position + u16::from(lcdc.sprite_size) {
+            return Option::None;
+        }
+        Some(sprite)
+    }
+    // ly の行に表示するタイルの行のアドレス。スプライトはスクロールしない
+    fn tile_address(&self, ly: u16, size: SpriteSize) -> Address {
+        // 8x16 のスプライトは上下に並んだ2つのタイルを使うので、タイル番号の bit0 を無視する
+        let tile_number = match size {
+            SpriteSize::Tall => self.tile_number & 0xFE,
+            SpriteSize::Normal => self.tile_number,
+        };
+        let base_address = Sprite::BASE_ADDRESS + tile_number.to_unsigned_u16().wrapping_mul(16);
+        let row = ly + 16 - self.y_position;
+        base_address + 2 * row
+    }
+}
+
+impl IntoIterator for TileLine {
+    type Item = Color;
+    type IntoIter = IntoIter<Color>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut v = Vec::with_capacity(8);
+        for offset in (0..WIDTH_TILE).rev() {
+            let color = match (((self.low >> offset) & 0b1), ((self.high >> offset) & 0b1)) {
+                (0, 0) => Color::White,
+                (1, 0) => Color::LightGray,
+                (0, 1) => Color::DarkGray,
+                (1, 1) => Color::Black,
+                _ => unreachable!(),
+            };
+            v.push(color)
+        }
+        v.into_iter()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LcdControl {
+    lcd_enable: bool,
+    window_tile_map_select: TileMapSelect,
+    window_enable: bool,
+    tile_data_select: TileDataSelect,
+    bg_tile_map_select: TileMapSelect,
+    sprite_size: SpriteSize,
+    sprite_enable: bool,
+    bg_win_enable: bool,
+}
+
+impl From<u8> for LcdControl {
+    fn from(v: u8) -> Self {
+        Self {
+            lcd_enable: (v & 0b_1000_0000) == 0b_1000_0000,
+            window_tile_map_select: if (v & 0b_0100_0000) == 0b_0100_0000 {
+                TileMapSelect::Method9C00
+            } else {
+                TileMapSelect::Method9800
+            },
+            window_enable: (v & 0b_0010_0000) == 0b_0010_0000,
+            tile_data_select: if (v & 0b_0001_0000) == 0b_0001_0000 {
+                TileDataSelect::Method8000
+            } else {
+                TileDataSelect::Method8800
+            },
+            bg_tile_map_select: if (v & 0b_0000_1000) == 0b_0000_1000 {
+                TileMapSelect::Method9C00
+            } else {
+                TileMapSelect::Method9800
+            },
+            sprite_size: if (v & 0b_0000_0100) == 0b_0000_0100 {
+                SpriteSize::Tall
+            } else {
+                SpriteSize::Normal
+            },
+            sprite_enable: (v & 0b_0000_0010) == 0b_0000_0010,
+            bg_win_enable: (v & 0b_0000_0001) == 0b_0000_0001,
+        }
+    }
+}
+
+impl From<LcdControl> for u8 {
+    fn from(lcdc: LcdControl) -> Self {
+        let mut v;
+        if lcdc.lcd_enable {
+            v = 0b_1000_0000;
+        } else {
+            v = 0b_0000_0000;
+        }
+        if lcdc.window_tile_map_select == TileMapSelect::Method9C00 {
+            v |= 0b_0100_0000;
+        }
+        if lcdc.window_enable {
+            v |= 0b_0010_0000;
+        }
+        if lcdc.tile_data_select == TileDataSelect::Method8000 {
+            v |= 0b_0001_0000;
+        }
+        if lcdc.bg_tile_map_select == TileMapSelect::Method9C00 {
+            v |= 0b_0000_1000;
+        }
+        if lcdc.sprite_size == SpriteSize::Tall {
+            v |= 0b_0000_0100;
+        }
+        if lcdc.sprite_enable {
+            v |= 0b_0000_0010;
+        }
+        if lcdc.bg_win_enable {
+            v |= 0b_0000_0001;
+        }
+        v
+    }
+}
+
+// 描画先と、何フレームに1回描画するか
+struct Sink {
+    lcd: Box<dyn LCD>,
+    interval: u32,
+}
+
+pub struct PPU {
+    // 描画先。構築時に渡したものが先頭で、add_lcd で増やせる
+    sinks: Vec<Sink>,
+    // ずっと起動していると溢れる
+    clock: u64,
+    // 70224 T-cycle ごとに1回描画するため、次の描画時の clock を記録する
+    clock_next_target: u64,
+    // 描画したフレーム数
+    frames: u64,
+    // VBlank に入った回数
+    vblanks: u64,
+    // 前のフレームを描画した時刻。速さの計測に使う
+    #[cfg(feature = "std")]
+    last_draw: Option<Instant>,
+    // メインループで集計した実行速度
+    stats: Option<FrameStats>,
+    // ゲームが最後に読んだボタンの状態
+    input: ButtonState,
+    // FrameInfo でフロントエンドにオーバーレイの表示を頼む
+    overlay: bool,
+    // LCD に渡す前に前のフレームを混ぜる割合(0 - 100%)。0 なら混ぜない
+    ghosting: u8,
+    // 混ぜる前の前のフレーム
+    previous_frame: Option<Box<FrameBuffer>>,
+    // STOP 中は LCD が止まる。フロントエンドが固まらないように時間は進めるが、何も表示せず割り込みも要求しない
+    stopped: bool,
+    // 実際の画面と対応
+    // scan_line は裏のバッファに書き込み、VBlank で表と入れ替える
+    // LCD には書き込み途中のフレームを見せないように表のバッファを渡す
+    frame_buffer: Box<FrameBuffer>,
+    front_buffer: Arc<FrameBuffer>,
+    // 表示色。背景とスプライトで別の色にできる
+    colors: Colorization,
+    // スプライト属性テーブル (OAM - Object Attribute Memory)
+    oam: [u8; 4 * 40],
+    // VRAM は 0x8000 - 0x9FFF の 8KB
+    // タイルパターンテーブル
+    // 0x8000 - 0x97FF
+    // 下記の2パターンでアクセスされる
+    // Method 8000(スプライトと背景で利用)
+    // 0x8000 - 0x8FFF: ここのタイルは 0(0x8000) ~ 255(0x8FFF) のタイル番号で指定
+    // 0x8000: 0
+    // 0x8010: 1
+    // 0x8020: 2
+    // ...
+    // 0x8FFF: 255
+    //
+    // Method 8800(背景とウインドウで利用)
+    // 0x8800 - 0x97FF: ここのタイルは-128(0x8800) ~ 0(0x9000)~ 127(0x97FF) タイル番号で指定
+    // 0x8800: -128
+    // ...
+    // 0x8FF0: -1
+    // 0x9000: 0
+    // 0x9010: 1
+    // ...
+    // 0x97FF: 127
+    // 背景データ(32 x 32タイル)の格納先は2つあり、背景/ウィンドウで利用できる
+    // 格納される値はタイルパターンテーブルのタイル番号
+    // 0x9800 - 0x9BFF
+    // 0x9C00 - 0x9FFF
+    vram: [u8; 8 * 1024],
+    // 8画素分の背景用FIFO
+    fifo_background: VecDeque<Pixel>,
+
+    // 以下はレジスタ
+    // 0xFF40: LCD制御
+    // 5bit: 1ならウィンドウも描画, 0ならウィンドウは無視
+    // 4bit: 1なら Method 8000, 0なら Method 8800
+    // 3bit: 1なら 0x9C00 - 0x9FFF, 0なら 0x9800 - 0x9BFF にある背景データを使う
+    lcdc: LcdControl,
+    // 0xFF41: LCDステータス
+    // 書き込めるのは割り込み要因を選ぶ bit 3 - 6 だけで、モードと LYC 一致は読み込み時に作る
+    stat: u8,
+    // 0xFF42: スクロールY座標
+    scy: u16,
+    // 0xFF43: スクロールX座標
+    scx: u16,
+    // 0xFF44: LCD Y座標
+    // LCDの縦サイズは144だが10本の擬似スキャンラインがあるので 1 - 153 になる
+    ly: u16,
+    // 0xFF45: LY比較
+    lyc: u8,
+    // 0xFF47: 背景パレットデータ
+    bgp: u8,
+    // 0xFF48: オブジェクトパレット0
+    obp0: u8,
+    // 0xFF49: オブジェクトパレット1
+    obp1: u8,
+    // 0xFF4A: ウィンドウY座標
+    wy: u8,
+    // 0xFF4B: ウィンドウX座標
+    wx: u8,
+    // ウィンドウの内部のライン数。ウィンドウを描画した行だけ進むので、
+    // 途中の行でウィンドウを無効にしたり WX を画面外にしても続きから描画される
+    window_line: u16,
+    // このフレームで LY が WY と一致したか。一致するまでウィンドウは描画されない
+    window_triggered: bool,
+
+    interruption: Rc<RefCell<Interruption>>,
+    events: Rc<EventBus>,
+    // 前回の tick でのモード。変わったら通知する
+    last_mode: u8,
+    // 現在の行を frame_buffer に描画したか。HBlank に入るときに描画する
+    line_drawn: bool,
+    // 現在の行で読み取ったレジスタ。Drawing に入るまでは None
+    line: Option<LineRegisters>,
+    // 現在の行の Drawing の長さ。Drawing に入るときに決める
+    drawing_cycles: u64,
+    // STAT で選んだ割り込み要因の OR。これが立ち上がったときだけ割り込みを要求する
+    stat_line: bool,
+    // SGB 対応のカートリッジの場合のみ Some
+    sgb: Option<Sgb>,
+    // 再現する実機の不具合
+    quirks: HardwareQuirks,
+    // 録画中のみ Some
+    #[cfg(feature = "recorder")]
+    recorder: Option<Recorder>,
+}
+
+impl PPU {
+    pub fn new(
+        lcd: Box<dyn LCD>,
+        interruption: Rc<RefCell<Interruption>>,
+        events: Rc<EventBus>,
+    ) -> Self {
+        Self {
+            interruption,
+            events,
+            last_mode: 2,
+            line_drawn: false,
+            line: Option::None,
+            drawing_cycles: DRAWING_CYCLE,
+            stat_line: false,
+            sinks: vec![Sink { lcd, interval: 1 }],
+            clock: 0,
+            clock_next_target: SCANLINE_CYCLE,
+            frames: 0,
+            vblanks: 0,
+            #[cfg(feature = "std")]
+            last_draw: Option::None,
+            stats: Option::None,
+            input: ButtonState::default(),
+            overlay: false,
+            ghosting: 0,
+            previous_frame: Option::None,
+            stopped: false,
+            frame_buffer: Box::new([[WHITE; 160]; 144]),
+            front_buffer: Arc::new([[WHITE; 160]; 144]),
+            colors: Colorization::default(),
+            oam: [0; 4 * 40],
+            vram: [0; 8 * 1024],
+            lcdc: LcdControl::from(0),
+            stat: 0,
+            scy: 0,
+            scx: 0,
+            ly: 0,
+            lyc: 0,
+            bgp: 0,
+            obp0: 0,
+            obp1: 0,
+            wy: 0,
+            wx: 0,
+            window_line: 0,
+            window_triggered: false,
+            fifo_background: VecDeque::with_capacity(WIDTH_TILE as usize),
+            sgb: Option::None,
+            quirks: HardwareQuirks::default(),
+            #[cfg(feature = "recorder")]
+            recorder: Option::None,
+        }
+    }
+
+    /// 電源投入直後の状態に戻す。sgb は差し替えたカートリッジが SGB に対応しているか
+    /// 描画先、表示色、録画などフロントエンドの設定と、描画したフレーム数は残す
+    pub fn reset(&mut self, sgb: bool) {
+        let mut ppu = Self::new(
+            Box::new(Headless),
+            Rc::clone(&self.interruption),
+            Rc::clone(&self.events),
+        );
+        ppu.sinks = core::mem::take(&mut self.sinks);
+        ppu.frames = self.frames;
+        ppu.vblanks = self.vblanks;
+        ppu.stats = self.stats;
+        ppu.overlay = self.overlay;
+        ppu.ghosting = self.ghosting;
+        ppu.colors = self.colors;
+        ppu.quirks = self.quirks;
+        #[cfg(feature = "std")]
+        {
+            ppu.last_draw = self.last_draw;
+        }
+        #[cfg(feature = "recorder")]
+        {
+            ppu.recorder = self.recorder.take();
+        }
+        if sgb {
+            ppu.enable_sgb();
+        }
+        *self = ppu;
+    }
+
+    /// 電源投入時の中身を書き込むため
+    pub(crate) fn vram_mut(&mut self) -> &mut [u8] {
+        &mut self.vram
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend(self.clock.to_le_bytes());
+        state.extend(self.clock_next_target.to_le_bytes());
+        state.extend(self.frames.to_le_bytes());
+        state.extend(self.vblanks.to_le_bytes());
+        let buffers = self.frame_buffer.iter().chain(self.front_buffer.iter());
+        for pixel in buffers.flatten() {
+            state.extend([pixel.0, pixel.1, pixel.2, pixel.3]);
+        }
+        state.extend(self.oam);
+        state.extend(self.vram);
+        // FIFO は scan_line の中でしか使われないので含めない
+        state.extend([u8::from(self.lcdc), self.stat]);
+        state.extend(self.scy.to_le_bytes());
+        state.extend(self.scx.to_le_bytes());
+        state.extend(self.ly.to_le_bytes());
+        state.extend([self.lyc, self.bgp, self.obp0, self.obp1, self.wy, self.wx]);
+        state.extend(self.window_line.to_le_bytes());
+        state.push(self.window_triggered as u8);
+        state.extend([self.line_drawn as u8, self.stat_line as u8]);
+        if let Some(line) = &self.line {
+            state.extend(line.scy.to_le_bytes());
+            state.extend(line.scx.to_le_bytes());
+            state.extend([line.bgp, line.obp0, line.obp1, line.wy, line.wx]);
+            state.extend(self.drawing_cycles.to_le_bytes());
+        }
+        if let Some(sgb) = &self.sgb {
+            sgb.save_state(state);
+        }
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn set_quirks(&mut self, quirks: HardwareQuirks) {
+        self.quirks = quirks;
+    }
+
+    pub fn vblanks(&self) -> u64 {
+        self.vblanks
+    }
+
+    /// LCD が点いていて HBlank 中か。HDMA の転送に使う
+    pub fn in_hblank(&self) -> bool {
+        self.lcdc.lcd_enable && self.mode() == 0
+    }
+
+    /// 最後に描画し終えたフレーム。複製しても画面はコピーされない
+    pub fn front_buffer(&self) -> Arc<FrameBuffer> {
+        Arc::clone(&self.front_buffer)
+    }
+
+    /// 以降のフレームで LCD に渡す実行速度
+    pub fn set_stats(&mut self, stats: FrameStats) {
+        self.stats = Some(stats);
+    }
+
+    /// 次に描画するフレームの情報に入力を載せる
+    pub fn set_input(&mut self, input: ButtonState) {
+        self.input = input;
+    }
+
+    pub fn set_overlay(&mut self, overlay: bool) {
+        self.overlay = overlay;
+    }
+
+    /// 実機の液晶の残像を真似て、LCD に渡す前に前のフレームを weight % 混ぜる
+    /// 1フレームおきに点滅させて半透明に見せるゲームがある。front_buffer は混ぜない
+    pub fn set_ghosting(&mut self, weight: u8) {
+        self.ghosting = weight.min(100);
+        self.previous_frame = Option::None;
+    }
+
+    /// STOP 命令で止まっている間は true
+    pub fn set_stopped(&mut self, stopped: bool) {
+        self.stopped = stopped;
+    }
+
+    /// 背景とスプライトに同じ表示色を使う
+    pub fn set_palette(&mut self, palette: [PixelData; 4]) {
+        self.colors = Colorization::uniform(palette);
+    }
+
+    /// CGB のブート ROM のように、背景、OBP0、OBP1 のスプライトに別の表示色を使う
+    pub fn set_colorization(&mut self, colors: Colorization) {
+        self.colors = colors;
+    }
+
+    /// 現在のレジスタと VRAM で画面の (x, y) を描画するときに使うタイルとスプライト
+    pub fn pixel_source(&self, x: u16, y: u16) -> PixelSource {
+        let map_address =
+            tile_number_address(self.lcdc.bg_tile_map_select.into(), y, x, self.scx, self.scy);
+        let tile_number = self.read(map_address);
+        // スプライトの画面上の X 座標は X - 8 から X - 1
+        let sprite = Sprite::oam_scan(&self.oam, y, self.lcdc)
+            .iter()
+            .find(|s| s.x_position <= x + 8 && x < s.x_position)
+            .map(|s| s.index);
+        PixelSource {
+            map_address,
+            tile_number,
+            tile_address: tile_number_to_address(
+                tile_number,
+                self.lcdc.tile_data_select,
+                y,
+                self.scy,
+            ),
+            sprite,
+        }
+    }
+
+    /// SGB のコマンドを受け付ける
+    pub fn enable_sgb(&mut self) {
+        self.sgb = Some(Sgb::new());
+    }
+
+    pub fn sgb(&self) -> Option<&Sgb> {
+        self.sgb.as_ref()
+    }
+
+    /// 0xFF00 への書き込みを SGB に渡す
+    /// VRAM 転送は背景のタイルデータの領域から 4KB 読む
+    pub fn write_sgb(&mut self, data: u8) {
+        if let Some(sgb) = &mut self.sgb {
+            let start = match self.lcdc.tile_data_select {
+                TileDataSelect::Method8000 => 0x0000,
+                TileDataSelect::Method8800 => 0x0800,
+            };
+            sgb.write(data, &self.vram[start..start + TRANSFER_SIZE]);
+        }
+    }
+
+    pub fn dump_vram(&self) -> String {
+        format!("{:?}", self.vram)
+    }
+
+    // タイル番号(0x8000 からの通し番号 0 - 383)のタイルを色番号(0 - 3)に展開する
+    fn decode_tile(&self, index: usize) -> TileData {
+        TileData::decode(self.vram[index * 16..(index + 1) * 16].try_into().unwrap())
+    }
+
+    /// 外部のデバッガ向けに、描画と同じ方法で展開したタイル、背景マップ、OAM を返す
+    pub fn debug_view(&self) -> DebugView {
+        let lcdc = u8::from(self.lcdc);
+        let tile_map = |base: Address| {
+            (0..32 * 32)
+                .map(|i| TileMapEntry::decode(self.read(base + i), lcdc))
+                .collect()
+        };
+        DebugView {
+            tiles: (0..NUM_OF_TILES).map(|i| self.decode_tile(i)).collect(),
+            tile_maps: [tile_map(0x9800), tile_map(0x9C00)],
+            bg_map: self.lcdc.bg_tile_map_select.into(),
+            window_map: self.lcdc.window_tile_map_select.into(),
+            oam: self.oam_entries(),
+        }
+    }
+
+    fn oam_entries(&self) -> Vec<OamEntry> {
+        self.oam
+            .chunks(4)
+            .enumerate()
+            .map(|(i, bytes)| OamEntry::decode(i, bytes.try_into().unwrap()))
+            .collect()
+    }
+
+    /// 1つのタイルを色番号ごとの文字で表示する
+    pub fn dump_tile(&self, index: usize) -> Result<String, String> {
+        if index >= NUM_OF_TILES {
+            return Err(format!("tile number must be less than {}", NUM_OF_TILES));
+        }
+        let mut lines = vec![format!("tile {} (0x{:04X})", index, 0x8000 + index * 16)];
+        for row in self.decode_tile(index).0 {
+            let line: String = row
+                .iter()
+                .map(|c| match c {
+                    0 => "..",
+                    1 => "--",
+                    2 => "oo",
+                    _ => "##",
+                })
+                .collect();
+            lines.push(line);
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// 全タイルを16個ずつ並べて点字で表示する。色番号2, 3を点とする
+    pub fn dump_tiles(&self) -> String {
+        let tiles: Vec<TileData> = (0..NUM_OF_TILES).map(|i| self.decode_tile(i)).collect();
+        let width = 16 * WIDTH_TILE as usize;
+        let height = NUM_OF_TILES / 16 * HEIGHT_TILE as usize;
+        let lines = braille::lines(width, height, |x, y| {
+            let tile = &tiles[(y / 8) * 16 + x / 8];
+            tile.0[y % 8][x % 8] >= 2
+        });
+        // 点字1行は4ピクセルなので、タイル1行は2行になる
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                if i % 2 == 0 {
+                    format!("{:03} {}", i / 2 * 16, line)
+                } else {
+                    format!("    {}", line)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// 背景マップ(32 x 32)のタイル番号を表示する
+    pub fn dump_tile_map(&self, base: Address) -> String {
+        let mut lines = vec![format!(
+            "tile map 0x{:04X} (tile data: {:?})",
+            base, self.lcdc.tile_data_select
+        )];
+        for row in 0..32 {
+            let address = base + row * 32;
+            let numbers: Vec<String> = (0..32)
+                .map(|col| format!("{:02X}", self.read(address + col)))
+                .collect();
+            lines.push(format!("0x{:04X}: {}", address, numbers.join(" ")));
+        }
+        lines.join("\n")
+    }
+
+    /// OAM の40個のエントリを属性を展開して表示する
+    pub fn dump_oam(&self) -> String {
+        let mut lines = Vec::new();
+        for entry in self.oam_entries() {
+            // 画面上の座標は Y - 16, X - 8
+            lines.push(format!(
+                "{:02}: y={:3} x={:3} tile=0x{:02X} bg_priority={} y_flip={} x_flip={} palette=OBP{}{}",
+                entry.index,
+                entry.y,
+                entry.x,
+                entry.tile_number,
+                entry.bg_priority,
+                entry.y_flip,
+                entry.x_flip,
+                entry.palette,
+                if entry.is_visible() { "" } else { " (hidden)" }
+            ));
+        }
+        lines.join("\n")
+    }
+
+    /// 録画を開始する。録画中だった場合はそれまでの録画を終了する
+    #[cfg(feature = "recorder")]
+    pub fn start_recording(&mut self, path: &str, scale: usize, skip: u64) -> std::io::Result<()> {
+        self.stop_recording()?;
+        self.recorder = Some(Recorder::new(path, scale, skip)?);
+        Ok(())
+    }
+
+    /// 録画を終了してファイルを閉じる
+    #[cfg(feature = "recorder")]
+    pub fn stop_recording(&mut self) -> std::io::Result<()> {
+        match self.recorder.take() {
+            Some(recorder) => recorder.finish(),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(feature = "recorder")]
+    pub fn dump_recorder(&self) -> String {
+        format!("{:?}", self.recorder)
+    }
+
+    pub fn tick(&mut self, cycle: u8) {
+        self.clock += cycle as u64;
+        // Drawing に入るときにレジスタを読み取る。1度に進めて Drawing を飛ばした場合は HBlank で読む
+        if self.line.is_none() && matches!(self.mode(), 3 | 0) {
+            let line = self.line_registers();
+            self.drawing_cycles = self.count_drawing_cycles(&line);
+            self.line = Some(line);
+        }
+        // HBlank に入るときに1行分を描画する
+        if !self.line_drawn && self.mode() == 0 {
+            if self.stopped {
+                self.frame_buffer[self.ly as usize] = [WHITE; 160];
+            } else {
+                self.scan_line(self.ly);
+            }
+            self.line_drawn = true;
+        }
+        if self.clock_next_target <= self.clock {
+            self.clock_next_target += SCANLINE_CYCLE;
+            self.line_drawn = false;
+            self.line = Option::None;
+            self.ly += 1;
+            if self.ly == HEIGHT_LCD {
+                // V-Blank 割り込み
+                if !self.stopped {
+                    self.interruption.borrow_mut().request(Peripheral::VBlank);
+                }
+                self.swap_buffers();
+                self.vblanks += 1;
+                self.events.publish(Event::VBlank);
+            }
+            if self.ly >= (HEIGHT_LCD + HEIGHT_LCD_MARGIN) {
+                let info = self.frame_info();
+                self.draw(&info);
+                self.frames += 1;
+                #[cfg(feature = "recorder")]
+                if let Some(recorder) = &mut self.recorder {
+                    // ディスクが一杯になった場合などは録画だけをやめて動かし続ける
+                    if let Err(e) = recorder.capture(&self.front_buffer) {
+                        warn!(target: "ppu", "Failed to record frame, stopped recording: {}", e);
+                        self.recorder = None;
+                    }
+                }
+                self.ly = 0;
+                self.window_line = 0;
+                self.window_triggered = false;
+            }
+        }
+        let mode = self.mode();
+        if mode != self.last_mode {
+            self.last_mode = mode;
+            self.events.publish(Event::ModeChange(PPUMode::from(mode)));
+        }
+        self.update_stat_line();
+    }
+
+    fn line_registers(&self) -> LineRegisters {
+        LineRegisters {
+            scy: self.scy,
+            scx: self.scx,
+            bgp: self.bgp,
+            obp0: self.obp0,
+            obp1: self.obp1,
+            wy: self.wy,
+            wx: self.wx,
+        }
+    }
+
+    // Drawing の長さ。最短の長さに、先頭のタイルで捨てる SCX % 8 ピクセルと
+    // ウィンドウやスプライトのタイルを取得する間に Pixel Fetcher が止まる分を足す
+    // https://gbdev.io/pandocs/Rendering.html#mode-3-length
+    fn count_drawing_cycles(&self, line: &LineRegisters) -> u64 {
+        let mut cycles = DRAWING_CYCLE + (line.scx % 8) as u64;
+        // ウィンドウに入ると背景の取得をやめて、ウィンドウの最初のタイルから取得し直す
+        let window_x = if self.lcdc.window_enable
+            && (self.window_triggered || self.ly == line.wy as u16)
+            && (line.wx as u16) < WIDTH_LCD + 7
+        {
+            cycles += 6;
+            Some((line.wx as u16).saturating_sub(7))
+        } else {
+            None
+        };
+        if !self.lcdc.sprite_enable {
+            return cycles;
+        }
+        // スプライトのタイルの取得は 6 T-Cycle。その前に、左端が掛かる背景かウィンドウのタイルの
+        // 取得が終わるのを待つ。待つのはタイルごとに最初のスプライトだけ
+        let mut waited: Vec<(bool, u16)> = Vec::new();
+        for sprite in Sprite::oam_scan(&self.oam, self.ly, self.lcdc) {
+            // 画面の左の外(X = 0)にあるスプライトは SCX によらず 11 T-Cycle
+            if sprite.x_position == 0 {
+                cycles += 11;
+                continue;
+            }
+            // 画面の右の外にあるスプライトは取得しない
+            if sprite.x_position >= WIDTH_LCD + 8 {
+                continue;
+            }
+            // 左端の、タイルの境界に揃えた座標(8 ずらして負にならないようにする)
+            let tile_x = match window_x {
+                Some(x) if sprite.x_position >= x + 8 => (true, sprite.x_position - x),
+                _ => (false, sprite.x_position + line.scx % 8),
+            };
+            let tile = (tile_x.0, tile_x.1 / WIDTH_TILE);
+            if !waited.contains(&tile) {
+                waited.push(tile);
+                cycles += 5u64.saturating_sub((tile_x.1 % WIDTH_TILE) as u64);
+            }
+            cycles += 6;
+        }
+        cycles
+    }
+
+    // STAT の割り込み要因のいずれかが成り立っているか
+    fn stat_sources(&self) -> bool {
+        if !self.lcdc.lcd_enable || self.stopped {
+            return false;
+        }
+        let mode = self.mode();
+        (self.stat & 0b_0000_1000 != 0 && mode == 0)
+            || (self.stat & 0b_0001_0000 != 0 && mode == 1)
+            || (self.stat & 0b_0010_0000 != 0 && mode == 2)
+            || (self.stat & 0b_0100_0000 != 0 && self.ly == self.lyc as u16)
+    }
+
+    // 割り込み要因の OR が立ち上がったときだけ割り込みを要求する
+    // 立ったままの間に別の要因が成り立っても要求しない(STAT blocking)
+    fn update_stat_line(&mut self) {
+        let line = self.stat_sources();
+        if line && !self.stat_line {
+            self.interruption
+                .borrow_mut()
+                .request(Peripheral::LcdStatus);
+        }
+        self.stat_line = line;
+    }
+
+    // STAT の下位2ビットに対応するモード
+    // 0: HBlank, 1: VBlank, 2: OAM Scan, 3: Drawing
+    fn mode(&self) -> u8 {
+        if self.ly >= HEIGHT_LCD {
+            return 1;
+        }
+        // 1ライン 456 T-Cycle のうち、OAM Scan が 80、Drawing が 172 からスクロールとスプライトで伸び、残りが HBlank
+        match self.dots() {
+            dots if dots < OAM_SCAN_CYCLE => 2,
+            dots if dots < OAM_SCAN_CYCLE + self.drawing_cycles => 3,
+            _ => 0,
+        }
+    }
+
+    // 現在のラインが始まってからの T-Cycle 数
+    fn dots(&self) -> u64 {
+        self.clock + SCANLINE_CYCLE - self.clock_next_target
+    }
+
+    /// OAM Scan 中に 16bit の INC/DEC で OAM を指すと、読み込み中の行が前の行と混ざる
+    /// 8 バイトの行を 4 T-Cycle に 1 行ずつ読んでいる
+    pub fn corrupt_oam(&mut self, address: Address) {
+        if !self.quirks.oam_corruption
+            || !self.lcdc.lcd_enable
+            || !(0xFE00..=0xFEFF).contains(&address)
+            || self.mode() != 2
+        {
+            return;
+        }
+        let row = (self.dots() / 4) as usize;
+        // 先頭の行は壊れない
+        if row == 0 {
+            return;
+        }
+        let word = |i: usize| u16::from_le_bytes([self.oam[i], self.oam[i + 1]]);
+        let (a, b, c) = (word(row * 8), word(row * 8 - 8), word(row * 8 - 4));
+        let first = ((a ^ c) & (b ^ c)) ^ c;
+        self.oam[row * 8..row * 8 + 2].copy_from_slice(&first.to_le_bytes());
+        // 残りの 3 ワードは前の行と同じになる
+        self.oam.copy_within(row * 8 - 6..row * 8, row * 8 + 2);
+    }
+
+    // 描画し終えた裏のバッファを表にする
+    // 書き込まれなかったピクセルは前のフレームのまま残すので、入れ替えずに写す
+    // LCD が前の表のバッファを持ち続けている場合は新しく確保される
+    fn swap_buffers(&mut self) {
+        *Arc::make_mut(&mut self.front_buffer) = *self.frame_buffer;
+    }
+
+    // これから描画するフレームの情報
+    fn frame_info(&mut self) -> FrameInfo {
+        FrameInfo {
+            frame: self.frames,
+            cycles: self.clock,
+            speed: self.measure_speed(),
+            stats: self.stats,
+            input: self.input,
+            overlay: self.overlay,
+        }
+    }
+
+    // 前のフレームからの実機に対する速さ
+    #[cfg(feature = "std")]
+    fn measure_speed(&mut self) -> f64 {
+        let now = Instant::now();
+        let frame_time = FRAME_CYCLE as f64 / CPU::CLOCK as f64;
+        match self.last_draw.replace(now) {
+            Some(last) if now > last => frame_time / (now - last).as_secs_f64(),
+            _ => 1.0,
+        }
+    }
+
+    // 時計が無いので実機と同じ速さとみなす
+    #[cfg(not(feature = "std"))]
+    fn measure_speed(&mut self) -> f64 {
+        1.0
+    }
+
+    /// 描画先を足す。interval フレームに1回描画する(1 なら毎フレーム)
+    /// 表示しながら録画やハッシュの計算をするときのように、同じ画面を複数の描画先に渡す
+    pub fn add_lcd(&mut self, lcd: Box<dyn LCD>, interval: u32) {
+        self.sinks.push(Sink {
+            lcd,
+            interval: interval.max(1),
+        });
+    }
+
+    // このフレームを描画する LCD に描画する。SGB の場合はマスクと枠を反映する
+    fn draw(&mut self, info: &FrameInfo) {
+        let frame = self.ghosted_frame();
+        let sinks: Vec<&Sink> = self
+            .sinks
+            .iter()
+            .filter(|sink| info.frame.is_multiple_of(sink.interval as u64))
+            .collect();
+        let sgb = match &self.sgb {
+            Some(sgb) => sgb,
+            None => {
+                for sink in sinks {
+                    sink.lcd.draw(&frame, info);
+                }
+                return;
+            }
+        };
+        let frame_buffer = match sgb.mask() {
+            Mask::Freeze => return,
+            Mask::Black => [[BLACK; WIDTH_LCD as usize]; HEIGHT_LCD as usize],
+            Mask::Color0 => [[sgb.backdrop(); WIDTH_LCD as usize]; HEIGHT_LCD as usize],
+            Mask::Cancel => *frame,
+        };
+        // 枠を合成するのは、枠を表示する描画先があるときだけ
+        let border = sinks
+            .iter()
+            .any(|sink| sink.lcd.viewport() == Viewport::SgbBorder);
+        let screen = (sgb.has_border() && border).then(|| sgb.compose(&frame_buffer));
+        for sink in sinks {
+            match &screen {
+                Some(screen) if sink.lcd.viewport() == Viewport::SgbBorder => {
+                    sink.lcd.draw_with_border(&frame_buffer, screen, info)
+                }
+                _ => sink.lcd.draw(&frame_buffer, info),
+            }
+        }
+    }
+
+    // 表のバッファに前のフレームを ghosting % 混ぜたもの
+    fn ghosted_frame(&mut self) -> Arc<FrameBuffer> {
+        if self.ghosting == 0 {
+            return Arc::clone(&self.front_buffer);
+        }
+        let current = Box::new(*self.front_buffer);
+        let previous = match self.previous_frame.replace(current) {
+            Some(previous) => previous,
+            None => return Arc::clone(&self.front_buffer),
+        };
+        let weight = self.ghosting as u16;
+        let mix = |c: u8, p: u8| ((c as u16 * (100 - weight) + p as u16 * weight) / 100) as u8;
+        let mut frame = *self.front_buffer;
+        for (line, previous) in frame.iter_mut().zip(previous.iter()) {
+            for (pixel, p) in line.iter_mut().zip(previous.iter()) {
+                *pixel = PixelData(
+                    mix(pixel.0, p.0),
+                    mix(pixel.1, p.1),
+                    mix(pixel.2, p.2),
+                    pixel.3,
+                );
+            }
+        }
+        Arc::new(frame)
+    }
+
+    // 1行(= 160 pixel)の描画
+    // 1行のスキャンラインは 456 T-Cycle
+    // ここでは frame_buffer に書き込む
+    fn scan_line(&mut self, ly: u16) {
+        // OAMをスキャンしてスプライトバッファに格納
+        // 背景描画。Pixel Fetcher が背景FIFOに描画するピクセルを供給し続ける。
+        // タイル番号を取得、タイルデータの1バイト目を取得、タイルデータの2バイト目を取得、対応するFIFOにプッシュ
+        // FIFOは2つある（背景ウィンドウ用とスプライト用）
+        // FIFOが埋まったらLCDにプッシュ。（2つのFIFOをマージすることもある）
+        if self.ly >= HEIGHT_LCD {
+            return;
+        }
+        let line = match self.line {
+            Some(line) => line,
+            None => self.line_registers(),
+        };
+
+        // mode 2: OAM Scan
+        let sprite_buffer = Sprite::oam_scan(&self.oam, ly, self.lcdc);
+        let sprite_line = self.sprite_line(ly, &sprite_buffer);
+        let window_x = self.window_x(ly, &line);
+
+        // mode 3: Drawing
+        // 1ピクセルずつ LCD に push する。FIFO が空になったら Pixel Fetcher が次のタイルの
+        // 8ピクセルを供給する。先頭のタイルだけ、SCX % 8 ピクセルを捨ててから push する
+        self.fifo_background.clear();
+        for rx in 0..WIDTH_LCD {
+            if self.fifo_background.is_empty() {
+                let tile_number = self.fetch_bg_tile_number(ly, rx, &line);
+                let tile_data = self.fetch_bg_tile_data(tile_number, ly, line.scy);
+                self.push_bg_fifo(tile_data);
+                if rx == 0 {
+                    self.fifo_background
+                        .drain(..(line.scx % WIDTH_TILE) as usize);
+                }
+            }
+            // 捨てるのは 7 ピクセルまでなので、取得した直後の FIFO は空にならない
+            let bg_pixel = self
+                .fifo_background
+                .pop_front()
+                .expect("Background FIFO is empty after fetch");
+            // ウィンドウは背景の上に描画する
+            let bg_pixel = match window_x {
+                Some(x) if rx >= x => self.fetch_window_pixel(rx + 7 - line.wx as u16),
+                _ => bg_pixel,
+            };
+            let sp_pixel = sprite_line[rx as usize];
+            // 背景とウィンドウの色は BGP、スプライトの色は OBP0 / OBP1 で決まる
+            // 優先度はパレットを通す前の色番号で比べる
+            // 表示色もどちらの層を描くかで選ぶ
+            let (color, colors) = match sp_pixel {
+                Some(sp_pixel) => {
+                    if sp_pixel.color == Color::White {
+                        (bg_pixel.color.apply_palette(line.bgp), &self.colors.bg)
+                    } else if sp_pixel.background_priority && bg_pixel.color != Color::White {
+                        (bg_pixel.color.apply_palette(line.bgp), &self.colors.bg)
+                    } else {
+                        (
+                            sp_pixel.color.apply_palette(line.obp(sp_pixel.palette)),
+                            self.colors.obj(sp_pixel.palette),
+                        )
+                    }
+                }
+                None => (bg_pixel.color.apply_palette(line.bgp), &self.colors.bg),
+            };
+            self.frame_buffer[ly as usize][rx as usize] = match self
+                .sgb
+                .as_ref()
+                .and_then(|sgb| sgb.color(rx as usize, ly as usize, color.number() as usize))
+            {
+                Some(color) => color,
+                None => color.to_rgba(colors),
+            };
+        }
+        // mode 0: H-Blank
+        // 最後のタイルの残りは次の行の先頭で捨てる
+        debug_assert!(self.fifo_background.len() < WIDTH_TILE as usize);
+        if window_x.is_some() {
+            self.window_line += 1;
+        }
+    }
+
+    // この行でウィンドウの描画を始める X 座標。描画しなければ None
+    // WX は 7 ずれていて、0 - 6 なら画面の左端から始まる
+    fn window_x(&mut self, ly: u16, line: &LineRegisters) -> Option<u16> {
+        if ly == line.wy as u16 {
+            self.window_triggered = true;
+        }
+        if !self.lcdc.window_enable || !self.window_triggered || line.wx as u16 >= WIDTH_LCD + 7 {
+            return None;
+        }
+        Some((line.wx as u16).saturating_sub(7))
+    }
+
+    // ウィンドウの中の x 列目のピクセル。行は内部のライン数で決まる
+    fn fetch_window_pixel(&self, x: u16) -> Pixel {
+        let base_address: Address = self.lcdc.window_tile_map_select.into();
+        let map_address = base_address + 32 * (self.window_line / HEIGHT_TILE) + x / WIDTH_TILE;
+        let tile_number = self.read(map_address);
+        let tile_data = self.fetch_bg_tile_data(tile_number, self.window_line, 0);
+        let color = tile_data.into_iter().nth((x % WIDTH_TILE) as usize);
+        Pixel {
+            color: color.unwrap(),
+            palette: 0,
+            background_priority: false,
+        }
+    }
+
+    // 優先度の高い順に並んだスプライトを重ねて、1行分のスプライトのピクセルを作る
+    // 優先度の高いスプライトの透明なピクセルには、低いスプライトのピクセルが見える
+    fn sprite_line(&self, ly: u16, sprites: &[Sprite]) -> [Option<Pixel>; WIDTH_LCD as usize] {
+        let mut line = [Option::None; WIDTH_LCD as usize];
+        for sprite in sprites {
+            let address = sprite.tile_address(ly, self.lcdc.sprite_size);
+            let tile_line = TileLine {
+                low: self.read(address),
+                high: self.read(address + 1),
+            };
+            for (i, color) in tile_line.into_iter().enumerate() {
+                // 画面上の X 座標は X - 8。画面の外と色番号0(透明)は描画しない
+                let x = match (sprite.x_position + i as u16).checked_sub(8) {
+                    Some(x) if x < WIDTH_LCD => x as usize,
+                    _ => continue,
+                };
+                if color == Color::White || line[x].is_some() {
+                    continue;
+                }
+                line[x] = Some(Pixel {
+                    color,
+                    palette: sprite.palette,
+                    background_priority: sprite.background_priority,
+                });
+            }
+        }
+        line
+    }
+
+    fn fetch_bg_tile_number(&self, ly: u16, rx: u16, line: &LineRegisters) -> u8 {
+        self.read(tile_number_address(
+            self.lcdc.bg_tile_map_select.into(),
+            ly,
+            rx,
+            line.scx,
+            line.scy,
+        ))
+    }
+    fn fetch_bg_tile_data(&self, tile_number: u8, ly: u16, scy: u16) -> TileLine {
+        let address = tile_number_to_address(tile_number, self.lcdc.tile_data_select, ly, scy);
+        let low = self.read(address);
+        let high = self.read(address + 1);
+        TileLine { low, high }
+    }
+    fn push_bg_fifo(&mut self, tile_line: TileLine) {
+        // 8画素分のピクセルデータを fifo にいれ、pushしたピクセル数を返す
+        for color in tile_line {
+            self.fifo_background.push_back(Pixel {
+                color,
+                palette: 0,
+                background_priority: false,
+            });
+        }
+    }
+}
+
+// レジスタの書き込めるビット
+// LY は読み込み専用で、STAT のモードと LYC 一致(bit 0 - 2)と常に 1 の bit 7 は書き換えられない
+fn write_mask(address: Address) -> u8 {
+    match address {
+        0xFF41 => 0b_0111_1000,
+        0xFF44 => 0x00,
+        _ => 0xFF,
+    }
+}
+
+impl IO for PPU {
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0xFE00..=0xFE9F => self.oam[(address - 0xFE00) as usize],
+            // 0x8000 - 0x9FFF: 8KB VRAM
+            0x8000..=0x9FFF => self.vram[(address - 0x8000) as usize],
+            // レジスタ
+            0xFF40..=0xFF4B => {
+                match address {
+                    0xFF40 => self.lcdc.into(),
+                    0xFF41 => {
+                        // bit 7 は常に 1。LCD が無効ならモードは 0
+                        let mode = if self.lcdc.lcd_enable { self.mode() } else { 0 };
+                        let coincidence = (self.ly == self.lyc as u16) as u8;
+                        0x80 | self.stat | coincidence << 2 | mode
+                    }
+                    0xFF42 => self.scy.try_into().unwrap(),
+                    0xFF43 => self.scx.try_into().unwrap(),
+                    0xFF44 => self.ly.try_into().unwrap(),
+                    0xFF45 => self.lyc,
+                    0xFF47 => self.bgp,
+                    0xFF48 => self.obp0,
+                    0xFF49 => self.obp1,
+                    0xFF4A => self.wy,
+                    0xFF4B => self.wx,
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xFE00..=0xFE9F => self.oam[(address - 0xFE00) as usize] = data,
+            // 0x8000 - 0x9FFF: 8KB VRAM
+            0x8000..=0x9FFF => self.vram[(address - 0x8000) as usize] = data,
+            // レジスタ
+            0xFF40..=0xFF4B => {
+                let data = data & write_mask(address);
+                match address {
+                    0xFF40 => {
+                        self.lcdc = LcdControl::from(data);
+                        self.update_stat_line();
+                    }
+                    0xFF41 => {
+                        // DMG では書き込んだ瞬間だけ全ての STAT 割り込みが有効になる
+                        if self.quirks.stat_write
+                            && self.lcdc.lcd_enable
+                            && !self.stat_line
+                            && (self.mode() < 2 || self.ly == self.lyc as u16)
+                        {
+                            self.interruption
+                                .borrow_mut()
+                                .request(Peripheral::LcdStatus);
+                        }
+                        self.stat = data;
+                        self.update_stat_line();
+                    }
+                    0xFF42 => self.scy = data as u16,
+                    0xFF43 => self.scx = data as u16,
+                    // 書き込みは無視する。poke でだけ書き換えられる
+                    0xFF44 => {}
+                    0xFF45 => {
+                        self.lyc = data;
+                        self.update_stat_line();
+                    }
+                    // 0xFF46 の OAM DMA は MotherBoard が転送する
+                    0xFF47 => self.bgp = data,
+                    0xFF48 => self.obp0 = data,
+                    0xFF49 => self.obp1 = data,
+                    0xFF4A => self.wy = data,
+                    0xFF4B => self.wx = data,
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    // デバッガや電源投入直後の状態を作るときは LY も書き換える
+    fn poke(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF44 => self.ly = data as u16,
+            _ => self.write(address, data),
+        }
+    }
+}
+
+impl Debug for PPU {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // rom_data は表示しない
+        write!(f, "Lcd")
+    }
+}
+
+fn tile_number_address(base_address: Address, ly: u16, rx: u16, scx: u16, scy: u16) -> Address {
+    // オフセット計算
+    let offset_x = (rx / WIDTH_TILE) + ((scx / WIDTH_TILE) & 0x001F);
+    let offset_y = 32 * (((ly + scy) & 0xFF) / HEIGHT_TILE);
+    let tile_address = base_address + ((offset_x + offset_y) & 0x03FF);
+    tile_address
+}
+
+fn tile_number_to_address(tile_number: u8, method: TileDataSelect, ly: u16, scy: u16) -> Address {
+    let base_address = match method {
+        TileDataSelect::Method8000 => 0x8000 + tile_number.to_unsigned_u16().wrapping_mul(16),
+        TileDataSelect::Method8800 => {
+            0x9000u16.add_signed_u16(tile_number.to_signed_u16().wrapping_mul(16))
+        }
+    };
+    // 1画素あたり2bitなので1行ズレると2バイト後ろのデータになる
+    let offset = 2 * ((ly + scy) % HEIGHT_TILE);
+    base_address + offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ram_init::RamInit;
+    use core::cell::Cell;
+    use proptest::prelude::*;
+
+    fn new_ppu() -> PPU {
+        let mut ppu = PPU::new(
+            Box::new(Headless),
+            Rc::new(RefCell::new(Interruption::new())),
+            Rc::new(EventBus::new()),
+        );
+        // 色番号をそのまま表示する
+        ppu.write(0xFF47, 0xE4);
+        ppu
+    }
+
+    // 書き込めなくなったら録画だけをやめて、panic せずに動かし続ける
+    #[cfg(all(feature = "recorder", target_os = "linux"))]
+    #[test]
+    fn test_recording_error() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x91);
+        ppu.start_recording("/dev/full", 1, 1).unwrap();
+        let mut frames = 0;
+        while ppu.recorder.is_some() {
+            assert!(frames < 100, "recording did not fail");
+            for _ in 0..70224 / 4 {
+                ppu.tick(4);
+            }
+            frames += 1;
+        }
+    }
+
+    #[test]
+    fn test_decode_tile() {
+        let mut ppu = new_ppu();
+        // https://www.huderlem.com/demos/gameboy2bpp.html の例
+        ppu.write(0x8010, 0x3C);
+        ppu.write(0x8011, 0x7E);
+        ppu.write(0x8012, 0x42);
+        ppu.write(0x8013, 0x42);
+        let tile = ppu.decode_tile(1).0;
+        assert_eq!(tile[0], [0, 2, 3, 3, 3, 3, 2, 0]);
+        assert_eq!(tile[1], [0, 3, 0, 0, 0, 0, 3, 0]);
+        assert_eq!(tile[2], [0; 8]);
+        assert_eq!(ppu.decode_tile(0), TileData([[0; 8]; 8]));
+        // debug_view も同じ展開をする。背景マップは LCDC の設定で解釈する
+        ppu.write(0x9C00, 0x01);
+        ppu.write(0xFF40, 0b_1001_0000);
+        let view = ppu.debug_view();
+        assert_eq!(view.tiles[1].0, tile);
+        assert_eq!(view.tile_maps[1][0].tile_index, 1);
+        assert_eq!(view.bg_map, 0x9800);
+        assert_eq!(view.oam.len(), 40);
+    }
+
+    #[test]
+    fn test_swap_buffers() {
+        let mut ppu = new_ppu();
+        // 描画途中のフレームは表に出ない
+        ppu.frame_buffer[0][0] = BLACK;
+        let front = ppu.front_buffer();
+        assert!(front[0][0] == WHITE);
+
+        // 前のフレームを持っている側には影響しない
+        ppu.swap_buffers();
+        assert!(front[0][0] == WHITE);
+        assert!(ppu.front_buffer()[0][0] == BLACK);
+        assert!(ppu.frame_buffer[0][0] == BLACK);
+    }
+
+    #[test]
+    fn test_ghosting() {
+        let mut ppu = new_ppu();
+        assert!(ppu.ghosted_frame()[0][0] == WHITE);
+        ppu.set_ghosting(50);
+        // 前のフレームが無ければそのまま
+        assert!(ppu.ghosted_frame()[0][0] == WHITE);
+        ppu.frame_buffer[0][0] = BLACK;
+        ppu.swap_buffers();
+        let frame = ppu.ghosted_frame();
+        assert_eq!(frame[0][0].luminance(), 127);
+        assert!(frame[0][1] == WHITE);
+        // 表のバッファには混ぜない
+        assert!(ppu.front_buffer()[0][0] == BLACK);
+        ppu.set_ghosting(0);
+        assert!(ppu.ghosted_frame()[0][0] == BLACK);
+    }
+
+    struct Count(Rc<Cell<u64>>);
+
+    impl LCD for Count {
+        fn draw(&self, _frame_buffer: &FrameBuffer, _info: &FrameInfo) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_sinks() {
+        let every = Rc::new(Cell::new(0));
+        let third = Rc::new(Cell::new(0));
+        let mut ppu = PPU::new(
+            Box::new(Count(Rc::clone(&every))),
+            Rc::new(RefCell::new(Interruption::new())),
+            Rc::new(EventBus::new()),
+        );
+        ppu.add_lcd(Box::new(Count(Rc::clone(&third))), 3);
+        for frame in 0..6 {
+            ppu.frames = frame;
+            let info = ppu.frame_info();
+            ppu.draw(&info);
+        }
+        // 描画先ごとに間引く
+        assert_eq!((every.get(), third.get()), (6, 2));
+        // リセットしても描画先は残る
+        ppu.reset(false);
+        let info = ppu.frame_info();
+        ppu.draw(&info);
+        assert_eq!((every.get(), third.get()), (7, 2));
+    }
+
+    #[test]
+    fn test_frame_info() {
+        let mut ppu = new_ppu();
+        ppu.clock = FRAME_CYCLE * 60;
+        ppu.frames = 60;
+        let info = ppu.frame_info();
+        assert_eq!((info.frame, info.speed), (60, 1.0));
+        // 60 フレームで約 1 秒
+        assert_eq!(info.emulated_time().as_millis(), 1004);
+        assert!(ppu.last_draw.is_some());
+        assert_eq!(info.stats, None);
+    }
+
+    // IF の LCD STAT のビットを読んでから消す
+    fn take_stat_request(ppu: &PPU) -> bool {
+        let mut interruption = ppu.interruption.borrow_mut();
+        let requested = interruption.read(0xFF0F) & 0b_0000_0010 != 0;
+        interruption.write(0xFF0F, 0x00);
+        requested
+    }
+
+    #[test]
+    fn test_hblank_stat_interrupt() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x80);
+        ppu.write(0xFF41, 0b_0000_1000);
+        // LYC はどの行とも一致させない
+        ppu.write(0xFF45, 0xFF);
+        // 表示する各行で、HBlank に入った tick で要求する
+        for ly in 0..HEIGHT_LCD {
+            for _ in 0..252 / 4 - 1 {
+                ppu.tick(4);
+            }
+            assert_eq!(ppu.read(0xFF41), 0b_1000_1011);
+            assert!(!take_stat_request(&ppu), "ly = {}", ly);
+            ppu.tick(4);
+            assert_eq!(ppu.read(0xFF41), 0b_1000_1000);
+            assert!(take_stat_request(&ppu), "ly = {}", ly);
+            for _ in 0..(SCANLINE_CYCLE - 252) / 4 {
+                ppu.tick(4);
+            }
+        }
+        // VBlank の間は要求しない
+        assert_eq!(ppu.read(0xFF44), 144);
+        for _ in 0..SCANLINE_CYCLE * HEIGHT_LCD_MARGIN as u64 / 4 {
+            ppu.tick(4);
+            assert!(!take_stat_request(&ppu));
+        }
+    }
+
+    #[test]
+    fn test_write_mask() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x80);
+        ppu.write(0xFF45, 0xFF);
+        while ppu.read(0xFF44) != 5 {
+            ppu.tick(4);
+        }
+        // LY への書き込みは無視する
+        ppu.write(0xFF44, 100);
+        assert_eq!(ppu.read(0xFF44), 5);
+        ppu.poke(0xFF44, 100);
+        assert_eq!(ppu.read(0xFF44), 100);
+        // STAT は bit 3 - 6 だけ書き換わり、モードは PPU の状態のまま
+        let mode = ppu.read(0xFF41) & 0b11;
+        ppu.write(0xFF41, 0xFF);
+        assert_eq!(ppu.read(0xFF41), 0b_1111_1000 | mode);
+        ppu.write(0xFF41, 0x00);
+        assert_eq!(ppu.read(0xFF41), 0b_1000_0000 | mode);
+        // 他のレジスタはそのまま書ける
+        ppu.write(0xFF42, 0xAB);
+        assert_eq!(ppu.read(0xFF42), 0xAB);
+    }
+
+    #[test]
+    fn test_stat_blocking() {
+        // HBlank と VBlank を選ぶと、143 行目の HBlank から立ったままなので VBlank では要求しない
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x80);
+        ppu.write(0xFF41, 0b_0001_1000);
+        while ppu.read(0xFF44) != 143 || ppu.read(0xFF41) & 0b11 != 0 {
+            ppu.tick(4);
+        }
+        assert!(take_stat_request(&ppu));
+        while ppu.read(0xFF44) != 144 {
+            ppu.tick(4);
+            assert!(!take_stat_request(&ppu));
+        }
+
+        // LYC 一致と HBlank を選ぶと、一致した行の HBlank では要求しない
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x80);
+        ppu.write(0xFF45, 10);
+        ppu.write(0xFF41, 0b_0100_1000);
+        while ppu.read(0xFF44) != 10 {
+            ppu.tick(4);
+        }
+        assert_eq!(ppu.read(0xFF41) & 0b_0000_0100, 0b_0000_0100);
+        assert!(take_stat_request(&ppu));
+        while ppu.read(0xFF44) == 10 {
+            ppu.tick(4);
+            assert!(!take_stat_request(&ppu));
+        }
+        // 次の行では一致しなくなるので HBlank で要求する
+        while ppu.read(0xFF41) & 0b11 != 0 {
+            ppu.tick(4);
+        }
+        assert!(take_stat_request(&ppu));
+    }
+
+    #[test]
+    fn test_draw_at_hblank() {
+        // 背景の 2 番目のタイルだけ黒
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x91);
+        for address in 0x8010..0x8020 {
+            ppu.write(address, 0xFF);
+        }
+        ppu.write(0x9801, 0x01);
+        // 0 行目の HBlank で SCX を書き換えると、次の行から反映される
+        while ppu.read(0xFF41) & 0b11 != 0 {
+            ppu.tick(4);
+        }
+        ppu.write(0xFF43, 8);
+        while ppu.read(0xFF44) != 2 {
+            ppu.tick(4);
+        }
+        assert!(ppu.frame_buffer[0][0] == WHITE);
+        assert!(ppu.frame_buffer[1][0] == BLACK);
+    }
+
+    #[test]
+    fn test_sample_registers_at_drawing() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x91);
+        for address in 0x8010..0x8020 {
+            ppu.write(address, 0xFF);
+        }
+        ppu.write(0x9801, 0x01);
+        // Drawing 中に SCX と BGP を書き換えても、その行は Drawing に入ったときの値で描画される
+        while ppu.read(0xFF41) & 0b11 != 3 {
+            ppu.tick(4);
+        }
+        ppu.write(0xFF43, 8);
+        ppu.write(0xFF47, 0x1B);
+        while ppu.read(0xFF44) != 2 {
+            ppu.tick(4);
+        }
+        assert!(ppu.frame_buffer[0][0] == WHITE);
+        assert!(ppu.frame_buffer[0][8] == BLACK);
+        // 次の行は書き換えた後の値。1 つずれた黒いタイルが BGP で白に反転する
+        assert!(ppu.frame_buffer[1][0] == WHITE);
+        assert!(ppu.frame_buffer[1][8] == BLACK);
+    }
+
+    #[test]
+    fn test_fine_scroll() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x91);
+        // 左端の列だけ色番号 1 のタイルを背景の1行目に並べる
+        for row in 0..8 {
+            ppu.write(0x8010 + row * 2, 0x80);
+        }
+        for column in 0..32 {
+            ppu.write(0x9800 + column, 0x01);
+        }
+        // 先頭のタイルだけ 3 ピクセル捨てるので、どのタイルも 8 ピクセル幅で描画される
+        ppu.write(0xFF43, 3);
+        ppu.scan_line(0);
+        for x in 0..WIDTH_LCD as usize {
+            let expected = if (x + 3) % 8 == 0 { LIGHT_GRAY } else { WHITE };
+            assert!(ppu.frame_buffer[0][x] == expected, "x = {}", x);
+        }
+    }
+
+    proptest! {
+        // VRAM と OAM とレジスタがどんな値でも、全ての行を描画し終える
+        #[test]
+        fn prop_scan_line_terminates(
+            seed in any::<u64>(),
+            lcdc in any::<u8>(),
+            scroll in any::<(u8, u8)>(),
+            window in any::<(u8, u8)>(),
+        ) {
+            let mut ppu = new_ppu();
+            RamInit::Random(seed).fill(&mut [&mut ppu.vram, &mut ppu.oam]);
+            ppu.write(0xFF40, lcdc);
+            ppu.write(0xFF42, scroll.0);
+            ppu.write(0xFF43, scroll.1);
+            ppu.write(0xFF4A, window.0);
+            ppu.write(0xFF4B, window.1);
+            for ly in 0..HEIGHT_LCD {
+                ppu.ly = ly;
+                ppu.scan_line(ly);
+                prop_assert!(ppu.fifo_background.len() < WIDTH_TILE as usize);
+            }
+        }
+    }
+
+    // ly = 0 の行の Drawing の長さを 1 T-Cycle ずつ進めて測る
+    fn measure_drawing(ppu: &mut PPU) -> u64 {
+        while ppu.read(0xFF41) & 0b11 != 3 {
+            ppu.tick(1);
+        }
+        let start = ppu.dots();
+        while ppu.read(0xFF41) & 0b11 == 3 {
+            ppu.tick(1);
+        }
+        ppu.dots() - start
+    }
+
+    #[test]
+    fn test_drawing_cycles() {
+        let drawing = |lcdc: u8, scx: u8, sprites: &[u8]| {
+            let mut ppu = new_ppu();
+            ppu.write(0xFF40, lcdc);
+            ppu.write(0xFF43, scx);
+            for (i, &x) in sprites.iter().enumerate() {
+                put_sprite(&mut ppu, i as u16, x, 0);
+            }
+            measure_drawing(&mut ppu)
+        };
+        assert_eq!(drawing(0x91, 0, &[]), 172);
+        // 先頭のタイルで捨てるピクセルの分だけ伸びる
+        assert_eq!(drawing(0x91, 3, &[]), 175);
+        // タイルの境界にあるスプライトは背景の取得を待つ 5 と取得の 6
+        assert_eq!(drawing(0x93, 0, &[8]), 183);
+        // 同じタイルの2つ目は取得の分だけ
+        assert_eq!(drawing(0x93, 0, &[8, 10]), 189);
+        // タイルの右端に近いほど待つ時間は短い
+        assert_eq!(drawing(0x93, 0, &[14]), 178);
+        assert_eq!(drawing(0x93, 4, &[14]), 176 + 3 + 6);
+        // 画面の左の外は 11、右の外は取得しない
+        assert_eq!(drawing(0x93, 0, &[0, 168]), 183);
+        // スプライトを表示しなければ伸びない
+        assert_eq!(drawing(0x91, 0, &[8]), 172);
+        // ウィンドウに入ると取得し直す
+        let mut ppu = new_ppu();
+        ppu.write(0xFF4B, 7);
+        ppu.write(0xFF40, 0xB1);
+        assert_eq!(measure_drawing(&mut ppu), 178);
+    }
+
+    // ly = 0 の行に掛かるスプライトを OAM の index 番目に置く
+    fn put_sprite(ppu: &mut PPU, index: u16, x: u8, tile: u8) {
+        let address = 0xFE00 + index * 4;
+        for (i, data) in [16, x, tile, 0].into_iter().enumerate() {
+            ppu.write(address + i as u16, data);
+        }
+    }
+
+    #[test]
+    fn test_oam_scan() {
+        let mut ppu = new_ppu();
+        // X 座標の大きい順に12個置いても、選ばれるのは OAM の先頭から10個
+        for i in 0..12 {
+            put_sprite(&mut ppu, i, 100 - i as u8, 0);
+        }
+        // Y 座標が合わないスプライトは数えない
+        ppu.write(0xFE00 + 12 * 4, 100);
+        let sprites = Sprite::oam_scan(&ppu.oam, 0, ppu.lcdc);
+        let xs: Vec<u16> = sprites.iter().map(|s| s.x_position).collect();
+        assert_eq!(xs, (91..=100).collect::<Vec<u16>>());
+        // 画面の外(X = 0)のスプライトも数に含まれる
+        put_sprite(&mut ppu, 0, 0, 0);
+        let sprites = Sprite::oam_scan(&ppu.oam, 0, ppu.lcdc);
+        assert_eq!(sprites.len(), 10);
+        assert_eq!(sprites[0].x_position, 0);
+    }
+
+    #[test]
+    fn test_sprite_priority() {
+        let mut ppu = new_ppu();
+        // タイル1: 黒、タイル2: 左半分が明るい灰色で右半分は透明、タイル3: 暗い灰色
+        for (address, data) in [
+            (0x8010, 0xFF),
+            (0x8011, 0xFF),
+            (0x8020, 0xF0),
+            (0x8031, 0xFF),
+        ] {
+            ppu.write(address, data);
+        }
+        // 画面上の X 座標は 12 - 19
+        put_sprite(&mut ppu, 0, 20, 1);
+        // 画面上の X 座標は 8 - 15
+        put_sprite(&mut ppu, 1, 16, 2);
+        put_sprite(&mut ppu, 2, 16, 3);
+        let sprites = Sprite::oam_scan(&ppu.oam, 0, ppu.lcdc);
+        let line = ppu.sprite_line(0, &sprites);
+        let colors: Vec<Option<Color>> = line[8..20].iter().map(|p| p.map(|p| p.color)).collect();
+        // X 座標が同じなら OAM の先にあるスプライトが優先される
+        assert_eq!(colors[0..4], [Some(Color::LightGray); 4]);
+        // 透明なピクセルには次に優先度の高いスプライトが見える
+        // OAM の順序より X 座標の小さい方が優先される
+        assert_eq!(colors[4..8], [Some(Color::DarkGray); 4]);
+        assert_eq!(colors[8..12], [Some(Color::Black); 4]);
+        assert_eq!((line[7], line[20]), (None, None));
+    }
+
+    #[test]
+    fn test_sprite_palette() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0x93);
+        // タイル1: 黒
+        for address in 0x8010..0x8020 {
+            ppu.write(address, 0xFF);
+        }
+        // OBP1 を使うスプライト
+        put_sprite(&mut ppu, 0, 8, 1);
+        ppu.write(0xFE03, 0b_0001_0000);
+        ppu.write(0xFF48, 0x00);
+        ppu.write(0xFF49, 0xFF);
+        // OBP1 の値は描画するときに読むので、0 行目の HBlank で書き換えると次の行から反映される
+        while ppu.read(0xFF41) & 0b11 != 0 {
+            ppu.tick(4);
+        }
+        ppu.write(0xFF49, 0x00);
+        ppu.write(0xFF48, 0xFF);
+        while ppu.read(0xFF44) != 2 {
+            ppu.tick(4);
+        }
+        assert!(ppu.frame_buffer[0][0] == BLACK);
+        assert!(ppu.frame_buffer[1][0] == WHITE);
+    }
+
+    #[test]
+    fn test_colorization() {
+        let mut ppu = new_ppu();
+        ppu.set_colorization(Colorization::BLUE);
+        ppu.write(0xFF40, 0x93);
+        // タイル1: 黒
+        for address in 0x8010..0x8020 {
+            ppu.write(address, 0xFF);
+        }
+        // 背景は色番号 1、スプライトは色番号 2 にする
+        ppu.write(0xFF47, 0xE5);
+        ppu.write(0xFF48, 0x90);
+        ppu.write(0xFF49, 0x90);
+        put_sprite(&mut ppu, 0, 8, 1);
+        put_sprite(&mut ppu, 1, 16, 1);
+        ppu.write(0xFE07, 0b_0001_0000);
+        while ppu.read(0xFF44) != 1 {
+            ppu.tick(4);
+        }
+        assert!(ppu.frame_buffer[0][0] == PixelData(0x94, 0x3A, 0x3A, 0));
+        assert!(ppu.frame_buffer[0][8] == PixelData(0x00, 0x84, 0x00, 0));
+        assert!(ppu.frame_buffer[0][20] == PixelData(0x63, 0xA5, 0xFF, 0));
+        // 同じ色にすれば層によらない
+        ppu.set_palette(DEFAULT_PALETTE);
+        while ppu.read(0xFF44) != 2 {
+            ppu.tick(4);
+        }
+        assert!(ppu.frame_buffer[1][0] == DARK_GRAY);
+        assert!(ppu.frame_buffer[1][8] == DARK_GRAY);
+    }
+
+    #[test]
+    fn test_window_line_counter() {
+        let mut ppu = new_ppu();
+        // タイル1: 黒、タイル2: 明るい灰色
+        for address in 0x8010..0x8020 {
+            ppu.write(address, 0xFF);
+        }
+        for address in (0x8020..0x8030).step_by(2) {
+            ppu.write(address, 0xFF);
+        }
+        // 背景は全て明るい灰色。ウィンドウは 8 行目から黒
+        for address in 0x9800..0x9C00 {
+            ppu.write(address, 0x02);
+        }
+        for address in 0x9C20..0x9C40 {
+            ppu.write(address, 0x01);
+        }
+        // ウィンドウは X = 80、Y = 2 から
+        ppu.write(0xFF4A, 2);
+        ppu.write(0xFF4B, 80 + 7);
+        let render = |ppu: &mut PPU, lcdc: u8, lines: core::ops::Range<u16>| {
+            ppu.write(0xFF40, lcdc);
+            for ly in lines {
+                ppu.ly = ly;
+                ppu.scan_line(ly);
+            }
+        };
+        // ウィンドウのマップ 0x9C00、有効
+        render(&mut ppu, 0b_1111_0001, 0..6);
+        assert!(ppu.frame_buffer[1][80] == LIGHT_GRAY);
+        assert!(ppu.frame_buffer[2][79] == LIGHT_GRAY);
+        assert!(ppu.frame_buffer[2][80] == WHITE);
+        assert_eq!(ppu.window_line, 4);
+        // 途中でウィンドウを無効にした行は数えない
+        render(&mut ppu, 0b_1101_0001, 6..20);
+        assert!(ppu.frame_buffer[10][80] == LIGHT_GRAY);
+        assert_eq!(ppu.window_line, 4);
+        // 有効に戻すと LY ではなく内部のライン数の続きから描画する
+        render(&mut ppu, 0b_1111_0001, 20..30);
+        assert!(ppu.frame_buffer[23][80] == WHITE);
+        assert!(ppu.frame_buffer[24][80] == BLACK);
+        assert_eq!(ppu.window_line, 14);
+        // WX を画面外にした行も数えない
+        ppu.write(0xFF4B, 167);
+        render(&mut ppu, 0b_1111_0001, 30..32);
+        assert!(ppu.frame_buffer[31][159] == LIGHT_GRAY);
+        assert_eq!(ppu.window_line, 14);
+    }
+
+    #[test]
+    fn test_pixel_source() {
+        let mut ppu = new_ppu();
+        // 背景マップ 0x9800、タイルデータ 0x8000
+        ppu.write(0xFF40, 0b_1001_0011);
+        ppu.write(0x9821, 0x05);
+        put_sprite(&mut ppu, 3, 20, 1);
+        let source = ppu.pixel_source(8, 9);
+        assert_eq!(
+            (source.map_address, source.tile_number, source.tile_address),
+            (0x9821, 0x05, 0x8052)
+        );
+        assert_eq!(source.sprite, None);
+        // put_sprite は ly = 0 の行に置く
+        assert_eq!(ppu.pixel_source(12, 0).sprite, Some(3));
+        assert_eq!(ppu.pixel_source(20, 0).sprite, None);
+    }
+
+    #[test]
+    fn test_tile_number_address() {
+        assert_eq!(tile_number_address(0x9800, 0, 0, 0, 0), 0x9800);
+        assert_eq!(tile_number_address(0x9800, 1, 1, 0, 0), 0x9800);
+        assert_eq!(tile_number_address(0x9800, 8, 1, 0, 0), 0x9820);
+        assert_eq!(tile_number_address(0x9800, 20, 2, 0, 0), 0x9840);
+        assert_eq!(tile_number_address(0x9800, 144, 0, 0, 0), 0x9A40);
+    }
+
+    #[test]
+    fn test_tile_data_address_m8800() {
+        assert_eq!(
+            tile_number_to_address(127, TileDataSelect::Method8800, 0, 0),
+            0x97F0,
+        );
+        assert_eq!(
+            tile_number_to_address(0, TileDataSelect::Method8800, 0, 0),
+            0x9000
+        );
+        assert_eq!(
+            tile_number_to_address(1, TileDataSelect::Method8800, 0, 0),
+            0x9010
+        );
+        assert_eq!(
+            tile_number_to_address(1, TileDataSelect::Method8800, 2, 0),
+            0x9014
+        );
+        assert_eq!(
+            tile_number_to_address(32, TileDataSelect::Method8800, 5, 0),
+            0x920A
+        );
+        assert_eq!(
+            tile_number_to_address(-1i8 as u8, TileDataSelect::Method8800, 0, 0),
+            0x8FF0
+        );
+        assert_eq!(
+            tile_number_to_address(-2i8 as u8, TileDataSelect::Method8800, 0, 0),
+            0x8FE0
+        );
+        assert_eq!(
+            tile_number_to_address(-128i8 as u8, TileDataSelect::Method8800, 0, 0),
+            0x8800,
+        );
+    }
+
+    #[test]
+    fn test_tile_data_address_m8000() {
+        assert_eq!(
+            tile_number_to_address(0x00, TileDataSelect::Method8000, 0, 0),
+            0x8000
+        );
+        assert_eq!(
+            tile_number_to_address(0x02, TileDataSelect::Method8000, 0, 0),
+            0x8020
+        );
+        assert_eq!(
+            tile_number_to_address(0xFF, TileDataSelect::Method8000, 0, 0),
+            0x8FF0
+        );
+    }
+
+    #[test]
+    fn test_tile_line() {
+        let tl = TileLine {
+            low: 0b11001100,
+            high: 0b10101010,
+        };
+        let mut it = tl.into_iter();
+        assert_eq!(it.next(), Some(Color::Black));
+        assert_eq!(it.next(), Some(Color::LightGray));
+        assert_eq!(it.next(), Some(Color::DarkGray));
+        assert_eq!(it.next(), Some(Color::White));
+        assert_eq!(it.next(), Some(Color::Black));
+        assert_eq!(it.next(), Some(Color::LightGray));
+        assert_eq!(it.next(), Some(Color::DarkGray));
+        assert_eq!(it.next(), Some(Color::White));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn test_corrupt_oam() {
+        let mut ppu = new_ppu();
+        ppu.write(0xFF40, 0b_1000_0000);
+        for i in 0..ppu.oam.len() {
+            ppu.oam[i] = i as u8;
+        }
+        let before = ppu.oam;
+        // 既定では壊れない
+        ppu.clock = 8;
+        ppu.corrupt_oam(0xFE00);
+        assert_eq!(ppu.oam, before);
+
+        ppu.set_quirks(HardwareQuirks::from(crate::quirks::Accuracy::Accurate));
+        // 先頭の行を読んでいるとき、OAM 以外を指したときは壊れない
+        ppu.clock = 0;
+        ppu.corrupt_oam(0xFE00);
+        ppu.clock = 8;
+        ppu.corrupt_oam(0xC000);
+        assert_eq!(ppu.oam, before);
+
+        // 2 行目を読んでいる
+        ppu.corrupt_oam(0xFE00);
+        let (a, b, c) = (0x1110, 0x0908, 0x0D0C);
+        let first: u16 = ((a ^ c) & (b ^ c)) ^ c;
+        assert_eq!(ppu.oam[16..18], first.to_le_bytes());
+        assert_eq!(ppu.oam[18..24], before[10..16]);
+        assert_eq!(ppu.oam[..16], before[..16]);
+        assert_eq!(ppu.oam[24..], before[24..]);
+
+        // Drawing 中は壊れない
+        let after = ppu.oam;
+        ppu.clock = 100;
+        ppu.corrupt_oam(0xFE00);
+        assert_eq!(ppu.oam, after);
+    }
+}
@@ -0,0 +1,136 @@
+// CGB のブート ROM が DMG のゲームに付ける色
+// 背景とウィンドウ、OBP0 のスプライト、OBP1 のスプライトにそれぞれ4色のパレットを使う
+// https://gbdev.io/pandocs/Power_Up_Sequence.html#compatibility-palettes
+use alloc::format;
+use alloc::string::String;
+use core::str::FromStr;
+
+use super::{PixelData, DEFAULT_PALETTE};
+
+// 0xRRGGBB
+const fn rgb(c: u32) -> PixelData {
+    PixelData((c >> 16) as u8, (c >> 8) as u8, c as u8, 0)
+}
+
+const fn palette(colors: [u32; 4]) -> [PixelData; 4] {
+    [
+        rgb(colors[0]),
+        rgb(colors[1]),
+        rgb(colors[2]),
+        rgb(colors[3]),
+    ]
+}
+
+const RED: [PixelData; 4] = palette([0xFFFFFF, 0xFF8484, 0x943A3A, 0x000000]);
+const BROWN: [PixelData; 4] = palette([0xFFFFFF, 0xFFAD63, 0x843100, 0x000000]);
+const BLUE: [PixelData; 4] = palette([0xFFFFFF, 0x63A5FF, 0x0000FF, 0x000000]);
+const GREEN: [PixelData; 4] = palette([0xFFFFFF, 0x7BFF31, 0x008400, 0x000000]);
+
+/// DMG のゲームの色番号 0 - 3 に対応する表示色。描画する層ごとに別のパレットを持つ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Colorization {
+    // 背景とウィンドウ
+    pub bg: [PixelData; 4],
+    // OBP0 を使うスプライト
+    pub obj0: [PixelData; 4],
+    // OBP1 を使うスプライト
+    pub obj1: [PixelData; 4],
+}
+
+impl Colorization {
+    // ブート ROM でボタンを押しながら選べる組み合わせ
+    pub const BROWN: Self = Self::uniform(BROWN);
+    pub const RED: Self = Self::uniform(RED);
+    pub const DARK_BROWN: Self = Self::uniform(palette([0xFFE6C5, 0xCE9C84, 0x846B29, 0x5A3108]));
+    pub const PASTEL: Self = Self::uniform(palette([0xFFFFA5, 0xFF9494, 0x9494FF, 0x000000]));
+    pub const ORANGE: Self = Self::uniform(palette([0xFFFFFF, 0xFFFF00, 0xFF0000, 0x000000]));
+    pub const YELLOW: Self = Self {
+        bg: palette([0xFFFFFF, 0xFFFF00, 0x7B4A00, 0x000000]),
+        obj0: BLUE,
+        obj1: GREEN,
+    };
+    pub const BLUE: Self = Self {
+        bg: BLUE,
+        obj0: RED,
+        obj1: GREEN,
+    };
+    pub const DARK_BLUE: Self = Self {
+        bg: palette([0xFFFFFF, 0x8C8CDE, 0x52528C, 0x000000]),
+        obj0: RED,
+        obj1: BROWN,
+    };
+    pub const GRAY: Self = Self::uniform(palette([0xFFFFFF, 0xA5A5A5, 0x525252, 0x000000]));
+    pub const GREEN: Self = Self::uniform(palette([0xFFFFFF, 0x52FF00, 0xFF4200, 0x000000]));
+    pub const DARK_GREEN: Self = Self {
+        bg: palette([0xFFFFFF, 0x7BFF31, 0x0063C5, 0x000000]),
+        obj0: RED,
+        obj1: RED,
+    };
+    pub const INVERTED: Self = Self::uniform(palette([0x000000, 0x008484, 0xFFDE00, 0xFFFFFF]));
+
+    /// 名前で選べる組み合わせ
+    pub const PRESETS: [(&'static str, Self); 12] = [
+        ("brown", Self::BROWN),
+        ("red", Self::RED),
+        ("dark-brown", Self::DARK_BROWN),
+        ("pastel", Self::PASTEL),
+        ("orange", Self::ORANGE),
+        ("yellow", Self::YELLOW),
+        ("blue", Self::BLUE),
+        ("dark-blue", Self::DARK_BLUE),
+        ("gray", Self::GRAY),
+        ("green", Self::GREEN),
+        ("dark-green", Self::DARK_GREEN),
+        ("inverted", Self::INVERTED),
+    ];
+
+    /// 全ての層で同じパレットを使う
+    pub const fn uniform(palette: [PixelData; 4]) -> Self {
+        Self {
+            bg: palette,
+            obj0: palette,
+            obj1: palette,
+        }
+    }
+
+    /// スプライトのパレットの選択(0 = OBP0, 1 = OBP1)に対応する表示色
+    pub(super) fn obj(&self, palette: u8) -> &[PixelData; 4] {
+        match palette {
+            0 => &self.obj0,
+            _ => &self.obj1,
+        }
+    }
+}
+
+impl Default for Colorization {
+    fn default() -> Self {
+        Self::uniform(DEFAULT_PALETTE)
+    }
+}
+
+impl FromStr for Colorization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::PRESETS
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, colorization)| *colorization)
+            .ok_or_else(|| format!("Unknown colorization: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("brown".parse(), Ok(Colorization::BROWN));
+        let blue: Colorization = "blue".parse().unwrap();
+        assert!(blue.bg[2] == PixelData(0x00, 0x00, 0xFF, 0));
+        assert!(blue.obj(0)[1] == PixelData(0xFF, 0x84, 0x84, 0));
+        assert!(blue.obj(1) == &GREEN);
+        assert!("sepia".parse::<Colorization>().is_err());
+    }
+}
@@ -0,0 +1,124 @@
+// VRAM と OAM の内容を外部のデバッガやタイルビューアに渡すための型
+// 描画と同じ展開処理を使うので、表示が描画結果と食い違わない
+use alloc::vec::Vec;
+
+use super::{tile_number_to_address, LcdControl, TileLine};
+use crate::Address;
+
+/// 8 x 8 ピクセルのタイル。各ピクセルはパレット適用前の色番号(0 - 3)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileData(pub [[u8; 8]; 8]);
+
+impl TileData {
+    /// VRAM の 16 バイトから展開する。2 バイトで 1 行になり、1 バイト目が色番号の下位ビット
+    pub fn decode(bytes: &[u8; 16]) -> Self {
+        let mut pixels = [[0; 8]; 8];
+        for (row, line) in pixels.iter_mut().zip(bytes.chunks(2)) {
+            let tile_line = TileLine {
+                low: line[0],
+                high: line[1],
+            };
+            for (pixel, color) in row.iter_mut().zip(tile_line) {
+                *pixel = color.number();
+            }
+        }
+        Self(pixels)
+    }
+}
+
+/// 背景マップの1エントリ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileMapEntry {
+    /// マップに書かれたタイル番号
+    pub tile_number: u8,
+    /// 指すタイルの 0x8000 からの通し番号(0 - 383)
+    pub tile_index: usize,
+}
+
+impl TileMapEntry {
+    /// LCDC の bit 4 に従ってタイル番号を符号なし(0x8000 から)か符号付き(0x9000 から)で解釈する
+    pub fn decode(tile_number: u8, lcdc: u8) -> Self {
+        let method = LcdControl::from(lcdc).tile_data_select;
+        let address = tile_number_to_address(tile_number, method, 0, 0);
+        Self {
+            tile_number,
+            tile_index: (address - 0x8000) as usize / 16,
+        }
+    }
+}
+
+/// OAM の1エントリ(4 バイト)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OamEntry {
+    /// OAM の何番目のエントリか
+    pub index: usize,
+    /// 画面上の Y 座標 + 16
+    pub y: u8,
+    /// 画面上の X 座標 + 8
+    pub x: u8,
+    pub tile_number: u8,
+    /// 背景とウィンドウの色番号 1 - 3 の下に描く
+    pub bg_priority: bool,
+    pub y_flip: bool,
+    pub x_flip: bool,
+    /// OBP0 なら 0、OBP1 なら 1
+    pub palette: u8,
+}
+
+impl OamEntry {
+    pub fn decode(index: usize, bytes: &[u8; 4]) -> Self {
+        let flags = bytes[3];
+        Self {
+            index,
+            y: bytes[0],
+            x: bytes[1],
+            tile_number: bytes[2],
+            bg_priority: (flags >> 7) & 1 == 1,
+            y_flip: (flags >> 6) & 1 == 1,
+            x_flip: (flags >> 5) & 1 == 1,
+            palette: (flags >> 4) & 1,
+        }
+    }
+
+    /// 座標が画面に掛かっているか(8x8 のスプライトとして)
+    pub fn is_visible(&self) -> bool {
+        0 < self.x && self.x < 168 && 0 < self.y && self.y < 160
+    }
+}
+
+/// PPU::debug_view で取り出す VRAM と OAM の内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugView {
+    /// 0x8000 - 0x97FF の 384 個のタイル
+    pub tiles: Vec<TileData>,
+    /// 0x9800 と 0x9C00 の背景マップ(32 x 32)。タイル番号は現在の LCDC で解釈する
+    pub tile_maps: [Vec<TileMapEntry>; 2],
+    /// 背景とウィンドウが使っている背景マップの先頭アドレス
+    pub bg_map: Address,
+    pub window_map: Address,
+    pub oam: Vec<OamEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let mut bytes = [0; 16];
+        bytes[..4].copy_from_slice(&[0x3C, 0x7E, 0x42, 0x42]);
+        let tile = TileData::decode(&bytes);
+        assert_eq!(tile.0[0], [0, 2, 3, 3, 3, 3, 2, 0]);
+        assert_eq!(tile.0[1], [0, 3, 0, 0, 0, 0, 3, 0]);
+        // Method 8000 は 0x8000 から、Method 8800 は 0x9000 を 0 とする符号付き
+        assert_eq!(TileMapEntry::decode(0x80, 0x10).tile_index, 0x80);
+        assert_eq!(TileMapEntry::decode(0x80, 0x00).tile_index, 0x80);
+        assert_eq!(TileMapEntry::decode(0x7F, 0x00).tile_index, 0x17F);
+        assert_eq!(TileMapEntry::decode(0x00, 0x00).tile_index, 0x100);
+        let entry = OamEntry::decode(3, &[16, 8, 0x42, 0b_1011_0000]);
+        assert!(entry.bg_priority && entry.x_flip && !entry.y_flip);
+        assert_eq!(entry.palette, 1);
+        assert!(entry.is_visible());
+        assert!(!OamEntry::decode(0, &[0, 8, 0, 0]).is_visible());
+    }
+}
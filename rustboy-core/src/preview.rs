@@ -0,0 +1,41 @@
+// ROM の一覧を表示するランチャー向けに、起動してしばらく経った画面を PNG で返す
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::cartridges::Cartridge;
+use crate::mother_board::MotherBoard;
+use crate::png;
+
+/// プレビューを撮るまでのフレーム数の目安。約5秒で、多くのゲームはタイトル画面になる
+pub const PREVIEW_FRAMES: u64 = 300;
+
+/// rom を描画も入力も無しで frames フレーム実行し、その画面を PNG で返す
+/// 時刻や乱数に依存しないので、同じ ROM からは常に同じ画像になる
+/// 壊れた ROM や未対応の MBC、実行できない命令に遭遇した場合はエラーを返す
+pub fn preview(rom: Vec<u8>, frames: u64) -> Result<Vec<u8>, String> {
+    Cartridge::check_rom(&rom)?;
+    let mb = MotherBoard::headless(rom);
+    let mb = mb.borrow();
+    mb.reset();
+    for _ in 0..frames {
+        mb.run_until_vblank().map_err(|e| e.to_string())?;
+    }
+    let frame = mb.ppu().front_buffer();
+    Ok(png::encode(&frame))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo;
+    use alloc::vec;
+
+    #[test]
+    fn test_preview() {
+        let png = preview(demo::rom(), 10).unwrap();
+        assert_eq!(&png[0..8], &png::SIGNATURE);
+        assert_eq!(preview(demo::rom(), 10), Ok(png.clone()));
+        assert_ne!(preview(demo::rom(), 0), Ok(png));
+        assert!(preview(vec![0; 0x100], 10).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+// Python から import rustboy で使うモジュール
+// python/pyproject.toml から maturin でビルドする
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::cartridges::Cartridge;
+use crate::ffi::{self, Emulator};
+
+// 画面の大きさ。frame は RGBA で WIDTH x HEIGHT x 4 バイト
+const WIDTH: usize = 160;
+const HEIGHT: usize = 144;
+
+/// ヘッドレスで動かすエミュレータ。描画も標準入力も使わない
+// MotherBoard は Rc で共有しているので、作ったスレッドからしか使えない
+#[pyclass(unsendable, name = "RustBoy")]
+struct RustBoy {
+    // close した後は None
+    emulator: Option<Emulator>,
+}
+
+impl RustBoy {
+    fn emulator(&mut self) -> PyResult<&mut Emulator> {
+        self.emulator
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("RustBoy is closed"))
+    }
+}
+
+#[pymethods]
+impl RustBoy {
+    #[new]
+    fn new(rom: Vec<u8>) -> PyResult<Self> {
+        Cartridge::check_rom(&rom).map_err(PyValueError::new_err)?;
+        Ok(Self {
+            emulator: Some(Emulator::new(rom)),
+        })
+    }
+
+    /// frames フレーム実行する。CPU が停止すれば RuntimeError
+    #[pyo3(signature = (frames = 1))]
+    fn step(&mut self, frames: usize) -> PyResult<()> {
+        let emulator = self.emulator()?;
+        for _ in 0..frames {
+            emulator
+                .step_frame()
+                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// 最後に実行したフレームの画面(RGBA)のコピー。numpy.frombuffer で読める
+    fn frame<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, self.emulator()?.frame()))
+    }
+
+    /// 押されているボタンを A | RIGHT のように指定する。次に指定するまで押したままになる
+    fn set_buttons(&mut self, buttons: u8) -> PyResult<()> {
+        self.emulator()?.set_buttons(buttons);
+        Ok(())
+    }
+
+    fn read(&mut self, address: u16) -> PyResult<u8> {
+        Ok(self.emulator()?.read(address))
+    }
+
+    fn write(&mut self, address: u16, data: u8) -> PyResult<()> {
+        self.emulator()?.write(address, data);
+        Ok(())
+    }
+
+    /// address から length バイト読む。IO レジスタは状態を変えずに読む
+    fn read_range<'py>(
+        &mut self,
+        py: Python<'py>,
+        address: u16,
+        length: usize,
+    ) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(
+            py,
+            &self.emulator()?.read_range(address, length),
+        ))
+    }
+
+    fn write_range(&mut self, address: u16, data: Vec<u8>) -> PyResult<()> {
+        self.emulator()?.write_range(address, &data);
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.emulator = None;
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_exc))]
+    fn __exit__(&mut self, _exc: &Bound<'_, PyAny>) {
+        self.close();
+    }
+}
+
+#[pymodule]
+fn rustboy(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<RustBoy>()?;
+    m.add("WIDTH", WIDTH)?;
+    m.add("HEIGHT", HEIGHT)?;
+    // set_buttons に渡すボタンのビット
+    m.add("A", ffi::RUSTBOY_A)?;
+    m.add("B", ffi::RUSTBOY_B)?;
+    m.add("SELECT", ffi::RUSTBOY_SELECT)?;
+    m.add("START", ffi::RUSTBOY_START)?;
+    m.add("RIGHT", ffi::RUSTBOY_RIGHT)?;
+    m.add("LEFT", ffi::RUSTBOY_LEFT)?;
+    m.add("UP", ffi::RUSTBOY_UP)?;
+    m.add("DOWN", ffi::RUSTBOY_DOWN)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demo;
+
+    #[test]
+    fn test_rustboy() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let err = RustBoy::new(vec![0; 0x150]).err().unwrap();
+            assert!(err.is_instance_of::<PyValueError>(py));
+
+            let mut gb = RustBoy::new(demo::rom()).unwrap();
+            gb.set_buttons(ffi::RUSTBOY_RIGHT).unwrap();
+            gb.step(3).unwrap();
+            assert_eq!(gb.frame(py).unwrap().as_bytes().len(), WIDTH * HEIGHT * 4);
+            gb.write(0xC000, 0x42).unwrap();
+            gb.write_range(0xC001, vec![0x01, 0x02]).unwrap();
+            let wram = gb.read_range(py, 0xC000, 3).unwrap();
+            assert_eq!(wram.as_bytes(), [0x42, 0x01, 0x02]);
+            assert_eq!(gb.read(0xE000).unwrap(), 0x42);
+
+            // close した後は ValueError になる
+            gb.close();
+            assert!(gb.step(1).unwrap_err().is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_module() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "rustboy").unwrap();
+            rustboy(&module).unwrap();
+            let width: usize = module.getattr("WIDTH").unwrap().extract().unwrap();
+            assert_eq!(width, 160);
+            let down: u8 = module.getattr("DOWN").unwrap().extract().unwrap();
+            assert_eq!(down, 0x80);
+            assert!(module.getattr("RustBoy").is_ok());
+        });
+    }
+}
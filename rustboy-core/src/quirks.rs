@@ -0,0 +1,46 @@
+use alloc::format;
+use alloc::string::String;
+use core::str::FromStr;
+
+/// 実機の不具合をどこまで再現するか
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Accuracy {
+    // 不具合は再現しない。普段遊ぶとき用
+    Fast,
+    // テスト ROM が通るように既知の不具合を再現する
+    Accurate,
+}
+
+impl FromStr for Accuracy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fast" => Ok(Accuracy::Fast),
+            "accurate" => Ok(Accuracy::Accurate),
+            _ => Err(format!("Unknown accuracy: {}", s)),
+        }
+    }
+}
+
+/// 再現する DMG の不具合
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HardwareQuirks {
+    // HBlank、VBlank 中か LY=LYC のときに STAT に書き込むと STAT 割り込みが発生する
+    pub stat_write: bool,
+    // OAM Scan 中に 16bit の INC/DEC で 0xFE00 - 0xFEFF を指すと OAM が壊れる
+    pub oam_corruption: bool,
+    // 割り込み処理で PC の上位バイトを IE に積むと、割り込みが取り消されることがある
+    pub ie_push: bool,
+}
+
+impl From<Accuracy> for HardwareQuirks {
+    fn from(accuracy: Accuracy) -> Self {
+        let enabled = accuracy == Accuracy::Accurate;
+        Self {
+            stat_write: enabled,
+            oam_corruption: enabled,
+            ie_push: enabled,
+        }
+    }
+}
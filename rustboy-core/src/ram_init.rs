@@ -0,0 +1,139 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// 電源投入時の作業 RAM、VRAM、HRAM の中身
+/// 実機では不定で、初期化していない RAM を読むゲームやバグはこれで挙動が変わる
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RamInit {
+    #[default]
+    Zero,
+    // seed から作る疑似乱数。同じ seed なら同じ中身になるので再現できる
+    Random(u64),
+    // 全てのバイトを同じ値にする
+    Pattern(u8),
+}
+
+impl RamInit {
+    /// 作業 RAM、VRAM、HRAM の順に渡すと、1つの乱数列で続けて埋める
+    pub(crate) fn fill(&self, memories: &mut [&mut [u8]]) {
+        let mut random = SplitMix64(match self {
+            RamInit::Random(seed) => *seed,
+            _ => 0,
+        });
+        for memory in memories.iter_mut() {
+            match self {
+                RamInit::Zero => memory.fill(0),
+                RamInit::Pattern(value) => memory.fill(*value),
+                RamInit::Random(_) => {
+                    for chunk in memory.chunks_mut(8) {
+                        let bytes = random.next().to_le_bytes();
+                        chunk.copy_from_slice(&bytes[..chunk.len()]);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        let (tag, value) = match self {
+            RamInit::Zero => (0, 0),
+            RamInit::Random(seed) => (1, *seed),
+            RamInit::Pattern(value) => (2, *value as u64),
+        };
+        state.push(tag);
+        state.extend(value.to_le_bytes());
+    }
+}
+
+// 16 進数(0x 付き)か 10 進数
+fn parse_number(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+impl FromStr for RamInit {
+    type Err = String;
+
+    // "zero", "random(seed)", "pattern(0x55)"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let argument = |name: &str| {
+            s.strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix('('))
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|n| parse_number(n.trim()))
+        };
+        if s == "zero" {
+            Ok(RamInit::Zero)
+        } else if let Some(seed) = argument("random") {
+            Ok(RamInit::Random(seed))
+        } else if let Some(value) = argument("pattern").filter(|v| *v <= 0xFF) {
+            Ok(RamInit::Pattern(value as u8))
+        } else {
+            Err(format!("Unknown RAM init: {}", s))
+        }
+    }
+}
+
+// FromStr で読み戻せる形式
+impl fmt::Display for RamInit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RamInit::Zero => write!(f, "zero"),
+            RamInit::Random(seed) => write!(f, "random({})", seed),
+            RamInit::Pattern(value) => write!(f, "pattern(0x{:02X})", value),
+        }
+    }
+}
+
+// https://prng.di.unimi.it/splitmix64.c
+// seed が 0 でも偏らない
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("zero".parse(), Ok(RamInit::Zero));
+        assert_eq!("random(42)".parse(), Ok(RamInit::Random(42)));
+        assert_eq!("pattern(0x55)".parse(), Ok(RamInit::Pattern(0x55)));
+        assert!("pattern(0x100)".parse::<RamInit>().is_err());
+        assert!("random".parse::<RamInit>().is_err());
+        for init in [RamInit::Zero, RamInit::Random(7), RamInit::Pattern(0xAA)] {
+            assert_eq!(init.to_string().parse(), Ok(init));
+        }
+    }
+
+    #[test]
+    fn test_fill() {
+        let (mut a, mut b) = ([0xFF; 12], [0xFF; 3]);
+        RamInit::Zero.fill(&mut [&mut a, &mut b]);
+        assert_eq!((a, b), ([0; 12], [0; 3]));
+        RamInit::Pattern(0x55).fill(&mut [&mut a, &mut b]);
+        assert_eq!((a, b), ([0x55; 12], [0x55; 3]));
+        // 同じ seed なら同じ中身
+        RamInit::Random(1).fill(&mut [&mut a, &mut b]);
+        let (mut c, mut d) = ([0; 12], [0; 3]);
+        RamInit::Random(1).fill(&mut [&mut c, &mut d]);
+        assert_eq!((a, b), (c, d));
+        RamInit::Random(2).fill(&mut [&mut c, &mut d]);
+        assert_ne!(a, c);
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{BufWriter, Result};
+
+use apng::ApngEncoder;
+use gif::GifEncoder;
+
+use crate::ppu::{FrameBuffer, PixelData};
+
+mod apng;
+mod gif;
+
+// 白、ライトグレー、ダークグレー、黒の順
+const PALETTE: [(u8, u8, u8); 4] = [(255, 255, 255), (170, 170, 170), (85, 85, 85), (0, 0, 0)];
+
+// 1フレームは 70224 T-Cycle なので約 59.73 fps
+fn frames_to_centiseconds(frames: u64) -> u64 {
+    (frames * 10000 + 5973 / 2) / 5973
+}
+
+enum Encoder {
+    Gif(GifEncoder<BufWriter<File>>),
+    Apng(ApngEncoder<BufWriter<File>>),
+}
+
+/// フレームバッファを GIF / APNG に録画する
+/// 拡張子が .png / .apng なら APNG、それ以外は GIF で書き出す
+pub struct Recorder {
+    path: String,
+    encoder: Encoder,
+    // 縮小率。2 なら 80x72 で記録する
+    scale: usize,
+    // skip フレームごとに1枚記録する
+    // GIF は 1/100 秒単位なので、多くのビューアでは 2 以上にしないと遅く再生される
+    skip: u64,
+    counter: u64,
+}
+
+impl Recorder {
+    pub fn new(path: &str, scale: usize, skip: u64) -> Result<Self> {
+        let scale = scale.max(1);
+        let width = 160 / scale;
+        let height = 144 / scale;
+        let out = BufWriter::new(File::create(path)?);
+        let encoder = if path.ends_with(".png") || path.ends_with(".apng") {
+            Encoder::Apng(ApngEncoder::new(out, width as u32, height as u32)?)
+        } else {
+            Encoder::Gif(GifEncoder::new(out, width as u16, height as u16)?)
+        };
+        Ok(Self {
+            path: path.to_string(),
+            encoder,
+            scale,
+            skip: skip.max(1),
+            counter: 0,
+        })
+    }
+
+    /// 描画のたびに呼び出す
+    pub fn capture(&mut self, frame_buffer: &FrameBuffer) -> Result<()> {
+        self.counter += 1;
+        if !(self.counter - 1).is_multiple_of(self.skip) {
+            return Ok(());
+        }
+        let pixels: Vec<u8> = frame_buffer
+            .iter()
+            .step_by(self.scale)
+            .flat_map(|line| line.iter().step_by(self.scale).map(shade))
+            .collect();
+        match &mut self.encoder {
+            Encoder::Gif(e) => e.add_frame(&pixels, self.skip),
+            Encoder::Apng(e) => e.add_frame(&pixels, self.skip),
+        }
+    }
+
+    pub fn finish(self) -> Result<()> {
+        match self.encoder {
+            Encoder::Gif(e) => e.finish(),
+            Encoder::Apng(e) => e.finish(),
+        }
+    }
+}
+
+impl Debug for Recorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Recorder: {{ path: {}, scale: {}, skip: {}, frames: {} }}",
+            self.path, self.scale, self.skip, self.counter
+        )
+    }
+}
+
+// パレットの番号に変換
+fn shade(pixel: &PixelData) -> u8 {
+    match pixel {
+        PixelData(255, 255, 255, _) => 0,
+        PixelData(170, 170, 170, _) => 1,
+        PixelData(85, 85, 85, _) => 2,
+        PixelData(0, 0, 0, _) => 3,
+        // 想定外の色は輝度で近いものに寄せる
+        PixelData(r, g, b, _) => {
+            let luminance = (*r as u16 + *g as u16 + *b as u16) / 3;
+            3 - (luminance * 4 / 256) as u8
+        }
+    }
+}
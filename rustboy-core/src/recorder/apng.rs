@@ -0,0 +1,140 @@
+use std::io::{Result, Seek, SeekFrom, Write};
+
+use super::PALETTE;
+use crate::png::{chunk, zlib_stored, SIGNATURE};
+// acTL チャンクの位置。フレーム数は後から書き戻す
+const ACTL_OFFSET: u64 = 8 + (12 + 13) + (12 + 3 * 4);
+
+/// アニメーションPNGを書き出す
+pub struct ApngEncoder<W: Write + Seek> {
+    out: W,
+    width: u32,
+    height: u32,
+    num_frames: u32,
+    sequence_number: u32,
+}
+
+impl<W: Write + Seek> ApngEncoder<W> {
+    pub fn new(mut out: W, width: u32, height: u32) -> Result<Self> {
+        out.write_all(&SIGNATURE)?;
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        // ビット深度2のインデックスカラー
+        ihdr.extend_from_slice(&[2, 3, 0, 0, 0]);
+        write_chunk(&mut out, b"IHDR", &ihdr)?;
+        let plte: Vec<u8> = PALETTE.iter().flat_map(|&(r, g, b)| [r, g, b]).collect();
+        write_chunk(&mut out, b"PLTE", &plte)?;
+        write_chunk(&mut out, b"acTL", &actl(0))?;
+        Ok(Self {
+            out,
+            width,
+            height,
+            num_frames: 0,
+            sequence_number: 0,
+        })
+    }
+
+    /// パレット番号(0-3)の配列を1フレームとして追加する
+    /// frames はこの画像を表示し続けるエミュレータのフレーム数
+    pub fn add_frame(&mut self, pixels: &[u8], frames: u64) -> Result<()> {
+        assert_eq!(pixels.len(), (self.width * self.height) as usize);
+        let mut fctl = Vec::with_capacity(26);
+        fctl.extend_from_slice(&self.next_sequence_number().to_be_bytes());
+        fctl.extend_from_slice(&self.width.to_be_bytes());
+        fctl.extend_from_slice(&self.height.to_be_bytes());
+        fctl.extend_from_slice(&[0; 8]);
+        // 1フレームは 1 / 59.73 秒
+        fctl.extend_from_slice(&((frames * 100) as u16).to_be_bytes());
+        fctl.extend_from_slice(&5973u16.to_be_bytes());
+        fctl.extend_from_slice(&[0, 0]);
+        write_chunk(&mut self.out, b"fcTL", &fctl)?;
+
+        let data = zlib_stored(&pack_rows(pixels, self.width as usize));
+        if self.num_frames == 0 {
+            // 最初のフレームは通常のPNGとしても表示される
+            write_chunk(&mut self.out, b"IDAT", &data)?;
+        } else {
+            let mut fdat = Vec::with_capacity(4 + data.len());
+            fdat.extend_from_slice(&self.next_sequence_number().to_be_bytes());
+            fdat.extend_from_slice(&data);
+            write_chunk(&mut self.out, b"fdAT", &fdat)?;
+        }
+        self.num_frames += 1;
+        // 途中で終了しても再生できるように毎フレームフレーム数を書き戻す
+        self.out.seek(SeekFrom::Start(ACTL_OFFSET))?;
+        write_chunk(&mut self.out, b"acTL", &actl(self.num_frames))?;
+        self.out.seek(SeekFrom::End(0))?;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<()> {
+        write_chunk(&mut self.out, b"IEND", &[])?;
+        self.out.flush()
+    }
+
+    fn next_sequence_number(&mut self) -> u32 {
+        let n = self.sequence_number;
+        self.sequence_number += 1;
+        n
+    }
+}
+
+fn actl(num_frames: u32) -> Vec<u8> {
+    let mut v = Vec::with_capacity(8);
+    v.extend_from_slice(&num_frames.to_be_bytes());
+    // 0 は無限ループ
+    v.extend_from_slice(&0u32.to_be_bytes());
+    v
+}
+
+// 各行の先頭にフィルタ種別(0: None)を置き、1バイトに4ピクセルずつ上位ビットから詰める
+fn pack_rows(pixels: &[u8], width: usize) -> Vec<u8> {
+    let mut v = Vec::with_capacity(pixels.len() / 4 + pixels.len() / width + 1);
+    for row in pixels.chunks(width) {
+        v.push(0);
+        for four in row.chunks(4) {
+            let mut byte = 0u8;
+            for (i, p) in four.iter().enumerate() {
+                byte |= (p & 0b11) << (6 - 2 * i);
+            }
+            v.push(byte);
+        }
+    }
+    v
+}
+
+fn write_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> Result<()> {
+    let mut bytes = Vec::with_capacity(12 + data.len());
+    chunk(&mut bytes, kind, data);
+    out.write_all(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_pack_rows() {
+        assert_eq!(
+            pack_rows(&[0, 1, 2, 3, 3, 0, 0, 0, 1, 1], 5),
+            vec![0, 0b_0001_1011, 0b_1100_0000, 0, 0b_0000_0001, 0b_0100_0000]
+        );
+    }
+
+    #[test]
+    fn test_apng_frame_count() {
+        let mut cursor = Cursor::new(Vec::new());
+        let mut encoder = ApngEncoder::new(&mut cursor, 4, 1).unwrap();
+        encoder.add_frame(&[0, 1, 2, 3], 1).unwrap();
+        encoder.add_frame(&[3, 2, 1, 0], 1).unwrap();
+        encoder.finish().unwrap();
+        let buf = cursor.into_inner();
+        assert_eq!(&buf[0..8], &SIGNATURE);
+        let actl = ACTL_OFFSET as usize;
+        assert_eq!(&buf[actl + 4..actl + 8], b"acTL");
+        assert_eq!(&buf[actl + 8..actl + 12], &2u32.to_be_bytes());
+        assert_eq!(&buf[buf.len() - 8..buf.len() - 4], b"IEND");
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::io::{Result, Write};
+
+use super::{frames_to_centiseconds, PALETTE};
+
+// 4色なので1ピクセル2bit。GIFの最小コードサイズは2
+const MIN_CODE_SIZE: u8 = 2;
+const CLEAR_CODE: u16 = 1 << MIN_CODE_SIZE;
+const END_CODE: u16 = CLEAR_CODE + 1;
+// LZWのコードは最大12bit
+const MAX_CODE: u16 = 4095;
+
+/// アニメーションGIFを書き出す
+pub struct GifEncoder<W: Write> {
+    out: W,
+    width: u16,
+    height: u16,
+    // これまでに書き出したフレームの合計時間(エミュレータのフレーム数と1/100秒)
+    elapsed_frames: u64,
+    elapsed_centiseconds: u64,
+}
+
+impl<W: Write> GifEncoder<W> {
+    pub fn new(mut out: W, width: u16, height: u16) -> Result<Self> {
+        out.write_all(b"GIF89a")?;
+        // Logical Screen Descriptor
+        out.write_all(&width.to_le_bytes())?;
+        out.write_all(&height.to_le_bytes())?;
+        // グローバルカラーテーブルあり、色解像度2bit、テーブルサイズ 2^(1+1)
+        out.write_all(&[0b_1001_0001, 0, 0])?;
+        for (r, g, b) in PALETTE {
+            out.write_all(&[r, g, b])?;
+        }
+        // NETSCAPE2.0 拡張でループ再生させる
+        out.write_all(&[0x21, 0xFF, 0x0B])?;
+        out.write_all(b"NETSCAPE2.0")?;
+        out.write_all(&[0x03, 0x01, 0x00, 0x00, 0x00])?;
+        Ok(Self {
+            out,
+            width,
+            height,
+            elapsed_frames: 0,
+            elapsed_centiseconds: 0,
+        })
+    }
+
+    /// パレット番号(0-3)の配列を1フレームとして追加する
+    /// frames はこの画像を表示し続けるエミュレータのフレーム数
+    pub fn add_frame(&mut self, pixels: &[u8], frames: u64) -> Result<()> {
+        assert_eq!(pixels.len(), self.width as usize * self.height as usize);
+        // GIFの遅延は1/100秒単位なので、誤差が累積しないように合計時間から差分を求める
+        self.elapsed_frames += frames;
+        let elapsed = frames_to_centiseconds(self.elapsed_frames);
+        let delay = (elapsed - self.elapsed_centiseconds) as u16;
+        self.elapsed_centiseconds = elapsed;
+
+        // Graphic Control Extension
+        self.out.write_all(&[0x21, 0xF9, 0x04, 0x00])?;
+        self.out.write_all(&delay.to_le_bytes())?;
+        self.out.write_all(&[0x00, 0x00])?;
+        // Image Descriptor
+        self.out.write_all(&[0x2C, 0x00, 0x00, 0x00, 0x00])?;
+        self.out.write_all(&self.width.to_le_bytes())?;
+        self.out.write_all(&self.height.to_le_bytes())?;
+        self.out.write_all(&[0x00, MIN_CODE_SIZE])?;
+        // 画像データは255バイトごとのサブブロックに分割する
+        for block in lzw_compress(pixels).chunks(255) {
+            self.out.write_all(&[block.len() as u8])?;
+            self.out.write_all(block)?;
+        }
+        self.out.write_all(&[0x00])
+    }
+
+    pub fn finish(mut self) -> Result<()> {
+        self.out.write_all(&[0x3B])?;
+        self.out.flush()
+    }
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        // GIFは下位ビットから詰める
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw_compress(pixels: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bits: 0,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = MIN_CODE_SIZE + 1;
+    let mut next_code = END_CODE + 1;
+
+    // 符号を書き出した後、次に割り当てるコードが収まらなければビット幅を広げる
+    let emit = |writer: &mut BitWriter, code: u16, code_size: &mut u8, next_code: u16| {
+        writer.write(code, *code_size);
+        if next_code >= (1 << *code_size) && *code_size < 12 {
+            *code_size += 1;
+        }
+    };
+
+    emit(&mut writer, CLEAR_CODE, &mut code_size, next_code);
+    let mut iter = pixels.iter();
+    let mut prefix = match iter.next() {
+        Some(p) => *p as u16,
+        None => {
+            emit(&mut writer, END_CODE, &mut code_size, next_code);
+            return writer.finish();
+        }
+    };
+    for &pixel in iter {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        emit(&mut writer, prefix, &mut code_size, next_code);
+        if next_code >= MAX_CODE {
+            // 辞書が一杯になったら初期化する
+            emit(&mut writer, CLEAR_CODE, &mut code_size, next_code);
+            table.clear();
+            code_size = MIN_CODE_SIZE + 1;
+            next_code = END_CODE + 1;
+        } else {
+            table.insert((prefix, pixel), next_code);
+            next_code += 1;
+        }
+        prefix = pixel as u16;
+    }
+    emit(&mut writer, prefix, &mut code_size, next_code);
+    emit(&mut writer, END_CODE, &mut code_size, next_code);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // テスト用の素朴なLZWデコーダ
+    fn lzw_decompress(bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            table.clear();
+            for i in 0..CLEAR_CODE {
+                table.push(vec![i as u8]);
+            }
+            // クリアコードと終了コードの分
+            table.push(vec![]);
+            table.push(vec![]);
+        };
+        reset(&mut table);
+        let mut code_size = MIN_CODE_SIZE + 1;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut pos = 0usize;
+        loop {
+            let mut code = 0u16;
+            for i in 0..code_size {
+                let bit = (bytes[(pos + i as usize) / 8] >> ((pos + i as usize) % 8)) & 1;
+                code |= (bit as u16) << i;
+            }
+            pos += code_size as usize;
+            if code == CLEAR_CODE {
+                reset(&mut table);
+                code_size = MIN_CODE_SIZE + 1;
+                prev = None;
+                continue;
+            }
+            if code == END_CODE {
+                break;
+            }
+            let entry = match (table.get(code as usize), &prev) {
+                (Some(e), _) => e.clone(),
+                (None, Some(p)) => {
+                    let mut e = p.clone();
+                    e.push(p[0]);
+                    e
+                }
+                _ => panic!("invalid code"),
+            };
+            if let Some(p) = prev {
+                if table.len() < 4096 {
+                    let mut e = p.clone();
+                    e.push(entry[0]);
+                    table.push(e);
+                }
+            }
+            if table.len() >= (1 << code_size) && code_size < 12 {
+                code_size += 1;
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(entry);
+        }
+        out
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let flat = vec![0u8; 160 * 144];
+        assert_eq!(lzw_decompress(&lzw_compress(&flat)), flat);
+
+        let stripes: Vec<u8> = (0..160 * 144).map(|i| ((i / 3) % 4) as u8).collect();
+        assert_eq!(lzw_decompress(&lzw_compress(&stripes)), stripes);
+
+        // 辞書が一杯になってクリアコードが出るくらいの乱雑なデータ
+        let mut x = 12345u32;
+        let noise: Vec<u8> = (0..160 * 144)
+            .map(|_| {
+                x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                ((x >> 16) % 4) as u8
+            })
+            .collect();
+        assert_eq!(lzw_decompress(&lzw_compress(&noise)), noise);
+    }
+
+    #[test]
+    fn test_gif_structure() {
+        let mut buf = Vec::new();
+        let mut encoder = GifEncoder::new(&mut buf, 2, 2).unwrap();
+        encoder.add_frame(&[0, 1, 2, 3], 1).unwrap();
+        encoder.add_frame(&[3, 2, 1, 0], 1).unwrap();
+        encoder.finish().unwrap();
+        assert_eq!(&buf[0..6], b"GIF89a");
+        assert_eq!(&buf[6..10], &[2, 0, 2, 0]);
+        assert_eq!(buf.last(), Some(&0x3B));
+        // 2フレームで 100 / 59.73 * 2 = 3.35 なので 2 + 1 に分配される
+        let delays: Vec<u16> = buf
+            .windows(8)
+            .filter(|w| w[0..4] == [0x21, 0xF9, 0x04, 0x00])
+            .map(|w| u16::from_le_bytes([w[4], w[5]]))
+            .collect();
+        assert_eq!(delays, vec![2, 1]);
+    }
+}
@@ -0,0 +1,171 @@
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::{Debug, Formatter};
+
+use crate::cpu::CPU;
+use crate::events::{Event, EventBus};
+use crate::interruption::{Interruption, Peripheral};
+use crate::io::IO;
+use crate::Address;
+
+/// 通信ケーブルの相手。std では TCP で繋ぐ Link
+pub trait Cable {
+    /// 内部クロック側: 転送の開始時に自分の SB を送る
+    fn start(&mut self, data: u8);
+    /// 内部クロック側: 転送の終了時に相手の SB を受け取る
+    fn finish(&mut self) -> u8;
+    /// 外部クロック側: 相手が転送を開始していれば、自分の SB を返して相手の SB を受け取る
+    fn exchange(&mut self, data: u8) -> Option<u8>;
+}
+
+/// シリアル通信(通信ケーブル)
+/// 通信相手がいない場合、受信するデータは常に 0xFF になる
+pub struct Serial {
+    // 0xFF01: 送受信データ
+    // 1bit ずつ上位から送信し、下位に受信する
+    sb: u8,
+    // 0xFF02: 制御
+    // Bit 7: 転送開始/転送中
+    // Bit 0: 1なら内部クロック、0なら外部クロック
+    sc: u8,
+    // 転送中の経過 T-Cycle 数
+    clock: u32,
+    // 転送済みのビット数
+    bits: u8,
+    // 通信相手
+    link: Option<Box<dyn Cable>>,
+
+    interruption: Rc<RefCell<Interruption>>,
+    events: Rc<EventBus>,
+}
+
+impl Serial {
+    // 内部クロックは 8192Hz
+    const CLOCK_INTERNAL: u32 = 8192;
+
+    pub fn new(interruption: Rc<RefCell<Interruption>>, events: Rc<EventBus>) -> Self {
+        Self {
+            sb: 0,
+            sc: 0,
+            clock: 0,
+            bits: 0,
+            link: Option::None,
+            interruption,
+            events,
+        }
+    }
+
+    /// 転送を中断して電源投入時の状態に戻す。通信ケーブルは繋いだまま
+    pub fn reset(&mut self) {
+        let link = self.link.take();
+        *self = Self::new(Rc::clone(&self.interruption), Rc::clone(&self.events));
+        self.link = link;
+    }
+
+    fn is_transferring(&self) -> bool {
+        self.sc & 0b_1000_0000 == 0b_1000_0000
+    }
+
+    fn is_internal_clock(&self) -> bool {
+        self.sc & 0b_0000_0001 == 0b_0000_0001
+    }
+
+    pub fn set_link(&mut self, link: Box<dyn Cable>) {
+        self.link = Some(link);
+    }
+
+    pub fn tick(&mut self, cycle: u8) {
+        if !self.is_transferring() {
+            return;
+        }
+        self.clock += cycle as u32;
+        if !self.is_internal_clock() {
+            // 外部クロックの場合は相手がクロックを送ってくるまで転送は進まない
+            // 1bit 分の時間ごとに相手が転送を開始したか確認する
+            if self.clock >= CPU::CLOCK / Serial::CLOCK_INTERNAL {
+                self.clock = 0;
+                let received = self.link.as_mut().and_then(|link| link.exchange(self.sb));
+                if let Some(data) = received {
+                    self.sb = data;
+                    self.complete();
+                }
+            }
+            return;
+        }
+        while self.clock >= CPU::CLOCK / Serial::CLOCK_INTERNAL {
+            self.clock -= CPU::CLOCK / Serial::CLOCK_INTERNAL;
+            // 相手がいなければ 1 を受信する
+            self.sb = (self.sb << 1) | 0b1;
+            self.bits += 1;
+            if self.bits == 8 {
+                // 8bit 送ったら相手の値を受け取る。相手が遅れている場合はここで待つ
+                if let Some(link) = &mut self.link {
+                    self.sb = link.finish();
+                }
+                self.complete();
+                break;
+            }
+        }
+    }
+
+    // 転送完了の割り込み
+    fn complete(&mut self) {
+        self.sc &= 0b_0111_1111;
+        self.clock = 0;
+        self.bits = 0;
+        self.interruption.borrow_mut().request(Peripheral::Serial);
+        self.events.publish(Event::SerialTransferred(self.sb));
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend([self.sb, self.sc, self.bits]);
+        state.extend(self.clock.to_le_bytes());
+    }
+}
+
+impl Debug for Serial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Serial: {{ sb: 0x{:02X}, sc: 0b{:08b}, bits: {}, clock: {}, link: {} }}",
+            self.sb,
+            self.sc,
+            self.bits,
+            self.clock,
+            self.link.is_some()
+        )
+    }
+}
+
+impl IO for Serial {
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0xFF01 => self.sb,
+            // 未使用のビットは 1
+            0xFF02 => self.sc | 0b_0111_1110,
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF01 => self.sb = data,
+            0xFF02 => {
+                self.sc = data & 0b_1000_0001;
+                // 転送を開始し直す
+                self.clock = 0;
+                self.bits = 0;
+                if self.is_transferring() {
+                    self.events.publish(Event::SerialSent(self.sb));
+                }
+                if self.is_transferring() && self.is_internal_clock() {
+                    if let Some(link) = &mut self.link {
+                        link.start(self.sb);
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
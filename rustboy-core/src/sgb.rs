@@ -0,0 +1,538 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+
+use log::debug;
+
+use crate::ppu::{FrameBuffer, PixelData};
+
+// SGB の画面は 256 x 224 で、その中央にゲームボーイの画面を表示する
+pub const WIDTH_SCREEN: usize = 256;
+pub const HEIGHT_SCREEN: usize = 224;
+const LEFT_FRAME: usize = 48;
+const TOP_FRAME: usize = 40;
+
+// ゲームボーイの画面は 20 x 18 タイル
+const WIDTH_ATTRIBUTES: usize = 20;
+const HEIGHT_ATTRIBUTES: usize = 18;
+
+// 枠は 32 x 28 タイルで、タイルは 4bpp の 256 種類
+const WIDTH_BORDER: usize = 32;
+const HEIGHT_BORDER: usize = 28;
+const NUM_OF_BORDER_TILES: usize = 256;
+
+// 1パケットは 16 バイト
+const PACKET_SIZE: usize = 16;
+// VRAM 転送は 4KB
+pub const TRANSFER_SIZE: usize = 4 * 1024;
+
+pub type Screen = [[PixelData; WIDTH_SCREEN]; HEIGHT_SCREEN];
+
+// RGB555
+type Palette = [u16; 4];
+
+/// SGB のコマンド
+/// https://gbdev.io/pandocs/SGB_Command_Summary.html
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    Pal01,
+    Pal23,
+    Pal03,
+    Pal12,
+    AttrBlk,
+    PalSet,
+    PalTrn,
+    MltReq,
+    ChrTrn,
+    PctTrn,
+    MaskEn,
+    // 未対応のコマンド
+    Unsupported(u8),
+}
+
+impl From<u8> for Command {
+    fn from(v: u8) -> Self {
+        match v {
+            0x00 => Command::Pal01,
+            0x01 => Command::Pal23,
+            0x02 => Command::Pal03,
+            0x03 => Command::Pal12,
+            0x04 => Command::AttrBlk,
+            0x0A => Command::PalSet,
+            0x0B => Command::PalTrn,
+            0x11 => Command::MltReq,
+            0x13 => Command::ChrTrn,
+            0x14 => Command::PctTrn,
+            0x17 => Command::MaskEn,
+            _ => Command::Unsupported(v),
+        }
+    }
+}
+
+/// 画面のマスク(MASK_EN)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mask {
+    Cancel,
+    // 直前の画面のまま止める
+    Freeze,
+    Black,
+    // パレットの色 0 で塗りつぶす
+    Color0,
+}
+
+/// Super Game Boy
+/// ジョイパッドのポート(0xFF00)に書き込まれたパケットを受け取り、パレットと枠を管理する
+pub struct Sgb {
+    // 受信中のパケット。None ならリセットパルス待ち
+    packet: Option<[u8; PACKET_SIZE]>,
+    // 受信済みのビット数
+    bits: usize,
+    // 前回書き込まれた P14/P15
+    previous: u8,
+    // 複数パケットのコマンドは全て揃ってから実行する
+    packets: Vec<u8>,
+
+    // パレット 0 - 3 は画面、4 - 7 は枠に使う
+    palettes: [Palette; 4],
+    border_palettes: [[u16; 16]; 4],
+    // PAL_TRN で転送された 512 個のパレット
+    system_palettes: Vec<Palette>,
+    // 画面のタイルごとに使うパレット番号
+    attributes: [u8; WIDTH_ATTRIBUTES * HEIGHT_ATTRIBUTES],
+    // パレットのコマンドを受け取るまでは通常の表示色で描画する
+    colorized: bool,
+    mask: Mask,
+
+    // 枠のタイル(32バイト x 256)とマップ
+    border_tiles: Vec<u8>,
+    border_map: [u16; WIDTH_BORDER * HEIGHT_BORDER],
+    has_border: bool,
+
+    // MLT_REQ で指定された人数と、選択中のコントローラ
+    players: u8,
+    player: u8,
+}
+
+impl Sgb {
+    pub fn new() -> Self {
+        Self {
+            packet: Option::None,
+            bits: 0,
+            previous: 0x30,
+            packets: Vec::new(),
+            palettes: [[0x7FFF, 0x5294, 0x294A, 0x0000]; 4],
+            border_palettes: [[0; 16]; 4],
+            system_palettes: vec![[0; 4]; 512],
+            attributes: [0; WIDTH_ATTRIBUTES * HEIGHT_ATTRIBUTES],
+            colorized: false,
+            mask: Mask::Cancel,
+            border_tiles: vec![0; 32 * NUM_OF_BORDER_TILES],
+            border_map: [0; WIDTH_BORDER * HEIGHT_BORDER],
+            has_border: false,
+            players: 1,
+            player: 0,
+        }
+    }
+
+    pub fn mask(&self) -> Mask {
+        self.mask
+    }
+
+    pub fn has_border(&self) -> bool {
+        self.has_border
+    }
+
+    /// 0xFF00 への書き込み
+    /// vram は転送元のタイルデータ(4KB)
+    pub fn write(&mut self, data: u8, vram: &[u8]) {
+        let pulse = data & 0b_0011_0000;
+        let previous = self.previous;
+        self.previous = pulse;
+        // P15 が LOW から HIGH に戻るたびに次のコントローラに切り替える
+        if self.players > 1 && previous & 0b_0010_0000 == 0 && pulse & 0b_0010_0000 != 0 {
+            self.player = (self.player + 1) % self.players;
+        }
+        match pulse {
+            // リセットパルスでパケットの受信を開始する
+            0b_0000_0000 => {
+                self.packet = Some([0; PACKET_SIZE]);
+                self.bits = 0;
+            }
+            // P14 が LOW なら 0、P15 が LOW なら 1
+            // ビットは HIGH に戻してから次のパルスで送られる
+            0b_0010_0000 | 0b_0001_0000 if previous == 0b_0011_0000 => {
+                let bit = pulse == 0b_0001_0000;
+                if let Some(mut packet) = self.packet {
+                    if self.bits == PACKET_SIZE * 8 {
+                        // 最後のストップビット(0)でパケットが完成する
+                        self.packet = Option::None;
+                        self.receive(packet, vram);
+                        return;
+                    }
+                    // 下位ビットから送られてくる
+                    if bit {
+                        packet[self.bits / 8] |= 1 << (self.bits % 8);
+                    }
+                    self.bits += 1;
+                    self.packet = Some(packet);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 0xFF00 の読み込み
+    /// 複数人モードで P14/P15 がどちらも HIGH の場合はコントローラの番号を返す
+    pub fn read(&self, data: u8) -> u8 {
+        if self.players > 1 && data & 0b_0011_0000 == 0b_0011_0000 {
+            (data & 0xF0) | (0x0F - self.player)
+        } else {
+            data
+        }
+    }
+
+    fn receive(&mut self, packet: [u8; PACKET_SIZE], vram: &[u8]) {
+        self.packets.extend(packet);
+        // 1バイト目の下位3ビットがパケット数
+        let length = (self.packets[0] & 0b_0000_0111).max(1) as usize;
+        if self.packets.len() < length * PACKET_SIZE {
+            return;
+        }
+        let packets = core::mem::take(&mut self.packets);
+        self.execute(&packets, vram);
+    }
+
+    fn execute(&mut self, data: &[u8], vram: &[u8]) {
+        let command = Command::from(data[0] >> 3);
+        debug!(target: "sgb", "SGB: {:?}", command);
+        match command {
+            Command::Pal01 => self.set_palettes(0, 1, data),
+            Command::Pal23 => self.set_palettes(2, 3, data),
+            Command::Pal03 => self.set_palettes(0, 3, data),
+            Command::Pal12 => self.set_palettes(1, 2, data),
+            Command::AttrBlk => self.set_attribute_blocks(data),
+            Command::PalSet => {
+                for i in 0..4 {
+                    let index = u16::from_le_bytes([data[1 + i * 2], data[2 + i * 2]]) as usize;
+                    self.palettes[i] = self.system_palettes[index % 512];
+                }
+                self.colorized = true;
+                // bit 6 が立っていればマスクを解除する
+                if data[9] & 0b_0100_0000 != 0 {
+                    self.mask = Mask::Cancel;
+                }
+            }
+            Command::PalTrn => {
+                for (palette, colors) in self
+                    .system_palettes
+                    .iter_mut()
+                    .zip(vram[..TRANSFER_SIZE].chunks(8))
+                {
+                    for (i, color) in colors.chunks(2).enumerate() {
+                        palette[i] = u16::from_le_bytes([color[0], color[1]]);
+                    }
+                }
+            }
+            Command::MltReq => {
+                self.players = match data[1] & 0b_0000_0011 {
+                    0b01 => 2,
+                    0b11 => 4,
+                    _ => 1,
+                };
+                self.player = 0;
+            }
+            Command::ChrTrn => {
+                // bit 0 が 0 ならタイル 0x00 - 0x7F、1 なら 0x80 - 0xFF
+                let start = (data[1] & 0b_0000_0001) as usize * TRANSFER_SIZE;
+                self.border_tiles[start..start + TRANSFER_SIZE]
+                    .copy_from_slice(&vram[..TRANSFER_SIZE]);
+            }
+            Command::PctTrn => {
+                // 先頭 0x800 バイトが 32 x 32 のマップ、続く 0x80 バイトがパレット 4 - 7
+                for (i, entry) in self.border_map.iter_mut().enumerate() {
+                    *entry = u16::from_le_bytes([vram[i * 2], vram[i * 2 + 1]]);
+                }
+                for (i, color) in vram[0x800..0x880].chunks(2).enumerate() {
+                    self.border_palettes[i / 16][i % 16] = u16::from_le_bytes([color[0], color[1]]);
+                }
+                self.has_border = true;
+            }
+            Command::MaskEn => {
+                self.mask = match data[1] & 0b_0000_0011 {
+                    0b01 => Mask::Freeze,
+                    0b10 => Mask::Black,
+                    0b11 => Mask::Color0,
+                    _ => Mask::Cancel,
+                };
+            }
+            Command::Unsupported(v) => {
+                debug!(target: "sgb", "SGB: unsupported command 0x{:02X}", v);
+            }
+        }
+    }
+
+    // PAL01 など。色 0 は全パレットで共通
+    fn set_palettes(&mut self, a: usize, b: usize, data: &[u8]) {
+        let color = |i: usize| u16::from_le_bytes([data[1 + i * 2], data[2 + i * 2]]);
+        for palette in self.palettes.iter_mut() {
+            palette[0] = color(0);
+        }
+        for i in 1..4 {
+            self.palettes[a][i] = color(i);
+            self.palettes[b][i] = color(i + 3);
+        }
+        self.colorized = true;
+    }
+
+    // ATTR_BLK: 矩形の内側、枠線、外側のパレットを指定する
+    fn set_attribute_blocks(&mut self, data: &[u8]) {
+        let count = (data[1] & 0b_0001_1111) as usize;
+        for block in data[2..].chunks(6).take(count) {
+            if block.len() < 6 {
+                break;
+            }
+            let control = block[0] & 0b_0000_0111;
+            let inside = block[1] & 0b11;
+            let line = (block[1] >> 2) & 0b11;
+            let outside = (block[1] >> 4) & 0b11;
+            let (x1, y1, x2, y2) = (
+                block[2] as usize,
+                block[3] as usize,
+                block[4] as usize,
+                block[5] as usize,
+            );
+            // 内側か外側だけ指定された場合は枠線も同じパレットになる
+            let line = match control {
+                0b001 => Some(inside),
+                0b100 => Some(outside),
+                _ if control & 0b010 != 0 => Some(line),
+                _ => Option::None,
+            };
+            for y in 0..HEIGHT_ATTRIBUTES {
+                for x in 0..WIDTH_ATTRIBUTES {
+                    let palette = if x > x1 && x < x2 && y > y1 && y < y2 {
+                        (control & 0b001 != 0).then_some(inside)
+                    } else if x >= x1 && x <= x2 && y >= y1 && y <= y2 {
+                        line
+                    } else {
+                        (control & 0b100 != 0).then_some(outside)
+                    };
+                    if let Some(palette) = palette {
+                        self.attributes[y * WIDTH_ATTRIBUTES + x] = palette;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 画面上の (x, y) にある色番号 shade の表示色
+    /// パレットが設定されていなければ None
+    pub fn color(&self, x: usize, y: usize, shade: usize) -> Option<PixelData> {
+        if !self.colorized {
+            return Option::None;
+        }
+        let palette = self.attributes[(y / 8) * WIDTH_ATTRIBUTES + x / 8] as usize;
+        Some(to_rgba(self.palettes[palette][shade]))
+    }
+
+    /// パレット 0 の色 0 で塗りつぶした画面
+    pub fn backdrop(&self) -> PixelData {
+        to_rgba(self.palettes[0][0])
+    }
+
+    /// 枠の中央にゲームボーイの画面を合成する
+    pub fn compose(&self, frame_buffer: &FrameBuffer) -> Box<Screen> {
+        let backdrop = self.backdrop();
+        let mut screen = Box::new([[backdrop; WIDTH_SCREEN]; HEIGHT_SCREEN]);
+        for (y, line) in frame_buffer.iter().enumerate() {
+            screen[TOP_FRAME + y][LEFT_FRAME..LEFT_FRAME + line.len()].copy_from_slice(line);
+        }
+        for ty in 0..HEIGHT_BORDER {
+            for tx in 0..WIDTH_BORDER {
+                let entry = self.border_map[ty * WIDTH_BORDER + tx];
+                let tile = (entry & 0x00FF) as usize;
+                // パレット番号は 4 - 7
+                let palette = ((entry >> 10) & 0b111) as usize % 4;
+                let flip_x = entry & 0b_0100_0000_0000_0000 != 0;
+                let flip_y = entry & 0b_1000_0000_0000_0000 != 0;
+                for row in 0..8 {
+                    for col in 0..8 {
+                        let shade = self.border_shade(tile, row, col);
+                        // 色 0 は透明
+                        if shade == 0 {
+                            continue;
+                        }
+                        let y = ty * 8 + if flip_y { 7 - row } else { row };
+                        let x = tx * 8 + if flip_x { 7 - col } else { col };
+                        screen[y][x] = to_rgba(self.border_palettes[palette][shade]);
+                    }
+                }
+            }
+        }
+        screen
+    }
+
+    // 4bpp のタイルの色番号
+    // 先頭16バイトがビットプレーン 0, 1、後半16バイトが 2, 3 で、各行2バイトずつ
+    fn border_shade(&self, tile: usize, row: usize, col: usize) -> usize {
+        let data = &self.border_tiles[tile * 32..tile * 32 + 32];
+        let bit = 7 - col;
+        [
+            data[row * 2],
+            data[row * 2 + 1],
+            data[16 + row * 2],
+            data[17 + row * 2],
+        ]
+        .iter()
+        .enumerate()
+        .fold(0, |shade, (plane, byte)| {
+            shade | ((((byte >> bit) & 0b1) as usize) << plane)
+        })
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        for palette in self.palettes.iter().chain(self.system_palettes.iter()) {
+            for color in palette {
+                state.extend(color.to_le_bytes());
+            }
+        }
+        for color in self.border_palettes.iter().flatten() {
+            state.extend(color.to_le_bytes());
+        }
+        state.extend(self.attributes);
+        state.extend(&self.border_tiles);
+        for entry in self.border_map {
+            state.extend(entry.to_le_bytes());
+        }
+        state.extend([
+            self.colorized as u8,
+            self.mask as u8,
+            self.has_border as u8,
+            self.players,
+            self.player,
+            self.previous,
+            self.bits as u8,
+        ]);
+        state.extend(self.packet.unwrap_or([0; PACKET_SIZE]));
+        state.extend(&self.packets);
+    }
+}
+
+// RGB555 を RGBA に変換する
+fn to_rgba(color: u16) -> PixelData {
+    let c = |shift: u16| {
+        let v = ((color >> shift) & 0b_0001_1111) as u8;
+        (v << 3) | (v >> 2)
+    };
+    PixelData(c(0), c(5), c(10), 255)
+}
+
+impl Debug for Sgb {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Sgb: {{ palettes: {:04X?}, mask: {:?}, border: {}, players: {} }}",
+            self.palettes, self.mask, self.has_border, self.players
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // パケットをジョイパッドのポートへの書き込みに変換して送る
+    fn send(sgb: &mut Sgb, packet: [u8; PACKET_SIZE], vram: &[u8]) {
+        sgb.write(0x00, vram);
+        sgb.write(0x30, vram);
+        for byte in packet {
+            for bit in 0..8 {
+                sgb.write(if byte >> bit & 1 == 1 { 0x10 } else { 0x20 }, vram);
+                sgb.write(0x30, vram);
+            }
+        }
+        // ストップビット
+        sgb.write(0x20, vram);
+        sgb.write(0x30, vram);
+    }
+
+    #[test]
+    fn test_pal01() {
+        let mut sgb = Sgb::new();
+        let vram = vec![0; TRANSFER_SIZE];
+        assert_eq!(sgb.color(0, 0, 0), None);
+        let mut packet = [0; PACKET_SIZE];
+        // PAL01 を1パケット
+        packet[0] = 1;
+        // 色 0: 白、パレット 0 の色 1: 赤、パレット 1 の色 1: 青
+        packet[1..3].copy_from_slice(&0x7FFFu16.to_le_bytes());
+        packet[3..5].copy_from_slice(&0x001Fu16.to_le_bytes());
+        packet[9..11].copy_from_slice(&0x7C00u16.to_le_bytes());
+        send(&mut sgb, packet, &vram);
+        assert_eq!(sgb.color(0, 0, 0), Some(PixelData(255, 255, 255, 255)));
+        assert_eq!(sgb.color(0, 0, 1), Some(PixelData(255, 0, 0, 255)));
+        assert_eq!(sgb.palettes[1][1], 0x7C00);
+        assert_eq!(sgb.palettes[3][0], 0x7FFF);
+    }
+
+    #[test]
+    fn test_attr_blk() {
+        let mut sgb = Sgb::new();
+        let vram = vec![0; TRANSFER_SIZE];
+        let mut packet = [0; PACKET_SIZE];
+        packet[0] = (0x04 << 3) | 1;
+        packet[1] = 1;
+        // 内側と枠線にパレット 1、外側にパレット 2
+        packet[2] = 0b111;
+        packet[3] = 0b10_01_01;
+        packet[4..8].copy_from_slice(&[2, 2, 5, 5]);
+        send(&mut sgb, packet, &vram);
+        assert_eq!(sgb.attributes[0], 2);
+        assert_eq!(sgb.attributes[2 * WIDTH_ATTRIBUTES + 2], 1);
+        assert_eq!(sgb.attributes[3 * WIDTH_ATTRIBUTES + 4], 1);
+        assert_eq!(sgb.attributes[5 * WIDTH_ATTRIBUTES + 6], 2);
+    }
+
+    #[test]
+    fn test_mlt_req() {
+        let mut sgb = Sgb::new();
+        let vram = vec![0; TRANSFER_SIZE];
+        assert_eq!(sgb.read(0xFF), 0xFF);
+        let mut packet = [0; PACKET_SIZE];
+        packet[0] = (0x11 << 3) | 1;
+        packet[1] = 0b01;
+        send(&mut sgb, packet, &vram);
+        assert_eq!(sgb.read(0xFF), 0xFF);
+        sgb.write(0x10, &vram);
+        sgb.write(0x30, &vram);
+        assert_eq!(sgb.read(0xFF), 0xFE);
+    }
+
+    #[test]
+    fn test_border() {
+        let mut sgb = Sgb::new();
+        // タイル 1 の左上だけ色 15
+        let mut tiles = vec![0; TRANSFER_SIZE];
+        tiles[32] = 0x80;
+        tiles[33] = 0x80;
+        tiles[48] = 0x80;
+        tiles[49] = 0x80;
+        let mut packet = [0; PACKET_SIZE];
+        packet[0] = (0x13 << 3) | 1;
+        send(&mut sgb, packet, &tiles);
+
+        // 左上のタイルを X 反転したタイル 1、パレット 4 の色 15 は緑
+        let mut map = vec![0; TRANSFER_SIZE];
+        map[0..2].copy_from_slice(&(0x4000u16 | (4 << 10) | 1).to_le_bytes());
+        map[0x800 + 30..0x800 + 32].copy_from_slice(&0x03E0u16.to_le_bytes());
+        packet[0] = (0x14 << 3) | 1;
+        send(&mut sgb, packet, &map);
+        assert!(sgb.has_border());
+
+        let screen = sgb.compose(&[[PixelData(1, 2, 3, 255); 160]; 144]);
+        assert_eq!(screen[0][7], PixelData(0, 255, 0, 255));
+        assert_eq!(screen[0][0], sgb.backdrop());
+        assert_eq!(screen[TOP_FRAME][LEFT_FRAME], PixelData(1, 2, 3, 255));
+    }
+}
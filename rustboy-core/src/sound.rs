@@ -0,0 +1,414 @@
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{BufWriter, Result, Write};
+
+use log::trace;
+
+use crate::io::IO;
+use crate::Address;
+
+// 読み出し時に1になるビット(未使用ビットと書き込み専用ビット)
+// https://gbdev.io/pandocs/Audio_Registers.html
+const READ_MASKS: [u8; 0x20] = [
+    // NR10 - NR14
+    0x80, 0x3F, 0x00, 0xFF, 0xBF, //
+    // NR20(未使用) - NR24
+    0xFF, 0x3F, 0x00, 0xFF, 0xBF, //
+    // NR30 - NR34
+    0x7F, 0xFF, 0x9F, 0xFF, 0xBF, //
+    // NR40(未使用) - NR44
+    0xFF, 0xFF, 0x00, 0x00, 0xBF, //
+    // NR50 - NR52
+    0x00, 0x00, 0x70, //
+    // 0xFF27 - 0xFF2F: 未使用
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+];
+
+#[cfg(feature = "std")]
+const NR10: usize = 0x00;
+const NR12: usize = 0x02;
+#[cfg(feature = "std")]
+const NR13: usize = 0x03;
+const NR14: usize = 0x04;
+const NR22: usize = 0x07;
+#[cfg(feature = "std")]
+const NR23: usize = 0x08;
+const NR24: usize = 0x09;
+const NR30: usize = 0x0A;
+#[cfg(feature = "std")]
+const NR32: usize = 0x0C;
+#[cfg(feature = "std")]
+const NR33: usize = 0x0D;
+const NR34: usize = 0x0E;
+const NR42: usize = 0x11;
+#[cfg(feature = "std")]
+const NR43: usize = 0x12;
+const NR44: usize = 0x13;
+#[cfg(feature = "std")]
+const NR50: usize = 0x14;
+#[cfg(feature = "std")]
+const NR51: usize = 0x15;
+const NR52: usize = 0x16;
+
+/// APU
+/// 音は鳴らさないが、レジスタの状態はデバッグ用に保持する
+pub struct Sound {
+    // 0xFF10 - 0xFF2F
+    registers: [u8; 0x20],
+    // 0xFF30 - 0xFF3F: 波形RAM(4bitのサンプルが32個)
+    wave_ram: [u8; 16],
+    // NR52 の下位4bit。トリガーされてからDACが切られるまで有効
+    channel_enables: [bool; 4],
+}
+
+impl Sound {
+    pub fn new() -> Self {
+        let mut registers = [0; 0x20];
+        // ブートROM実行後は電源が入っている
+        registers[NR52] = 0b_1000_0000;
+        Self {
+            registers,
+            wave_ram: [0; 16],
+            channel_enables: [false; 4],
+        }
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend(self.registers);
+        state.extend(self.wave_ram);
+        state.extend(self.channel_enables.map(|e| e as u8));
+    }
+
+    fn is_powered(&self) -> bool {
+        self.registers[NR52] & 0b_1000_0000 == 0b_1000_0000
+    }
+
+    // チャンネルごとのDACの状態
+    fn is_dac_on(&self, channel: usize) -> bool {
+        match channel {
+            0 => self.registers[NR12] & 0xF8 != 0,
+            1 => self.registers[NR22] & 0xF8 != 0,
+            2 => self.registers[NR30] & 0x80 != 0,
+            3 => self.registers[NR42] & 0xF8 != 0,
+            _ => unreachable!(),
+        }
+    }
+
+    // 11bitの周期の値
+    #[cfg(feature = "std")]
+    fn period(&self, low: usize, high: usize) -> u16 {
+        ((self.registers[high] as u16 & 0x07) << 8) | self.registers[low] as u16
+    }
+
+    /// チャンネル1, 2の周波数(Hz)
+    #[cfg(feature = "std")]
+    fn pulse_frequency(period: u16) -> f64 {
+        131072.0 / (2048 - period) as f64
+    }
+
+    /// チャンネル3の周波数(Hz)。波形RAM 32サンプルを1周期とする
+    #[cfg(feature = "std")]
+    fn wave_frequency(period: u16) -> f64 {
+        65536.0 / (2048 - period) as f64
+    }
+
+    /// チャンネル4のLFSRのクロック(Hz)
+    #[cfg(feature = "std")]
+    fn noise_frequency(nr43: u8) -> f64 {
+        let shift = (nr43 >> 4) as i32;
+        let divider = match nr43 & 0x07 {
+            0 => 0.5,
+            r => r as f64,
+        };
+        262144.0 / (divider * 2f64.powi(shift))
+    }
+
+    #[cfg(feature = "std")]
+    fn wave_samples(&self) -> Vec<u8> {
+        self.wave_ram
+            .iter()
+            .flat_map(|b| [b >> 4, b & 0x0F])
+            .collect()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn dump_apu(&self) -> String {
+        let r = &self.registers;
+        let mut lines = vec![format!(
+            "NR50: 0x{:02X}, NR51: 0b{:08b}, NR52: 0b{:08b} (power: {})",
+            r[NR50],
+            r[NR51],
+            self.read(0xFF26),
+            if self.is_powered() { "on" } else { "off" }
+        )];
+        let duty = |v: u8| match v >> 6 {
+            0 => "12.5%",
+            1 => "25%",
+            2 => "50%",
+            _ => "75%",
+        };
+        let envelope = |v: u8| {
+            format!(
+                "volume: {}, envelope: {} pace {}",
+                v >> 4,
+                if v & 0x08 == 0x08 { "up" } else { "down" },
+                v & 0x07
+            )
+        };
+        let names = ["CH1 Pulse+Sweep", "CH2 Pulse", "CH3 Wave", "CH4 Noise"];
+        for (channel, name) in names.iter().enumerate() {
+            let base = channel * 5;
+            let status = format!(
+                "{}: {}, dac: {}, NRx0-4: {:02X} {:02X} {:02X} {:02X} {:02X}",
+                name,
+                if self.channel_enables[channel] {
+                    "on"
+                } else {
+                    "off"
+                },
+                if self.is_dac_on(channel) { "on" } else { "off" },
+                self.read(0xFF10 + base as u16),
+                self.read(0xFF11 + base as u16),
+                self.read(0xFF12 + base as u16),
+                self.read(0xFF13 + base as u16),
+                self.read(0xFF14 + base as u16),
+            );
+            let detail = match channel {
+                0 => format!(
+                    "freq: {:.1}Hz, duty: {}, {}, sweep: 0x{:02X}",
+                    Self::pulse_frequency(self.period(NR13, NR14)),
+                    duty(r[NR10 + 1]),
+                    envelope(r[NR12]),
+                    r[NR10] & 0x7F
+                ),
+                1 => format!(
+                    "freq: {:.1}Hz, duty: {}, {}",
+                    Self::pulse_frequency(self.period(NR23, NR24)),
+                    duty(r[NR22 - 1]),
+                    envelope(r[NR22])
+                ),
+                2 => format!(
+                    "freq: {:.1}Hz, output level: {}",
+                    Self::wave_frequency(self.period(NR33, NR34)),
+                    match (r[NR32] >> 5) & 0x03 {
+                        0 => "mute",
+                        1 => "100%",
+                        2 => "50%",
+                        _ => "25%",
+                    }
+                ),
+                _ => format!(
+                    "clock: {:.1}Hz, width: {}bit, {}",
+                    Self::noise_frequency(r[NR43]),
+                    if r[NR43] & 0x08 == 0x08 { 7 } else { 15 },
+                    envelope(r[NR42])
+                ),
+            };
+            lines.push(format!("{}\n  {}", status, detail));
+        }
+        lines.push(format!("Wave RAM: {:X?}", self.wave_samples()));
+        lines.join("\n")
+    }
+
+    /// チャンネル3の波形RAMを現在の周波数で seconds 秒分 WAV に書き出す
+    #[cfg(feature = "std")]
+    pub fn dump_wave(&self, path: &str, seconds: u32) -> Result<()> {
+        const SAMPLE_RATE: u32 = 44100;
+        let pcm = self.wave_pcm(SAMPLE_RATE, 0, (SAMPLE_RATE * seconds) as usize);
+        write_wav(BufWriter::new(File::create(path)?), SAMPLE_RATE, &pcm)
+    }
+
+    /// チャンネル3の波形RAMを現在の周波数で鳴らした 8bit の PCM
+    /// start は先頭からのサンプル数で、続けて呼ぶと波形が途切れない
+    #[cfg(feature = "std")]
+    pub(crate) fn wave_pcm(&self, sample_rate: u32, start: u64, len: usize) -> Vec<u8> {
+        let samples = self.wave_samples();
+        let frequency = Self::wave_frequency(self.period(NR33, NR34));
+        (start..start + len as u64)
+            .map(|i| {
+                // 1周期中の位置から波形RAMのサンプルを選ぶ
+                let phase = (i as f64 * frequency / sample_rate as f64).fract();
+                samples[(phase * samples.len() as f64) as usize] * 0x11
+            })
+            .collect()
+    }
+
+    /// 鳴っているチャンネルを混ぜた 8bit の PCM。start は先頭からのサンプル数
+    /// 合成しているのはチャンネル3だけで、鳴っていなければ無音(0x80)
+    #[cfg(feature = "std")]
+    pub fn mixed_pcm(&self, sample_rate: u32, start: u64, len: usize) -> Vec<u8> {
+        if self.is_wave_playing() {
+            self.wave_pcm(sample_rate, start, len)
+        } else {
+            vec![0x80; len]
+        }
+    }
+
+    /// チャンネル3が鳴っているか。電源、チャンネル、DAC のどれかが切れていれば無音
+    #[cfg(feature = "std")]
+    fn is_wave_playing(&self) -> bool {
+        self.is_powered() && self.channel_enables[2] && self.is_dac_on(2)
+    }
+}
+
+/// 8bitモノラルのPCMを WAV 形式で書き出す
+#[cfg(feature = "std")]
+pub fn write_wav<W: Write>(mut out: W, sample_rate: u32, pcm: &[u8]) -> Result<()> {
+    let data_size = pcm.len() as u32;
+    out.write_all(b"RIFF")?;
+    out.write_all(&(36 + data_size).to_le_bytes())?;
+    out.write_all(b"WAVE")?;
+    out.write_all(b"fmt ")?;
+    out.write_all(&16u32.to_le_bytes())?;
+    // リニアPCM、1チャンネル
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&sample_rate.to_le_bytes())?;
+    // バイトレート、ブロックサイズ、ビット深度
+    out.write_all(&sample_rate.to_le_bytes())?;
+    out.write_all(&1u16.to_le_bytes())?;
+    out.write_all(&8u16.to_le_bytes())?;
+    out.write_all(b"data")?;
+    out.write_all(&data_size.to_le_bytes())?;
+    out.write_all(pcm)?;
+    out.flush()
+}
+
+impl IO for Sound {
+    fn read(&self, address: Address) -> u8 {
+        trace!(target: "sound", "Read Sound: {:X?}", address);
+        match address {
+            0xFF10..=0xFF2F => {
+                let i = (address - 0xFF10) as usize;
+                let mut v = self.registers[i] | READ_MASKS[i];
+                if i == NR52 {
+                    for (channel, enable) in self.channel_enables.iter().enumerate() {
+                        if *enable {
+                            v |= 1 << channel;
+                        }
+                    }
+                }
+                v
+            }
+            0xFF30..=0xFF3F => self.wave_ram[(address - 0xFF30) as usize],
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        trace!(target: "sound", "Write Sound: {:X?}, Data: {}", address, data);
+        match address {
+            0xFF26 => {
+                // 下位4bitは読み込み専用
+                self.registers[NR52] = data & 0b_1000_0000;
+                if !self.is_powered() {
+                    // 電源を切ると NR10 - NR51 がクリアされる
+                    self.registers[..NR52].fill(0);
+                    self.channel_enables = [false; 4];
+                }
+            }
+            0xFF10..=0xFF2F => {
+                if !self.is_powered() {
+                    // 電源が切れている間は書き込めない
+                    return;
+                }
+                let i = (address - 0xFF10) as usize;
+                self.registers[i] = data;
+                let channel = i / 5;
+                match i {
+                    // NRx4 の bit7 でトリガー
+                    NR14 | NR24 | NR34 | NR44 if data & 0x80 == 0x80 => {
+                        self.channel_enables[channel] = self.is_dac_on(channel);
+                    }
+                    // DACを切るとチャンネルも止まる
+                    NR12 | NR22 | NR30 | NR42 if !self.is_dac_on(channel) => {
+                        self.channel_enables[channel] = false;
+                    }
+                    _ => {}
+                }
+            }
+            0xFF30..=0xFF3F => self.wave_ram[(address - 0xFF30) as usize] = data,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Debug for Sound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        // rom_data は表示しない
+        write!(f, "Sound")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_masks() {
+        let mut sound = Sound::new();
+        sound.write(0xFF11, 0b_1000_0000);
+        sound.write(0xFF13, 0x12);
+        assert_eq!(sound.read(0xFF11), 0b_1011_1111);
+        // 周期の下位は書き込み専用
+        assert_eq!(sound.read(0xFF13), 0xFF);
+        assert_eq!(sound.read(0xFF15), 0xFF);
+        assert_eq!(sound.read(0xFF26), 0b_1111_0000);
+    }
+
+    #[test]
+    fn test_channel_enables() {
+        let mut sound = Sound::new();
+        // DACが切れているとトリガーしても有効にならない
+        sound.write(0xFF14, 0x80);
+        assert_eq!(sound.read(0xFF26), 0b_1111_0000);
+        sound.write(0xFF12, 0xF3);
+        sound.write(0xFF14, 0x80);
+        sound.write(0xFF1A, 0x80);
+        sound.write(0xFF1E, 0x80);
+        assert_eq!(sound.read(0xFF26), 0b_1111_0101);
+        sound.write(0xFF1A, 0x00);
+        assert_eq!(sound.read(0xFF26), 0b_1111_0001);
+        // 電源を切ると全てクリアされる
+        sound.write(0xFF26, 0x00);
+        assert_eq!(sound.read(0xFF26), 0b_0111_0000);
+        assert_eq!(sound.read(0xFF12), 0x00);
+    }
+
+    #[test]
+    fn test_frequencies() {
+        assert_eq!(Sound::pulse_frequency(0), 64.0);
+        assert_eq!(Sound::pulse_frequency(2047), 131072.0);
+        assert_eq!(Sound::wave_frequency(1024), 64.0);
+        assert_eq!(Sound::noise_frequency(0x00), 524288.0);
+        assert_eq!(Sound::noise_frequency(0x21), 65536.0);
+    }
+
+    #[test]
+    fn test_write_wav() {
+        let mut buf = Vec::new();
+        write_wav(&mut buf, 44100, &[0x80, 0xFF]).unwrap();
+        assert_eq!(&buf[0..4], b"RIFF");
+        assert_eq!(&buf[4..8], &38u32.to_le_bytes());
+        assert_eq!(&buf[36..40], b"data");
+        assert_eq!(&buf[40..44], &2u32.to_le_bytes());
+        assert_eq!(&buf[44..], &[0x80, 0xFF]);
+    }
+
+    #[test]
+    fn test_mixed_pcm() {
+        let mut sound = Sound::new();
+        sound.write(0xFF30, 0xF0);
+        assert_eq!(sound.mixed_pcm(44100, 0, 2), [0x80, 0x80]);
+        // 周期 64Hz の先頭は波形RAMの最初のサンプル
+        sound.write(0xFF1A, 0x80);
+        sound.write(0xFF1D, 0x00);
+        sound.write(0xFF1E, 0x84);
+        assert_eq!(sound.mixed_pcm(44100, 0, 2), [0xFF, 0xFF]);
+        // 電源を切ると無音
+        sound.write(0xFF26, 0x00);
+        assert_eq!(sound.mixed_pcm(44100, 0, 1), [0x80]);
+    }
+}
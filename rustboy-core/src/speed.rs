@@ -0,0 +1,94 @@
+use alloc::vec::Vec;
+
+use crate::io::IO;
+use crate::Address;
+
+/// CGB の倍速モード (KEY1: 0xFF4D)
+/// bit 0 を 1 にしてから STOP を実行すると CPU のクロックが 2 倍になる
+/// PPU は等速のままなので、MotherBoard は CPU の T-Cycle 数を等速の T-Cycle 数に換算して進める
+/// https://gbdev.io/pandocs/CGB_Registers.html#ff4d--key1-cgb-mode-only-prepare-speed-switch
+#[derive(Debug, Default)]
+pub struct Speed {
+    // 倍速で動いているか。KEY1 の bit 7
+    double: bool,
+    // 次の STOP で切り替える。KEY1 の bit 0
+    prepared: bool,
+}
+
+impl Speed {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 等速の 1 T-Cycle の間に CPU が進める T-Cycle 数
+    pub fn factor(&self) -> u8 {
+        if self.double {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// CPU の T-Cycle 数を等速の T-Cycle 数に換算する
+    pub fn to_normal(&self, cycles: u8) -> u8 {
+        cycles / self.factor()
+    }
+
+    /// STOP を実行したときに呼ぶ。切り替えが準備されていれば切り替えて true を返す
+    pub fn switch(&mut self) -> bool {
+        if !self.prepared {
+            return false;
+        }
+        self.double = !self.double;
+        self.prepared = false;
+        true
+    }
+
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.push(self.double as u8);
+        state.push(self.prepared as u8);
+    }
+}
+
+impl IO for Speed {
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            // 未使用のビットは 1 が読める
+            0xFF4D => (self.double as u8) << 7 | 0x7E | self.prepared as u8,
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            // bit 7 は読み込み専用
+            0xFF4D => self.prepared = data & 0x01 != 0,
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch() {
+        let mut speed = Speed::new();
+        assert_eq!(speed.read(0xFF4D), 0x7E);
+        // 準備していなければ切り替わらない
+        assert!(!speed.switch());
+        assert_eq!(speed.to_normal(4), 4);
+
+        speed.write(0xFF4D, 0xFF);
+        assert_eq!(speed.read(0xFF4D), 0x7F);
+        assert!(speed.switch());
+        assert_eq!(speed.read(0xFF4D), 0xFE);
+        assert_eq!(speed.to_normal(4), 2);
+
+        // もう一度準備して STOP すると等速に戻る
+        speed.write(0xFF4D, 0x01);
+        assert!(speed.switch());
+        assert_eq!(speed.read(0xFF4D), 0x7E);
+        assert_eq!(speed.factor(), 1);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "std")]
+use crate::cpu::CPU;
+
+// 集計する間隔
+#[cfg(feature = "std")]
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// 1秒ごとに集計した実行速度。フロントエンドが状態行に表示する
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameStats {
+    // 実機での1秒あたりのフレーム数。LCD が有効なら約 59.7
+    pub emulated_fps: f64,
+    // 実時間での1秒あたりのフレーム数
+    pub host_fps: f64,
+    // 1.0 で実機と同じ速さ
+    pub speed: f64,
+    // 集計時に選択されていた ROM バンク
+    pub rom_bank: usize,
+}
+
+/// メインループで経過 T-Cycle 数と描画したフレーム数から FrameStats を集計する
+#[cfg(feature = "std")]
+pub struct StatsCollector {
+    // 前回集計した時刻と、その時点の経過 T-Cycle 数とフレーム数
+    origin: Instant,
+    origin_cycles: u64,
+    origin_frames: u64,
+}
+
+#[cfg(feature = "std")]
+impl StatsCollector {
+    pub fn new(cycles: u64, frames: u64) -> Self {
+        Self {
+            origin: Instant::now(),
+            origin_cycles: cycles,
+            origin_frames: frames,
+        }
+    }
+
+    /// フレームを描画するたびに呼び出す。前回の集計から1秒経っていれば集計する
+    pub fn tick(&mut self, cycles: u64, frames: u64, rom_bank: usize) -> Option<FrameStats> {
+        let elapsed = self.origin.elapsed();
+        // リセットされたら集計し直す
+        if cycles < self.origin_cycles {
+            self.origin = Instant::now();
+            self.origin_cycles = cycles;
+            self.origin_frames = frames;
+            return Option::None;
+        }
+        if elapsed < INTERVAL {
+            return Option::None;
+        }
+        let stats = self.collect(elapsed, cycles, frames, rom_bank);
+        self.origin = Instant::now();
+        self.origin_cycles = cycles;
+        self.origin_frames = frames;
+        Some(stats)
+    }
+
+    fn collect(&self, elapsed: Duration, cycles: u64, frames: u64, rom_bank: usize) -> FrameStats {
+        let frames = (frames - self.origin_frames) as f64;
+        let emulated = (cycles - self.origin_cycles) as f64 / CPU::CLOCK as f64;
+        let host = elapsed.as_secs_f64();
+        FrameStats {
+            emulated_fps: frames / emulated,
+            host_fps: frames / host,
+            speed: emulated / host,
+            rom_bank,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect() {
+        let collector = StatsCollector::new(70224, 1);
+        // 実時間 2 秒で実機 1 秒分(60 フレーム)を実行した
+        let stats = collector.collect(Duration::from_secs(2), 70224 * 61, 61, 3);
+        assert_eq!(stats.rom_bank, 3);
+        assert_eq!(stats.host_fps, 30.0);
+        assert!((stats.emulated_fps - 59.73).abs() < 0.01);
+        assert!((stats.speed - 0.502).abs() < 0.001);
+        // 1秒経っていなければ集計しない
+        let mut collector = StatsCollector::new(0, 0);
+        assert_eq!(collector.tick(70224, 1, 1), None);
+    }
+}
@@ -0,0 +1,361 @@
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt::{Debug, Formatter};
+
+use crate::arithmetic::ArithmeticUtil;
+use crate::cpu::CPU;
+use crate::interruption::{Interruption, Peripheral};
+use crate::io::IO;
+use crate::Address;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TimerStatus {
+    // 1
+    RUNNING,
+    // 0
+    STOPPED,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Clock {
+    // 00
+    // 4096Hz
+    Hz4096,
+    // 01
+    // 4096Hz * 64
+    Hz262144,
+    // 10
+    // 4096Hz * 16
+    Hz65536,
+    // 11
+    // 4096Hz * 4
+    Hz16384,
+}
+
+impl Clock {
+    // 内部カウンタのどのビットの立ち下がりで TIMA を進めるか
+    // CPU は 4.194304 MHz なので、4096Hz なら 1024 T-Cycle ごとに立ち下がる bit 9
+    fn bit(&self) -> u16 {
+        match self {
+            Clock::Hz4096 => 9,
+            Clock::Hz16384 => 7,
+            Clock::Hz65536 => 5,
+            Clock::Hz262144 => 3,
+        }
+    }
+    // TIMA が進む間隔の T-Cycle 数
+    fn period(&self) -> u32 {
+        2 << self.bit()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TAC {
+    // Bit 7-3 未使用
+    // Bit 2 タイマー停止状態
+    status: TimerStatus,
+    // Bit 1-0
+    clock: Clock,
+}
+
+impl From<u8> for TAC {
+    fn from(v: u8) -> Self {
+        let status = match v & 0b_0000_0100 {
+            0b100 => TimerStatus::RUNNING,
+            0b000 => TimerStatus::STOPPED,
+            _ => unreachable!(),
+        };
+        let clock = match v & 0b_0000_0011 {
+            0b00 => Clock::Hz4096,
+            0b01 => Clock::Hz262144,
+            0b10 => Clock::Hz65536,
+            0b11 => Clock::Hz16384,
+            _ => unreachable!(),
+        };
+        Self { status, clock }
+    }
+}
+
+impl From<TAC> for u8 {
+    fn from(tac: TAC) -> Self {
+        let status = match tac.status {
+            TimerStatus::RUNNING => 0b100,
+            TimerStatus::STOPPED => 0b000,
+        };
+        let clock = match tac.clock {
+            Clock::Hz4096 => 0b00,
+            Clock::Hz262144 => 0b01,
+            Clock::Hz65536 => 0b10,
+            Clock::Hz16384 => 0b11,
+        };
+        status | clock
+    }
+}
+
+pub struct Timer {
+    // 内部の 16bit カウンタ。T-Cycle ごとにインクリメントされる
+    // 上位 8bit が分周レジスタ (FF04) で、16384Hz でインクリメントされるように見える
+    // FF04 に何かが書き込まれた時は、カウンタ全体が 0 にリセットされる
+    counter: u16,
+
+    // タイマーカウンタ
+    // 周波数はtac.clockにより可変
+    // オーバーフローしたら tma の値をセットして割り込みを入れる
+    // FF05
+    tima: u8,
+
+    // タイマーモジュロ
+    // tima がオーバーフローしたらこの値がセットされる
+    // FF06
+    tma: u8,
+
+    // タイマー制御
+    // FF07
+    tac: TAC,
+
+    interruption: Rc<RefCell<Interruption>>,
+}
+
+impl Timer {
+    pub fn new(interruption: Rc<RefCell<Interruption>>) -> Self {
+        Self {
+            interruption,
+            counter: 0,
+            tima: 0,
+            tma: 0,
+            tac: TAC::from(0),
+        }
+    }
+    pub fn reset(&mut self) {
+        *self = Self::new(Rc::clone(&self.interruption));
+    }
+    pub fn tick(&mut self, cycle: u8) {
+        for _ in 0..cycle {
+            let signal = self.signal();
+            self.counter = self.counter.wrapping_add(1);
+            self.detect_falling_edge(signal);
+        }
+    }
+    // tac.clock で選んだカウンタのビットとタイマー動作中かの AND
+    // 実機ではこの信号の立ち下がりで TIMA が進む
+    fn signal(&self) -> bool {
+        self.tac.status == TimerStatus::RUNNING && self.counter & (1 << self.tac.clock.bit()) != 0
+    }
+    // DIV や TAC への書き込みで信号が下がった場合も TIMA が進む
+    fn detect_falling_edge(&mut self, before: bool) {
+        if before && !self.signal() {
+            self.increment_tima();
+        }
+    }
+    fn increment_tima(&mut self) {
+        if self.tima.calc_carry(1) {
+            // タイマーを初期化して割り込み
+            self.tima = self.tma;
+            self.interruption.borrow_mut().request(Peripheral::Timer);
+        } else {
+            self.tima = self.tima.wrapping_add(1);
+        }
+    }
+    pub fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend(self.counter.to_le_bytes());
+        state.extend([self.tima, self.tma, u8::from(self.tac)]);
+    }
+    /// 次に TIMA が進むまでの T-Cycle 数。止まっていれば None
+    /// 選んだビットの立ち下がりで進むので、カウンタがそのビットの2倍の周期の倍数になったとき
+    pub fn cycles_to_increment(&self) -> Option<u32> {
+        if self.tac.status == TimerStatus::STOPPED {
+            return None;
+        }
+        let period = self.tac.clock.period();
+        Some(period - self.counter as u32 % period)
+    }
+    /// TIMA がオーバーフローして割り込みを要求するまでの T-Cycle 数。止まっていれば None
+    pub fn cycles_to_overflow(&self) -> Option<u32> {
+        let next = self.cycles_to_increment()?;
+        Some(next + (0xFF - self.tima as u32) * self.tac.clock.period())
+    }
+    pub fn dump_timer(&self) -> String {
+        let schedule = match (self.cycles_to_increment(), self.cycles_to_overflow()) {
+            (Some(next), Some(overflow)) => format!(
+                "next TIMA in {} cycles, overflow in {} cycles ({:.3} ms)",
+                next,
+                overflow,
+                overflow as f64 * 1000.0 / CPU::CLOCK as f64
+            ),
+            _ => "stopped".to_string(),
+        };
+        format!("{:?} {}", self, schedule)
+    }
+}
+
+impl Debug for Timer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Timer: {{ div: {}, counter: 0x{:04X}, tima: {}, tma: {}, tac: 0b{:08b} }}",
+            self.counter >> 8,
+            self.counter,
+            self.tima,
+            self.tma,
+            u8::from(self.tac)
+        )
+    }
+}
+
+impl IO for Timer {
+    fn read(&self, address: Address) -> u8 {
+        match address {
+            0xFF04 => (self.counter >> 8) as u8,
+            0xFF05 => self.tima,
+            0xFF06 => self.tma as u8,
+            0xFF07 => u8::from(self.tac),
+            _ => unreachable!(),
+        }
+    }
+    fn write(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF04 => {
+                let signal = self.signal();
+                self.counter = 0;
+                self.detect_falling_edge(signal);
+            }
+            0xFF05 => self.tima = data,
+            0xFF06 => self.tma = data,
+            0xFF07 => {
+                // 周波数の切り替えや停止でも TIMA が進むことがある
+                let signal = self.signal();
+                self.tac = TAC::from(data);
+                self.detect_falling_edge(signal);
+            }
+            _ => unreachable!(),
+        }
+    }
+    // DIV をリセットせずに書き換え、TIMA を進めない
+    fn poke(&mut self, address: Address, data: u8) {
+        match address {
+            0xFF04 => self.counter = (data as u16) << 8 | (self.counter & 0xFF),
+            0xFF07 => self.tac = TAC::from(data),
+            _ => self.write(address, data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_timer() -> Timer {
+        Timer::new(Rc::new(RefCell::new(Interruption::new())))
+    }
+
+    #[test]
+    fn test_div() {
+        let mut timer = new_timer();
+        timer.tick(255);
+        assert_eq!(timer.read(0xFF04), 0x00);
+        timer.tick(1);
+        assert_eq!(timer.read(0xFF04), 0x01);
+        // 書き込んだ値に関係なく 0 になる
+        timer.write(0xFF04, 0x12);
+        assert_eq!(timer.read(0xFF04), 0x00);
+    }
+
+    #[test]
+    fn test_tima() {
+        // 262144Hz なので 16 T-Cycle ごとに進む
+        let mut timer = new_timer();
+        timer.write(0xFF07, 0b101);
+        timer.tick(15);
+        assert_eq!(timer.read(0xFF05), 0);
+        timer.tick(1);
+        assert_eq!(timer.read(0xFF05), 1);
+
+        // オーバーフローすると TMA を読み込んで割り込み
+        timer.write(0xFF05, 0xFF);
+        timer.write(0xFF06, 0x80);
+        timer.tick(16);
+        assert_eq!(timer.read(0xFF05), 0x80);
+        assert_eq!(timer.interruption.borrow().read(0xFF0F) & 0b100, 0b100);
+    }
+
+    #[test]
+    fn test_cycles_to_overflow() {
+        let mut timer = new_timer();
+        assert_eq!(timer.cycles_to_increment(), None);
+        assert!(timer.dump_timer().ends_with("stopped"));
+        // 4096Hz なので 1024 T-Cycle ごとに進む
+        timer.write(0xFF07, 0b100);
+        timer.tick(24);
+        assert_eq!(timer.cycles_to_increment(), Some(1000));
+        timer.write(0xFF05, 0xFE);
+        assert_eq!(timer.cycles_to_overflow(), Some(1000 + 1024));
+        for _ in 0..(1000 + 1024 - 1) / 7 {
+            timer.tick(7);
+        }
+        assert_eq!(timer.interruption.borrow().read(0xFF0F) & 0b100, 0);
+        timer.tick(1);
+        assert_eq!(timer.interruption.borrow().read(0xFF0F) & 0b100, 0b100);
+        // 262144Hz でオーバーフローから数え直す
+        timer.write(0xFF07, 0b101);
+        timer.write(0xFF05, 0x00);
+        assert_eq!(timer.cycles_to_increment(), Some(16));
+        assert_eq!(timer.cycles_to_overflow(), Some(16 * 256));
+        assert!(timer
+            .dump_timer()
+            .ends_with("next TIMA in 16 cycles, overflow in 4096 cycles (0.977 ms)"));
+    }
+
+    #[test]
+    fn test_div_write_glitch() {
+        // bit 3 が 1 のときに DIV をリセットすると TIMA が進む
+        let mut timer = new_timer();
+        timer.write(0xFF07, 0b101);
+        timer.tick(8);
+        timer.write(0xFF04, 0);
+        assert_eq!(timer.read(0xFF05), 1);
+        // bit 3 が 0 なら進まない
+        timer.tick(7);
+        timer.write(0xFF04, 0);
+        assert_eq!(timer.read(0xFF05), 1);
+    }
+
+    #[test]
+    fn test_tac_write_glitch() {
+        // 停止すると信号が下がるので TIMA が進む
+        let mut timer = new_timer();
+        timer.write(0xFF07, 0b101);
+        timer.tick(8);
+        timer.write(0xFF07, 0b001);
+        assert_eq!(timer.read(0xFF05), 1);
+
+        // bit 3 が 1 で bit 9 が 0 の周波数に切り替えても進む
+        let mut timer = new_timer();
+        timer.write(0xFF07, 0b101);
+        timer.tick(8);
+        timer.write(0xFF07, 0b100);
+        assert_eq!(timer.read(0xFF05), 1);
+        // 止まっている状態から動かしても進まない
+        let mut timer = new_timer();
+        timer.tick(8);
+        timer.write(0xFF07, 0b101);
+        assert_eq!(timer.read(0xFF05), 0);
+    }
+
+    #[test]
+    fn test_poke() {
+        let mut timer = new_timer();
+        timer.write(0xFF07, 0b101);
+        timer.tick(8);
+        // DIV をリセットせず、TIMA も進まない
+        timer.poke(0xFF04, 0x12);
+        assert_eq!(timer.read(0xFF04), 0x12);
+        assert_eq!(timer.read(0xFF05), 0);
+        timer.poke(0xFF07, 0b001);
+        assert_eq!(timer.read(0xFF05), 0);
+        timer.poke(0xFF05, 0x34);
+        assert_eq!(timer.read(0xFF05), 0x34);
+    }
+}